@@ -17,6 +17,7 @@ use reth_node_builder::{
 use reth_node_core::{
     args::{DatabaseArgs, DatadirArgs},
     dirs::{ChainPath, DataDirPath},
+    zstd_dictionaries::load_zstd_dictionaries,
 };
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider, StaticFileProvider},
@@ -89,6 +90,8 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
             config.stages.era = config.stages.era.with_datadir(data_dir.data_dir());
         }
 
+        load_zstd_dictionaries(&data_dir.zstd_dictionaries())?;
+
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
         let (db, sfp) = match access {
             AccessRights::RW => (
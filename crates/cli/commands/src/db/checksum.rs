@@ -1,9 +1,11 @@
 use crate::{
     common::CliNodeTypes,
-    db::get::{maybe_json_value_parser, table_key},
+    db::get::{maybe_json_value_parser, walk_range},
 };
-use ahash::RandomState;
+use ahash::{AHasher, RandomState};
+use alloy_primitives::BlockNumber;
 use clap::Parser;
+use eyre::OptionExt;
 use reth_chainspec::EthereumHardforks;
 use reth_db::DatabaseEnv;
 use reth_db_api::{
@@ -12,19 +14,48 @@ use reth_db_api::{
 };
 use reth_db_common::DbTool;
 use reth_node_builder::{NodeTypesWithDB, NodeTypesWithDBAdapter};
-use reth_provider::{providers::ProviderNodeTypes, DBProvider};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, BlockNumReader, DBProvider,
+    ProviderError, ProviderFactory, StageCheckpointReader, StaticFileProviderFactory,
+};
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use serde::{Deserialize, Serialize};
 use std::{
     hash::{BuildHasher, Hasher},
+    mem,
+    ops::RangeInclusive,
+    path::PathBuf,
     sync::Arc,
     time::{Duration, Instant},
 };
 use tracing::{info, warn};
 
+/// Current version of the range checksums file format.
+pub(crate) const RANGE_CHECKSUMS_VERSION: u64 = 1;
+
+/// Tables with the state of the chain at the tip.
+const STATE_TABLES: [Tables; 7] = [
+    Tables::PlainAccountState,
+    Tables::PlainStorageState,
+    Tables::Bytecodes,
+    Tables::HashedAccounts,
+    Tables::HashedStorages,
+    Tables::AccountsTrie,
+    Tables::StoragesTrie,
+];
+
+/// Tables with the plain state, which only hold the accounts and storage slots changed after the
+/// pivot block on a snap synced node.
+const PLAIN_STATE_TABLES: [Tables; 2] = [Tables::PlainAccountState, Tables::PlainStorageState];
+
 #[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 /// The arguments for the `reth db checksum` command
 pub struct Command {
     /// The table name
-    table: Tables,
+    #[arg(required = true)]
+    table: Option<Tables>,
 
     /// The start of the range to checksum.
     #[arg(long, value_parser = maybe_json_value_parser)]
@@ -38,6 +69,57 @@ pub struct Command {
     /// checksum.
     #[arg(long)]
     limit: Option<usize>,
+
+    #[command(subcommand)]
+    subcommand: Option<Subcommand>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Subcommand {
+    /// Calculates the content checksum of a static file segment over a block range
+    StaticFile {
+        /// The static file segment
+        segment: StaticFileSegment,
+
+        /// The first block of the range to checksum.
+        #[arg(long, default_value_t = 0)]
+        start_block: BlockNumber,
+
+        /// The last block of the range to checksum. Defaults to the highest block of the segment.
+        #[arg(long)]
+        end_block: Option<BlockNumber>,
+    },
+    /// Calculates checksums over block ranges of static file segments and key ranges of state
+    /// tables, to be compared with another node using `reth db compare`
+    Ranges {
+        /// The file to write the checksums to.
+        #[arg(long, short)]
+        output: PathBuf,
+
+        /// The number of blocks covered by each static file checksum.
+        #[arg(long, default_value_t = 100_000)]
+        blocks_per_range: u64,
+
+        /// The first block of the static file ranges.
+        #[arg(long, default_value_t = 0)]
+        start_block: BlockNumber,
+
+        /// The last block of the static file ranges. Defaults to the highest block of each
+        /// segment.
+        #[arg(long)]
+        end_block: Option<BlockNumber>,
+
+        /// The static file segments to checksum. Defaults to all segments with static files.
+        #[arg(long, value_delimiter = ',')]
+        segments: Option<Vec<StaticFileSegment>>,
+
+        /// The tables to checksum. Defaults to the plain state, hashed state and trie tables.
+        ///
+        /// The plain state is left out by default on nodes that were snap synced, since it only
+        /// holds the accounts and storage slots changed after the pivot block.
+        #[arg(long, value_delimiter = ',')]
+        tables: Option<Vec<Tables>>,
+    },
 }
 
 impl Command {
@@ -47,16 +129,173 @@ impl Command {
         tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
     ) -> eyre::Result<()> {
         warn!("This command should be run without the node running!");
-        self.table.view(&ChecksumViewer {
-            tool,
-            start_key: self.start_key,
-            end_key: self.end_key,
-            limit: self.limit,
-        })?;
+        let Some(subcommand) = self.subcommand else {
+            let table = self.table.ok_or_eyre("The table name is required")?;
+            warn_snap_synced_plain_state(tool, table)?;
+            table.view(&ChecksumViewer {
+                tool,
+                start_key: self.start_key,
+                end_key: self.end_key,
+                limit: self.limit,
+            })?;
+            return Ok(())
+        };
+
+        match subcommand {
+            Subcommand::StaticFile { segment, start_block, end_block } => {
+                let end_block = match end_block {
+                    Some(end_block) => end_block,
+                    None => highest_static_file_block(tool, segment)?,
+                };
+                let start_time = Instant::now();
+                let checksum =
+                    static_file_checksum(&tool.provider_factory, segment, start_block..=end_block)?;
+                info!(
+                    "Checksum for segment `{segment}` blocks {start_block}..={end_block}: {checksum:#x} (elapsed: {:?})",
+                    start_time.elapsed()
+                );
+            }
+            Subcommand::Ranges {
+                output,
+                blocks_per_range,
+                start_block,
+                end_block,
+                segments,
+                tables,
+            } => {
+                let checksums = range_checksums(
+                    tool,
+                    blocks_per_range,
+                    start_block..=end_block.unwrap_or(BlockNumber::MAX),
+                    segments,
+                    tables,
+                )?;
+                reth_fs_util::write(&output, serde_json::to_vec_pretty(&checksums)?)?;
+                info!(target: "reth::cli", path = ?output, "Checksums written");
+            }
+        }
         Ok(())
     }
 }
 
+/// Calculates the checksums of the static file segments over consecutive ranges of
+/// `blocks_per_range` blocks, and of the tables for every first byte of their keys.
+///
+/// Segments and tables default to all segments with static files and the state tables, without
+/// the plain state tables on snap synced nodes.
+pub(crate) fn range_checksums<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    blocks_per_range: u64,
+    blocks: RangeInclusive<BlockNumber>,
+    segments: Option<Vec<StaticFileSegment>>,
+    tables: Option<Vec<Tables>>,
+) -> eyre::Result<RangeChecksums> {
+    eyre::ensure!(blocks_per_range > 0, "--blocks-per-range must be greater than 0");
+
+    let mut checksums = RangeChecksums {
+        version: RANGE_CHECKSUMS_VERSION,
+        tip: tool.provider_factory.provider()?.best_block_number()?,
+        static_files: Vec::new(),
+        tables: Vec::new(),
+    };
+
+    let segments = segments.unwrap_or_else(|| StaticFileSegment::iter().collect());
+    for segment in segments {
+        let Some(highest) =
+            tool.provider_factory.static_file_provider().get_highest_static_file_block(segment)
+        else {
+            continue
+        };
+
+        let end_block = highest.min(*blocks.end());
+        let mut ranges = Vec::new();
+        for blocks in block_ranges(*blocks.start(), end_block, blocks_per_range) {
+            let checksum = static_file_checksum(&tool.provider_factory, segment, blocks.clone())?;
+            info!(
+                target: "reth::cli",
+                %segment,
+                ?blocks,
+                checksum = format_args!("{checksum:#x}"),
+                "Calculated checksum"
+            );
+            ranges.push(BlockRangeChecksum { blocks, checksum });
+        }
+        checksums.static_files.push(SegmentChecksums { segment, ranges });
+    }
+
+    let tables = match tables {
+        Some(tables) => {
+            for table in &tables {
+                warn_snap_synced_plain_state(tool, *table)?;
+            }
+            tables
+        }
+        None if is_snap_synced(tool)? => {
+            info!(target: "reth::cli", "Skipping the plain state tables of the snap synced node");
+            STATE_TABLES.into_iter().filter(|table| !PLAIN_STATE_TABLES.contains(table)).collect()
+        }
+        None => STATE_TABLES.to_vec(),
+    };
+    for table in tables {
+        let key_prefixes = table.view(&KeyPrefixChecksumViewer::new(tool))?;
+        info!(target: "reth::cli", %table, "Calculated checksums");
+        checksums.tables.push(TableChecksums { table: table.name().to_string(), key_prefixes });
+    }
+
+    Ok(checksums)
+}
+
+/// Returns `true` if the state was downloaded with snap sync.
+fn is_snap_synced<N: ProviderNodeTypes>(tool: &DbTool<N>) -> eyre::Result<bool> {
+    Ok(tool
+        .provider_factory
+        .get_stage_checkpoint(StageId::SnapSync)?
+        .and_then(|checkpoint| checkpoint.snap_sync_pivot())
+        .is_some())
+}
+
+/// Warns if the table is a plain state table of a snap synced node, whose entries differ from the
+/// ones of a fully synced node.
+pub(crate) fn warn_snap_synced_plain_state<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    table: Tables,
+) -> eyre::Result<()> {
+    if PLAIN_STATE_TABLES.contains(&table) && is_snap_synced(tool)? {
+        warn!(
+            target: "reth::cli",
+            %table,
+            "The node was snap synced, the plain state only holds the accounts and storage slots \
+             changed after the pivot block"
+        );
+    }
+    Ok(())
+}
+
+/// Hashes table entries and static file rows.
+///
+/// Uses a hasher with fixed seeds, so the checksums of two nodes running the same build can be
+/// compared.
+#[derive(Debug)]
+pub(crate) struct Checksummer(AHasher);
+
+impl Default for Checksummer {
+    fn default() -> Self {
+        Self(RandomState::with_seeds(1, 2, 3, 4).build_hasher())
+    }
+}
+
+impl Checksummer {
+    /// Adds a value to the checksum.
+    pub(crate) fn update(&mut self, value: &[u8]) {
+        self.0.write(value);
+    }
+
+    /// Returns the checksum of all added values.
+    pub(crate) fn finalize(&self) -> u64 {
+        self.0.finish()
+    }
+}
+
 pub(crate) struct ChecksumViewer<'a, N: NodeTypesWithDB> {
     tool: &'a DbTool<N>,
     start_key: Option<String>,
@@ -83,26 +322,11 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
         );
 
         let mut cursor = tx.cursor_read::<RawTable<T>>()?;
-        let walker = match (self.start_key.as_deref(), self.end_key.as_deref()) {
-            (Some(start), Some(end)) => {
-                let start_key = table_key::<T>(start).map(RawKey::new)?;
-                let end_key = table_key::<T>(end).map(RawKey::new)?;
-                cursor.walk_range(start_key..=end_key)?
-            }
-            (None, Some(end)) => {
-                let end_key = table_key::<T>(end).map(RawKey::new)?;
-
-                cursor.walk_range(..=end_key)?
-            }
-            (Some(start), None) => {
-                let start_key = table_key::<T>(start).map(RawKey::new)?;
-                cursor.walk_range(start_key..)?
-            }
-            (None, None) => cursor.walk_range(..)?,
-        };
+        let walker =
+            walk_range::<T, _>(&mut cursor, self.start_key.as_deref(), self.end_key.as_deref())?;
 
         let start_time = Instant::now();
-        let mut hasher = RandomState::with_seeds(1, 2, 3, 4).build_hasher();
+        let mut checksummer = Checksummer::default();
         let mut total = 0;
 
         let limit = self.limit.unwrap_or(usize::MAX);
@@ -115,8 +339,8 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
                 info!("Hashed {index} entries.");
             }
 
-            hasher.write(k.raw_key());
-            hasher.write(v.raw_value());
+            checksummer.update(k.raw_key());
+            checksummer.update(v.raw_value());
 
             if enumerate_start_key.is_none() {
                 enumerate_start_key = Some(k.clone());
@@ -135,7 +359,7 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
             info!("end-key: {}", serde_json::to_string(&e.key()?).unwrap_or_default());
         }
 
-        let checksum = hasher.finish();
+        let checksum = checksummer.finalize();
         let elapsed = start_time.elapsed();
 
         info!("Checksum for table `{}`: {:#x} (elapsed: {:?})", T::NAME, checksum, elapsed);
@@ -143,3 +367,204 @@ impl<N: ProviderNodeTypes> TableViewer<(u64, Duration)> for ChecksumViewer<'_, N
         Ok((checksum, elapsed))
     }
 }
+
+/// Calculates the checksums of a table for every first byte of its keys, in key order.
+///
+/// If the checksums of another node are given, stops at the first checksum that differs from
+/// them.
+pub(crate) struct KeyPrefixChecksumViewer<'a, N: NodeTypesWithDB> {
+    tool: &'a DbTool<N>,
+    expected: Option<&'a [u64]>,
+}
+
+impl<'a, N: NodeTypesWithDB> KeyPrefixChecksumViewer<'a, N> {
+    pub(crate) const fn new(tool: &'a DbTool<N>) -> Self {
+        Self { tool, expected: None }
+    }
+
+    pub(crate) const fn with_expected(tool: &'a DbTool<N>, expected: &'a [u64]) -> Self {
+        Self { tool, expected: Some(expected) }
+    }
+
+    /// Finalizes the checksum of the next key prefix, returning `true` if it differs from the
+    /// expected one.
+    fn push(&self, checksums: &mut Vec<u64>, checksummer: &mut Checksummer) -> bool {
+        checksums.push(mem::take(checksummer).finalize());
+        self.expected.is_some_and(|expected| expected.get(checksums.len() - 1) != checksums.last())
+    }
+}
+
+impl<N: ProviderNodeTypes> TableViewer<Vec<u64>> for KeyPrefixChecksumViewer<'_, N> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<Vec<u64>, Self::Error> {
+        let provider =
+            self.tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+        let mut cursor = provider.tx_ref().cursor_read::<RawTable<T>>()?;
+
+        let mut checksums = Vec::with_capacity(256);
+        let mut checksummer = Checksummer::default();
+        for entry in cursor.walk(None)? {
+            let (k, v): (RawKey<T::Key>, RawValue<T::Value>) = entry?;
+
+            let prefix = k.raw_key().first().copied().unwrap_or_default() as usize;
+            while checksums.len() < prefix {
+                if self.push(&mut checksums, &mut checksummer) {
+                    return Ok(checksums)
+                }
+            }
+
+            checksummer.update(k.raw_key());
+            checksummer.update(v.raw_value());
+        }
+
+        while checksums.len() < 256 {
+            if self.push(&mut checksums, &mut checksummer) {
+                break
+            }
+        }
+        Ok(checksums)
+    }
+}
+
+/// Calculates the checksum of the rows of a static file segment in the given block range.
+///
+/// Rows are hashed decompressed, so the checksum doesn't depend on how the static files are
+/// compressed.
+pub(crate) fn static_file_checksum<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    segment: StaticFileSegment,
+    blocks: RangeInclusive<BlockNumber>,
+) -> eyre::Result<u64> {
+    let rows = if segment.is_tx_based() {
+        let provider = provider_factory.provider()?;
+        let first = provider
+            .block_body_indices(*blocks.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*blocks.start()))?;
+        let last = provider
+            .block_body_indices(*blocks.end())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*blocks.end()))?;
+        first.first_tx_num()..last.next_tx_num()
+    } else {
+        *blocks.start()..*blocks.end() + 1
+    };
+
+    let static_file_provider = provider_factory.static_file_provider();
+    let mask = (1 << segment.columns()) - 1;
+    let mut checksummer = Checksummer::default();
+    let mut number = rows.start;
+    while number < rows.end {
+        let jar = if segment.is_tx_based() {
+            static_file_provider.get_segment_provider_from_transaction(segment, number, None)?
+        } else {
+            static_file_provider.get_segment_provider_from_block(segment, number, None)?
+        };
+        let jar_end = if segment.is_tx_based() {
+            jar.user_header().tx_end()
+        } else {
+            jar.user_header().block_end()
+        }
+        .ok_or_eyre("Static file is empty")?;
+
+        let mut cursor = jar.cursor()?;
+        let end = rows.end.min(jar_end + 1);
+        for number in number..end {
+            let row = cursor
+                .get(number.into(), mask)?
+                .ok_or_else(|| eyre::eyre!("Missing row {number} in segment {segment}"))?;
+            for column in row {
+                checksummer.update(column);
+            }
+        }
+        number = end;
+    }
+
+    Ok(checksummer.finalize())
+}
+
+/// Returns the highest block of a static file segment.
+pub(crate) fn highest_static_file_block<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    segment: StaticFileSegment,
+) -> eyre::Result<BlockNumber> {
+    tool.provider_factory
+        .static_file_provider()
+        .get_highest_static_file_block(segment)
+        .ok_or_else(|| eyre::eyre!("No static files found for segment {segment}"))
+}
+
+/// Splits the blocks from `start` to `end` into ranges of `blocks_per_range` blocks.
+fn block_ranges(
+    start: BlockNumber,
+    end: BlockNumber,
+    blocks_per_range: u64,
+) -> impl Iterator<Item = RangeInclusive<BlockNumber>> {
+    (start..=end)
+        .step_by(blocks_per_range as usize)
+        .map(move |range_start| range_start..=end.min(range_start + blocks_per_range - 1))
+}
+
+/// Checksums written by `reth db checksum ranges`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RangeChecksums {
+    /// Version of the file format.
+    pub(crate) version: u64,
+    /// The best block of the node, which the table checksums correspond to.
+    pub(crate) tip: BlockNumber,
+    /// Checksums of static file segments.
+    pub(crate) static_files: Vec<SegmentChecksums>,
+    /// Checksums of database tables.
+    pub(crate) tables: Vec<TableChecksums>,
+}
+
+/// Checksums of a static file segment over consecutive block ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SegmentChecksums {
+    /// The static file segment.
+    pub(crate) segment: StaticFileSegment,
+    /// Checksums of the block ranges, in block order.
+    pub(crate) ranges: Vec<BlockRangeChecksum>,
+}
+
+/// Checksum of a static file segment over a block range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BlockRangeChecksum {
+    /// The block range.
+    pub(crate) blocks: RangeInclusive<BlockNumber>,
+    /// The checksum of the rows of the block range.
+    pub(crate) checksum: u64,
+}
+
+/// Checksums of a database table for every first byte of its keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TableChecksums {
+    /// The table name.
+    pub(crate) table: String,
+    /// Checksums of the entries whose key starts with the byte at the same index.
+    pub(crate) key_prefixes: Vec<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_table_or_subcommand() {
+        let command = Command::try_parse_from(["checksum", "PlainAccountState"]).unwrap();
+        assert_eq!(command.table, Some(Tables::PlainAccountState));
+        assert!(command.subcommand.is_none());
+
+        let command = Command::try_parse_from(["checksum", "static-file", "headers"]).unwrap();
+        assert!(command.table.is_none());
+        assert!(matches!(command.subcommand, Some(Subcommand::StaticFile { .. })));
+
+        assert!(Command::try_parse_from(["checksum"]).is_err());
+    }
+
+    #[test]
+    fn split_block_ranges() {
+        assert_eq!(block_ranges(0, 24, 10).collect::<Vec<_>>(), vec![0..=9, 10..=19, 20..=24]);
+        assert_eq!(block_ranges(5, 5, 10).collect::<Vec<_>>(), vec![5..=5]);
+        assert_eq!(block_ranges(10, 5, 10).count(), 0);
+    }
+}
@@ -0,0 +1,288 @@
+//! Command that writes a compacted copy of the database.
+
+use crate::db::checksum::Checksummer;
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use eyre::{ensure, WrapErr};
+use human_bytes::human_bytes;
+use reth_db::{
+    lockfile::StorageLock,
+    mdbx::{tx::Tx, CopyFlags, DatabaseArguments, RO},
+    open_db_read_only,
+    version::DB_VERSION_FILE_NAME,
+    DatabaseEnv,
+};
+use reth_db_api::{database::Database, transaction::DbTx, Tables};
+use std::{
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+/// Name of the MDBX data file in a database directory.
+const MDBX_DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Name of the MDBX lock file in a database directory.
+const MDBX_LOCK_FILE_NAME: &str = "mdbx.lck";
+
+/// Interval at which the progress of the copy is logged.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The arguments for the `reth db compact` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the compacted database to. It must not contain a database yet.
+    #[arg(long, short, value_name = "DIR")]
+    output: PathBuf,
+
+    /// Replaces the database with the compacted copy once it has been verified.
+    ///
+    /// The node must be stopped, and the output directory must be on the same filesystem as the
+    /// database.
+    #[arg(long)]
+    replace: bool,
+}
+
+impl Command {
+    /// Execute `db compact` command
+    ///
+    /// The copy omits the free pages of the database, so it's only as large as the data it holds.
+    /// It's written from a read transaction, and verified by comparing the entries of every table
+    /// with the snapshot of that same transaction, so the node can keep running unless the
+    /// database is replaced.
+    pub fn execute(self, db_path: &Path, args: DatabaseArguments) -> eyre::Result<()> {
+        let output_file = self.output.join(MDBX_DATA_FILE_NAME);
+        ensure!(!output_file.exists(), "Database already exists at {:?}", output_file);
+        reth_fs_util::create_dir_all(&self.output)?;
+
+        // Make sure no node writes to the database while it's being replaced
+        let _lock = self
+            .replace
+            .then(|| StorageLock::try_acquire(db_path))
+            .transpose()
+            .wrap_err("Database is in use, stop the node to replace it")?;
+
+        let db = open_db_read_only(db_path, args.clone())?;
+        let before = SpaceStats::new(&db, &db_path.join(MDBX_DATA_FILE_NAME))?;
+        let mut tx = db.tx()?;
+        tx.disable_long_read_transaction_safety();
+
+        info!(target: "reth::cli", path = ?output_file, "Writing compacted database");
+        let start = Instant::now();
+        thread::scope(|scope| {
+            let copy = scope.spawn(|| {
+                tx.inner.copy(&output_file, CopyFlags::COMPACT | CopyFlags::FORCE_DYNAMIC_SIZE)
+            });
+
+            let used_bytes = before.used_pages() * before.page_size;
+            let mut last_log = Instant::now();
+            while !copy.is_finished() {
+                thread::sleep(Duration::from_millis(100));
+                if last_log.elapsed() >= PROGRESS_INTERVAL {
+                    let written = disk_usage(&output_file).unwrap_or_default();
+                    info!(
+                        target: "reth::cli",
+                        written = %human_bytes(written as f64),
+                        progress = %format!("{:.2}%", written as f64 / used_bytes as f64 * 100.0),
+                        "Writing compacted database"
+                    );
+                    last_log = Instant::now();
+                }
+            }
+
+            copy.join().expect("copy thread panicked")
+        })?;
+        info!(target: "reth::cli", elapsed = ?start.elapsed(), "Wrote compacted database");
+
+        // The version file is required to open the compacted database as a node's database
+        let version_file = db_path.join(DB_VERSION_FILE_NAME);
+        if version_file.exists() {
+            reth_fs_util::write(
+                self.output.join(DB_VERSION_FILE_NAME),
+                reth_fs_util::read(version_file)?,
+            )?;
+        }
+
+        let compacted = open_db_read_only(&self.output, args)?;
+        info!(target: "reth::cli", "Verifying compacted database");
+        verify_entries(&tx, &compacted)?;
+        drop(tx);
+        let after = SpaceStats::new(&compacted, &output_file)?;
+
+        let mut report = ComfyTable::new();
+        report.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        report.set_header(["", "Before", "After"]);
+        for (name, before, after) in [
+            (
+                "Disk Usage",
+                human_bytes(before.disk_usage as f64),
+                human_bytes(after.disk_usage as f64),
+            ),
+            (
+                "Page Size",
+                human_bytes(before.page_size as f64),
+                human_bytes(after.page_size as f64),
+            ),
+            ("Pages", before.pages.to_string(), after.pages.to_string()),
+            ("Used Pages", before.used_pages().to_string(), after.used_pages().to_string()),
+            ("Free Pages", before.free_pages.to_string(), after.free_pages.to_string()),
+            (
+                "Free Space",
+                human_bytes((before.free_pages * before.page_size) as f64),
+                human_bytes((after.free_pages * after.page_size) as f64),
+            ),
+        ] {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name)).add_cell(Cell::new(before)).add_cell(Cell::new(after));
+            report.add_row(row);
+        }
+        println!("{report}");
+
+        drop(compacted);
+        reth_fs_util::remove_file(self.output.join(MDBX_LOCK_FILE_NAME))?;
+
+        if self.replace {
+            drop(db);
+            // Renaming the data file replaces the database atomically, the lock file is recreated
+            // for the new data file on the next open
+            reth_fs_util::rename(&output_file, db_path.join(MDBX_DATA_FILE_NAME))?;
+            reth_fs_util::remove_file(db_path.join(MDBX_LOCK_FILE_NAME))?;
+            if self.output.join(DB_VERSION_FILE_NAME).exists() {
+                reth_fs_util::remove_file(self.output.join(DB_VERSION_FILE_NAME))?;
+            }
+            info!(target: "reth::cli", path = ?db_path, "Replaced database with compacted copy");
+        }
+
+        Ok(())
+    }
+}
+
+/// Page usage of a database.
+#[derive(Debug)]
+struct SpaceStats {
+    /// Disk space used by the data file in bytes.
+    disk_usage: u64,
+    /// Size of a page in bytes.
+    page_size: u64,
+    /// Number of pages allocated in the data file.
+    pages: u64,
+    /// Number of allocated pages that are free for reuse.
+    free_pages: u64,
+}
+
+impl SpaceStats {
+    fn new(db: &DatabaseEnv, data_file: &Path) -> eyre::Result<Self> {
+        Ok(Self {
+            disk_usage: disk_usage(data_file)?,
+            page_size: db.stat()?.page_size() as u64,
+            // Page numbers are 0 based
+            pages: db.info()?.last_pgno() as u64 + 1,
+            free_pages: db.freelist()? as u64,
+        })
+    }
+
+    const fn used_pages(&self) -> u64 {
+        self.pages - self.free_pages
+    }
+}
+
+/// Returns the disk space used by a file, which is less than its size if the file is sparse, as
+/// MDBX data files usually are.
+fn disk_usage(path: &Path) -> eyre::Result<u64> {
+    let metadata = reth_fs_util::metadata(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        // Blocks are always 512 bytes, regardless of the block size of the filesystem
+        Ok(metadata.blocks() * 512)
+    }
+    #[cfg(not(unix))]
+    Ok(metadata.len())
+}
+
+/// Checks that every table of the compacted database has the same entries as the snapshot of the
+/// database transaction.
+fn verify_entries(tx: &Tx<RO>, compacted: &DatabaseEnv) -> eyre::Result<()> {
+    let mut compacted_tx = compacted.tx()?;
+    compacted_tx.disable_long_read_transaction_safety();
+
+    // The values of the main database are the locations of the tables, which differ after
+    // compaction, so only the number of tables is compared
+    let tables = |tx: &Tx<RO>| {
+        let main_db = tx.inner.open_db(None).wrap_err("Could not open db.")?;
+        Ok::<_, eyre::Report>(tx.inner.db_stat(&main_db)?.entries())
+    };
+    ensure!(
+        tables(&compacted_tx)? == tables(tx)?,
+        "Compacted database has a different number of tables"
+    );
+
+    for table in Tables::ALL {
+        let (expected_entries, expected_checksum) = table_checksum(tx, table.name())?;
+        let (actual_entries, actual_checksum) = table_checksum(&compacted_tx, table.name())?;
+        ensure!(
+            actual_entries == expected_entries,
+            "Compacted table {table} has {actual_entries} entries, expected {expected_entries}"
+        );
+        ensure!(
+            actual_checksum == expected_checksum,
+            "Compacted table {table} has checksum {actual_checksum:#x}, expected {expected_checksum:#x}"
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the number of entries of a table and the checksum of its keys and values.
+fn table_checksum(tx: &Tx<RO>, table: &str) -> eyre::Result<(usize, u64)> {
+    let table_db = tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
+    let mut entries = 0;
+    let mut checksummer = Checksummer::default();
+    for entry in tx.inner.cursor(&table_db)?.iter_slices() {
+        let (key, value) = entry?;
+        checksummer.update(&key);
+        checksummer.update(&value);
+        entries += 1;
+    }
+    Ok((entries, checksummer.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use reth_db::{init_db, ClientVersion};
+    use reth_db_api::{tables, transaction::DbTxMut};
+
+    #[test]
+    fn compact_and_replace_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("db");
+        let args = DatabaseArguments::new(ClientVersion::default());
+
+        let db = init_db(&db_path, args.clone()).unwrap();
+        db.update(|tx| {
+            for number in 0..10_000 {
+                tx.put::<tables::CanonicalHeaders>(number, B256::with_last_byte(1)).unwrap();
+            }
+        })
+        .unwrap();
+        db.update(|tx| {
+            for number in 1_000..10_000 {
+                tx.delete::<tables::CanonicalHeaders>(number, None).unwrap();
+            }
+        })
+        .unwrap();
+        drop(db);
+
+        let output = dir.path().join("compacted");
+        Command { output: output.clone(), replace: true }.execute(&db_path, args.clone()).unwrap();
+        assert!(!output.join(MDBX_DATA_FILE_NAME).exists());
+
+        let db = open_db_read_only(&db_path, args).unwrap();
+        assert_eq!(db.freelist().unwrap(), 0);
+        let entries = db.view(|tx| tx.entries::<tables::CanonicalHeaders>()).unwrap().unwrap();
+        assert_eq!(entries, 1_000);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::db::checksum::{
+    static_file_checksum, warn_snap_synced_plain_state, BlockRangeChecksum,
+    KeyPrefixChecksumViewer, RangeChecksums, RANGE_CHECKSUMS_VERSION,
+};
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use eyre::ensure;
+use reth_db_api::Tables;
+use reth_db_common::DbTool;
+use reth_provider::{providers::ProviderNodeTypes, BlockNumReader, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use std::{fmt, ops::RangeInclusive, path::PathBuf};
+use tracing::{info, warn};
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db compare` command
+pub struct Command {
+    /// The checksums of the other node, written by `reth db checksum ranges`.
+    #[arg(long, value_name = "FILE")]
+    remote_checksums: PathBuf,
+}
+
+impl Command {
+    /// Execute `db compare` command
+    ///
+    /// Finds the first block range of every static file segment and the first key range of every
+    /// table whose checksum differs from the remote checksums.
+    ///
+    /// A diverging block range can be narrowed down by writing the remote checksums again with a
+    /// smaller `--blocks-per-range` over that range only.
+    ///
+    /// The plain state tables of a snap synced node only hold the accounts and storage slots
+    /// changed after the pivot block, so they always differ from a fully synced node. The hashed
+    /// state tables hold the complete state on both.
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let remote: RangeChecksums =
+            serde_json::from_slice(&reth_fs_util::read(&self.remote_checksums)?)?;
+
+        let mut report = ComfyTable::new();
+        report.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        report.set_header(vec![Cell::new("Segment / Table"), Cell::new("Result")]);
+        for (name, comparison) in compare_checksums(tool, &remote)? {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name)).add_cell(Cell::new(comparison));
+            report.add_row(row);
+        }
+
+        println!("{report}");
+        Ok(())
+    }
+}
+
+/// Result of comparing the checksums of a static file segment or table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Comparison {
+    /// All checksums match.
+    Matches,
+    /// The static files of the block range are missing locally.
+    MissingBlocks(RangeInclusive<BlockNumber>),
+    /// The first block range whose checksum differs.
+    DivergingBlocks(RangeInclusive<BlockNumber>),
+    /// The first byte of the first key range whose checksum differs.
+    DivergingKeys(u8),
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Matches => f.write_str("matches"),
+            Self::MissingBlocks(blocks) => write!(f, "missing locally from blocks {blocks:?}"),
+            Self::DivergingBlocks(blocks) => write!(f, "diverges at blocks {blocks:?}"),
+            Self::DivergingKeys(prefix) => {
+                write!(f, "diverges at keys starting with {prefix:#04x}")
+            }
+        }
+    }
+}
+
+/// Compares the static files and tables with the remote checksums, returning the result for every
+/// segment and table of the remote checksums.
+pub(crate) fn compare_checksums<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    remote: &RangeChecksums,
+) -> eyre::Result<Vec<(String, Comparison)>> {
+    ensure!(
+        remote.version == RANGE_CHECKSUMS_VERSION,
+        "Unsupported checksums version {}, expected {RANGE_CHECKSUMS_VERSION}",
+        remote.version
+    );
+
+    let tip = tool.provider_factory.provider()?.best_block_number()?;
+    if tip != remote.tip {
+        warn!(
+            target: "reth::cli",
+            local = tip,
+            remote = remote.tip,
+            "Nodes are at different blocks, table checksums will differ"
+        );
+    }
+
+    let mut results = Vec::new();
+    for segment_checksums in &remote.static_files {
+        let comparison =
+            compare_segment(tool, segment_checksums.segment, &segment_checksums.ranges)?;
+        results.push((segment_checksums.segment.to_string(), comparison));
+    }
+
+    for table_checksums in &remote.tables {
+        let table = table_checksums.table.parse::<Tables>().map_err(|err| eyre::eyre!(err))?;
+        warn_snap_synced_plain_state(tool, table)?;
+
+        info!(target: "reth::cli", %table, "Comparing checksums");
+        let checksums = table
+            .view(&KeyPrefixChecksumViewer::with_expected(tool, &table_checksums.key_prefixes))?;
+        let comparison = match checksums
+            .iter()
+            .zip(&table_checksums.key_prefixes)
+            .position(|(local, remote)| local != remote)
+        {
+            Some(prefix) => Comparison::DivergingKeys(prefix as u8),
+            None => Comparison::Matches,
+        };
+        results.push((table.to_string(), comparison));
+    }
+
+    Ok(results)
+}
+
+/// Returns the first block range of a segment whose checksum differs from the remote one.
+///
+/// The local checksums are calculated in order and the scan stops at the first diverging range, so
+/// an early divergence is found without reading the rest of the segment.
+fn compare_segment<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    segment: StaticFileSegment,
+    remote: &[BlockRangeChecksum],
+) -> eyre::Result<Comparison> {
+    let highest =
+        tool.provider_factory.static_file_provider().get_highest_static_file_block(segment);
+
+    for range in remote {
+        let blocks = &range.blocks;
+        if highest.is_none_or(|highest| highest < *blocks.end()) {
+            return Ok(Comparison::MissingBlocks(blocks.clone()))
+        }
+
+        info!(target: "reth::cli", %segment, ?blocks, "Calculating checksum");
+        if static_file_checksum(&tool.provider_factory, segment, blocks.clone())? != range.checksum
+        {
+            return Ok(Comparison::DivergingBlocks(blocks.clone()))
+        }
+    }
+
+    Ok(Comparison::Matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::checksum::range_checksums;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Address, BlockHash, U256};
+    use reth_db_api::{tables, transaction::DbTxMut};
+    use reth_primitives_traits::Account;
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        StaticFileWriter,
+    };
+
+    /// Creates a database with the headers up to `tip` in static files and an account, changing
+    /// the header at `diverging_block` and the balance of the account by `divergence`.
+    fn create_tool(
+        tip: BlockNumber,
+        diverging_block: BlockNumber,
+        divergence: u64,
+    ) -> DbTool<MockNodeTypesWithDB> {
+        let provider_factory = create_test_provider_factory();
+
+        let static_file_provider = provider_factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        for number in 0..=tip {
+            let gas_limit = if number == diverging_block { divergence } else { 0 };
+            let header = Header { number, gas_limit, ..Default::default() };
+            writer.append_header(&header, U256::ZERO, &BlockHash::ZERO).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::PlainAccountState>(
+                Address::repeat_byte(0xab),
+                Account { balance: U256::from(divergence), ..Default::default() },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        DbTool::new(provider_factory).unwrap()
+    }
+
+    #[test]
+    fn compare_diverging_databases() {
+        let remote = range_checksums(
+            &create_tool(9, 6, 1),
+            2,
+            0..=BlockNumber::MAX,
+            Some(vec![StaticFileSegment::Headers]),
+            Some(vec![Tables::PlainAccountState]),
+        )
+        .unwrap();
+        assert_eq!(remote.static_files[0].ranges.len(), 5);
+
+        let compare = |tool: DbTool<_>| {
+            compare_checksums(&tool, &remote)
+                .unwrap()
+                .into_iter()
+                .map(|(_, comparison)| comparison)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(compare(create_tool(9, 6, 1)), vec![Comparison::Matches, Comparison::Matches]);
+        assert_eq!(
+            compare(create_tool(9, 6, 2)),
+            vec![Comparison::DivergingBlocks(6..=7), Comparison::DivergingKeys(0xab)]
+        );
+        assert_eq!(compare(create_tool(9, 1, 2))[0], Comparison::DivergingBlocks(0..=1));
+        assert_eq!(compare(create_tool(6, 6, 1))[0], Comparison::MissingBlocks(6..=7));
+    }
+}
@@ -0,0 +1,434 @@
+//! Command that exports the entries of a database table to a portable file.
+
+use crate::db::{
+    checksum::warn_snap_synced_plain_state,
+    get::{maybe_json_value_parser, table_key},
+};
+use alloy_primitives::Bytes;
+use alloy_rlp::{Decodable, Encodable};
+use clap::{Parser, ValueEnum};
+use eyre::{bail, ensure, OptionExt};
+use reth_db_api::{
+    cursor::{DbCursorRO, RangeWalker},
+    table::Table,
+    transaction::DbTx,
+    RawKey, RawTable, RawValue, TableViewer, Tables,
+};
+use reth_db_common::DbTool;
+use reth_provider::providers::ProviderNodeTypes;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufWriter, Read, Write},
+    ops::Bound,
+    path::PathBuf,
+};
+use tracing::info;
+
+/// Current version of the export file format.
+pub(crate) const EXPORT_VERSION: u64 = 1;
+
+/// Maximum length of an RLP list read from an export file.
+///
+/// Bounds the allocation for a corrupted or malicious length prefix, table entries are much
+/// smaller.
+const MAX_RLP_LIST_LENGTH: usize = 64 * 1024 * 1024;
+
+/// The arguments for the `reth db export` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name
+    #[arg(long)]
+    table: Tables,
+
+    /// The range of keys to export, as `<START>..<END>` or `<START>..=<END>`.
+    ///
+    /// Either bound can be omitted, e.g. `100..` exports all keys from `100`. Keys are parsed
+    /// the same way as by `reth db get`.
+    #[arg(long, value_parser = parse_key_range)]
+    range: Option<KeyRange>,
+
+    /// The maximum number of entries to export.
+    ///
+    /// The entries of a key in a dupsort table are always exported together, so the limit is
+    /// exceeded to complete the last key.
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// The format of the export file.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Jsonl)]
+    format: ExportFormat,
+
+    /// The file to write the entries to.
+    #[arg(long, short)]
+    output: PathBuf,
+}
+
+impl Command {
+    /// Execute `db export` command
+    ///
+    /// The entries are exported as they're stored, so the plain state tables of a snap synced
+    /// node only hold the accounts and storage slots changed after the pivot block.
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        warn_snap_synced_plain_state(tool, self.table)?;
+        self.table.view(&ExportViewer { tool, args: &self })
+    }
+}
+
+struct ExportViewer<'a, N: ProviderNodeTypes> {
+    tool: &'a DbTool<N>,
+    args: &'a Command,
+}
+
+impl<N: ProviderNodeTypes> TableViewer<()> for ExportViewer<'_, N> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        let provider = self.tool.provider_factory.provider()?;
+        let mut cursor = provider.tx_ref().cursor_read::<RawTable<T>>()?;
+        let walker = self.args.range.clone().unwrap_or_default().walk::<T, _>(&mut cursor)?;
+
+        let header = ExportHeader { version: EXPORT_VERSION, table: T::NAME.to_string() };
+        let file = BufWriter::new(File::create(&self.args.output)?);
+        let mut writer = ExportWriter::new(self.args.format, file, &header)?;
+
+        let limit = self.args.limit.unwrap_or(usize::MAX);
+        let mut last_key = None;
+        let mut total = 0;
+        for entry in walker {
+            let (key, value): (RawKey<T::Key>, RawValue<T::Value>) = entry?;
+            // Import replaces all entries of a key, so the entries of a dupsort key must not be
+            // split by the limit
+            if total >= limit && !(T::DUPSORT && last_key.as_ref() == Some(&key)) {
+                break
+            }
+            if T::DUPSORT {
+                last_key = Some(key.clone());
+            }
+
+            // Only the raw entry is read back on import, the decoded one is for readability
+            let (decoded_key, decoded_value) = match self.args.format {
+                ExportFormat::Jsonl => (
+                    Some(serde_json::to_value(key.key()?)?),
+                    Some(serde_json::to_value(value.value()?)?),
+                ),
+                ExportFormat::Rlp => (None, None),
+            };
+            writer.write_entry(&ExportEntry {
+                raw_key: key.into_key().into(),
+                raw_value: value.into_value().into(),
+                key: decoded_key,
+                value: decoded_value,
+            })?;
+
+            total += 1;
+            if total % 100_000 == 0 {
+                info!(target: "reth::cli", "Exported {total} entries.");
+            }
+        }
+        writer.finish()?;
+
+        info!(
+            target: "reth::cli",
+            table = T::NAME,
+            entries = total,
+            path = ?self.args.output,
+            "Exported table"
+        );
+        Ok(())
+    }
+}
+
+/// A range of table keys, with the keys as JSON strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KeyRange {
+    start: Option<String>,
+    end: Bound<String>,
+}
+
+impl Default for KeyRange {
+    fn default() -> Self {
+        Self { start: None, end: Bound::Unbounded }
+    }
+}
+
+impl KeyRange {
+    /// Walks the entries of the table within the range.
+    pub(crate) fn walk<'cursor, T: Table, C: DbCursorRO<RawTable<T>>>(
+        self,
+        cursor: &'cursor mut C,
+    ) -> eyre::Result<RangeWalker<'cursor, RawTable<T>, C>> {
+        let start = self.start.as_deref().map(table_key::<T>).transpose()?.map(RawKey::new);
+        let end = match self.end {
+            Bound::Included(key) => Bound::Included(RawKey::new(table_key::<T>(&key)?)),
+            Bound::Excluded(key) => Bound::Excluded(RawKey::new(table_key::<T>(&key)?)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        Ok(cursor.walk_range((start.map_or(Bound::Unbounded, Bound::Included), end))?)
+    }
+}
+
+/// Parses a [`KeyRange`] from `<START>..<END>` or `<START>..=<END>`.
+pub(crate) fn parse_key_range(value: &str) -> eyre::Result<KeyRange> {
+    let (start, end) = value
+        .split_once("..")
+        .ok_or_eyre("Expected a range of keys, e.g. `<START>..<END>` or `<START>..=<END>`")?;
+    let start = (!start.is_empty()).then(|| maybe_json_value_parser(start)).transpose()?;
+    let end = match end.strip_prefix('=') {
+        Some(end) => Bound::Included(maybe_json_value_parser(end)?),
+        None if end.is_empty() => Bound::Unbounded,
+        None => Bound::Excluded(maybe_json_value_parser(end)?),
+    };
+    Ok(KeyRange { start, end })
+}
+
+/// Format of the files written by `reth db export`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// One JSON object per line, with both the raw and the decoded entry.
+    #[default]
+    Jsonl,
+    /// A sequence of RLP lists with the raw entry.
+    Rlp,
+}
+
+/// Header written at the start of every export file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ExportHeader {
+    /// Version of the export file format.
+    pub(crate) version: u64,
+    /// Name of the exported table.
+    pub(crate) table: String,
+}
+
+/// A table entry, with its key and value as they're encoded in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ExportEntry {
+    /// The encoded key.
+    pub(crate) raw_key: Bytes,
+    /// The compressed value.
+    pub(crate) raw_value: Bytes,
+    /// The decoded key, if written by the format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) key: Option<serde_json::Value>,
+    /// The decoded value, if written by the format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) value: Option<serde_json::Value>,
+}
+
+/// Writes an export file in the given [`ExportFormat`].
+pub(crate) struct ExportWriter<W> {
+    format: ExportFormat,
+    writer: W,
+}
+
+impl<W: Write> ExportWriter<W> {
+    /// Creates a new writer and writes the header.
+    pub(crate) fn new(
+        format: ExportFormat,
+        writer: W,
+        header: &ExportHeader,
+    ) -> eyre::Result<Self> {
+        let mut this = Self { format, writer };
+        match format {
+            ExportFormat::Jsonl => this.write_json(header)?,
+            ExportFormat::Rlp => this.write_rlp_list(&[&header.version, &header.table])?,
+        }
+        Ok(this)
+    }
+
+    /// Writes a table entry.
+    pub(crate) fn write_entry(&mut self, entry: &ExportEntry) -> eyre::Result<()> {
+        match self.format {
+            ExportFormat::Jsonl => self.write_json(entry),
+            ExportFormat::Rlp => self.write_rlp_list(&[&entry.raw_key, &entry.raw_value]),
+        }
+    }
+
+    /// Flushes the underlying writer.
+    pub(crate) fn finish(mut self) -> eyre::Result<()> {
+        Ok(self.writer.flush()?)
+    }
+
+    fn write_json<T: Serialize>(&mut self, value: &T) -> eyre::Result<()> {
+        serde_json::to_writer(&mut self.writer, value)?;
+        Ok(self.writer.write_all(b"\n")?)
+    }
+
+    fn write_rlp_list(&mut self, items: &[&dyn Encodable]) -> eyre::Result<()> {
+        let payload_length = items.iter().map(|item| item.length()).sum();
+        let mut buf =
+            Vec::with_capacity(alloy_rlp::length_of_length(payload_length) + payload_length);
+        alloy_rlp::Header { list: true, payload_length }.encode(&mut buf);
+        for item in items {
+            item.encode(&mut buf);
+        }
+        Ok(self.writer.write_all(&buf)?)
+    }
+}
+
+/// Reads an export file written by [`ExportWriter`].
+pub(crate) struct ExportReader<R> {
+    format: ExportFormat,
+    reader: R,
+}
+
+impl<R: BufRead> ExportReader<R> {
+    /// Creates a new reader and reads the header.
+    pub(crate) fn new(format: ExportFormat, reader: R) -> eyre::Result<(Self, ExportHeader)> {
+        let mut this = Self { format, reader };
+        let header = match format {
+            ExportFormat::Jsonl => this.read_json()?,
+            ExportFormat::Rlp => this.read_rlp_list()?.map(decode_rlp_header).transpose()?,
+        }
+        .ok_or_eyre("Export file is empty")?;
+
+        ensure!(
+            header.version == EXPORT_VERSION,
+            "Unsupported export version {}, expected {EXPORT_VERSION}",
+            header.version
+        );
+        Ok((this, header))
+    }
+
+    /// Reads the next table entry, returning `None` at the end of the file.
+    pub(crate) fn read_entry(&mut self) -> eyre::Result<Option<ExportEntry>> {
+        match self.format {
+            ExportFormat::Jsonl => self.read_json(),
+            ExportFormat::Rlp => self.read_rlp_list()?.map(decode_rlp_entry).transpose(),
+        }
+    }
+
+    fn read_json<T: DeserializeOwned>(&mut self) -> eyre::Result<Option<T>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_str(&line)?))
+    }
+
+    /// Reads the payload of the next RLP list.
+    fn read_rlp_list(&mut self) -> eyre::Result<Option<Vec<u8>>> {
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(None)
+        }
+
+        let mut prefix = [0; 1];
+        self.reader.read_exact(&mut prefix)?;
+        let payload_length = match prefix[0] {
+            prefix @ alloy_rlp::EMPTY_LIST_CODE..=0xf7 => {
+                (prefix - alloy_rlp::EMPTY_LIST_CODE) as usize
+            }
+            prefix @ 0xf8.. => {
+                let length_of_length = (prefix - 0xf7) as usize;
+                let mut length = [0; 8];
+                self.reader.read_exact(&mut length[8 - length_of_length..])?;
+                u64::from_be_bytes(length) as usize
+            }
+            _ => bail!("Expected an RLP list"),
+        };
+
+        ensure!(
+            payload_length <= MAX_RLP_LIST_LENGTH,
+            "RLP list length {payload_length} exceeds the maximum of {MAX_RLP_LIST_LENGTH}"
+        );
+
+        // Grow the buffer with the bytes actually read instead of trusting the length prefix
+        let mut payload = Vec::new();
+        (&mut self.reader).take(payload_length as u64).read_to_end(&mut payload)?;
+        ensure!(payload.len() == payload_length, "Unexpected end of the export file");
+        Ok(Some(payload))
+    }
+}
+
+fn decode_rlp_header(payload: Vec<u8>) -> eyre::Result<ExportHeader> {
+    let buf = &mut payload.as_slice();
+    let header = ExportHeader { version: u64::decode(buf)?, table: String::decode(buf)? };
+    ensure!(buf.is_empty(), "Unexpected trailing bytes in export header");
+    Ok(header)
+}
+
+fn decode_rlp_entry(payload: Vec<u8>) -> eyre::Result<ExportEntry> {
+    let buf = &mut payload.as_slice();
+    let entry = ExportEntry {
+        raw_key: Bytes::decode(buf)?,
+        raw_value: Bytes::decode(buf)?,
+        key: None,
+        value: None,
+    };
+    ensure!(buf.is_empty(), "Unexpected trailing bytes in export entry");
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_key_ranges() {
+        assert_eq!(
+            parse_key_range("100..200").unwrap(),
+            KeyRange { start: Some("100".into()), end: Bound::Excluded("200".into()) }
+        );
+        assert_eq!(
+            parse_key_range("..=0x01").unwrap(),
+            KeyRange { start: None, end: Bound::Included("\"0x01\"".into()) }
+        );
+        assert_eq!(parse_key_range("..").unwrap(), KeyRange::default());
+        assert!(parse_key_range("100").is_err());
+    }
+
+    #[test]
+    fn rlp_list_length_is_bounded() {
+        let mut buf = vec![0xfb];
+        buf.extend_from_slice(&(MAX_RLP_LIST_LENGTH as u32 + 1).to_be_bytes());
+        let mut reader = ExportReader { format: ExportFormat::Rlp, reader: buf.as_slice() };
+        assert!(reader.read_entry().is_err());
+
+        // Truncated payload
+        let mut reader =
+            ExportReader { format: ExportFormat::Rlp, reader: [0xc3, 0x80].as_slice() };
+        assert!(reader.read_entry().is_err());
+    }
+
+    #[test]
+    fn export_file_roundtrip() {
+        let header = ExportHeader { version: EXPORT_VERSION, table: "PlainStorageState".into() };
+        let entries = [
+            ExportEntry {
+                raw_key: Bytes::from_static(&[1; 20]),
+                raw_value: Bytes::from_static(&[2; 33]),
+                key: Some(serde_json::json!("key")),
+                value: Some(serde_json::json!({ "value": 1 })),
+            },
+            // Large enough to need a long RLP list header
+            ExportEntry {
+                raw_key: Bytes::from_static(&[3; 20]),
+                raw_value: Bytes::from(vec![4; 1024]),
+                key: None,
+                value: None,
+            },
+        ];
+
+        for format in [ExportFormat::Jsonl, ExportFormat::Rlp] {
+            let mut buf = Vec::new();
+            let mut writer = ExportWriter::new(format, &mut buf, &header).unwrap();
+            for entry in &entries {
+                writer.write_entry(entry).unwrap();
+            }
+            writer.finish().unwrap();
+
+            let (mut reader, read_header) = ExportReader::new(format, buf.as_slice()).unwrap();
+            assert_eq!(read_header, header);
+            for entry in &entries {
+                let read_entry = reader.read_entry().unwrap().unwrap();
+                assert_eq!(read_entry.raw_key, entry.raw_key);
+                assert_eq!(read_entry.raw_value, entry.raw_value);
+                if format == ExportFormat::Jsonl {
+                    assert_eq!(&read_entry, entry);
+                }
+            }
+            assert!(reader.read_entry().unwrap().is_none());
+        }
+    }
+}
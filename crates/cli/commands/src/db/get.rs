@@ -1,10 +1,13 @@
 use alloy_consensus::Header;
-use alloy_primitives::{hex, BlockHash};
+use alloy_primitives::{hex, Address, BlockHash};
 use clap::Parser;
 use reth_db::static_file::{
-    ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask, TransactionMask,
+    AccountChangeSetMask, ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask,
+    SenderMask, StorageChangeSetMask, TransactionMask,
 };
 use reth_db_api::{
+    cursor::{DbCursorRO, RangeWalker},
+    models::{StaticFileAccountChangeSet, StaticFileStorageChangeSet},
     table::{Decompress, DupSort, Table},
     tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
 };
@@ -13,6 +16,7 @@ use reth_node_api::{ReceiptTy, TxTy};
 use reth_node_builder::NodeTypesWithDB;
 use reth_provider::{providers::ProviderNodeTypes, StaticFileProviderFactory};
 use reth_static_file_types::StaticFileSegment;
+use std::ops::Bound;
 use tracing::error;
 
 /// The arguments for the `reth db get` command
@@ -72,6 +76,17 @@ impl Command {
                     StaticFileSegment::Receipts => {
                         (table_key::<tables::Receipts>(&key)?, <ReceiptMask<ReceiptTy<N>>>::MASK)
                     }
+                    StaticFileSegment::Senders => {
+                        (table_key::<tables::TransactionSenders>(&key)?, <SenderMask>::MASK)
+                    }
+                    StaticFileSegment::AccountChangeSets => (
+                        table_key::<tables::AccountChangeSets>(&key)?,
+                        <AccountChangeSetMask>::MASK,
+                    ),
+                    StaticFileSegment::StorageChangeSets => (
+                        table_key::<tables::AccountChangeSets>(&key)?,
+                        <StorageChangeSetMask>::MASK,
+                    ),
                     StaticFileSegment::BlockMeta => todo!(),
                 };
 
@@ -114,6 +129,22 @@ impl Command {
                                     )?;
                                     println!("{}", serde_json::to_string_pretty(&receipt)?);
                                 }
+                                StaticFileSegment::Senders => {
+                                    let sender = Address::decompress(content[0].as_slice())?;
+                                    println!("{}", serde_json::to_string_pretty(&sender)?);
+                                }
+                                StaticFileSegment::AccountChangeSets => {
+                                    let change_set = StaticFileAccountChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&change_set)?);
+                                }
+                                StaticFileSegment::StorageChangeSets => {
+                                    let change_set = StaticFileStorageChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&change_set)?);
+                                }
                                 StaticFileSegment::BlockMeta => {
                                     todo!()
                                 }
@@ -136,6 +167,20 @@ pub(crate) fn table_key<T: Table>(key: &str) -> Result<T::Key, eyre::Error> {
     serde_json::from_str(key).map_err(|e| eyre::eyre!(e))
 }
 
+/// Walks the entries of a table, optionally bounded by the given keys.
+pub(crate) fn walk_range<'cursor, T: Table, C: DbCursorRO<RawTable<T>>>(
+    cursor: &'cursor mut C,
+    start_key: Option<&str>,
+    end_key: Option<&str>,
+) -> eyre::Result<RangeWalker<'cursor, RawTable<T>, C>> {
+    let start_key = start_key.map(table_key::<T>).transpose()?.map(RawKey::new);
+    let end_key = end_key.map(table_key::<T>).transpose()?.map(RawKey::new);
+    Ok(cursor.walk_range((
+        start_key.map_or(Bound::Unbounded, Bound::Included),
+        end_key.map_or(Bound::Unbounded, Bound::Included),
+    ))?)
+}
+
 /// Get an instance of subkey for given dupsort table
 fn table_subkey<T: DupSort>(subkey: Option<&str>) -> Result<T::SubKey, eyre::Error> {
     serde_json::from_str(subkey.unwrap_or_default()).map_err(|e| eyre::eyre!(e))
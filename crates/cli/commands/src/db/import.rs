@@ -0,0 +1,218 @@
+//! Command that imports table entries written by `reth db export`.
+
+use crate::db::export::{ExportFormat, ExportReader};
+use clap::Parser;
+use reth_db_api::{
+    table::Table, transaction::DbTxMut, RawKey, RawTable, RawValue, TableViewer, Tables,
+};
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+use tracing::info;
+
+/// Number of imported entries after which the transaction is committed.
+const IMPORT_COMMIT_THRESHOLD: usize = 100_000;
+
+/// The arguments for the `reth db import` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The file written by `reth db export`.
+    input: PathBuf,
+
+    /// The format of the export file.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Jsonl)]
+    format: ExportFormat,
+}
+
+impl Command {
+    /// Execute `db import` command
+    ///
+    /// The entries of every imported key replace the existing entries of that key. The entries are
+    /// committed in batches, so an interrupted import leaves the entries imported so far.
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: &ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let (reader, header) =
+            ExportReader::new(self.format, BufReader::new(File::open(&self.input)?))?;
+        let table = header.table.parse::<Tables>().map_err(|err| eyre::eyre!(err))?;
+
+        let entries =
+            table.view(&ImportViewer { provider_factory, reader: RefCell::new(reader) })?;
+        info!(target: "reth::cli", %table, entries, path = ?self.input, "Imported table");
+        Ok(())
+    }
+}
+
+struct ImportViewer<'a, N: ProviderNodeTypes, R> {
+    provider_factory: &'a ProviderFactory<N>,
+    reader: RefCell<ExportReader<R>>,
+}
+
+impl<N: ProviderNodeTypes, R: BufRead> TableViewer<usize> for ImportViewer<'_, N, R> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        let mut provider = self.provider_factory.provider_rw()?;
+
+        let mut reader = self.reader.borrow_mut();
+        let mut last_key = None;
+        let mut total = 0;
+        while let Some(entry) = reader.read_entry()? {
+            let tx = provider.tx_ref();
+            let key = RawKey::<T::Key>::from_vec(entry.raw_key.into());
+            let value = RawValue::<T::Value>::from_vec(entry.raw_value.into());
+            // Make sure the entry can be decoded before writing it
+            key.key()?;
+            value.value()?;
+
+            // Remove all existing entries of the key, so that the entries of dupsort tables are
+            // replaced instead of merged
+            if last_key.as_ref() != Some(&key) {
+                tx.delete::<RawTable<T>>(key.clone(), None)?;
+                last_key = Some(key.clone());
+            }
+            tx.put::<RawTable<T>>(key, value)?;
+
+            total += 1;
+            if total % IMPORT_COMMIT_THRESHOLD == 0 {
+                provider.commit()?;
+                provider = self.provider_factory.provider_rw()?;
+                info!(target: "reth::cli", "Imported {total} entries.");
+            }
+        }
+
+        provider.commit()?;
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::export;
+    use alloy_primitives::{address, Address, B256, U256};
+    use reth_db_api::{cursor::DbCursorRO, tables, transaction::DbTx};
+    use reth_db_common::DbTool;
+    use reth_primitives_traits::StorageEntry;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    fn storage_entries<N: ProviderNodeTypes>(
+        provider_factory: &ProviderFactory<N>,
+    ) -> Vec<(Address, StorageEntry)> {
+        let provider = provider_factory.provider().unwrap();
+        let mut cursor = provider.tx_ref().cursor_read::<tables::PlainStorageState>().unwrap();
+        cursor.walk(None).unwrap().collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn export_import_roundtrip() {
+        let first = address!("0x0000000000000000000000000000000000000001");
+        let second = address!("0x0000000000000000000000000000000000000002");
+        let third = address!("0x0000000000000000000000000000000000000003");
+        let entry = |slot: u8, value: u64| StorageEntry {
+            key: B256::with_last_byte(slot),
+            value: U256::from(value),
+        };
+
+        let source = create_test_provider_factory();
+        let provider_rw = source.provider_rw().unwrap();
+        for (address, entry) in [
+            (first, entry(1, 10)),
+            (first, entry(2, 20)),
+            (second, entry(1, 30)),
+            (third, entry(1, 40)),
+        ] {
+            provider_rw.tx_ref().put::<tables::PlainStorageState>(address, entry).unwrap();
+        }
+        provider_rw.commit().unwrap();
+        let source_entries = storage_entries(&source);
+
+        let dir = tempfile::tempdir().unwrap();
+        for format in ["jsonl", "rlp"] {
+            let path = dir.path().join(format);
+            export::Command::try_parse_from([
+                "export",
+                "--table",
+                "PlainStorageState",
+                "--range",
+                &format!("{first}..={second}"),
+                "--format",
+                format,
+                "--output",
+                path.to_str().unwrap(),
+            ])
+            .unwrap()
+            .execute(&DbTool::new(source.clone()).unwrap())
+            .unwrap();
+
+            // The imported entries replace the existing entries of their keys
+            let target = create_test_provider_factory();
+            let provider_rw = target.provider_rw().unwrap();
+            for (address, entry) in [(first, entry(3, 50)), (third, entry(2, 60))] {
+                provider_rw.tx_ref().put::<tables::PlainStorageState>(address, entry).unwrap();
+            }
+            provider_rw.commit().unwrap();
+
+            Command::try_parse_from(["import", path.to_str().unwrap(), "--format", format])
+                .unwrap()
+                .execute(&target)
+                .unwrap();
+
+            let mut expected = source_entries[..3].to_vec();
+            expected.push((third, entry(2, 60)));
+            assert_eq!(storage_entries(&target), expected);
+        }
+    }
+
+    #[test]
+    fn limit_keeps_dupsort_keys_whole() {
+        let first = address!("0x0000000000000000000000000000000000000001");
+        let second = address!("0x0000000000000000000000000000000000000002");
+        let entry = |slot: u8, value: u64| StorageEntry {
+            key: B256::with_last_byte(slot),
+            value: U256::from(value),
+        };
+
+        let source = create_test_provider_factory();
+        let provider_rw = source.provider_rw().unwrap();
+        for (address, entry) in
+            [(first, entry(1, 10)), (first, entry(2, 20)), (second, entry(1, 30))]
+        {
+            provider_rw.tx_ref().put::<tables::PlainStorageState>(address, entry).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export");
+        export::Command::try_parse_from([
+            "export",
+            "--table",
+            "PlainStorageState",
+            "--limit",
+            "1",
+            "--output",
+            path.to_str().unwrap(),
+        ])
+        .unwrap()
+        .execute(&DbTool::new(source.clone()).unwrap())
+        .unwrap();
+
+        let target = create_test_provider_factory();
+        let provider_rw = target.provider_rw().unwrap();
+        provider_rw.tx_ref().put::<tables::PlainStorageState>(first, entry(3, 50)).unwrap();
+        provider_rw.commit().unwrap();
+
+        Command::try_parse_from(["import", path.to_str().unwrap()])
+            .unwrap()
+            .execute(&target)
+            .unwrap();
+
+        // All entries of the first key are exported, so none of them are lost on import
+        assert_eq!(storage_entries(&target), storage_entries(&source)[..2]);
+    }
+}
@@ -10,10 +10,15 @@ use std::{
 };
 mod checksum;
 mod clear;
+mod compact;
+mod compare;
 mod diff;
+mod export;
 mod get;
+mod import;
 mod list;
 mod stats;
+pub(crate) mod train_dictionary;
 /// DB List TUI
 mod tui;
 
@@ -34,12 +39,18 @@ pub enum Subcommands {
     Stats(stats::Command),
     /// Lists the contents of a table
     List(list::Command),
-    /// Calculates the content checksum of a table
+    /// Calculates the content checksum of a table or static file segment
     Checksum(checksum::Command),
+    /// Compares the database and static files with the checksums of another node
+    Compare(compare::Command),
     /// Create a diff between two database tables or two entire databases.
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Exports the entries of a table to a portable file
+    Export(export::Command),
+    /// Imports the entries of a table from a file written by `reth db export`
+    Import(import::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -48,6 +59,10 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Writes a compacted copy of the database, optionally replacing the database with it
+    Compact(compact::Command),
+    /// Trains a zstd dictionary from the static files of a segment
+    TrainDictionary(train_dictionary::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -99,6 +114,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::Compare(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Diff(command) => {
                 db_ro_exec!(self.env, tool, N, {
                     command.execute(&tool)?;
@@ -109,6 +129,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::Export(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::Import(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(&provider_factory)?;
+            }
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
@@ -135,6 +164,13 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::Compact(command) => {
+                command.execute(&db_path, self.env.db.database_args())?;
+            }
+            Subcommands::TrainDictionary(command) => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(&provider_factory, &data_dir)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
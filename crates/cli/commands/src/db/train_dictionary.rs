@@ -0,0 +1,222 @@
+//! Command that trains zstd dictionaries from the node's own static files.
+
+use crate::static_file::recompress::recompress_jar;
+use clap::{Parser, ValueEnum};
+use eyre::{ensure, OptionExt};
+use human_bytes::human_bytes;
+use reth_db::static_file::iter_static_files;
+use reth_db_api::table::{Compress, Decompress};
+use reth_nippy_jar::{NippyJar, NippyJarCursor};
+use reth_node_core::{
+    args::ByteSize,
+    dirs::{ChainPath, DataDirPath},
+    zstd_dictionaries::{TrainedDictionary, DICTIONARY_VERSION},
+};
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory, StaticFileProviderFactory};
+use reth_static_file_types::{SegmentHeader, StaticFileSegment};
+use reth_zstd_compressors::with_decompressed_samples;
+use std::{
+    borrow::Cow,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::info;
+
+/// The arguments for the `reth db train-dictionary` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The static file segment to train the dictionary for.
+    #[arg(long, value_enum)]
+    segment: DictionarySegment,
+
+    /// The maximum number of values sampled from the static files.
+    #[arg(long, default_value_t = 100_000)]
+    samples: usize,
+
+    /// The maximum size of the trained dictionary.
+    #[arg(long, value_name = "SIZE", default_value = "110KB")]
+    max_size: ByteSize,
+
+    /// Re-encodes the static files of the segment with the trained dictionary.
+    #[arg(long)]
+    recompress: bool,
+}
+
+/// Static file segments that can be compressed with a trained dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DictionarySegment {
+    /// Receipts segment.
+    Receipts,
+    /// Transactions segment.
+    Transactions,
+}
+
+impl From<DictionarySegment> for StaticFileSegment {
+    fn from(segment: DictionarySegment) -> Self {
+        match segment {
+            DictionarySegment::Receipts => Self::Receipts,
+            DictionarySegment::Transactions => Self::Transactions,
+        }
+    }
+}
+
+impl Command {
+    /// Execute `db train-dictionary` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: &ProviderFactory<N>,
+        data_dir: &ChainPath<DataDirPath>,
+    ) -> eyre::Result<()> {
+        match self.segment {
+            DictionarySegment::Receipts => self
+                .train::<N, <N::Primitives as NodePrimitives>::Receipt>(provider_factory, data_dir),
+            DictionarySegment::Transactions => self
+                .train::<N, <N::Primitives as NodePrimitives>::SignedTx>(
+                    provider_factory,
+                    data_dir,
+                ),
+        }
+    }
+
+    /// Trains the dictionary on the values of type `T` stored in the static files of the segment.
+    fn train<N: ProviderNodeTypes, T: Compress + Decompress>(
+        self,
+        provider_factory: &ProviderFactory<N>,
+        data_dir: &ChainPath<DataDirPath>,
+    ) -> eyre::Result<()> {
+        let segment = StaticFileSegment::from(self.segment);
+        let static_file_provider = provider_factory.static_file_provider();
+        let static_files = iter_static_files(static_file_provider.directory())?;
+        let ranges = static_files
+            .get(&segment)
+            .ok_or_eyre(format!("No static files found for segment {segment}"))?;
+
+        let jars = ranges
+            .iter()
+            .map(|(block_range, _)| {
+                let fixed_block_range = static_file_provider.find_fixed_range(block_range.start());
+                let path =
+                    static_file_provider.directory().join(segment.filename(&fixed_block_range));
+                Ok((NippyJar::<SegmentHeader>::load(&path)?, path))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        // Sample rows evenly across all static files of the segment
+        let total_rows = jars.iter().map(|(jar, _)| jar.rows()).sum::<usize>();
+        ensure!(total_rows > 0, "No rows found in static files for segment {segment}");
+        let step = total_rows.div_ceil(self.samples.max(1));
+
+        // The dictionary compresses the encoding of the values before compression, so the values
+        // are decoded and the encodings the codec decompressed are sampled. Values too small to be
+        // compressed are left out.
+        let mut data = Vec::new();
+        let mut sizes = Vec::new();
+        for (jar, _) in &jars {
+            let mut cursor = NippyJarCursor::new(jar)?;
+            for row_number in (0..jar.rows()).step_by(step) {
+                let row = cursor.row_by_number(row_number)?.ok_or_eyre("missing row")?;
+                for value in row {
+                    let (decoded, samples) = with_decompressed_samples(|| T::decompress(value));
+                    decoded?;
+                    for sample in samples {
+                        sizes.push(sample.len());
+                        data.extend(sample);
+                    }
+                }
+            }
+        }
+        ensure!(
+            !sizes.is_empty(),
+            "No compressed values found in static files for segment {segment}"
+        );
+
+        info!(
+            target: "reth::cli",
+            %segment,
+            samples = sizes.len(),
+            size = %human_bytes(data.len() as f64),
+            "Training dictionary"
+        );
+        let dictionary = zstd::dict::from_continuous(&data, &sizes, self.max_size.0)?;
+        let id = zstd::zstd_safe::get_dict_id_from_dict(&dictionary)
+            .ok_or_eyre("Trained dictionary has no ID")?
+            .get();
+
+        let block_range = ranges.first().expect("not empty").0.start()..=
+            ranges.last().expect("not empty").0.end();
+        let trained = TrainedDictionary {
+            version: DICTIONARY_VERSION,
+            segment,
+            id,
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            block_range,
+            samples: sizes.len(),
+            dictionary: dictionary.into(),
+        };
+
+        let dictionary_path = trained.save(&data_dir.zstd_dictionaries())?;
+        info!(
+            target: "reth::cli",
+            %segment,
+            id,
+            size = %human_bytes(trained.dictionary.len() as f64),
+            path = ?dictionary_path,
+            "Dictionary trained, it is used to encode new values from the next start on"
+        );
+
+        if self.recompress {
+            reth_zstd_compressors::load_dictionary(
+                trained.kind().expect("dictionary segment"),
+                trained.dictionary.to_vec(),
+            );
+            reencode_jars::<T>(segment, jars)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Re-encodes the values of the jars, compressing them with the dictionary loaded last.
+fn reencode_jars<T: Compress + Decompress>(
+    segment: StaticFileSegment,
+    jars: Vec<(NippyJar<SegmentHeader>, PathBuf)>,
+) -> eyre::Result<()> {
+    let (mut size_before, mut size_after) = (0, 0);
+    for (jar, path) in jars {
+        if jar.rows() == 0 {
+            continue
+        }
+
+        let compressor = jar.compressor().cloned();
+        let (before, after) = recompress_jar(jar, &path, compressor, |value| {
+            let (decoded, samples) = with_decompressed_samples(|| T::decompress(value));
+            let reencoded = decoded?.compress();
+
+            // The value must decode to the same encoding it was compressed from
+            let (decoded, reencoded_samples) =
+                with_decompressed_samples(|| T::decompress(reencoded.as_ref()));
+            decoded?;
+            ensure!(samples == reencoded_samples, "Re-encoded value of {path:?} differs");
+
+            Ok(Cow::Owned(reencoded.as_ref().to_vec()))
+        })?;
+        info!(
+            target: "reth::cli",
+            ?path,
+            before = %human_bytes(before as f64),
+            after = %human_bytes(after as f64),
+            "Re-encoded static file"
+        );
+        size_before += before;
+        size_after += after;
+    }
+    info!(
+        target: "reth::cli",
+        %segment,
+        before = %human_bytes(size_before as f64),
+        after = %human_bytes(size_after as f64),
+        "Re-encoded static files"
+    );
+    Ok(())
+}
@@ -1,14 +1,23 @@
-use crate::common::EnvironmentArgs;
+use crate::{
+    common::EnvironmentArgs,
+    snapshot::manifest::{
+        extract_archive, hash_file, SnapshotEntry, SnapshotManifest, DB_DIR, MANIFEST_FILE_NAME,
+        MANIFEST_VERSION, STATIC_FILES_DIR, ZSTD_DICTIONARIES_DIR,
+    },
+};
 use clap::Parser;
-use eyre::Result;
+use eyre::{bail, ensure, Result};
 use lz4::Decoder;
-use reqwest::Client;
+use reqwest::{header::RANGE, Client, StatusCode};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
+use reth_config::Config;
 use reth_fs_util as fs;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
 use std::{
-    io::{self, Read, Write},
-    path::Path,
+    fs::{File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -19,6 +28,10 @@ use tracing::info;
 const BYTE_UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
 const MERKLE_BASE_URL: &str = "https://downloads.merkle.io";
 const EXTENSION_TAR_FILE: &str = ".tar.lz4";
+/// Directory in the datadir that snapshot chunks are downloaded to before extraction.
+const SNAPSHOT_DOWNLOAD_DIR: &str = "snapshot-download";
+/// Directory in the datadir that snapshots are extracted to before being moved into place.
+const SNAPSHOT_EXTRACT_DIR: &str = "snapshot-extract";
 
 #[derive(Debug, Parser)]
 pub struct DownloadCommand<C: ChainSpecParser> {
@@ -36,7 +49,12 @@ pub struct DownloadCommand<C: ChainSpecParser> {
         - https://publicnode.com/snapshots (full nodes & testnets)\n\
         \n\
         If no URL is provided, the latest mainnet archive snapshot\n\
-        will be proposed for download from merkle.io"
+        will be proposed for download from merkle.io\n\
+        \n\
+        A URL ending in .tar.lz4 is streamed and extracted as a single archive.\n\
+        Any other URL, file:// URL or local directory is treated as a snapshot\n\
+        created by `reth snapshot create`: its chunks are verified against the\n\
+        manifest and interrupted downloads are resumed."
     )]
     url: Option<String>,
 }
@@ -62,7 +80,17 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> DownloadCo
             "Starting snapshot download and extraction"
         );
 
-        stream_and_extract(&url, data_dir.data_dir()).await?;
+        if url.ends_with(EXTENSION_TAR_FILE) {
+            stream_and_extract(&url, data_dir.data_dir()).await?;
+        } else {
+            let source = SnapshotSource::new(&url);
+            let chain_id = self.env.chain.chain().id();
+            let config_path = self.env.config.unwrap_or_else(|| data_dir.config());
+            task::spawn_blocking(move || {
+                blocking_download_snapshot(&source, &data_dir, chain_id, &config_path)
+            })
+            .await??;
+        }
         info!(target: "reth::cli", "Snapshot downloaded and extracted successfully");
 
         Ok(())
@@ -178,6 +206,209 @@ async fn stream_and_extract(url: &str, target_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Location of a snapshot created by `reth snapshot create`.
+#[derive(Debug)]
+enum SnapshotSource {
+    /// Base URL the manifest and the chunks are served from.
+    Http(String),
+    /// Local directory containing the manifest and the chunks.
+    Local(PathBuf),
+}
+
+impl SnapshotSource {
+    /// Parses a `http(s)://` URL, a `file://` URL or a local directory path.
+    fn new(url: &str) -> Self {
+        if url.starts_with("http://") || url.starts_with("https://") {
+            Self::Http(url.trim_end_matches('/').to_string())
+        } else {
+            Self::Local(PathBuf::from(url.strip_prefix("file://").unwrap_or(url)))
+        }
+    }
+
+    /// Reads the snapshot manifest.
+    fn manifest(&self) -> Result<SnapshotManifest> {
+        let manifest = match self {
+            Self::Http(base) => reqwest::blocking::get(format!("{base}/{MANIFEST_FILE_NAME}"))?
+                .error_for_status()?
+                .bytes()?
+                .to_vec(),
+            Self::Local(dir) => fs::read(dir.join(MANIFEST_FILE_NAME))?,
+        };
+        Ok(serde_json::from_slice(&manifest)?)
+    }
+
+    /// Opens the file at `path` relative to the snapshot, starting at `offset` if supported.
+    ///
+    /// Returns the reader and the offset it actually starts at.
+    fn open(&self, path: &str, offset: u64) -> Result<(Box<dyn Read>, u64)> {
+        match self {
+            Self::Http(base) => {
+                let client = reqwest::blocking::Client::builder().timeout(None).build()?;
+                let mut request = client.get(format!("{base}/{path}"));
+                if offset > 0 {
+                    request = request.header(RANGE, format!("bytes={offset}-"));
+                }
+                let response = request.send()?.error_for_status()?;
+                // The server may ignore the range and respond with the whole file
+                let offset =
+                    if response.status() == StatusCode::PARTIAL_CONTENT { offset } else { 0 };
+                Ok((Box::new(response), offset))
+            }
+            Self::Local(dir) => {
+                let mut file = File::open(dir.join(path))?;
+                file.seek(SeekFrom::Start(offset))?;
+                Ok((Box::new(file), offset))
+            }
+        }
+    }
+}
+
+/// Downloads, verifies and extracts a snapshot created by `reth snapshot create`.
+///
+/// Chunks are kept in the datadir until the snapshot is extracted, so an interrupted download
+/// resumes from the already downloaded chunks.
+fn blocking_download_snapshot(
+    source: &SnapshotSource,
+    data_dir: &ChainPath<DataDirPath>,
+    chain_id: u64,
+    config_path: &Path,
+) -> Result<()> {
+    let manifest = source.manifest()?;
+    ensure!(
+        manifest.version == MANIFEST_VERSION,
+        "Unsupported snapshot version {}, expected {MANIFEST_VERSION}",
+        manifest.version
+    );
+    ensure!(
+        manifest.chain_id == chain_id,
+        "Snapshot is for chain {}, expected chain {chain_id}",
+        manifest.chain_id
+    );
+    for dir in [data_dir.db(), data_dir.static_files(), data_dir.zstd_dictionaries()] {
+        ensure!(
+            is_missing_or_empty(&dir)?,
+            "{dir:?} is not empty, remove it before downloading a snapshot"
+        );
+    }
+
+    info!(target: "reth::cli",
+        block_number = manifest.block_number,
+        block_hash = %manifest.block_hash,
+        chunks = manifest.chunks.len(),
+        "Downloading snapshot"
+    );
+
+    let download_dir = data_dir.data_dir().join(SNAPSHOT_DOWNLOAD_DIR);
+    fs::create_dir_all(&download_dir)?;
+
+    let mut progress = DownloadProgress::new(manifest.chunks.iter().map(|chunk| chunk.size).sum());
+    let mut chunk_paths = Vec::with_capacity(manifest.chunks.len());
+    for chunk in &manifest.chunks {
+        ensure!(
+            Path::new(&chunk.path).file_name().is_some_and(|name| name == chunk.path.as_str()),
+            "Invalid snapshot chunk path: {}",
+            chunk.path
+        );
+        let path = download_dir.join(&chunk.path);
+        download_chunk(source, chunk, &path, &mut progress)?;
+        chunk_paths.push(path);
+    }
+    println!();
+
+    // Extract into a separate directory first, so an interrupted extraction never leaves a
+    // partial database behind
+    info!(target: "reth::cli", "Extracting and verifying snapshot");
+    let extract_dir = data_dir.data_dir().join(SNAPSHOT_EXTRACT_DIR);
+    if extract_dir.exists() {
+        fs::remove_dir_all(&extract_dir)?;
+    }
+    extract_archive(&manifest, chunk_paths, |name| match name {
+        DB_DIR | STATIC_FILES_DIR | ZSTD_DICTIONARIES_DIR => Some(extract_dir.join(name)),
+        _ => None,
+    })?;
+
+    // The database is moved last, its presence marks a completely extracted snapshot
+    for (name, dir) in [
+        (ZSTD_DICTIONARIES_DIR, data_dir.zstd_dictionaries()),
+        (STATIC_FILES_DIR, data_dir.static_files()),
+        (DB_DIR, data_dir.db()),
+    ] {
+        let extracted = extract_dir.join(name);
+        if !extracted.exists() {
+            continue
+        }
+        if dir.exists() {
+            std::fs::remove_dir(&dir)?;
+        }
+        fs::rename(&extracted, &dir)?;
+    }
+    fs::remove_dir_all(&extract_dir)?;
+    fs::remove_dir_all(&download_dir)?;
+
+    // Nodes running from a pruned snapshot must keep the prune configuration it was created with
+    if !config_path.exists() {
+        let config = Config { prune: manifest.prune, ..Default::default() };
+        config.save(config_path)?;
+        info!(target: "reth::cli", path = ?config_path, "Saved configuration of the snapshot");
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the directory at `path` doesn't exist or has no entries.
+fn is_missing_or_empty(path: &Path) -> Result<bool> {
+    if !path.exists() {
+        return Ok(true)
+    }
+    Ok(fs::read_dir(path)?.next().is_none())
+}
+
+/// Downloads the chunk to `path`, resuming a partial download, and verifies its hash.
+fn download_chunk(
+    source: &SnapshotSource,
+    chunk: &SnapshotEntry,
+    path: &Path,
+    progress: &mut DownloadProgress,
+) -> Result<()> {
+    let mut offset = path.metadata().map(|metadata| metadata.len()).unwrap_or_default();
+    if offset > chunk.size {
+        fs::remove_file(path)?;
+        offset = 0;
+    }
+
+    if offset < chunk.size {
+        let (mut reader, start) = source.open(&chunk.path, offset)?;
+        let mut file = OpenOptions::new().create(true).write(true).truncate(false).open(path)?;
+        file.set_len(start)?;
+        file.seek(SeekFrom::Start(start))?;
+        progress.update(start)?;
+
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            let read = reader.read(&mut buf)?;
+            if read == 0 {
+                break
+            }
+            file.write_all(&buf[..read])?;
+            progress.update(read as u64)?;
+        }
+        file.sync_all()?;
+    } else {
+        progress.update(chunk.size)?;
+    }
+
+    let (size, hash) = hash_file(path)?;
+    if size != chunk.size || hash != chunk.hash {
+        fs::remove_file(path)?;
+        bail!(
+            "Snapshot chunk {} failed verification and was removed, retry the download",
+            chunk.path
+        )
+    }
+
+    Ok(())
+}
+
 // Builds default URL for latest mainnet archive  snapshot
 async fn get_latest_snapshot_url() -> Result<String> {
     let latest_url = format!("{MERKLE_BASE_URL}/latest.txt");
@@ -69,7 +69,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
 
         let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
 
-        let mut hash_collector = Collector::new(config.stages.etl.file_size, config.stages.etl.dir);
+        let mut hash_collector = Collector::new(config.stages.etl.file_size, config.stages.etl.dir)
+            .with_compression_level(config.stages.etl.compression_level)
+            .with_max_disk_usage(config.stages.etl.max_disk_usage);
         let provider_factory = &provider_factory.provider_rw()?.0;
 
         if let Some(path) = self.import.path {
@@ -22,7 +22,9 @@ pub mod node;
 pub mod p2p;
 pub mod prune;
 pub mod recover;
+pub mod snapshot;
 pub mod stage;
+pub mod static_file;
 #[cfg(feature = "arbitrary")]
 pub mod test_vectors;
 
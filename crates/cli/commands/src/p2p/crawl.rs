@@ -0,0 +1,629 @@
+//! Crawl subcommand of P2P Debugging tool.
+
+use alloy_primitives::hex;
+use clap::{Parser, ValueEnum};
+use futures::{stream::FuturesUnordered, StreamExt};
+use reth_chainspec::{
+    EnrForkIdEntry, ForkCondition, ForkFilter, ForkId, Hardforks, Head, ValidationError,
+};
+use reth_cli_util::parse_duration_from_secs;
+use reth_ecies::stream::ECIESStream;
+use reth_eth_wire::{
+    errors::{EthHandshakeError, EthStreamError},
+    CanDisconnect, DisconnectReason, HelloMessageWithProtocols, UnauthedEthStream,
+    UnauthedP2PStream, UnifiedStatus,
+};
+use reth_network::{DiscoveredEvent, Discovery, DiscoveryEvent, NetworkConfig, NetworkPrimitives};
+use reth_network_peers::{NodeRecord, PeerId};
+use secp256k1::SecretKey;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::net::TcpStream;
+use tracing::{debug, info};
+
+/// The maximum number of discovered peers that wait for a handshake.
+///
+/// Peers discovered while the queue is full are skipped until they are discovered again.
+const MAX_QUEUED_PEERS: usize = 4096;
+
+/// `reth p2p crawl` command
+///
+/// Walks the discovery network and attempts an `RLPx` and `eth` `Status` handshake with every
+/// discovered peer.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// How long to discover new peers for, in seconds.
+    ///
+    /// Handshakes that are in progress when the time is up are finished.
+    #[arg(long, value_name = "SECONDS", default_value = "300", value_parser = parse_duration_from_secs)]
+    duration: Duration,
+
+    /// The maximum number of concurrent handshakes.
+    #[arg(long, default_value_t = 64)]
+    concurrency: usize,
+
+    /// The maximum number of discovered peers to crawl.
+    ///
+    /// The crawl ends early once this many peers were discovered and crawled.
+    #[arg(long, default_value_t = 10_000)]
+    max_peers: usize,
+
+    /// The timeout of connecting to and handshaking with a single peer, in seconds.
+    #[arg(long, value_name = "SECONDS", default_value = "10", value_parser = parse_duration_from_secs)]
+    timeout: Duration,
+
+    /// The file the report of all crawled peers is written to.
+    #[arg(long, short, value_name = "FILE")]
+    output: PathBuf,
+
+    /// The format of the report.
+    #[arg(long, value_enum, default_value_t = ReportFormat::Csv)]
+    format: ReportFormat,
+
+    /// Also write the peers that completed the handshake with a compatible fork id to a peers
+    /// file, which can be loaded with `--peers-file`.
+    #[arg(long, value_name = "FILE")]
+    peers_file: Option<PathBuf>,
+}
+
+/// The format of the crawl report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// One row per peer.
+    #[default]
+    Csv,
+    /// An array of one object per peer.
+    Json,
+}
+
+impl Command {
+    /// Execute `p2p crawl` command.
+    ///
+    /// The peers are checked against the fork filter at the current time, assuming all block
+    /// based forks are active.
+    pub async fn execute<C, N: NetworkPrimitives>(
+        self,
+        config: NetworkConfig<C, N>,
+        chain_spec: impl Hardforks,
+    ) -> eyre::Result<()> {
+        let NetworkConfig {
+            secret_key,
+            boot_nodes,
+            dns_discovery_config,
+            discovery_v4_addr,
+            mut discovery_v4_config,
+            mut discovery_v5_config,
+            listener_addr,
+            hello_message,
+            mut status,
+            mut fork_filter,
+            ..
+        } = config;
+
+        let head = crawl_head(&chain_spec, SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs());
+        fork_filter.set_head(head);
+        status.forkid = fork_filter.current();
+        info!(target: "reth::cli", fork_id = ?status.forkid, "Crawling the network");
+
+        let boot_nodes =
+            futures::future::try_join_all(boot_nodes.iter().map(|record| record.resolve())).await?;
+        if let Some(config) = discovery_v4_config.as_mut() {
+            config.bootstrap_nodes.extend(boot_nodes.clone());
+            config.add_eip868_pair("eth", EnrForkIdEntry::from(status.forkid));
+        }
+        if let Some(config) = discovery_v5_config.as_mut() {
+            config.extend_unsigned_boot_nodes(boot_nodes)
+        }
+        let mut discovery = Discovery::new(
+            listener_addr,
+            discovery_v4_addr,
+            secret_key,
+            discovery_v4_config,
+            discovery_v5_config,
+            dns_discovery_config,
+        )
+        .await?;
+
+        let mut discovered = HashMap::new();
+        let mut queued = VecDeque::new();
+        let mut handshakes = FuturesUnordered::new();
+        let mut peers = Vec::new();
+
+        let deadline = tokio::time::sleep(self.duration);
+        tokio::pin!(deadline);
+        let mut progress = tokio::time::interval(Duration::from_secs(30));
+        loop {
+            if discovered.len() >= self.max_peers && queued.is_empty() && handshakes.is_empty() {
+                break
+            }
+            while handshakes.len() < self.concurrency.max(1) {
+                let Some((peer_id, addr)) = queued.pop_front() else { break };
+                let handshake = crawl_peer::<N>(
+                    peer_id,
+                    addr,
+                    secret_key,
+                    hello_message.clone(),
+                    status,
+                    fork_filter.clone(),
+                );
+                let timeout = self.timeout;
+                handshakes.push(async move {
+                    tokio::time::timeout(timeout, handshake).await.unwrap_or_else(|_| {
+                        CrawledPeer::failed(peer_id, addr, "handshake timed out".to_string())
+                    })
+                });
+            }
+
+            tokio::select! {
+                _ = &mut deadline => break,
+                Some(event) = discovery.next() => {
+                    if let DiscoveryEvent::NewNode(DiscoveredEvent::EventQueued { peer_id, addr, .. }) = event {
+                        if discovered.len() < self.max_peers &&
+                            queued.len() < MAX_QUEUED_PEERS &&
+                            !discovered.contains_key(&peer_id)
+                        {
+                            let record = NodeRecord::new_with_ports(
+                                addr.tcp().ip(),
+                                addr.tcp().port(),
+                                addr.udp().map(|udp| udp.port()),
+                                peer_id,
+                            );
+                            discovered.insert(peer_id, record);
+                            queued.push_back((peer_id, addr.tcp()));
+                        }
+                    }
+                }
+                Some(peer) = handshakes.next() => {
+                    debug!(target: "reth::cli", ?peer, "Crawled peer");
+                    peers.push(peer);
+                }
+                _ = progress.tick() => {
+                    info!(
+                        target: "reth::cli",
+                        discovered = discovered.len(),
+                        crawled = peers.len(),
+                        "Crawl progress"
+                    );
+                }
+            }
+        }
+
+        info!(
+            target: "reth::cli",
+            in_progress = handshakes.len(),
+            not_dialed = queued.len(),
+            "Finishing handshakes in progress"
+        );
+        drop(discovery);
+        while let Some(peer) = handshakes.next().await {
+            peers.push(peer);
+        }
+        peers.sort_by_key(|peer| peer.address);
+
+        write_report(&self.output, self.format, &peers)?;
+        if let Some(peers_file) = &self.peers_file {
+            reth_fs_util::write_json_file(peers_file, &compatible_peers(&peers, &discovered))?;
+        }
+
+        print_summary(&peers, status.forkid);
+        Ok(())
+    }
+}
+
+/// Returns the head the fork filter of the crawl is set to: the given timestamp, and the highest
+/// block based fork of the chain.
+fn crawl_head(chain_spec: &impl Hardforks, timestamp: u64) -> Head {
+    let number = chain_spec
+        .forks_iter()
+        .filter_map(|(_, condition)| match condition {
+            ForkCondition::Block(block) | ForkCondition::TTD { fork_block: Some(block), .. } => {
+                Some(block)
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or_default();
+    Head { number, timestamp, ..Default::default() }
+}
+
+/// Writes the report of the crawled peers in the given format.
+fn write_report(path: &Path, format: ReportFormat, peers: &[CrawledPeer]) -> eyre::Result<()> {
+    match format {
+        ReportFormat::Csv => {
+            let mut writer = csv::Writer::from_path(path)?;
+            for peer in peers {
+                writer.serialize(peer)?;
+            }
+            writer.flush()?;
+        }
+        ReportFormat::Json => reth_fs_util::write_json_file(path, &peers)?,
+    }
+    Ok(())
+}
+
+/// Returns the records of the peers that completed the handshake with a compatible fork id.
+fn compatible_peers(
+    peers: &[CrawledPeer],
+    discovered: &HashMap<PeerId, NodeRecord>,
+) -> Vec<NodeRecord> {
+    peers
+        .iter()
+        .filter(|peer| peer.fork_compatibility == Some(ForkCompatibility::Compatible))
+        .filter_map(|peer| discovered.get(&peer.peer_id).copied())
+        .collect()
+}
+
+/// Whether the fork id of a peer is compatible with the local one, see
+/// [EIP-2124](https://eips.ethereum.org/EIPS/eip-2124).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForkCompatibility {
+    /// The peer is on the same chain, and not missing any forks.
+    Compatible,
+    /// The peer is on the same chain, but needs a software update.
+    RemoteStale,
+    /// The peer is on another chain, or the local node needs a software update.
+    Incompatible,
+}
+
+impl ForkCompatibility {
+    /// Returns the fork id of the peer, and its compatibility, of a failed fork id validation.
+    const fn from_validation_error(err: ValidationError) -> (ForkId, Self) {
+        match err {
+            ValidationError::RemoteStale { remote, .. } => (remote, Self::RemoteStale),
+            ValidationError::LocalIncompatibleOrStale { remote, .. } => {
+                (remote, Self::Incompatible)
+            }
+        }
+    }
+}
+
+/// The outcome of crawling a peer.
+///
+/// The fields are flat, so that a peer is a single row of a CSV report.
+#[derive(Debug, Clone, Serialize)]
+struct CrawledPeer {
+    /// The id of the peer.
+    peer_id: PeerId,
+    /// The `RLPx` address of the peer.
+    address: SocketAddr,
+    /// The client version of the peer's `Hello` message.
+    client_version: Option<String>,
+    /// The capabilities of the peer's `Hello` message, separated by spaces.
+    capabilities: Option<String>,
+    /// The hash of the peer's fork id.
+    fork_hash: Option<String>,
+    /// The next fork of the peer's fork id.
+    fork_next: Option<u64>,
+    /// Whether the fork id of the peer is compatible.
+    fork_compatibility: Option<ForkCompatibility>,
+    /// Whether the fork id of the peer is equal to the local one, which means that the peer is
+    /// ready for the next fork.
+    next_fork_ready: Option<bool>,
+    /// The time it took to establish the TCP connection, in milliseconds.
+    latency_ms: Option<u128>,
+    /// The error that ended the handshake early.
+    error: Option<String>,
+}
+
+impl CrawledPeer {
+    fn failed(peer_id: PeerId, address: SocketAddr, error: String) -> Self {
+        Self {
+            peer_id,
+            address,
+            client_version: None,
+            capabilities: None,
+            fork_hash: None,
+            fork_next: None,
+            fork_compatibility: None,
+            next_fork_ready: None,
+            latency_ms: None,
+            error: Some(error),
+        }
+    }
+
+    fn set_fork_id(&mut self, fork_id: ForkId, compatibility: ForkCompatibility, local: ForkId) {
+        self.fork_hash = Some(hex::encode_prefixed(fork_id.hash.0));
+        self.fork_next = Some(fork_id.next);
+        self.fork_compatibility = Some(compatibility);
+        self.next_fork_ready = Some(fork_id == local);
+    }
+}
+
+/// Connects to the peer, and performs the `RLPx` and `eth` handshakes.
+async fn crawl_peer<N: NetworkPrimitives>(
+    peer_id: PeerId,
+    address: SocketAddr,
+    secret_key: SecretKey,
+    hello: HelloMessageWithProtocols,
+    mut status: UnifiedStatus,
+    fork_filter: ForkFilter,
+) -> CrawledPeer {
+    let mut peer = CrawledPeer::failed(peer_id, address, String::new());
+
+    let start = Instant::now();
+    let stream = match TcpStream::connect(address).await {
+        Ok(stream) => stream,
+        Err(err) => return CrawledPeer::failed(peer_id, address, format!("tcp: {err}")),
+    };
+    peer.latency_ms = Some(start.elapsed().as_millis());
+
+    let ecies_stream = match ECIESStream::connect(stream, secret_key, peer_id).await {
+        Ok(stream) => stream,
+        Err(err) => {
+            peer.error = Some(format!("ecies: {err}"));
+            return peer
+        }
+    };
+    let (p2p_stream, their_hello) =
+        match UnauthedP2PStream::new(ecies_stream).handshake(hello).await {
+            Ok(res) => res,
+            Err(err) => {
+                peer.error = Some(format!("p2p: {err}"));
+                return peer
+            }
+        };
+    peer.client_version = Some(their_hello.client_version);
+    peer.capabilities = Some(
+        their_hello.capabilities.iter().map(ToString::to_string).collect::<Vec<_>>().join(" "),
+    );
+
+    match p2p_stream.shared_capabilities().eth_version() {
+        Ok(version) => status.set_eth_version(version),
+        Err(err) => {
+            peer.error = Some(format!("eth: {err}"));
+            return peer
+        }
+    }
+    let local = fork_filter.current();
+    match UnauthedEthStream::new(p2p_stream).handshake::<N>(status, fork_filter).await {
+        Ok((mut eth_stream, their_status)) => {
+            peer.set_fork_id(their_status.forkid, ForkCompatibility::Compatible, local);
+            peer.error = None;
+            let _ = eth_stream.disconnect(DisconnectReason::ClientQuitting).await;
+        }
+        Err(EthStreamError::EthHandshakeError(EthHandshakeError::InvalidFork(err))) => {
+            let (remote, compatibility) = ForkCompatibility::from_validation_error(err);
+            peer.set_fork_id(remote, compatibility, local);
+            peer.error = None;
+        }
+        Err(err) => peer.error = Some(format!("eth: {err}")),
+    }
+    peer
+}
+
+/// Prints the number of peers by client and by fork id.
+fn print_summary(peers: &[CrawledPeer], local: ForkId) {
+    let handshaked = peers.iter().filter(|peer| peer.fork_compatibility.is_some()).count();
+    println!("Crawled {} peers, {handshaked} completed the eth handshake", peers.len());
+
+    let mut clients = BTreeMap::<_, usize>::new();
+    for peer in peers {
+        // Client versions look like `Geth/v1.15.0-stable/linux-amd64/go1.24.1`
+        if let Some(client) = peer.client_version.as_deref() {
+            *clients.entry(client.split('/').next().unwrap_or(client)).or_default() += 1;
+        }
+    }
+    println!("\nClients:");
+    for (client, count) in clients {
+        println!("  {client}: {count}");
+    }
+
+    let mut fork_ids = BTreeMap::<_, usize>::new();
+    for peer in peers {
+        if let (Some(hash), Some(next), Some(compatibility)) =
+            (&peer.fork_hash, peer.fork_next, peer.fork_compatibility)
+        {
+            *fork_ids.entry((compatibility, hash, next)).or_default() += 1;
+        }
+    }
+    println!(
+        "\nFork ids (local: hash {}, next {}):",
+        hex::encode_prefixed(local.hash.0),
+        local.next
+    );
+    for ((compatibility, hash, next), count) in fork_ids {
+        println!("  hash {hash}, next {next} ({compatibility:?}): {count}");
+    }
+
+    let ready = peers.iter().filter(|peer| peer.next_fork_ready == Some(true)).count();
+    println!("\n{ready} of {handshaked} peers are ready for the next fork");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B512, U256};
+    use reth_chainspec::{ChainHardforks, ChainSpec, EthereumHardfork, ForkHash, Hardfork};
+    use std::net::Ipv4Addr;
+
+    const LOCAL: ForkId = ForkId { hash: ForkHash([0xfc, 0x64, 0xec, 0x04]), next: 1150000 };
+    const REMOTE: ForkId = ForkId { hash: ForkHash([0x97, 0xc2, 0xc3, 0x4c]), next: 0 };
+
+    fn compatible_peer(id: u8) -> CrawledPeer {
+        let mut peer = CrawledPeer::failed(
+            B512::repeat_byte(id),
+            SocketAddr::from((Ipv4Addr::new(10, 0, 0, id), 30303)),
+            String::new(),
+        );
+        peer.client_version = Some("Geth/v1.15.0-stable/linux-amd64/go1.24.1".to_string());
+        peer.capabilities = Some("eth/68 snap/1".to_string());
+        peer.latency_ms = Some(12);
+        peer.error = None;
+        peer.set_fork_id(LOCAL, ForkCompatibility::Compatible, LOCAL);
+        peer
+    }
+
+    #[test]
+    fn fork_compatibility_of_validation_error() {
+        assert_eq!(
+            ForkCompatibility::from_validation_error(ValidationError::RemoteStale {
+                local: LOCAL,
+                remote: REMOTE
+            }),
+            (REMOTE, ForkCompatibility::RemoteStale)
+        );
+        assert_eq!(
+            ForkCompatibility::from_validation_error(ValidationError::LocalIncompatibleOrStale {
+                local: LOCAL,
+                remote: REMOTE
+            }),
+            (REMOTE, ForkCompatibility::Incompatible)
+        );
+    }
+
+    #[test]
+    fn next_fork_ready() {
+        let mut peer = compatible_peer(1);
+        assert_eq!(peer.next_fork_ready, Some(true));
+
+        // Same fork hash, but not aware of the next fork
+        peer.set_fork_id(ForkId { next: 0, ..LOCAL }, ForkCompatibility::Compatible, LOCAL);
+        assert_eq!(peer.next_fork_ready, Some(false));
+
+        peer.set_fork_id(REMOTE, ForkCompatibility::Incompatible, LOCAL);
+        assert_eq!(peer.fork_hash.as_deref(), Some("0x97c2c34c"));
+        assert_eq!(peer.fork_next, Some(0));
+        assert_eq!(peer.next_fork_ready, Some(false));
+    }
+
+    #[test]
+    fn head_of_chain_spec_forks() {
+        let chain_spec = ChainSpec {
+            hardforks: ChainHardforks::new(vec![
+                (EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0)),
+                (EthereumHardfork::London.boxed(), ForkCondition::Block(100)),
+                (
+                    EthereumHardfork::Paris.boxed(),
+                    ForkCondition::TTD {
+                        activation_block_number: 200,
+                        fork_block: Some(250),
+                        total_difficulty: U256::from(1000),
+                    },
+                ),
+                (EthereumHardfork::Shanghai.boxed(), ForkCondition::Timestamp(5000)),
+                (EthereumHardfork::Cancun.boxed(), ForkCondition::Never),
+            ]),
+            ..Default::default()
+        };
+        let head = crawl_head(&chain_spec, 6000);
+        assert_eq!(head.number, 250);
+        assert_eq!(head.timestamp, 6000);
+
+        // Without a fork block, the merge isn't taken into account
+        let chain_spec = ChainSpec {
+            hardforks: ChainHardforks::new(vec![
+                (EthereumHardfork::London.boxed(), ForkCondition::Block(100)),
+                (
+                    EthereumHardfork::Paris.boxed(),
+                    ForkCondition::TTD {
+                        activation_block_number: 200,
+                        fork_block: None,
+                        total_difficulty: U256::from(1000),
+                    },
+                ),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(crawl_head(&chain_spec, 6000).number, 100);
+    }
+
+    #[test]
+    fn csv_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.csv");
+        let failed = CrawledPeer::failed(
+            B512::repeat_byte(2),
+            SocketAddr::from((Ipv4Addr::new(10, 0, 0, 2), 30303)),
+            "tcp: connection refused".to_string(),
+        );
+        write_report(&path, ReportFormat::Csv, &[compatible_peer(1), failed]).unwrap();
+
+        let report = std::fs::read_to_string(&path).unwrap();
+        let mut lines = report.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "peer_id,address,client_version,capabilities,fork_hash,fork_next,\
+                 fork_compatibility,next_fork_ready,latency_ms,error"
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                format!(
+                    "{},10.0.0.1:30303,Geth/v1.15.0-stable/linux-amd64/go1.24.1,eth/68 snap/1,\
+                     0xfc64ec04,1150000,compatible,true,12,",
+                    B512::repeat_byte(1)
+                )
+                .as_str()
+            )
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                format!("{},10.0.0.2:30303,,,,,,,,tcp: connection refused", B512::repeat_byte(2))
+                    .as_str()
+            )
+        );
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn json_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        write_report(&path, ReportFormat::Json, &[compatible_peer(1)]).unwrap();
+
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            report,
+            serde_json::json!([{
+                "peer_id": B512::repeat_byte(1),
+                "address": "10.0.0.1:30303",
+                "client_version": "Geth/v1.15.0-stable/linux-amd64/go1.24.1",
+                "capabilities": "eth/68 snap/1",
+                "fork_hash": "0xfc64ec04",
+                "fork_next": 1150000,
+                "fork_compatibility": "compatible",
+                "next_fork_ready": true,
+                "latency_ms": 12,
+                "error": null,
+            }])
+        );
+    }
+
+    #[test]
+    fn peers_file_only_has_compatible_peers() {
+        let mut stale = compatible_peer(2);
+        stale.set_fork_id(REMOTE, ForkCompatibility::RemoteStale, LOCAL);
+        let mut incompatible = compatible_peer(3);
+        incompatible.set_fork_id(REMOTE, ForkCompatibility::Incompatible, LOCAL);
+        let failed = CrawledPeer::failed(
+            B512::repeat_byte(4),
+            SocketAddr::from((Ipv4Addr::new(10, 0, 0, 4), 30303)),
+            "handshake timed out".to_string(),
+        );
+        let peers = [compatible_peer(1), stale, incompatible, failed];
+        let discovered = (1..=4)
+            .map(|id| {
+                let peer_id = B512::repeat_byte(id);
+                let record = NodeRecord::new_with_ports(
+                    Ipv4Addr::new(10, 0, 0, id).into(),
+                    30303,
+                    Some(30301),
+                    peer_id,
+                );
+                (peer_id, record)
+            })
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(compatible_peers(&peers, &discovered), vec![discovered[&B512::repeat_byte(1)]]);
+    }
+}
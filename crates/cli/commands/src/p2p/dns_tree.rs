@@ -0,0 +1,250 @@
+//! DNS tree subcommand of P2P Debugging tool.
+
+use clap::{Parser, Subcommand, ValueEnum};
+use reth_cli_util::{load_secret_key::rng_secret_key, parse_duration_from_secs};
+use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config};
+use reth_dns_discovery::{tree::LinkEntry, DnsTree};
+use reth_network_peers::{pk2id, Enr, NodeRecord};
+use secp256k1::{SecretKey, SECP256K1};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio_stream::StreamExt;
+use tracing::{info, warn};
+
+/// EIP-1459 DNS node list commands
+#[derive(Parser, Debug)]
+pub struct Command {
+    #[command(subcommand)]
+    subcommand: Subcommands,
+}
+
+impl Command {
+    /// Execute `p2p dns-tree` command.
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.subcommand {
+            Subcommands::Build(command) => command.execute().await,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    /// Build a signed ENR tree of node records, and write its TXT records.
+    Build(BuildCommand),
+}
+
+/// The format the TXT records of a tree are written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum TreeOutputFormat {
+    /// A DNS zone file.
+    #[default]
+    Zone,
+    /// A JSON object of the record contents by name.
+    Json,
+}
+
+/// `reth p2p dns-tree build` command
+#[derive(Parser, Debug)]
+pub struct BuildCommand {
+    /// File with the node records to publish, one `enr:` record per line.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    #[arg(long, value_name = "FILE", required_unless_present = "nodes")]
+    enrs: Option<PathBuf>,
+
+    /// JSON file with the nodes to publish, as written by `reth p2p crawl --peers-file` or the
+    /// known peers file of a node.
+    ///
+    /// The signed records of the nodes are requested over discv4, nodes that don't send theirs
+    /// within `--enr-timeout` are left out.
+    #[arg(long, value_name = "FILE")]
+    nodes: Option<PathBuf>,
+
+    /// How long to wait for the signed records of `--nodes`, in seconds.
+    #[arg(long, value_name = "SECONDS", default_value = "30", value_parser = parse_duration_from_secs)]
+    enr_timeout: Duration,
+
+    /// The UDP address discv4 listens on while requesting the signed records of `--nodes`.
+    #[arg(long, value_name = "ADDR", default_value = "0.0.0.0:0")]
+    discovery_addr: SocketAddr,
+
+    /// Link to another tree to include, as `enrtree://<key>@<domain>`.
+    #[arg(long = "link", value_name = "LINK")]
+    links: Vec<LinkEntry>,
+
+    /// The domain the tree is published at.
+    #[arg(long)]
+    domain: String,
+
+    /// File with the hex encoded key the root of the tree is signed with.
+    #[arg(long, value_name = "PATH")]
+    key: PathBuf,
+
+    /// The sequence number of the tree, which has to increase with every update of the published
+    /// tree.
+    ///
+    /// Defaults to the current unix timestamp.
+    #[arg(long)]
+    seq: Option<u64>,
+
+    /// The format the TXT records are written in.
+    #[arg(long, value_enum, default_value_t = TreeOutputFormat::Zone)]
+    format: TreeOutputFormat,
+
+    /// The TTL of the records in the zone file, in seconds.
+    #[arg(long, default_value_t = 3600)]
+    ttl: u32,
+
+    /// The file the records are written to.
+    #[arg(long, short, value_name = "FILE")]
+    output: PathBuf,
+}
+
+impl BuildCommand {
+    /// Execute `p2p dns-tree build` command.
+    pub async fn execute(self) -> eyre::Result<()> {
+        // The key of a published tree must not change, so it's never generated
+        let key = reth_fs_util::read_to_string(&self.key)?
+            .trim()
+            .parse::<SecretKey>()
+            .map_err(|err| eyre::eyre!("invalid key in {}: {err}", self.key.display()))?;
+
+        let mut enrs = Vec::new();
+        if let Some(path) = &self.enrs {
+            for line in reth_fs_util::read_to_string(path)?
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            {
+                enrs.push(
+                    line.parse::<Enr<SecretKey>>()
+                        .map_err(|err| eyre::eyre!("invalid node record {line}: {err}"))?,
+                );
+            }
+        }
+        if let Some(path) = &self.nodes {
+            let nodes: Vec<NodeRecord> = reth_fs_util::read_json_file(path)?;
+            enrs.extend(request_enrs(self.discovery_addr, nodes, self.enr_timeout).await?);
+        }
+
+        let seq = match self.seq {
+            Some(seq) => seq,
+            None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+        let mut tree = DnsTree::new(seq, enrs, self.links);
+        tree.sign(&key);
+
+        let output = match self.format {
+            TreeOutputFormat::Zone => tree.to_zone_file(&self.domain, self.ttl),
+            TreeOutputFormat::Json => {
+                serde_json::to_string_pretty(&tree.txt_records(&self.domain))?
+            }
+        };
+        reth_fs_util::write(&self.output, output)?;
+
+        let link = LinkEntry::<SecretKey> {
+            domain: self.domain.trim_end_matches('.').to_string(),
+            pubkey: key.public_key(SECP256K1),
+        };
+        println!(
+            "Wrote tree of {} nodes with sequence number {seq} to {}",
+            tree.nodes().count(),
+            self.output.display()
+        );
+        println!("Tree link: {link}");
+
+        Ok(())
+    }
+}
+
+/// Requests the signed records of the nodes over discv4.
+///
+/// Nodes only answer after verifying the endpoint of the requesting node, so the requests are
+/// repeated until all nodes answered or the timeout is reached.
+async fn request_enrs(
+    addr: SocketAddr,
+    nodes: Vec<NodeRecord>,
+    timeout: Duration,
+) -> eyre::Result<Vec<Enr<SecretKey>>> {
+    let secret_key = rng_secret_key();
+    let config = Discv4Config::builder()
+        .external_ip_resolver(None)
+        .enable_lookup(false)
+        .enable_dht_random_walk(false)
+        .build();
+    let (discv4, mut service) =
+        Discv4::bind(addr, NodeRecord::from_secret_key(addr, &secret_key), secret_key, config)
+            .await?;
+    let mut updates = service.update_stream();
+    let _handle = service.spawn();
+
+    let mut pending = nodes.into_iter().map(|node| (node.id, node)).collect::<HashMap<_, _>>();
+    info!(target: "reth::cli", nodes = pending.len(), "Requesting node records");
+    for node in pending.values() {
+        discv4.add_node(*node);
+    }
+
+    let mut enrs = Vec::new();
+    let deadline = tokio::time::sleep(timeout);
+    tokio::pin!(deadline);
+    let mut retry = tokio::time::interval(Duration::from_secs(1));
+    while !pending.is_empty() {
+        tokio::select! {
+            _ = &mut deadline => break,
+            _ = retry.tick() => {
+                for node in pending.values() {
+                    discv4.request_enr(*node);
+                }
+            }
+            Some(update) = updates.next() => {
+                if let DiscoveryUpdate::Enr(record, enr) = update {
+                    if pk2id(&enr.public_key()) == record.id && pending.remove(&record.id).is_some() {
+                        enrs.push(enr);
+                    }
+                }
+            }
+        }
+    }
+    discv4.terminate();
+
+    if !pending.is_empty() {
+        warn!(
+            target: "reth::cli",
+            missing = ?pending.keys().collect::<Vec<_>>(),
+            "Leaving out nodes that didn't send their node record"
+        );
+    }
+    Ok(enrs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn request_node_records() {
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let secret_key = rng_secret_key();
+        let config = Discv4Config::builder().external_ip_resolver(None).build();
+        let (discv4, service) =
+            Discv4::bind(addr, NodeRecord::from_secret_key(addr, &secret_key), secret_key, config)
+                .await
+                .unwrap();
+        let _handle = service.spawn();
+        let node = discv4.node_record();
+        // A node that doesn't exist
+        let missing = NodeRecord::new(
+            SocketAddr::from((Ipv4Addr::LOCALHOST, 1)),
+            pk2id(&rng_secret_key().public_key(SECP256K1)),
+        );
+
+        let enrs = request_enrs(addr, vec![node, missing], Duration::from_secs(3)).await.unwrap();
+        assert_eq!(enrs.len(), 1);
+        assert_eq!(pk2id(&enrs[0].public_key()), node.id);
+    }
+}
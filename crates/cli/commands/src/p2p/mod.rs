@@ -17,6 +17,8 @@ use reth_node_core::{
 };
 
 pub mod bootnode;
+mod crawl;
+pub mod dns_tree;
 mod rlpx;
 
 /// `reth p2p` command
@@ -72,11 +74,20 @@ pub enum Subcommands {
     },
     // RLPx utilities
     Rlpx(rlpx::Command),
+    /// EIP-1459 DNS node list utilities
+    DnsTree(dns_tree::Command),
+    /// Crawl the network and handshake with the discovered peers
+    Crawl(crawl::Command),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
     /// Execute `p2p` command
     pub async fn execute<N: NetworkPrimitives>(self) -> eyre::Result<()> {
+        // Building a tree doesn't need the network
+        if let Subcommands::DnsTree(command) = self.command {
+            return command.execute().await
+        }
+
         let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
         let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
 
@@ -98,9 +109,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             self.network.p2p_secret_key.clone().unwrap_or(default_secret_key_path);
         let p2p_secret_key = get_secret_key(&secret_key_path)?;
         let rlpx_socket = (self.network.addr, self.network.port).into();
-        let boot_nodes = self.chain.bootnodes().unwrap_or_default();
+        let boot_nodes = self
+            .network
+            .resolved_bootnodes()
+            .unwrap_or_else(|| self.chain.bootnodes().unwrap_or_default());
 
-        let net = NetworkConfigBuilder::<N>::new(p2p_secret_key)
+        let config = NetworkConfigBuilder::<N>::new(p2p_secret_key)
             .peer_config(config.peers_config_with_basic_nodes_from_file(None))
             .external_ip_resolver(self.network.nat)
             .disable_discv4_discovery_if(self.chain.chain().is_optimism())
@@ -108,9 +122,13 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             .apply(|builder| {
                 self.network.discovery.apply_to_builder(builder, rlpx_socket, boot_nodes)
             })
-            .build_with_noop_provider(self.chain)
-            .manager()
-            .await?;
+            .build_with_noop_provider(self.chain.clone());
+
+        if let Subcommands::Crawl(command) = self.command {
+            return command.execute(config, self.chain).await
+        }
+
+        let net = config.manager().await?;
         let network = net.handle().clone();
         tokio::task::spawn(net);
 
@@ -161,6 +179,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Rlpx(command) => {
                 command.execute().await?;
             }
+            Subcommands::DnsTree(_) | Subcommands::Crawl(_) => {
+                unreachable!("executed without the network manager")
+            }
         }
 
         Ok(())
@@ -0,0 +1,109 @@
+//! Command that packages the datadir into a snapshot.
+
+use super::manifest::{
+    write_archive, SnapshotManifest, DB_DIR, MANIFEST_FILE_NAME, MANIFEST_VERSION,
+    STATIC_FILES_DIR, ZSTD_DICTIONARIES_DIR,
+};
+use clap::Parser;
+use eyre::ensure;
+use reth_chainspec::EthChainSpec;
+use reth_config::Config;
+use reth_node_core::{
+    args::ByteSize,
+    dirs::{ChainPath, DataDirPath},
+};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockHashReader, ChainSpecProvider, ProviderError,
+    ProviderFactory, StageCheckpointReader,
+};
+use reth_stages::StageId;
+use std::{path::PathBuf, time::Instant};
+use tracing::info;
+
+/// The arguments for the `reth snapshot create` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory the snapshot chunks and manifest are written to.
+    #[arg(long, short, value_name = "DIR")]
+    output: PathBuf,
+
+    /// The maximum size of a snapshot chunk.
+    #[arg(long, value_name = "SIZE", default_value = "2GB")]
+    chunk_size: ByteSize,
+}
+
+impl Command {
+    /// Execute `snapshot create` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        config: &Config,
+        provider_factory: &ProviderFactory<N>,
+        data_dir: &ChainPath<DataDirPath>,
+    ) -> eyre::Result<()> {
+        let db_path = data_dir.db();
+        let static_files_path = data_dir.static_files();
+        let zstd_dictionaries_path = data_dir.zstd_dictionaries();
+
+        reth_fs_util::create_dir_all(&self.output)?;
+        let output = self.output.canonicalize()?;
+        for path in [&db_path, &static_files_path] {
+            ensure!(
+                !output.starts_with(path.canonicalize()?),
+                "Snapshot output directory {output:?} must not be inside {path:?}"
+            );
+        }
+        ensure!(
+            !output.join(MANIFEST_FILE_NAME).exists(),
+            "Snapshot output directory {output:?} already contains a snapshot"
+        );
+
+        let provider = provider_factory.provider()?;
+        let block_number =
+            provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        let block_hash = provider
+            .block_hash(block_number)?
+            .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+        drop(provider);
+
+        info!(target: "reth::cli", block_number, %block_hash, ?output, "Creating snapshot");
+        let start = Instant::now();
+
+        let mut dirs =
+            vec![(DB_DIR, db_path.as_path()), (STATIC_FILES_DIR, static_files_path.as_path())];
+        // Static file values compressed with trained dictionaries can't be read without them
+        if zstd_dictionaries_path.exists() {
+            dirs.push((ZSTD_DICTIONARIES_DIR, zstd_dictionaries_path.as_path()));
+        }
+        let (chunks, files) = write_archive(
+            &dirs,
+            &output,
+            &format!("snapshot-{block_number}.tar.lz4"),
+            self.chunk_size.0 as u64,
+        )?;
+
+        let manifest = SnapshotManifest {
+            version: MANIFEST_VERSION,
+            chain_id: provider_factory.chain_spec().chain().id(),
+            block_number,
+            block_hash,
+            prune: config.prune.clone(),
+            chunks,
+            files,
+        };
+        reth_fs_util::write(
+            output.join(MANIFEST_FILE_NAME),
+            serde_json::to_vec_pretty(&manifest)?,
+        )?;
+
+        info!(
+            target: "reth::cli",
+            chunks = manifest.chunks.len(),
+            files = manifest.files.len(),
+            size = %ByteSize(manifest.chunks.iter().map(|chunk| chunk.size).sum::<u64>() as usize),
+            elapsed = ?start.elapsed(),
+            "Snapshot created"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,425 @@
+//! Snapshot manifest and the archive format shared by `reth snapshot create` and `reth download`.
+//!
+//! A snapshot is a `tar` archive of the `db`, `static_files` and, if static file values are
+//! compressed with trained dictionaries, `zstd-dictionaries` directories, compressed with lz4 and
+//! split into chunks of a fixed size. The manifest lists the hashes of all chunks, so they can
+//! be verified and resumed independently, and the hashes of all archived files, so the extracted
+//! datadir can be verified as well.
+
+use alloy_primitives::{BlockNumber, Keccak256, B256};
+use eyre::{bail, ensure, OptionExt};
+use reth_config::PruneConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// Name of the manifest file in the snapshot directory.
+pub(crate) const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Current version of the snapshot format.
+pub(crate) const MANIFEST_VERSION: u64 = 1;
+
+/// Archive directory of the database files.
+pub(crate) const DB_DIR: &str = "db";
+
+/// Archive directory of the static files.
+pub(crate) const STATIC_FILES_DIR: &str = "static_files";
+
+/// Archive directory of the trained zstd dictionaries, which are needed to read the static files.
+pub(crate) const ZSTD_DICTIONARIES_DIR: &str = "zstd-dictionaries";
+
+/// Files that are specific to a running instance and never archived.
+const SKIPPED_FILE_NAMES: [&str; 2] = ["lock", "mdbx.lck"];
+
+/// Describes a snapshot created by `reth snapshot create`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Version of the snapshot format.
+    pub version: u64,
+    /// Chain ID of the snapshot.
+    pub chain_id: u64,
+    /// Number of the chain head at the time of the snapshot.
+    pub block_number: BlockNumber,
+    /// Hash of the chain head at the time of the snapshot.
+    pub block_hash: B256,
+    /// Prune configuration of the node the snapshot was created from.
+    pub prune: Option<PruneConfig>,
+    /// Chunks of the compressed archive, in order.
+    pub chunks: Vec<SnapshotEntry>,
+    /// Files contained in the archive.
+    pub files: Vec<SnapshotEntry>,
+}
+
+/// A file or an archive chunk of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    /// Path relative to the snapshot directory for chunks, or to the archive root for files.
+    pub path: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Keccak-256 hash of the content.
+    pub hash: B256,
+}
+
+/// Archives the given directories into lz4 compressed chunks of at most `chunk_size` bytes.
+///
+/// Each directory is archived under its archive name. Returns the written chunks and the archived
+/// files.
+pub(crate) fn write_archive(
+    dirs: &[(&str, &Path)],
+    output: &Path,
+    chunk_prefix: &str,
+    chunk_size: u64,
+) -> eyre::Result<(Vec<SnapshotEntry>, Vec<SnapshotEntry>)> {
+    ensure!(chunk_size > 0, "chunk size must be positive");
+
+    let writer = ChunkWriter::new(output, chunk_prefix, chunk_size);
+    let encoder = lz4::EncoderBuilder::new().build(writer)?;
+    let mut builder = tar::Builder::new(encoder);
+
+    let mut files = Vec::new();
+    for (name, dir) in dirs {
+        for path in list_files(dir)? {
+            let relative = path.strip_prefix(dir)?;
+            let archive_path = Path::new(name).join(relative);
+
+            let file = File::open(&path)?;
+            let size = file.metadata()?.len();
+            let mut header = tar::Header::new_gnu();
+            header.set_size(size);
+            header.set_mode(0o644);
+            header.set_cksum();
+
+            let mut reader = HashingReader::new(BufReader::new(file));
+            builder.append_data(&mut header, &archive_path, &mut reader)?;
+
+            files.push(SnapshotEntry {
+                path: archive_path.to_string_lossy().into_owned(),
+                size,
+                hash: reader.finalize(),
+            });
+        }
+    }
+
+    let (writer, result) = builder.into_inner()?.finish();
+    result?;
+    let chunks = writer.finish()?;
+
+    Ok((chunks, files))
+}
+
+/// Extracts the archive from the chunks at `chunk_paths`, verifying every file against the
+/// manifest.
+///
+/// `target_dir` maps archive directory names to the directories they're extracted to.
+pub(crate) fn extract_archive(
+    manifest: &SnapshotManifest,
+    chunk_paths: Vec<PathBuf>,
+    target_dir: impl Fn(&str) -> Option<PathBuf>,
+) -> eyre::Result<()> {
+    let mut expected =
+        manifest.files.iter().map(|file| (file.path.as_str(), file)).collect::<HashMap<_, _>>();
+
+    let decoder = lz4::Decoder::new(ChunkReader::new(chunk_paths))?;
+    let mut archive = tar::Archive::new(decoder);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let archive_path = path.to_string_lossy().into_owned();
+
+        let Some(file) = expected.remove(archive_path.as_str()) else {
+            bail!("Unexpected file in snapshot archive: {archive_path}")
+        };
+
+        // Only plain relative paths below one of the known directories are extracted
+        let mut components = path.components();
+        let Some(Component::Normal(root)) = components.next() else {
+            bail!("Invalid path in snapshot archive: {archive_path}")
+        };
+        let relative = components.as_path();
+        ensure!(
+            !relative.as_os_str().is_empty() &&
+                relative.components().all(|c| matches!(c, Component::Normal(_))),
+            "Invalid path in snapshot archive: {archive_path}"
+        );
+        let dir = target_dir(&root.to_string_lossy())
+            .ok_or_eyre(format!("Unknown directory in snapshot archive: {archive_path}"))?;
+
+        let target = dir.join(relative);
+        if let Some(parent) = target.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        let mut writer = HashingWriter::new(BufWriter::new(File::create(&target)?));
+        let size = io::copy(&mut entry, &mut writer)?;
+        let hash = writer.finalize()?;
+
+        ensure!(
+            size == file.size && hash == file.hash,
+            "Snapshot file {archive_path} does not match the manifest: expected {} bytes with hash \
+             {}, got {size} bytes with hash {hash}",
+            file.size,
+            file.hash,
+        );
+    }
+
+    if let Some(path) = expected.keys().next() {
+        bail!("Snapshot archive is missing {} files, including {path}", expected.len())
+    }
+
+    Ok(())
+}
+
+/// Returns the size and the hash of the file at `path`.
+pub(crate) fn hash_file(path: &Path) -> io::Result<(u64, B256)> {
+    let mut reader = HashingReader::new(BufReader::new(File::open(path)?));
+    let size = io::copy(&mut reader, &mut io::sink())?;
+    Ok((size, reader.finalize()))
+}
+
+/// Lists all files below `dir` recursively, sorted by path.
+fn list_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                dirs.push(entry.path());
+            } else if file_type.is_file() &&
+                !SKIPPED_FILE_NAMES.iter().any(|name| entry.file_name() == *name)
+            {
+                files.push(entry.path());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Reader that hashes everything read through it.
+struct HashingReader<R> {
+    reader: R,
+    hasher: Keccak256,
+}
+
+impl<R> HashingReader<R> {
+    fn new(reader: R) -> Self {
+        Self { reader, hasher: Keccak256::new() }
+    }
+
+    fn finalize(self) -> B256 {
+        self.hasher.finalize()
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+/// Writer that hashes everything written through it.
+struct HashingWriter<W> {
+    writer: W,
+    hasher: Keccak256,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(writer: W) -> Self {
+        Self { writer, hasher: Keccak256::new() }
+    }
+
+    fn finalize(mut self) -> io::Result<B256> {
+        self.writer.flush()?;
+        Ok(self.hasher.finalize())
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Writer that splits its output into files of at most `chunk_size` bytes.
+struct ChunkWriter {
+    dir: PathBuf,
+    prefix: String,
+    chunk_size: u64,
+    current: Option<(HashingWriter<BufWriter<File>>, SnapshotEntry)>,
+    chunks: Vec<SnapshotEntry>,
+}
+
+impl ChunkWriter {
+    fn new(dir: &Path, prefix: &str, chunk_size: u64) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            prefix: prefix.to_string(),
+            chunk_size,
+            current: None,
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Closes the current chunk, if any.
+    fn close_chunk(&mut self) -> io::Result<()> {
+        if let Some((writer, mut chunk)) = self.current.take() {
+            chunk.hash = writer.finalize()?;
+            self.chunks.push(chunk);
+        }
+        Ok(())
+    }
+
+    /// Closes the last chunk and returns all written chunks.
+    fn finish(mut self) -> io::Result<Vec<SnapshotEntry>> {
+        self.close_chunk()?;
+        Ok(self.chunks)
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.current.as_ref().is_some_and(|(_, chunk)| chunk.size >= self.chunk_size) {
+            self.close_chunk()?;
+        }
+
+        let (writer, chunk) = match &mut self.current {
+            Some(current) => current,
+            None => {
+                let path = format!("{}.{:05}", self.prefix, self.chunks.len());
+                let file = File::create(self.dir.join(&path))?;
+                self.current.insert((
+                    HashingWriter::new(BufWriter::new(file)),
+                    SnapshotEntry { path, size: 0, hash: B256::ZERO },
+                ))
+            }
+        };
+
+        let len = buf.len().min((self.chunk_size - chunk.size) as usize);
+        let written = writer.write(&buf[..len])?;
+        chunk.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some((writer, _)) = &mut self.current {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Reader over the concatenation of the files at the given paths.
+struct ChunkReader {
+    paths: std::vec::IntoIter<PathBuf>,
+    current: Option<BufReader<File>>,
+}
+
+impl ChunkReader {
+    fn new(paths: Vec<PathBuf>) -> Self {
+        Self { paths: paths.into_iter(), current: None }
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(reader) = &mut self.current {
+                let read = reader.read(buf)?;
+                if read > 0 || buf.is_empty() {
+                    return Ok(read)
+                }
+            }
+
+            match self.paths.next() {
+                Some(path) => self.current = Some(BufReader::new(File::open(path)?)),
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_roundtrip() {
+        let source = tempfile::tempdir().unwrap();
+        let db = source.path().join("db");
+        let static_files = source.path().join("static_files");
+        fs::create_dir_all(&db).unwrap();
+        fs::create_dir_all(static_files.join("nested")).unwrap();
+        fs::write(db.join("mdbx.dat"), vec![7u8; 10_000]).unwrap();
+        fs::write(db.join("mdbx.lck"), b"lock").unwrap();
+        fs::write(static_files.join("static_file_headers_0_499999"), b"headers").unwrap();
+        fs::write(static_files.join("nested").join("file"), b"nested").unwrap();
+        fs::write(static_files.join("lock"), b"lock").unwrap();
+
+        let output = tempfile::tempdir().unwrap();
+        let (chunks, files) = write_archive(
+            &[(DB_DIR, &db), (STATIC_FILES_DIR, &static_files)],
+            output.path(),
+            "snapshot.tar.lz4",
+            64,
+        )
+        .unwrap();
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.size <= 64));
+        for chunk in &chunks {
+            assert_eq!(
+                hash_file(&output.path().join(&chunk.path)).unwrap(),
+                (chunk.size, chunk.hash)
+            );
+        }
+        assert_eq!(
+            files.iter().map(|file| file.path.as_str()).collect::<Vec<_>>(),
+            [
+                "db/mdbx.dat",
+                "static_files/nested/file",
+                "static_files/static_file_headers_0_499999"
+            ]
+        );
+
+        let mut manifest = SnapshotManifest {
+            version: MANIFEST_VERSION,
+            chain_id: 1,
+            block_number: 0,
+            block_hash: B256::ZERO,
+            prune: None,
+            chunks,
+            files,
+        };
+        let chunk_paths =
+            manifest.chunks.iter().map(|chunk| output.path().join(&chunk.path)).collect::<Vec<_>>();
+
+        let target = tempfile::tempdir().unwrap();
+        let target_dir = |name: &str| Some(target.path().join(name));
+        extract_archive(&manifest, chunk_paths.clone(), target_dir).unwrap();
+        assert_eq!(fs::read(target.path().join("db").join("mdbx.dat")).unwrap(), vec![7u8; 10_000]);
+        assert_eq!(
+            fs::read(target.path().join("static_files").join("nested").join("file")).unwrap(),
+            b"nested"
+        );
+        assert!(!target.path().join("db").join("mdbx.lck").exists());
+
+        // Tampered files are rejected
+        manifest.files[0].hash = B256::repeat_byte(1);
+        let target = tempfile::tempdir().unwrap();
+        let target_dir = |name: &str| Some(target.path().join(name));
+        assert!(extract_archive(&manifest, chunk_paths, target_dir).is_err());
+    }
+}
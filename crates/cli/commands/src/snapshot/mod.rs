@@ -0,0 +1,52 @@
+//! `reth snapshot` command
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use std::sync::Arc;
+
+mod create;
+pub(crate) mod manifest;
+
+/// `reth snapshot` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Subcommand, Debug)]
+/// `reth snapshot` subcommands
+pub enum Subcommands {
+    /// Packages the database and static files into chunked archives with a manifest of their
+    /// hashes. The node must be stopped.
+    Create(create::Command),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `snapshot` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Create(command) => {
+                // Opening the storage with write access locks it, so no node can modify it while
+                // the snapshot is created.
+                let Environment { config, provider_factory, data_dir } =
+                    self.env.init::<N>(AccessRights::RW)?;
+                command.execute(&config, &provider_factory, &data_dir)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
@@ -0,0 +1,52 @@
+//! `reth static-file` command
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use std::sync::Arc;
+
+pub(crate) mod recompress;
+
+/// `reth static-file` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Subcommand, Debug)]
+/// `reth static-file` subcommands
+pub enum Subcommands {
+    /// Rewrites static files with a different compression. The node must be stopped.
+    ///
+    /// The files keep their block ranges, since nodes only read static files of 500000 blocks
+    /// each.
+    Recompress(recompress::Command),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `static-file` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Recompress(command) => {
+                // Opening the storage with write access locks it, and completes any replacement of
+                // static files that was interrupted.
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                command.execute(&provider_factory)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
@@ -0,0 +1,267 @@
+//! Command that rewrites static files with a different compression.
+
+use clap::{Parser, ValueEnum};
+use eyre::{ensure, OptionExt};
+use human_bytes::human_bytes;
+use itertools::Itertools;
+use reth_db::static_file::iter_static_files;
+use reth_nippy_jar::{
+    compression::{Compressors, Lz4, Zstd},
+    NippyJar, NippyJarChecker, NippyJarCursor, NippyJarWriter,
+};
+use reth_node_core::zstd_dictionaries::TrainedDictionary;
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory, StaticFileProviderFactory};
+use reth_static_file_types::{SegmentHeader, StaticFileSegment};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// The arguments for the `reth static-file recompress` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The static file segments to recompress. Defaults to the segment of the dictionary if one is
+    /// set, or to all segments otherwise.
+    #[arg(long, value_delimiter = ',')]
+    segments: Vec<StaticFileSegment>,
+
+    /// The compression the static files are rewritten with.
+    #[arg(long, value_enum)]
+    compression: StaticFileCompression,
+
+    /// The zstd compression level. A level of `0` uses zstd's default.
+    #[arg(long, default_value_t = 0)]
+    zstd_level: i32,
+
+    /// A dictionary trained by `reth db train-dictionary` to compress the static files with.
+    /// Requires `--compression zstd`.
+    #[arg(long, value_name = "FILE")]
+    dictionary: Option<PathBuf>,
+}
+
+/// Compressions static files can be rewritten with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StaticFileCompression {
+    /// No compression.
+    None,
+    /// LZ4 compression.
+    Lz4,
+    /// Zstandard compression.
+    Zstd,
+}
+
+impl Command {
+    /// Execute `static-file recompress` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: &ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let dictionary = self.dictionary.as_deref().map(TrainedDictionary::load).transpose()?;
+        ensure!(
+            dictionary.is_none() || self.compression == StaticFileCompression::Zstd,
+            "A dictionary can only be used with zstd compression"
+        );
+
+        let static_file_provider = provider_factory.static_file_provider();
+        let static_files = iter_static_files(static_file_provider.directory())?;
+
+        let segments = if !self.segments.is_empty() {
+            self.segments.clone()
+        } else if let Some(dictionary) = &dictionary {
+            vec![dictionary.segment]
+        } else {
+            static_files.keys().copied().sorted().collect()
+        };
+        if let Some(dictionary) = &dictionary {
+            ensure!(
+                segments.iter().all(|segment| *segment == dictionary.segment),
+                "Dictionary was trained for segment {}",
+                dictionary.segment
+            );
+        }
+
+        for segment in segments {
+            let Some(ranges) = static_files.get(&segment) else {
+                info!(target: "reth::cli", %segment, "No static files to recompress");
+                continue
+            };
+
+            let (mut size_before, mut size_after) = (0, 0);
+            for (block_range, _) in ranges {
+                let fixed_block_range = static_file_provider.find_fixed_range(block_range.start());
+                let path =
+                    static_file_provider.directory().join(segment.filename(&fixed_block_range));
+                let jar = NippyJar::<SegmentHeader>::load(&path)?;
+                if jar.rows() == 0 {
+                    continue
+                }
+
+                let compressor = self.compressor(dictionary.as_ref(), jar.columns());
+                let (before, after) =
+                    recompress_jar(jar, &path, compressor, |value| Ok(Cow::Borrowed(value)))?;
+                info!(
+                    target: "reth::cli",
+                    ?path,
+                    before = %human_bytes(before as f64),
+                    after = %human_bytes(after as f64),
+                    "Recompressed static file"
+                );
+                size_before += before;
+                size_after += after;
+            }
+
+            info!(
+                target: "reth::cli",
+                %segment,
+                before = %human_bytes(size_before as f64),
+                after = %human_bytes(size_after as f64),
+                "Recompressed static files"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns the compressor for a jar with the given number of columns.
+    fn compressor(
+        &self,
+        dictionary: Option<&TrainedDictionary>,
+        columns: usize,
+    ) -> Option<Compressors> {
+        match self.compression {
+            StaticFileCompression::None => None,
+            StaticFileCompression::Lz4 => Some(Compressors::Lz4(Lz4::default())),
+            StaticFileCompression::Zstd => {
+                let zstd = match dictionary {
+                    Some(dictionary) => {
+                        Zstd::with_dictionaries(vec![dictionary.dictionary.to_vec(); columns])
+                    }
+                    None => Zstd::new(false, 0, columns),
+                };
+                Some(Compressors::Zstd(zstd.with_level(self.zstd_level)))
+            }
+        }
+    }
+}
+
+/// Rewrites the jar at `path` with `compressor` and every value passed through `reencode`, and
+/// replaces it once the new jar is verified.
+///
+/// Returns the data file sizes before and after recompression.
+pub(crate) fn recompress_jar(
+    jar: NippyJar<SegmentHeader>,
+    path: &Path,
+    compressor: Option<Compressors>,
+    reencode: impl Fn(&[u8]) -> eyre::Result<Cow<'_, [u8]>>,
+) -> eyre::Result<(u64, u64)> {
+    // Jar files are named by replacing the extension of the data file, so the temporary jar gets a
+    // prefix instead, which also keeps it from being picked up as a static file.
+    let file_name = path.file_name().ok_or_eyre("Static file path has no file name")?;
+    let tmp_path = path.with_file_name(format!("recompress_{}", file_name.to_string_lossy()));
+    let tmp_jar = NippyJar::new(jar.columns(), &tmp_path, jar.user_header().clone());
+    remove_jar_files(&tmp_jar)?;
+
+    let mut writer = NippyJarWriter::new(tmp_jar.with_compressor(compressor))?;
+    let mut cursor = NippyJarCursor::new(&jar)?;
+    while let Some(row) = cursor.next_row()? {
+        for value in row {
+            writer.append_column(Some(Ok(reencode(value)?)))?;
+        }
+    }
+    writer.commit()?;
+    drop(writer);
+
+    // Verify the new jar before replacing the old one
+    NippyJarChecker::new(NippyJar::<SegmentHeader>::load(&tmp_path)?).check_consistency()?;
+    let tmp_jar = NippyJar::<SegmentHeader>::load(&tmp_path)?;
+    ensure!(
+        tmp_jar.rows() == jar.rows(),
+        "Recompressed static file {tmp_path:?} has {} rows, expected {}",
+        tmp_jar.rows(),
+        jar.rows()
+    );
+    let mut cursor = NippyJarCursor::new(&jar)?;
+    let mut tmp_cursor = NippyJarCursor::new(&tmp_jar)?;
+    let mut row_number = 0;
+    while let Some(row) = cursor.next_row()? {
+        let row = row.into_iter().map(&reencode).collect::<eyre::Result<Vec<_>>>()?;
+        ensure!(
+            tmp_cursor
+                .next_row()?
+                .is_some_and(|tmp_row| tmp_row.iter().copied().eq(row.iter().map(AsRef::as_ref))),
+            "Recompressed static file {tmp_path:?} differs at row {row_number}"
+        );
+        row_number += 1;
+    }
+    drop(tmp_cursor);
+
+    let size_before = reth_fs_util::metadata(jar.data_path())?.len();
+    let size_after = reth_fs_util::metadata(tmp_jar.data_path())?.len();
+
+    tmp_jar.replace(path)?;
+
+    Ok((size_before, size_after))
+}
+
+/// Removes the data, offsets and configuration files of the jar, if they exist.
+fn remove_jar_files(jar: &NippyJar<SegmentHeader>) -> eyre::Result<()> {
+    for path in [jar.data_path().to_path_buf(), jar.offsets_path(), jar.config_path()] {
+        if path.exists() {
+            reth_fs_util::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_static_file_types::SegmentRangeInclusive;
+    use std::fs;
+
+    #[test]
+    fn recompress_jar_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let segment = StaticFileSegment::Receipts;
+        let block_range = SegmentRangeInclusive::new(0, 499_999);
+        let path = dir.path().join(segment.filename(&block_range));
+
+        let rows = (0..1000u64)
+            .map(|i| format!("receipt {i} with some repeated content").into_bytes())
+            .collect::<Vec<_>>();
+        let mut writer = NippyJarWriter::new(NippyJar::new(
+            segment.columns(),
+            &path,
+            SegmentHeader::new(block_range, Some(block_range), Some(block_range), segment),
+        ))
+        .unwrap();
+        for row in &rows {
+            writer.append_column(Some(Ok(row))).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let sizes = rows.iter().map(Vec::len).collect::<Vec<_>>();
+        let dictionary = zstd::dict::from_continuous(&rows.concat(), &sizes, 4096).unwrap();
+        let compressors = [
+            Some(Compressors::Zstd(Zstd::with_dictionaries(vec![dictionary]))),
+            Some(Compressors::Lz4(Lz4::default())),
+            Some(Compressors::Zstd(Zstd::new(false, 0, 1).with_level(3))),
+            None,
+        ];
+
+        for compressor in compressors {
+            let jar = NippyJar::<SegmentHeader>::load(&path).unwrap();
+            recompress_jar(jar, &path, compressor, |value| Ok(Cow::Borrowed(value))).unwrap();
+            assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+
+            let jar = NippyJar::<SegmentHeader>::load(&path).unwrap();
+            assert_eq!(jar.rows(), rows.len());
+            let mut cursor = NippyJarCursor::new(&jar).unwrap();
+            for row in &rows {
+                assert_eq!(cursor.next_row().unwrap().unwrap(), vec![row.as_slice()]);
+            }
+        }
+    }
+}
@@ -18,7 +18,8 @@ use reth_codecs::alloy::{
 };
 use reth_db::{
     models::{
-        AccountBeforeTx, StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlockOmmers,
+        AccountBeforeTx, StaticFileAccountChangeSet, StaticFileBlockWithdrawals,
+        StaticFileStorageChangeSet, StorageBeforeTx, StoredBlockBodyIndices, StoredBlockOmmers,
         StoredBlockWithdrawals,
     },
     ClientVersion,
@@ -29,8 +30,8 @@ use reth_primitives_traits::{Account, Log, LogData, StorageEntry};
 use reth_prune_types::{PruneCheckpoint, PruneMode};
 use reth_stages_types::{
     AccountHashingCheckpoint, CheckpointBlockRange, EntitiesCheckpoint, ExecutionCheckpoint,
-    HeadersCheckpoint, IndexHistoryCheckpoint, StageCheckpoint, StageUnitCheckpoint,
-    StorageHashingCheckpoint,
+    HeadersCheckpoint, IndexHistoryCheckpoint, SnapSyncCheckpoint, StageCheckpoint,
+    StageUnitCheckpoint, StorageHashingCheckpoint,
 };
 use reth_trie::{hash_builder::HashBuilderValue, TrieMask};
 use reth_trie_common::{hash_builder::HashBuilderState, StoredNibbles, StoredNibblesSubKey};
@@ -104,6 +105,7 @@ compact_types!(
         ExecutionCheckpoint,
         HeadersCheckpoint,
         IndexHistoryCheckpoint,
+        SnapSyncCheckpoint,
         EntitiesCheckpoint,
         CheckpointBlockRange,
         StageCheckpoint,
@@ -113,12 +115,15 @@ compact_types!(
         StoredBlockBodyIndices,
         StoredBlockWithdrawals,
         StaticFileBlockWithdrawals,
+        StaticFileAccountChangeSet,
+        StaticFileStorageChangeSet,
         // Manual implementations
         TransactionSigned,
         // Bytecode, // todo revm arbitrary
         StorageEntry,
         // MerkleCheckpoint, // todo storedsubnode -> branchnodecompact arbitrary
         AccountBeforeTx,
+        StorageBeforeTx,
         ClientVersion,
         StoredNibbles,
         StoredNibblesSubKey,
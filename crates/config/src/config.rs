@@ -125,6 +125,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Transactions stage configuration.
+    pub index_address_transactions: IndexAddressTransactionsConfig,
+    /// Snap Sync stage configuration.
+    pub snap_sync: SnapSyncConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -377,18 +381,28 @@ pub struct EtlConfig {
     pub dir: Option<PathBuf>,
     /// The maximum size in bytes of data held in memory before being flushed to disk as a file.
     pub file_size: usize,
+    /// The zstd compression level of the files flushed to disk. Files aren't compressed if unset.
+    pub compression_level: Option<i32>,
+    /// The maximum size in bytes of the files a stage flushes to disk. The stage fails once it's
+    /// exceeded. Unlimited if unset.
+    pub max_disk_usage: Option<u64>,
 }
 
 impl Default for EtlConfig {
     fn default() -> Self {
-        Self { dir: None, file_size: Self::default_file_size() }
+        Self {
+            dir: None,
+            file_size: Self::default_file_size(),
+            compression_level: None,
+            max_disk_usage: None,
+        }
     }
 }
 
 impl EtlConfig {
     /// Creates an ETL configuration
     pub const fn new(dir: Option<PathBuf>, file_size: usize) -> Self {
-        Self { dir, file_size }
+        Self { dir, file_size, compression_level: None, max_disk_usage: None }
     }
 
     /// Return default ETL directory from datadir path.
@@ -418,6 +432,41 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Address transactions index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexAddressTransactionsConfig {
+    /// Whether the address transactions index should be built. Disabled by default.
+    pub enabled: bool,
+    /// The maximum number of transactions to process before writing to disk.
+    pub chunk_size: u64,
+}
+
+impl Default for IndexAddressTransactionsConfig {
+    fn default() -> Self {
+        Self { enabled: false, chunk_size: 5_000_000 }
+    }
+}
+
+/// Snap sync stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SnapSyncConfig {
+    /// Whether the state should be downloaded over the snap protocol instead of executing every
+    /// block since genesis. Disabled by default.
+    pub enabled: bool,
+    /// The soft limit in bytes of a single snap response requested from peers.
+    pub response_bytes: u64,
+}
+
+impl Default for SnapSyncConfig {
+    fn default() -> Self {
+        Self { enabled: false, response_bytes: 512 * 1024 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -455,6 +504,7 @@ impl PruneConfig {
                     receipts,
                     account_history,
                     storage_history,
+                    address_transactions,
                     receipts_log_filter,
                 },
         } = other;
@@ -470,6 +520,8 @@ impl PruneConfig {
         self.segments.receipts = self.segments.receipts.or(receipts);
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
+        self.segments.address_transactions =
+            self.segments.address_transactions.or(address_transactions);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -990,6 +1042,7 @@ receipts = 'full'
                 receipts: Some(PruneMode::Distance(1000)),
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
+                address_transactions: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -1005,6 +1058,7 @@ receipts = 'full'
                 receipts: Some(PruneMode::Full),
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
+                address_transactions: Some(PruneMode::Distance(4000)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
@@ -1023,6 +1077,7 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.address_transactions, Some(PruneMode::Distance(4000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
@@ -1,16 +1,17 @@
 //! Contains the implementation of the mining mode for the local engine.
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{TxHash, B256};
-use alloy_rpc_types_engine::ForkchoiceState;
+use alloy_primitives::{BlockNumber, TxHash, B256};
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus};
 use eyre::OptionExt;
 use futures_util::{stream::Fuse, StreamExt};
-use reth_engine_primitives::BeaconConsensusEngineHandle;
+use reth_engine_primitives::{BeaconConsensusEngineHandle, BeaconOnNewPayloadError};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, PayloadAttributesBuilder, PayloadKind, PayloadTypes,
 };
 use reth_provider::BlockReader;
+use reth_revm::state_changes::PendingStateChanges;
 use reth_transaction_pool::TransactionPool;
 use std::{
     future::Future,
@@ -18,9 +19,12 @@ use std::{
     task::{Context, Poll},
     time::{Duration, UNIX_EPOCH},
 };
-use tokio::time::Interval;
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Interval,
+};
 use tokio_stream::wrappers::ReceiverStream;
-use tracing::error;
+use tracing::{debug, error};
 
 /// A mining mode for the local dev engine.
 #[derive(Debug)]
@@ -30,6 +34,8 @@ pub enum MiningMode {
     Instant(Fuse<ReceiverStream<TxHash>>),
     /// In this mode a block is built at a fixed interval.
     Interval(Interval),
+    /// In this mode blocks are only built on request, see [`LocalMinerHandle::mine`].
+    Manual,
 }
 
 impl MiningMode {
@@ -44,6 +50,11 @@ impl MiningMode {
         let start = tokio::time::Instant::now() + duration;
         Self::Interval(tokio::time::interval_at(start, duration))
     }
+
+    /// Returns `true` if this is [`MiningMode::Instant`].
+    pub const fn is_instant(&self) -> bool {
+        matches!(self, Self::Instant(_))
+    }
 }
 
 impl Future for MiningMode {
@@ -65,10 +76,112 @@ impl Future for MiningMode {
                 }
                 Poll::Pending
             }
+            Self::Manual => Poll::Pending,
         }
     }
 }
 
+/// A command sent to the [`LocalMiner`] by a [`LocalMinerHandle`].
+#[derive(Debug)]
+enum MinerCommand {
+    /// Mines the given number of blocks, spacing their timestamps by the given interval.
+    Mine { blocks: u64, interval: Option<u64>, tx: oneshot::Sender<eyre::Result<()>> },
+    /// Mines a block if the miner is in [`MiningMode::Instant`].
+    MineIfInstant { tx: oneshot::Sender<eyre::Result<()>> },
+    /// Replaces the mining mode.
+    SetMode(MiningMode),
+    /// Returns whether the miner is in [`MiningMode::Instant`].
+    IsInstant { tx: oneshot::Sender<bool> },
+    /// Sets the timestamp of the next block.
+    SetNextTimestamp { timestamp: u64, tx: oneshot::Sender<eyre::Result<()>> },
+    /// Records the current head and returns the id of the snapshot.
+    Snapshot { tx: oneshot::Sender<u64> },
+    /// Reverts the chain to the snapshot with the given id.
+    Revert { id: u64, tx: oneshot::Sender<eyre::Result<bool>> },
+}
+
+/// A handle to control a running [`LocalMiner`].
+#[derive(Debug, Clone)]
+pub struct LocalMinerHandle {
+    to_miner: mpsc::UnboundedSender<MinerCommand>,
+    pending_state_changes: PendingStateChanges,
+}
+
+impl LocalMinerHandle {
+    /// Returns the state changes that are committed in the next mined block.
+    pub const fn pending_state_changes(&self) -> &PendingStateChanges {
+        &self.pending_state_changes
+    }
+
+    /// Mines the given number of blocks right away, regardless of the mining mode.
+    ///
+    /// If an interval is given, the timestamps of the blocks are that many seconds apart.
+    pub async fn mine(&self, blocks: u64, interval: Option<u64>) -> eyre::Result<()> {
+        self.request(|tx| MinerCommand::Mine { blocks, interval, tx }).await?
+    }
+
+    /// Mines a block if the miner builds a block for every transaction, so that the pending state
+    /// changes take effect right away.
+    pub async fn mine_if_instant(&self) -> eyre::Result<()> {
+        self.request(|tx| MinerCommand::MineIfInstant { tx }).await?
+    }
+
+    /// Replaces the mining mode of the miner.
+    pub fn set_mining_mode(&self, mode: MiningMode) -> eyre::Result<()> {
+        self.to_miner.send(MinerCommand::SetMode(mode)).map_err(|_| eyre::eyre!("miner stopped"))
+    }
+
+    /// Returns `true` if the miner builds a block for every transaction.
+    pub async fn is_instant(&self) -> eyre::Result<bool> {
+        self.request(|tx| MinerCommand::IsInstant { tx }).await
+    }
+
+    /// Sets the timestamp of the next mined block, which must be later than the current head.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> eyre::Result<()> {
+        self.request(|tx| MinerCommand::SetNextTimestamp { timestamp, tx }).await?
+    }
+
+    /// Records the current head of the chain and returns an id to revert to it later.
+    pub async fn snapshot(&self) -> eyre::Result<u64> {
+        self.request(|tx| MinerCommand::Snapshot { tx }).await
+    }
+
+    /// Reverts the chain to the head recorded by the snapshot with the given id.
+    ///
+    /// The snapshot and all later ones are removed. Returns `false` if there is no snapshot with
+    /// that id.
+    pub async fn revert(&self, id: u64) -> eyre::Result<bool> {
+        self.request(|tx| MinerCommand::Revert { id, tx }).await?
+    }
+
+    async fn request<R>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<R>) -> MinerCommand,
+    ) -> eyre::Result<R> {
+        let (tx, rx) = oneshot::channel();
+        self.to_miner.send(command(tx)).map_err(|_| eyre::eyre!("miner stopped"))?;
+        rx.await.map_err(|_| eyre::eyre!("miner stopped"))
+    }
+}
+
+/// The head of the chain recorded by [`LocalMinerHandle::snapshot`].
+#[derive(Debug, Clone, Copy)]
+struct Snapshot {
+    number: BlockNumber,
+    hash: B256,
+    timestamp: u64,
+}
+
+/// A payload built by the [`LocalMiner`], sent to the engine launcher so the executed block is
+/// inserted right before the payload is submitted as `newPayload`.
+#[derive(Debug)]
+pub struct LocalPayload<T: PayloadTypes> {
+    /// The built payload.
+    pub payload: T::BuiltPayload,
+    /// The sender for returning the `newPayload` status.
+    pub tx: oneshot::Sender<Result<PayloadStatus, BeaconOnNewPayloadError>>,
+}
+
 /// Local miner advancing the chain/
 #[derive(Debug)]
 pub struct LocalMiner<T: PayloadTypes, B> {
@@ -82,8 +195,22 @@ pub struct LocalMiner<T: PayloadTypes, B> {
     payload_builder: PayloadBuilderHandle<T>,
     /// Timestamp for the next block.
     last_timestamp: u64,
+    /// Number of the latest mined block.
+    last_block_number: BlockNumber,
     /// Stores latest mined blocks.
     last_block_hashes: Vec<B256>,
+    /// Timestamp requested for the next block.
+    next_timestamp: Option<u64>,
+    /// Heads recorded by [`LocalMinerHandle::snapshot`].
+    snapshots: Vec<Snapshot>,
+    /// State changes committed in the next block.
+    pending_state_changes: PendingStateChanges,
+    /// Sender half of the command channel, cloned into [`LocalMinerHandle`]s.
+    to_miner: mpsc::UnboundedSender<MinerCommand>,
+    /// Commands from [`LocalMinerHandle`]s.
+    from_handles: mpsc::UnboundedReceiver<MinerCommand>,
+    /// Receives the built payloads instead of the engine handle, if set.
+    local_payloads: Option<mpsc::UnboundedSender<LocalPayload<T>>>,
 }
 
 impl<T, B> LocalMiner<T, B>
@@ -101,6 +228,7 @@ where
     ) -> Self {
         let latest_header =
             provider.sealed_header(provider.best_block_number().unwrap()).unwrap().unwrap();
+        let (to_miner, from_handles) = mpsc::unbounded_channel();
 
         Self {
             payload_attributes_builder,
@@ -108,7 +236,46 @@ where
             mode,
             payload_builder,
             last_timestamp: latest_header.timestamp(),
+            last_block_number: latest_header.number(),
             last_block_hashes: vec![latest_header.hash()],
+            next_timestamp: None,
+            snapshots: Vec::new(),
+            pending_state_changes: PendingStateChanges::default(),
+            to_miner,
+            from_handles,
+            local_payloads: None,
+        }
+    }
+
+    /// Sets the [`PendingStateChanges`] that are committed in the next mined block.
+    ///
+    /// These must be the same changes the payload builder commits.
+    pub fn with_pending_state_changes(
+        mut self,
+        pending_state_changes: PendingStateChanges,
+    ) -> Self {
+        self.pending_state_changes = pending_state_changes;
+        self
+    }
+
+    /// Sends the built payloads as [`LocalPayload`]s to the given channel instead of submitting
+    /// them to the engine handle.
+    ///
+    /// The receiver is expected to insert the executed block and forward the payload as
+    /// `newPayload` in the same order.
+    pub fn with_local_payloads(
+        mut self,
+        local_payloads: mpsc::UnboundedSender<LocalPayload<T>>,
+    ) -> Self {
+        self.local_payloads = Some(local_payloads);
+        self
+    }
+
+    /// Returns a [`LocalMinerHandle`] to control the miner once it's running.
+    pub fn handle(&self) -> LocalMinerHandle {
+        LocalMinerHandle {
+            to_miner: self.to_miner.clone(),
+            pending_state_changes: self.pending_state_changes.clone(),
         }
     }
 
@@ -129,10 +296,104 @@ where
                         error!(target: "engine::local", "Error updating fork choice: {:?}", e);
                     }
                 }
+                Some(command) = self.from_handles.recv() => {
+                    self.on_command(command).await;
+                }
             }
         }
     }
 
+    /// Handles a command from a [`LocalMinerHandle`].
+    async fn on_command(&mut self, command: MinerCommand) {
+        match command {
+            MinerCommand::Mine { blocks, interval, tx } => {
+                let mut res = Ok(());
+                for block in 0..blocks {
+                    if block > 0 {
+                        if let Some(interval) = interval {
+                            self.next_timestamp = Some(self.last_timestamp + interval.max(1));
+                        }
+                    }
+                    res = self.advance().await;
+                    if res.is_err() {
+                        break
+                    }
+                }
+                // make the mined blocks canonical before responding
+                if res.is_ok() {
+                    res = self.update_forkchoice_state().await;
+                }
+                let _ = tx.send(res);
+            }
+            MinerCommand::MineIfInstant { tx } => {
+                let res = if self.mode.is_instant() {
+                    match self.advance().await {
+                        Ok(()) => self.update_forkchoice_state().await,
+                        err => err,
+                    }
+                } else {
+                    Ok(())
+                };
+                let _ = tx.send(res);
+            }
+            MinerCommand::SetMode(mode) => {
+                self.mode = mode;
+            }
+            MinerCommand::IsInstant { tx } => {
+                let _ = tx.send(self.mode.is_instant());
+            }
+            MinerCommand::SetNextTimestamp { timestamp, tx } => {
+                let res = if timestamp <= self.last_timestamp {
+                    Err(eyre::eyre!(
+                        "timestamp {timestamp} is not after the latest block timestamp {}",
+                        self.last_timestamp
+                    ))
+                } else {
+                    self.next_timestamp = Some(timestamp);
+                    Ok(())
+                };
+                let _ = tx.send(res);
+            }
+            MinerCommand::Snapshot { tx } => {
+                self.snapshots.push(Snapshot {
+                    number: self.last_block_number,
+                    hash: *self.last_block_hashes.last().expect("at least 1 block exists"),
+                    timestamp: self.last_timestamp,
+                });
+                let _ = tx.send(self.snapshots.len() as u64 - 1);
+            }
+            MinerCommand::Revert { id, tx } => {
+                let _ = tx.send(self.revert(id).await);
+            }
+        }
+    }
+
+    /// Reverts the chain to the snapshot with the given id, see [`LocalMinerHandle::revert`].
+    async fn revert(&mut self, id: u64) -> eyre::Result<bool> {
+        let Some(snapshot) = self.snapshots.get(id as usize).copied() else { return Ok(false) };
+
+        if snapshot.number < self.last_block_number {
+            debug!(target: "engine::local", ?snapshot, "Reverting to snapshot");
+            self.to_engine.set_head(snapshot.number).await?;
+        }
+
+        // keep the hashes of the blocks up to the snapshot, if we still have them
+        let reverted = (self.last_block_number - snapshot.number) as usize;
+        if reverted < self.last_block_hashes.len() {
+            self.last_block_hashes.truncate(self.last_block_hashes.len() - reverted);
+        } else {
+            self.last_block_hashes = vec![snapshot.hash];
+        }
+        self.last_block_number = snapshot.number;
+        self.last_timestamp = snapshot.timestamp;
+        self.next_timestamp = None;
+        self.snapshots.truncate(id as usize);
+        self.pending_state_changes.clear();
+
+        self.update_forkchoice_state().await?;
+        Ok(true)
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
@@ -165,13 +426,19 @@ where
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
     /// through newPayload.
     async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
-            self.last_timestamp + 1,
-            std::time::SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("cannot be earlier than UNIX_EPOCH")
-                .as_secs(),
-        );
+        let timestamp = self.next_timestamp.unwrap_or_else(|| {
+            std::cmp::max(
+                self.last_timestamp + 1,
+                std::time::SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("cannot be earlier than UNIX_EPOCH")
+                    .as_secs(),
+            )
+        });
+
+        // the payload builder commits the changes staged for the parent of the new block
+        let parent_hash = *self.last_block_hashes.last().expect("at least 1 block exists");
+        self.pending_state_changes.stage(parent_hash);
 
         let res = self
             .to_engine
@@ -194,17 +461,26 @@ where
             eyre::bail!("No payload")
         };
 
-        let block = payload.block();
+        let block_number = payload.block().number();
+        let block_hash = payload.block().hash();
 
-        let payload = T::block_to_payload(payload.block().clone());
-        let res = self.to_engine.new_payload(payload).await?;
+        let res = if let Some(local_payloads) = &self.local_payloads {
+            let (tx, rx) = oneshot::channel();
+            let _ = local_payloads.send(LocalPayload { payload, tx });
+            rx.await.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)??
+        } else {
+            self.to_engine.new_payload(T::block_to_payload(payload.block().clone())).await?
+        };
 
         if !res.is_valid() {
             eyre::bail!("Invalid payload")
         }
 
+        self.pending_state_changes.clear_staged(parent_hash);
+        self.next_timestamp = None;
         self.last_timestamp = timestamp;
-        self.last_block_hashes.push(block.hash());
+        self.last_block_number = block_number;
+        self.last_block_hashes.push(block_hash);
         // ensure we keep at most 64 blocks
         if self.last_block_hashes.len() > 64 {
             self.last_block_hashes =
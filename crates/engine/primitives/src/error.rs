@@ -45,3 +45,32 @@ impl BeaconForkChoiceUpdateError {
         Self::Internal(Box::new(e))
     }
 }
+
+/// Represents error cases when resetting the canonical head of the chain.
+#[derive(Debug, thiserror::Error)]
+pub enum BeaconSetHeadError {
+    /// Thrown when the requested head is not below the current canonical head.
+    #[error("block #{target} is not below the canonical head #{head}")]
+    InvalidTarget {
+        /// The requested head block number.
+        target: u64,
+        /// The current canonical head block number.
+        head: u64,
+    },
+    /// Thrown when the engine is busy syncing or already resetting the head.
+    #[error("engine is busy syncing, retry later")]
+    Busy,
+    /// Thrown when the engine task is unavailable/stopped.
+    #[error("beacon consensus engine task stopped")]
+    EngineUnavailable,
+    /// An internal error occurred while resetting the head.
+    #[error(transparent)]
+    Internal(Box<dyn core::error::Error + Send + Sync>),
+}
+
+impl BeaconSetHeadError {
+    /// Create a new internal error.
+    pub fn internal<E: core::error::Error + Send + Sync + 'static>(e: E) -> Self {
+        Self::Internal(Box::new(e))
+    }
+}
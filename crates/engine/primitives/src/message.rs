@@ -1,7 +1,8 @@
 use crate::{
-    error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, EngineApiMessageVersion,
-    ExecutionPayload, ForkchoiceStatus,
+    error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, BeaconSetHeadError,
+    EngineApiMessageVersion, ExecutionPayload, ForkchoiceStatus,
 };
+use alloy_primitives::BlockNumber;
 use alloy_rpc_types_engine::{
     ForkChoiceUpdateResult, ForkchoiceState, ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId,
     PayloadStatus, PayloadStatusEnum,
@@ -161,6 +162,15 @@ pub enum BeaconEngineMessage<Payload: PayloadTypes> {
         /// The sender for returning forkchoice updated result.
         tx: oneshot::Sender<RethResult<OnForkChoiceUpdated>>,
     },
+    /// Message to reset the canonical head to an ancestor block, discarding all blocks above it.
+    ///
+    /// This is not part of the Engine API, it backs `debug_setHead`.
+    SetHead {
+        /// The number of the block that becomes the new canonical head.
+        block_number: BlockNumber,
+        /// The sender for returning the result once the head was reset.
+        tx: oneshot::Sender<Result<(), BeaconSetHeadError>>,
+    },
 }
 
 impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
@@ -184,6 +194,7 @@ impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
                     payload_attrs.is_some()
                 )
             }
+            Self::SetHead { block_number, .. } => write!(f, "SetHead(number: {block_number})"),
         }
     }
 }
@@ -254,4 +265,12 @@ where
         });
         rx
     }
+
+    /// Resets the canonical head of the beacon consensus engine to the given block and waits for
+    /// the blocks above it to be removed.
+    pub async fn set_head(&self, block_number: BlockNumber) -> Result<(), BeaconSetHeadError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::SetHead { block_number, tx });
+        rx.await.map_err(|_| BeaconSetHeadError::EngineUnavailable)?
+    }
 }
@@ -10,7 +10,7 @@ use crate::{
 use alloy_consensus::BlockHeader;
 use alloy_eips::{merge::EPOCH_SLOTS, BlockNumHash, NumHash};
 use alloy_evm::block::BlockExecutor;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use alloy_rpc_types_engine::{
     ForkchoiceState, PayloadStatus, PayloadStatusEnum, PayloadValidationError,
 };
@@ -26,8 +26,8 @@ use reth_chain_state::{
 use reth_consensus::{Consensus, FullConsensus};
 pub use reth_engine_primitives::InvalidBlockHook;
 use reth_engine_primitives::{
-    BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError, EngineValidator,
-    ExecutionPayload, ForkchoiceStateTracker, OnForkChoiceUpdated,
+    BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError, BeaconSetHeadError,
+    EngineValidator, ExecutionPayload, ForkchoiceStateTracker, OnForkChoiceUpdated,
 };
 use reth_errors::{ConsensusError, ProviderResult};
 use reth_evm::{ConfigureEvm, Evm, SpecFor};
@@ -43,7 +43,7 @@ use reth_provider::{
     TransactionVariant,
 };
 use reth_revm::{database::StateProviderDatabase, State};
-use reth_stages_api::ControlFlow;
+use reth_stages_api::{ControlFlow, PipelineTarget};
 use reth_trie::{updates::TrieUpdates, HashedPostState, TrieInput};
 use reth_trie_db::{DatabaseHashedPostState, StateCommitment};
 use reth_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
@@ -222,6 +222,20 @@ pub enum TreeAction {
     },
 }
 
+/// A request to reset the canonical head to an ancestor block.
+///
+/// The canonical blocks up to the target are persisted first, then the pipeline unwinds the
+/// database to the target and the tree state is reset once backfill reports back.
+#[derive(Debug)]
+struct SetHeadRequest {
+    /// The number of the block that becomes the new canonical head.
+    block_number: BlockNumber,
+    /// Whether the unwind was already handed to the pipeline.
+    unwinding: bool,
+    /// The sender for returning the result.
+    tx: oneshot::Sender<Result<(), BeaconSetHeadError>>,
+}
+
 /// The engine API tree handler implementation.
 ///
 /// This type is responsible for processing engine API requests, maintaining the canonical state and
@@ -256,6 +270,8 @@ where
     persistence_state: PersistenceState,
     /// Flag indicating the state of the node's backfill synchronization process.
     backfill_sync_state: BackfillSyncState,
+    /// A pending request to reset the canonical head, if any.
+    set_head: Option<SetHeadRequest>,
     /// Keeps track of the state of the canonical chain that isn't persisted yet.
     /// This is intended to be accessed from external sources, such as rpc.
     canonical_in_memory_state: CanonicalInMemoryState<N>,
@@ -294,6 +310,7 @@ where
             .field("persistence", &self.persistence)
             .field("persistence_state", &self.persistence_state)
             .field("backfill_sync_state", &self.backfill_sync_state)
+            .field("set_head", &self.set_head)
             .field("canonical_in_memory_state", &self.canonical_in_memory_state)
             .field("payload_builder", &self.payload_builder)
             .field("config", &self.config)
@@ -359,6 +376,7 @@ where
             persistence,
             persistence_state,
             backfill_sync_state: BackfillSyncState::Idle,
+            set_head: None,
             state,
             canonical_in_memory_state,
             payload_builder,
@@ -1063,12 +1081,80 @@ where
             } else if self.should_persist() {
                 let blocks_to_persist = self.get_canonical_blocks_to_persist()?;
                 self.persist_blocks(blocks_to_persist);
+            } else if self.set_head.as_ref().is_some_and(|request| !request.unwinding) {
+                self.advance_set_head()?;
             }
         }
 
         Ok(())
     }
 
+    /// Handles a request to reset the canonical head to the given block.
+    ///
+    /// The request is only validated here, the actual work is driven by
+    /// [`Self::advance_set_head`] once there's no persistence task in progress.
+    fn on_set_head(
+        &mut self,
+        block_number: BlockNumber,
+        tx: oneshot::Sender<Result<(), BeaconSetHeadError>>,
+    ) {
+        let head = self.state.tree_state.canonical_block_number();
+        if !self.backfill_sync_state.is_idle() || self.set_head.is_some() {
+            let _ = tx.send(Err(BeaconSetHeadError::Busy));
+        } else if block_number >= head {
+            let _ = tx.send(Err(BeaconSetHeadError::InvalidTarget { target: block_number, head }));
+        } else {
+            debug!(target: "engine::tree", ?block_number, ?head, "Received set head request");
+            self.set_head = Some(SetHeadRequest { block_number, unwinding: false, tx });
+        }
+    }
+
+    /// Advances a pending set head request.
+    ///
+    /// If the target block is still in memory, the canonical blocks up to it are persisted first.
+    /// Afterwards the pipeline is asked to unwind to the target, just like `reth stage unwind`
+    /// would, and the request is answered once the backfill sync finished.
+    fn advance_set_head(&mut self) -> Result<(), AdvancePersistenceError> {
+        debug_assert!(!self.persistence_state.in_progress());
+
+        if !self.backfill_sync_state.is_idle() {
+            // wait for the running backfill to finish first
+            return Ok(())
+        }
+
+        let Some(block_number) = self.set_head.as_ref().map(|request| request.block_number) else {
+            return Ok(())
+        };
+
+        if block_number > self.persistence_state.last_persisted_block.number {
+            let blocks_to_persist = self.canonical_blocks_to_persist_until(block_number)?;
+            if !blocks_to_persist.is_empty() {
+                self.persist_blocks(blocks_to_persist);
+                return Ok(())
+            }
+        }
+
+        if block_number > self.persistence_state.last_persisted_block.number {
+            // the canonical chain no longer reaches the target, e.g. because of a reorg
+            let head = self.state.tree_state.canonical_block_number();
+            let request = self.set_head.take().expect("is some");
+            let _ = request
+                .tx
+                .send(Err(BeaconSetHeadError::InvalidTarget { target: block_number, head }));
+            return Ok(())
+        }
+
+        debug!(target: "engine::tree", ?block_number, "Unwinding to set head target");
+        if let Some(request) = self.set_head.as_mut() {
+            request.unwinding = true;
+        }
+        self.emit_event(EngineApiEvent::BackfillAction(BackfillAction::Start(
+            PipelineTarget::Unwind(block_number),
+        )));
+
+        Ok(())
+    }
+
     /// Handles a message from the engine.
     fn on_engine_message(
         &mut self,
@@ -1170,6 +1256,9 @@ where
                                 // handle the event if any
                                 self.on_maybe_tree_event(maybe_event)?;
                             }
+                            BeaconEngineMessage::SetHead { block_number, tx } => {
+                                self.on_set_head(block_number, tx);
+                            }
                         }
                     }
                 }
@@ -1203,6 +1292,9 @@ where
         debug!(target: "engine::tree", "received backfill sync finished event");
         self.backfill_sync_state = BackfillSyncState::Idle;
 
+        // only a backfill run that was started for the set head request completes it
+        let set_head = self.set_head.take_if(|request| request.unwinding);
+
         // backfill height is the block number that the backfill finished at
         let mut backfill_height = ctrl.block_number();
 
@@ -1230,10 +1322,15 @@ where
             .map(|hash| BlockNumHash { hash, number: backfill_height })
         else {
             debug!(target: "engine::tree", ?ctrl, "Backfill block not found");
+            if let Some(request) = set_head {
+                let _ = request.tx.send(Err(BeaconSetHeadError::internal(
+                    ProviderError::HeaderNotFound(backfill_height.into()),
+                )));
+            }
             return Ok(())
         };
 
-        if ctrl.is_unwind() {
+        if ctrl.is_unwind() || set_head.is_some() {
             // the node reset so we need to clear everything above that height so that backfill
             // height is the new canonical block.
             self.state.tree_state.reset(backfill_num_hash)
@@ -1264,6 +1361,13 @@ where
             self.canonical_in_memory_state.set_canonical_head(new_head);
         }
 
+        if let Some(request) = set_head {
+            // the head was reset on purpose, don't immediately sync back to the previous target
+            debug!(target: "engine::tree", head=?backfill_num_hash, "Reset canonical head");
+            let _ = request.tx.send(Ok(()));
+            return Ok(())
+        }
+
         // check if we need to run backfill again by comparing the most recent finalized height to
         // the backfill height
         let Some(sync_target_state) = self.state.forkchoice_state_tracker.sync_target_state()
@@ -1382,6 +1486,11 @@ where
             return false
         }
 
+        if self.set_head.is_some() {
+            // blocks above the set head target are about to be discarded
+            return false
+        }
+
         let min_block = self.persistence_state.last_persisted_block.number;
         self.state.tree_state.canonical_block_number().saturating_sub(min_block) >
             self.config.persistence_threshold()
@@ -1397,6 +1506,20 @@ where
     /// Returns an error if the state root calculation fails.
     fn get_canonical_blocks_to_persist(
         &mut self,
+    ) -> Result<Vec<ExecutedBlockWithTrieUpdates<N>>, AdvancePersistenceError> {
+        let target_number = self
+            .state
+            .tree_state
+            .canonical_block_number()
+            .saturating_sub(self.config.memory_block_buffer_target());
+        self.canonical_blocks_to_persist_until(target_number)
+    }
+
+    /// Returns a batch of consecutive canonical blocks to persist in the range
+    /// `(last_persisted_number .. target_number]`. The expected order is oldest -> newest.
+    fn canonical_blocks_to_persist_until(
+        &mut self,
+        target_number: BlockNumber,
     ) -> Result<Vec<ExecutedBlockWithTrieUpdates<N>>, AdvancePersistenceError> {
         // We will calculate the state root using the database, so we need to be sure there are no
         // changes
@@ -1408,9 +1531,6 @@ where
 
         let canonical_head_number = self.state.tree_state.canonical_block_number();
 
-        let target_number =
-            canonical_head_number.saturating_sub(self.config.memory_block_buffer_target());
-
         debug!(target: "engine::tree", ?last_persisted_number, ?canonical_head_number, ?target_number, ?current_hash, "Returning canonical blocks to persist");
         while let Some(block) = self.state.tree_state.blocks_by_hash.get(&current_hash) {
             if block.recovered_block().number() <= last_persisted_number {
@@ -865,6 +865,71 @@ async fn test_get_canonical_blocks_to_persist() {
     );
 }
 
+#[tokio::test]
+async fn test_set_head() {
+    let chain_spec = MAINNET.clone();
+    let mut test_harness = TestHarness::new(chain_spec);
+
+    let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(0..10).collect();
+    test_harness = test_harness.with_blocks(blocks.clone());
+    test_harness.tree.persistence_state.last_persisted_block = blocks[3].recovered_block.num_hash();
+
+    let set_head = |test_harness: &mut TestHarness, block_number| {
+        let (tx, rx) = oneshot::channel();
+        test_harness
+            .tree
+            .on_engine_message(FromEngine::Request(
+                BeaconEngineMessage::SetHead { block_number, tx }.into(),
+            ))
+            .unwrap();
+        rx
+    };
+
+    // the target must be below the canonical head
+    let mut rx = set_head(&mut test_harness, 9);
+    assert_matches!(
+        rx.try_recv(),
+        Ok(Err(BeaconSetHeadError::InvalidTarget { target: 9, head: 9 }))
+    );
+
+    // the in-memory blocks up to the target are persisted first
+    let mut rx = set_head(&mut test_harness, 6);
+    test_harness.tree.advance_persistence().unwrap();
+    assert_eq!(
+        test_harness.tree.persistence_state.current_action().cloned(),
+        Some(CurrentPersistenceAction::SavingBlocks {
+            highest: blocks[6].recovered_block().num_hash()
+        })
+    );
+
+    // only one request can be handled at a time
+    let mut busy_rx = set_head(&mut test_harness, 5);
+    assert_matches!(busy_rx.try_recv(), Ok(Err(BeaconSetHeadError::Busy)));
+
+    // once persisted, the pipeline unwinds to the target
+    let persisted = blocks[6].recovered_block().num_hash();
+    test_harness.tree.persistence_state.finish(persisted.hash, persisted.number);
+    test_harness.tree.advance_persistence().unwrap();
+    let event = test_harness.from_tree_rx.recv().await.unwrap();
+    assert_matches!(
+        event,
+        EngineApiEvent::BackfillAction(BackfillAction::Start(PipelineTarget::Unwind(6)))
+    );
+    assert!(rx.try_recv().is_err());
+
+    // the tree is reset to the target after the unwind
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Event(FromOrchestrator::BackfillSyncFinished(
+            ControlFlow::Continue { block_number: 6 },
+        )))
+        .unwrap();
+    assert_matches!(rx.try_recv(), Ok(Ok(())));
+    assert_eq!(test_harness.tree.state.tree_state.canonical_block_number(), 6);
+    assert_eq!(test_harness.tree.state.tree_state.block_count(), 0);
+    assert!(test_harness.tree.set_head.is_none());
+}
+
 #[tokio::test]
 async fn test_engine_tree_fcu_missing_head() {
     let chain_spec = MAINNET.clone();
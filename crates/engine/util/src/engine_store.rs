@@ -86,6 +86,8 @@ impl EngineMessageStore {
                     })?,
                 )?;
             }
+            // not an Engine API message, there's nothing to replay
+            BeaconEngineMessage::SetHead { .. } => {}
         };
         Ok(())
     }
@@ -12,6 +12,7 @@
 pub mod chainspec;
 pub mod debug_cmd;
 pub mod interface;
+pub mod replica;
 pub use interface::Cli;
 
 #[cfg(test)]
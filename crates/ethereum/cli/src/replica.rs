@@ -0,0 +1,384 @@
+//! `reth replica` command. Serves the RPC of a running node from its datadir.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use eyre::ensure;
+use reth_chainspec::{ChainSpec, ChainSpecProvider};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_cli_util::parse_duration_from_secs_or_ms;
+use reth_ethereum_primitives::EthPrimitives;
+use reth_network_api::noop::NoopNetwork;
+use reth_node_core::args::RpcServerArgs;
+use reth_node_ethereum::{consensus::EthBeaconConsensus, EthEvmConfig};
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock};
+use reth_provider::{
+    providers::{BlockchainProvider, ProviderNodeTypes},
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, CanonChainTracker,
+    CanonStateNotification, Chain, ChainStateBlockReader, DatabaseProviderRO, ExecutionOutcome,
+    HeaderProvider, ProviderError, ProviderFactory, ProviderResult, StaticFileProviderFactory,
+    TransactionVariant,
+};
+use reth_rpc::EthApiBuilder;
+use reth_rpc_builder::{
+    config::RethRpcServerConfig, constants::DEFAULT_IPC_ENDPOINT, RethRpcModule, RpcModuleBuilder,
+    RpcModuleSelection,
+};
+use reth_transaction_pool::noop::NoopTransactionPool;
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+use tracing::*;
+
+/// RPC modules that can be served without p2p, a transaction pool or the engine API.
+const REPLICA_MODULES: [RethRpcModule; 3] =
+    [RethRpcModule::Eth, RethRpcModule::Trace, RethRpcModule::Debug];
+
+/// `reth replica` command
+///
+/// Opens the database and static files of a running node read-only and serves the `eth`, `trace`
+/// and `debug` RPC namespaces, following the blocks persisted by the node. The replica doesn't
+/// connect to the network and doesn't serve the engine API.
+///
+/// The replica only sees the blocks the node has persisted, so it trails the node by the blocks
+/// the node still holds in memory (see `--engine.persistence-threshold`). `newHeads` and `logs`
+/// subscriptions are notified once the blocks are persisted.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(flatten)]
+    rpc: RpcServerArgs,
+
+    /// Interval at which the database is checked for blocks persisted by the node.
+    ///
+    /// Interval is specified in seconds or in milliseconds if the value ends with `ms`:
+    ///   * `50ms` -> 50 milliseconds
+    ///   * `1` -> 1 second
+    #[arg(
+        long = "replica.poll-interval",
+        value_parser = parse_duration_from_secs_or_ms,
+        default_value = "1",
+        value_name = "DURATION",
+        verbatim_doc_comment
+    )]
+    poll_interval: Duration,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `replica` command
+    pub async fn execute<N: CliNodeTypes<Primitives = EthPrimitives, ChainSpec = C::ChainSpec>>(
+        mut self,
+        ctx: CliContext,
+    ) -> eyre::Result<()> {
+        ensure!(
+            !self.rpc.is_ipc_enabled() || self.rpc.ipcpath != DEFAULT_IPC_ENDPOINT,
+            "The replica can't use the default IPC endpoint of the node, set --ipcpath or --ipcdisable"
+        );
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        // Pick up the static files written by the node after the replica was started
+        provider_factory.static_file_provider().watch_directory();
+
+        let provider = BlockchainProvider::new(provider_factory.clone())?;
+        let chain_spec = provider_factory.chain_spec();
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
+
+        let eth_api = EthApiBuilder::new(
+            provider.clone(),
+            NoopTransactionPool::default(),
+            NoopNetwork::default(),
+            evm_config.clone(),
+        )
+        .task_spawner(ctx.task_executor.clone())
+        .eth_state_cache_config(self.rpc.state_cache_config())
+        .gas_cap(self.rpc.rpc_gas_cap.into())
+        .max_simulate_blocks(self.rpc.rpc_max_simulate_blocks)
+        .eth_proof_window(self.rpc.rpc_eth_proof_window)
+        .proof_permits(self.rpc.rpc_proof_permits)
+        .gas_oracle_config(self.rpc.gas_price_oracle_config())
+        .build();
+
+        // Serve all supported modules unless configured otherwise
+        self.rpc.http_api.get_or_insert_with(|| REPLICA_MODULES.to_vec().into());
+        self.rpc.ws_api.get_or_insert_with(|| REPLICA_MODULES.to_vec().into());
+        let mut module_config = self.rpc.transport_rpc_module_config();
+        restrict_modules(module_config.http_mut());
+        restrict_modules(module_config.ws_mut());
+        restrict_modules(module_config.ipc_mut());
+
+        let modules = RpcModuleBuilder::default()
+            .with_provider(provider.clone())
+            .with_noop_pool()
+            .with_noop_network()
+            .with_executor(Box::new(ctx.task_executor))
+            .with_evm_config(evm_config)
+            .with_consensus(EthBeaconConsensus::new(chain_spec))
+            .build(module_config, eth_api);
+        let handle = self.rpc.rpc_server_config().start(&modules).await?;
+        info!(
+            target: "reth::cli",
+            http = ?handle.http_local_addr(),
+            ws = ?handle.ws_local_addr(),
+            ipc = ?handle.ipc_endpoint(),
+            "RPC replica started"
+        );
+
+        let mut follower = NodeFollower::default();
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = follower.follow_node(&provider_factory, &provider) {
+                // The static files of a newly persisted block may not have been picked up yet
+                debug!(target: "reth::cli", %err, "Failed to update the replica head");
+            }
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Removes the modules that can't be served by the replica from the selection.
+fn restrict_modules(selection: &mut Option<RpcModuleSelection>) {
+    let Some(modules) = selection else { return };
+
+    let (supported, unsupported): (Vec<_>, Vec<_>) =
+        modules.iter_selection().partition(|module| REPLICA_MODULES.contains(module));
+    if !unsupported.is_empty() {
+        warn!(target: "reth::cli", ?unsupported, "RPC modules not supported by the replica");
+    }
+    *modules = supported.into();
+}
+
+/// Maximum number of notified blocks kept to build the reverted chain when the node unwinds
+/// persisted blocks.
+const MAX_REVERTED_BLOCKS: usize = 64;
+
+/// Maximum number of blocks notified at once, the older blocks of a larger range, e.g. when the
+/// node is syncing, are skipped.
+const MAX_NOTIFIED_BLOCKS: u64 = 64;
+
+/// A block with its execution outcome.
+type BlockWithState<N> = (
+    RecoveredBlock<<N as NodePrimitives>::Block>,
+    ExecutionOutcome<<N as NodePrimitives>::Receipt>,
+);
+
+/// Follows the blocks persisted by the node and notifies the subscribers of the replica.
+#[derive(Debug)]
+struct NodeFollower<N: NodePrimitives> {
+    /// The most recently notified blocks with their execution outcome, oldest first.
+    notified: VecDeque<BlockWithState<N>>,
+    /// Notified blocks unwound by the node that weren't reverted yet, newest first.
+    unwound: Vec<BlockWithState<N>>,
+}
+
+impl<N: NodePrimitives> Default for NodeFollower<N> {
+    fn default() -> Self {
+        Self { notified: VecDeque::new(), unwound: Vec::new() }
+    }
+}
+
+impl<N: NodePrimitives> NodeFollower<N> {
+    /// Updates the canonical, safe and finalized heads of the replica to the ones persisted by
+    /// the node, and emits a [`CanonStateNotification`] for the newly persisted blocks.
+    fn follow_node<T: ProviderNodeTypes<Primitives = N>>(
+        &mut self,
+        provider_factory: &ProviderFactory<T>,
+        replica: &BlockchainProvider<T>,
+    ) -> ProviderResult<()> {
+        let provider = provider_factory.provider()?;
+
+        let best = provider.chain_info()?;
+        let head = replica.chain_info()?;
+        if head.best_hash != best.best_hash {
+            if let Some(header) = provider.sealed_header(best.best_number)? {
+                // Read the notification before updating the head, so that the head is only
+                // advanced once the blocks can be read.
+                let notification =
+                    self.canonical_update(&provider, replica, head.best_number, best.best_number)?;
+                debug!(
+                    target: "reth::cli",
+                    number = best.best_number,
+                    hash = %best.best_hash,
+                    "New replica head"
+                );
+                replica.set_canonical_head(header);
+                if let Some(notification) = notification {
+                    replica.canonical_in_memory_state().notify_canon_state(notification);
+                }
+            }
+        }
+
+        if let Some(number) = provider.last_finalized_block_number()? {
+            if replica.finalized_block_number()? != Some(number) {
+                if let Some(header) = provider.sealed_header(number)? {
+                    replica.set_finalized(header);
+                }
+            }
+        }
+
+        if let Some(number) = provider.last_safe_block_number()? {
+            if replica.safe_block_number()? != Some(number) {
+                if let Some(header) = provider.sealed_header(number)? {
+                    replica.set_safe(header);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the notification for the blocks persisted by the node on top of the replica head.
+    ///
+    /// Notified blocks that are no longer canonical are reverted by the notification. The
+    /// notification is `None` if the node only unwound blocks.
+    fn canonical_update<T: ProviderNodeTypes<Primitives = N>>(
+        &mut self,
+        provider: &DatabaseProviderRO<T::DB, T>,
+        replica: &BlockchainProvider<T>,
+        head: BlockNumber,
+        best: BlockNumber,
+    ) -> ProviderResult<Option<CanonStateNotification<N>>> {
+        // Collect the notified blocks that were unwound by the node, they're reverted together with
+        // the next notified blocks.
+        while let Some((block, _)) = self.notified.back() {
+            if provider.block_hash(block.number())? == Some(block.hash()) {
+                break
+            }
+            self.unwound.extend(self.notified.pop_back());
+        }
+        let fork = match (self.notified.back(), self.unwound.last()) {
+            (Some((block, _)), _) => block.number(),
+            (None, Some((block, _))) => block.number() - 1,
+            (None, None) => head,
+        };
+
+        let first = (fork + 1).max(best.saturating_sub(MAX_NOTIFIED_BLOCKS - 1));
+        if first > fork + 1 {
+            // The skipped blocks leave a gap to the notified blocks
+            self.notified.clear();
+        }
+        let state_provider = replica.consistent_provider()?;
+        let mut new = Vec::new();
+        for number in first..=best {
+            let block = provider
+                .recovered_block(number.into(), TransactionVariant::WithHash)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+            let state = state_provider
+                .get_state(number..=number)?
+                .ok_or(ProviderError::StateForNumberNotFound(number))?;
+            new.push((block, state));
+        }
+
+        self.notified.extend(new.iter().cloned());
+        while self.notified.len() > MAX_REVERTED_BLOCKS {
+            self.notified.pop_front();
+        }
+
+        let Some(new) = into_chain(new) else { return Ok(None) };
+        let new = Arc::new(new);
+        Ok(Some(match into_chain(self.unwound.drain(..).rev()) {
+            Some(old) => CanonStateNotification::Reorg { old: Arc::new(old), new },
+            None => CanonStateNotification::Commit { new },
+        }))
+    }
+}
+
+/// Builds a chain from the given consecutive blocks, returns `None` if there are no blocks.
+fn into_chain<N: NodePrimitives>(
+    blocks: impl IntoIterator<Item = BlockWithState<N>>,
+) -> Option<Chain<N>> {
+    let mut blocks = blocks.into_iter();
+    let (block, state) = blocks.next()?;
+    let mut chain = Chain::from_block(block, state, None);
+    for (block, state) in blocks {
+        chain.append_block(block, state);
+    }
+    Some(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::{
+        test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
+        BlockWriter, CanonStateSubscriptions, DatabaseProviderFactory, OriginalValuesKnown,
+        StageCheckpointWriter, StateWriter, StorageLocation,
+    };
+    use reth_stages::{StageCheckpoint, StageId};
+
+    #[test]
+    fn follow_node_notifies_persisted_blocks() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let persist =
+            |blocks: &[(RecoveredBlock<reth_ethereum_primitives::Block>, ExecutionOutcome)]| {
+                let provider_rw = factory.database_provider_rw().unwrap();
+                for (block, state) in blocks {
+                    provider_rw.insert_block(block.clone(), StorageLocation::Database).unwrap();
+                    provider_rw
+                        .write_state(state, OriginalValuesKnown::No, StorageLocation::Database)
+                        .unwrap();
+                }
+                let last = blocks.last().unwrap().0.number;
+                provider_rw
+                    .save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(last))
+                    .unwrap();
+                provider_rw.commit().unwrap();
+            };
+
+        let genesis = data.genesis.clone().try_recover().unwrap();
+        persist(&[(genesis, ExecutionOutcome::default()), data.blocks[0].clone()]);
+
+        let replica = BlockchainProvider::new(factory.clone()).unwrap();
+        let mut notifications = replica.subscribe_to_canonical_state();
+        let mut follower = NodeFollower::default();
+
+        // Nothing was persisted since the replica was started
+        follower.follow_node(&factory, &replica).unwrap();
+        assert!(notifications.try_recv().is_err());
+
+        persist(&data.blocks[1..3]);
+        follower.follow_node(&factory, &replica).unwrap();
+        assert_eq!(replica.chain_info().unwrap().best_number, 3);
+
+        let notification = notifications.try_recv().unwrap();
+        assert!(notification.reverted().is_none());
+        let committed = notification.committed();
+        assert_eq!(committed.range(), 2..=3);
+        assert_eq!(
+            committed.execution_outcome().receipts(),
+            &data.blocks[1..3]
+                .iter()
+                .flat_map(|(_, state)| state.receipts().clone())
+                .collect::<Vec<_>>()
+        );
+
+        // Blocks unwound by the node are reverted when new blocks are persisted on top
+        let provider_rw = factory.database_provider_rw().unwrap();
+        provider_rw.remove_blocks_above(2, StorageLocation::Database).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(2)).unwrap();
+        provider_rw.commit().unwrap();
+        follower.follow_node(&factory, &replica).unwrap();
+        assert_eq!(replica.chain_info().unwrap().best_number, 2);
+        assert!(notifications.try_recv().is_err());
+
+        // The state of the unwound block was kept
+        let provider_rw = factory.database_provider_rw().unwrap();
+        provider_rw.insert_block(data.blocks[2].0.clone(), StorageLocation::Database).unwrap();
+        provider_rw.commit().unwrap();
+        persist(&data.blocks[3..4]);
+        follower.follow_node(&factory, &replica).unwrap();
+        let notification = notifications.try_recv().unwrap();
+        assert_eq!(notification.reverted().unwrap().range(), 3..=3);
+        assert_eq!(notification.committed().range(), 3..=4);
+    }
+}
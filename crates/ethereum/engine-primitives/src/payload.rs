@@ -15,6 +15,7 @@ use alloy_rpc_types_engine::{
     ExecutionPayloadV1, ExecutionPayloadV3, PayloadAttributes, PayloadId,
 };
 use core::convert::Infallible;
+use reth_chain_state::ExecutedBlockWithTrieUpdates;
 use reth_ethereum_primitives::{Block, EthPrimitives};
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
 use reth_primitives_traits::SealedBlock;
@@ -39,6 +40,8 @@ pub struct EthBuiltPayload {
     pub(crate) sidecars: BlobSidecars,
     /// The requests of the payload
     pub(crate) requests: Option<Requests>,
+    /// The execution data of the built block, if available.
+    pub(crate) executed_block: Option<ExecutedBlockWithTrieUpdates<EthPrimitives>>,
 }
 
 // === impl BuiltPayload ===
@@ -53,7 +56,7 @@ impl EthBuiltPayload {
         fees: U256,
         requests: Option<Requests>,
     ) -> Self {
-        Self { id, block, fees, requests, sidecars: BlobSidecars::Empty }
+        Self { id, block, fees, requests, sidecars: BlobSidecars::Empty, executed_block: None }
     }
 
     /// Returns the identifier of the payload.
@@ -82,6 +85,17 @@ impl EthBuiltPayload {
         self
     }
 
+    /// Sets the execution data of the built block on the payload.
+    ///
+    /// This allows the engine to insert the block without executing it again.
+    pub fn with_executed_block(
+        mut self,
+        executed_block: ExecutedBlockWithTrieUpdates<EthPrimitives>,
+    ) -> Self {
+        self.executed_block = Some(executed_block);
+        self
+    }
+
     /// Try converting built payload into [`ExecutionPayloadEnvelopeV3`].
     ///
     /// Returns an error if the payload contains non EIP-4844 sidecar.
@@ -169,6 +183,10 @@ impl BuiltPayload for EthBuiltPayload {
         self.fees
     }
 
+    fn executed_block(&self) -> Option<ExecutedBlockWithTrieUpdates<EthPrimitives>> {
+        self.executed_block.clone()
+    }
+
     fn requests(&self) -> Option<Requests> {
         self.requests.clone()
     }
@@ -116,6 +116,20 @@ impl<EvmFactory> EthEvmConfig<EvmFactory> {
     }
 }
 
+/// An EVM configuration that can change the extra data of the blocks it assembles.
+///
+/// This lets the payload builder apply extra data that is changed while the node is running.
+pub trait ConfigureExtraData {
+    /// Sets the extra data of the blocks assembled with this configuration.
+    fn set_extra_data(&mut self, extra_data: Bytes);
+}
+
+impl<EvmFactory> ConfigureExtraData for EthEvmConfig<EvmFactory> {
+    fn set_extra_data(&mut self, extra_data: Bytes) {
+        self.block_assembler.extra_data = extra_data;
+    }
+}
+
 impl<EvmF> ConfigureEvm for EthEvmConfig<EvmF>
 where
     EvmF: EvmFactory<
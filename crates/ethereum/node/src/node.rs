@@ -11,7 +11,10 @@ use reth_ethereum_engine_primitives::{
 };
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{ConfigureEvm, EvmFactory, EvmFactoryFor, NextBlockEnvAttributes};
-use reth_network::{primitives::BasicNetworkPrimitives, NetworkHandle, PeersInfo};
+use reth_net_snap::{SnapProtocolHandler, SnapRequestHandler, SNAP_REQUEST_CHANNEL_CAPACITY};
+use reth_network::{
+    primitives::BasicNetworkPrimitives, protocol::IntoRlpxSubProtocol, NetworkHandle, PeersInfo,
+};
 use reth_node_api::{
     AddOnsContext, FullNodeComponents, NodeAddOns, NodePrimitives, PrimitivesTy, TxTy,
 };
@@ -29,8 +32,11 @@ use reth_node_builder::{
     PayloadTypes,
 };
 use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
-use reth_rpc::{eth::core::EthApiFor, ValidationApi};
-use reth_rpc_api::{eth::FullEthApiServer, servers::BlockSubmissionValidationApiServer};
+use reth_rpc::{eth::core::EthApiFor, MinerApi, ValidationApi};
+use reth_rpc_api::{
+    eth::FullEthApiServer,
+    servers::{BlockSubmissionValidationApiServer, MinerApiServer},
+};
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
@@ -42,6 +48,7 @@ use reth_transaction_pool::{
 use reth_trie_db::MerklePatriciaTrie;
 use revm::context::TxEnv;
 use std::{default::Default, sync::Arc, time::SystemTime};
+use tokio::sync::mpsc;
 
 /// Type configuration for a regular Ethereum node.
 #[derive(Debug, Default, Clone, Copy)]
@@ -187,6 +194,7 @@ where
         self,
         ctx: reth_node_api::AddOnsContext<'_, N>,
     ) -> eyre::Result<Self::Handle> {
+        let miner_api = MinerApi::new(ctx.miner_config.clone());
         let validation_api = ValidationApi::new(
             ctx.node.provider().clone(),
             Arc::new(ctx.node.consensus().clone()),
@@ -202,6 +210,10 @@ where
                     RethRpcModule::Flashbots,
                     validation_api.into_rpc(),
                 )?;
+                // the ethereum payload builder applies the settings of the `miner_` API
+                container
+                    .modules
+                    .replace_if_module_configured(RethRpcModule::Miner, miner_api.into_rpc())?;
 
                 Ok(())
             })
@@ -227,6 +239,10 @@ where
     fn hooks_mut(&mut self) -> &mut reth_node_builder::rpc::RpcHooks<N, Self::EthApi> {
         self.inner.hooks_mut()
     }
+
+    fn set_local_miner(&mut self, miner: reth_engine_local::LocalMinerHandle) {
+        self.inner.set_local_miner(miner)
+    }
 }
 
 impl<N> EngineValidatorAddOn<N> for EthereumAddOns<N>
@@ -383,7 +399,27 @@ where
         ctx: &BuilderContext<Node>,
         pool: Pool,
     ) -> eyre::Result<Self::Network> {
-        let network = ctx.network_builder().await?;
+        let mut network = ctx.network_builder().await?;
+
+        // The snap protocol is shared by serving the state to peers and downloading it from them
+        let serve_snap = ctx.config().network.serve_snap;
+        let snap_sync = ctx.reth_config().stages.snap_sync.enabled;
+        if serve_snap || snap_sync {
+            let requests = serve_snap.then(|| {
+                let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+                ctx.task_executor().spawn_critical_blocking(
+                    "p2p snap request handler",
+                    SnapRequestHandler::new(ctx.provider().clone(), rx),
+                );
+                tx
+            });
+            let snap_protocol = SnapProtocolHandler::new(requests);
+            if snap_sync {
+                ctx.set_snap_client(snap_protocol.client());
+            }
+            network.network_mut().add_rlpx_sub_protocol(snap_protocol.into_rlpx_sub_protocol());
+        }
+
         let handle = ctx.start_network(network, pool);
         info!(target: "reth::cli", enode=%handle.local_node_record(), "P2P networking initialized");
         Ok(handle)
@@ -7,6 +7,7 @@ use reth_ethereum_engine_primitives::{
 use reth_ethereum_payload_builder::EthereumBuilderConfig;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::ConfigureEvm;
+use reth_evm_ethereum::ConfigureExtraData;
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
 use reth_node_builder::{
     components::PayloadBuilderBuilder, BuilderContext, PayloadBuilderConfig, PayloadTypes,
@@ -28,7 +29,8 @@ where
     Evm: ConfigureEvm<
             Primitives = PrimitivesTy<Types>,
             NextBlockEnvCtx = reth_evm::NextBlockEnvAttributes,
-        > + 'static,
+        > + ConfigureExtraData
+        + 'static,
     Types::Payload: PayloadTypes<
         BuiltPayload = EthBuiltPayload,
         PayloadAttributes = EthPayloadAttributes,
@@ -48,11 +50,19 @@ where
         let chain = ctx.chain_spec().chain();
         let gas_limit = conf.gas_limit_for(chain);
 
+        let mut config = EthereumBuilderConfig::new()
+            .with_gas_limit(gas_limit)
+            .with_miner_config(ctx.miner_config().clone());
+        if ctx.is_dev() {
+            // state changes made over the dev RPC namespaces are committed in the next block
+            config = config.with_pending_state_changes(ctx.pending_state_changes().clone());
+        }
+
         Ok(reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(gas_limit),
+            config,
         ))
     }
 }
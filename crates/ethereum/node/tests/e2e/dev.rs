@@ -1,18 +1,34 @@
-use alloy_eips::eip2718::Encodable2718;
-use alloy_genesis::Genesis;
-use alloy_primitives::{b256, hex};
+use alloy_consensus::constants::ETH_TO_WEI;
+use alloy_eips::{eip2718::Encodable2718, BlockNumberOrTag};
+use alloy_genesis::{Genesis, GenesisAccount};
+use alloy_primitives::{address, b256, hex, Address, Bytes, B256, U128, U256, U64};
+use alloy_provider::{network::TransactionBuilder, Provider, ProviderBuilder};
+use alloy_rpc_types_eth::{Filter, TransactionRequest};
+use alloy_rpc_types_trace::{
+    geth::{
+        GethDebugBuiltInTracerType, GethDebugTracingOptions, GethTrace, PreStateFrame, TraceResult,
+    },
+    parity::{Delta, StateDiff},
+};
 use futures::StreamExt;
 use reth_chainspec::ChainSpec;
+use reth_db::test_utils::tempdir_path;
+use reth_e2e_test_utils::{transaction::TransactionTestContext, wallet::Wallet};
 use reth_node_api::{BlockBody, FullNodeComponents, FullNodePrimitives, NodeTypes};
 use reth_node_builder::{
     rpc::RethRpcAddOns, EngineNodeLauncher, FullNode, NodeBuilder, NodeConfig, NodeHandle,
 };
-use reth_node_core::args::DevArgs;
+use reth_node_core::args::{DevArgs, EngineArgs, RpcServerArgs};
 use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
 use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
+use reth_rpc_api::{
+    AddressTransaction, AddressTransactionsPage, ChainNotification, DebugApiClient,
+    DebugSubscriptionKind, RethApiClient,
+};
 use reth_rpc_eth_api::helpers::EthTransactions;
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
 use reth_tasks::TaskManager;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 #[tokio::test]
 async fn can_run_dev_node() -> eyre::Result<()> {
@@ -43,6 +59,573 @@ async fn can_run_dev_node() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_use_anvil_api() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config =
+        NodeConfig::test()
+            .with_chain(custom_chain())
+            .with_dev(DevArgs { dev: true, ..Default::default() })
+            .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+                RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Anvil]),
+            ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+
+    let url = node.rpc_server_handle().http_url().unwrap().parse()?;
+    let provider = ProviderBuilder::new().connect_http(url);
+    let account = Address::with_last_byte(42);
+
+    // state changes are mined right away
+    provider
+        .raw_request::<_, ()>("anvil_setBalance".into(), (account, U256::from(ETH_TO_WEI)))
+        .await?;
+    assert_eq!(provider.get_block_number().await?, 1);
+    assert_eq!(provider.get_balance(account).await?, U256::from(ETH_TO_WEI));
+
+    let snapshot: U256 = provider.raw_request("anvil_snapshot".into(), ()).await?;
+
+    provider.raw_request::<_, ()>("anvil_setNonce".into(), (account, U256::from(5))).await?;
+    let contract = Address::with_last_byte(44);
+    provider
+        .raw_request::<_, ()>("anvil_setCode".into(), (contract, Bytes::from_static(&hex!("6000"))))
+        .await?;
+    provider
+        .raw_request::<_, bool>(
+            "anvil_setStorageAt".into(),
+            (contract, U256::from(1), B256::with_last_byte(2)),
+        )
+        .await?;
+    assert_eq!(provider.get_transaction_count(account).await?, 5);
+    assert_eq!(provider.get_code_at(contract).await?, hex!("6000").to_vec());
+    assert_eq!(provider.get_storage_at(contract, U256::from(1)).await?, U256::from(2));
+
+    // send a transaction from an account without its key
+    provider.raw_request::<_, ()>("anvil_impersonateAccount".into(), (account,)).await?;
+    let receiver = Address::with_last_byte(43);
+    let tx = TransactionRequest::default()
+        .from(account)
+        .to(receiver)
+        .value(U256::from(1))
+        .gas_limit(21_000)
+        .max_fee_per_gas(1_000_000_000)
+        .max_priority_fee_per_gas(1);
+    let hash = provider.send_transaction(tx).await?.watch().await?;
+    let receipt = provider.get_transaction_receipt(hash).await?.unwrap();
+    assert!(receipt.status());
+    assert_eq!(receipt.from, account);
+    assert_eq!(provider.get_transaction_by_hash(hash).await?.unwrap().inner.signer(), account);
+    assert_eq!(provider.get_transaction_count(account).await?, 6);
+    assert_eq!(provider.get_balance(receiver).await?, U256::from(1));
+
+    // revert to the block that only changed the balance
+    let reverted: bool = provider.raw_request("anvil_revert".into(), (snapshot,)).await?;
+    assert!(reverted);
+    assert_eq!(provider.get_block_number().await?, 1);
+    assert_eq!(provider.get_transaction_count(account).await?, 0);
+    assert!(provider.get_code_at(contract).await?.is_empty());
+    assert_eq!(provider.get_balance(receiver).await?, U256::ZERO);
+    assert_eq!(provider.get_balance(account).await?, U256::from(ETH_TO_WEI));
+
+    // blocks are only mined on request in manual mode
+    provider.raw_request::<_, ()>("anvil_setAutomine".into(), (false,)).await?;
+    provider.raw_request::<_, ()>("anvil_setBalance".into(), (receiver, U256::from(7))).await?;
+    assert_eq!(provider.get_block_number().await?, 1);
+    provider.raw_request::<_, ()>("anvil_mine".into(), (Some(U256::from(2)), None::<U256>)).await?;
+    assert_eq!(provider.get_block_number().await?, 3);
+    assert_eq!(provider.get_balance(receiver).await?, U256::from(7));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_use_miner_api() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+            RpcModuleSelection::from([
+                RethRpcModule::Eth,
+                RethRpcModule::Anvil,
+                RethRpcModule::Miner,
+            ]),
+        ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+
+    let url = node.rpc_server_handle().http_url().unwrap().parse()?;
+    let provider = ProviderBuilder::new().connect_http(url);
+    provider.raw_request::<_, ()>("anvil_setAutomine".into(), (false,)).await?;
+
+    let extra_data = Bytes::from_static(b"reth miner");
+    assert!(provider.raw_request::<_, bool>("miner_setExtra".into(), (&extra_data,)).await?);
+    assert!(
+        provider.raw_request::<_, bool>("miner_setGasLimit".into(), (U128::from(100_000),)).await?
+    );
+    assert!(provider.raw_request::<_, bool>("miner_setGasPrice".into(), (U128::from(2),)).await?);
+
+    // a transaction that tips less than the minimum is not included
+    let account = Address::with_last_byte(42);
+    provider
+        .raw_request::<_, ()>("anvil_setBalance".into(), (account, U256::from(ETH_TO_WEI)))
+        .await?;
+    provider.raw_request::<_, ()>("anvil_mine".into(), ()).await?;
+    provider.raw_request::<_, ()>("anvil_impersonateAccount".into(), (account,)).await?;
+    let tx = TransactionRequest::default()
+        .from(account)
+        .to(Address::with_last_byte(43))
+        .gas_limit(21_000)
+        .max_fee_per_gas(1_000_000_000)
+        .max_priority_fee_per_gas(1);
+    let hash = *provider.send_transaction(tx).await?.tx_hash();
+    provider.raw_request::<_, ()>("anvil_mine".into(), ()).await?;
+
+    let block = provider.get_block_by_number(2.into()).await?.unwrap();
+    assert_eq!(block.header.extra_data, extra_data);
+    assert!(block.header.gas_limit > 80_000);
+    assert!(block.transactions.is_empty());
+
+    // the transaction is included once the minimum is lowered
+    assert!(provider.raw_request::<_, bool>("miner_setGasPrice".into(), (U128::ZERO,)).await?);
+    provider.raw_request::<_, ()>("anvil_mine".into(), ()).await?;
+    let receipt = provider.get_transaction_receipt(hash).await?.unwrap();
+    assert_eq!(receipt.block_number, Some(3));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_get_transactions_by_address() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let config_path = tempdir_path().join("reth.toml");
+    std::fs::write(&config_path, "[stages.index_address_transactions]\nenabled = true\n")?;
+
+    let mut node_config =
+        NodeConfig::test()
+            .with_chain(custom_chain())
+            .with_config(config_path)
+            .with_dev(DevArgs { dev: true, ..Default::default() })
+            .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+                RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Reth]),
+            ));
+    // persist every block right away, only persisted blocks are indexed
+    node_config.engine = EngineArgs {
+        persistence_threshold: 0,
+        memory_block_buffer_target: 0,
+        ..Default::default()
+    };
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+
+    let url = node.rpc_server_handle().http_url().unwrap().parse()?;
+    let provider = ProviderBuilder::new().connect_http(url);
+
+    let raw_tx = hex!(
+        "02f876820a28808477359400847735940082520894ab0840c0e43688012c1adb0f5e3fc665188f83d28a029d394a5d630544000080c080a0a044076b7e67b5deecc63f61a8d7913fab86ca365b344b5759d1fe3563b4c39ea019eab979dd000da04dfc72bb0377c092d30fd9e1cab5ae487de49586cc8b0090"
+    );
+    let hash = *provider.send_raw_transaction(&raw_tx).await?.tx_hash();
+    let sender = address!("0x6Be02d1d3665660d22FF9624b7BE0551ee1Ac91b");
+    let recipient = address!("0xab0840c0e43688012c1adb0f5e3fc665188f83d2");
+
+    let transactions_by_address = async |address: Address, from_block: u64| {
+        provider
+            .raw_request::<_, AddressTransactionsPage>(
+                "reth_getTransactionsByAddress".into(),
+                (address, Some(BlockNumberOrTag::Number(from_block)), None::<()>, None::<()>),
+            )
+            .await
+    };
+
+    // wait for the block to be persisted and indexed
+    let mut page = transactions_by_address(recipient, 0).await?;
+    for _ in 0..50 {
+        if !page.transactions.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        page = transactions_by_address(recipient, 0).await?;
+    }
+
+    let expected = AddressTransaction {
+        hash,
+        block_hash: provider.get_block_by_number(1.into()).await?.unwrap().header.hash,
+        block_number: 1,
+        transaction_index: 0,
+    };
+    assert_eq!(page, AddressTransactionsPage { transactions: vec![expected], next_cursor: None });
+    assert_eq!(transactions_by_address(sender, 0).await?, page);
+    assert!(transactions_by_address(sender, 2).await?.transactions.is_empty());
+    assert!(transactions_by_address(Address::with_last_byte(42), 0).await?.transactions.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_get_state_diffs() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let node_config =
+        NodeConfig::test()
+            .with_chain(custom_chain())
+            .with_dev(DevArgs { dev: true, ..Default::default() })
+            .with_rpc(RpcServerArgs::default().with_unused_ports().with_http().with_http_api(
+                RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Reth]),
+            ));
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+
+    let url = node.rpc_server_handle().http_url().unwrap().parse()?;
+    let provider = ProviderBuilder::new().connect_http(url);
+
+    let raw_tx = hex!(
+        "02f876820a28808477359400847735940082520894ab0840c0e43688012c1adb0f5e3fc665188f83d28a029d394a5d630544000080c080a0a044076b7e67b5deecc63f61a8d7913fab86ca365b344b5759d1fe3563b4c39ea019eab979dd000da04dfc72bb0377c092d30fd9e1cab5ae487de49586cc8b0090"
+    );
+    let hash = *provider.send_raw_transaction(&raw_tx).await?.tx_hash();
+    let sender = address!("0x6Be02d1d3665660d22FF9624b7BE0551ee1Ac91b");
+    let recipient = address!("0xab0840c0e43688012c1adb0f5e3fc665188f83d2");
+
+    // wait for the transaction to be mined
+    let mut receipt = provider.get_transaction_receipt(hash).await?;
+    for _ in 0..50 {
+        if receipt.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        receipt = provider.get_transaction_receipt(hash).await?;
+    }
+    assert_eq!(receipt.unwrap().block_number, Some(1));
+
+    let block_diff = provider
+        .raw_request::<_, StateDiff>(
+            "reth_getStateDiffInBlock".into(),
+            (BlockNumberOrTag::Number(1),),
+        )
+        .await?;
+    let tx_diff = provider
+        .raw_request::<_, StateDiff>("reth_getStateDiffByTransaction".into(), (hash,))
+        .await?;
+
+    let value = U256::from_be_slice(&hex!("029d394a5d6305440000"));
+    assert_eq!(block_diff[&recipient].balance, Delta::Added(value));
+    assert_eq!(tx_diff[&recipient].balance, Delta::changed(U256::ZERO, value));
+    for diff in [&block_diff, &tx_diff] {
+        assert_eq!(diff[&sender].nonce, Delta::changed(U64::ZERO, U64::from(1)));
+    }
+    assert_eq!(block_diff[&sender], tx_diff[&sender]);
+
+    // the genesis allocation is reported as added
+    let genesis_diff = provider
+        .raw_request::<_, StateDiff>(
+            "reth_getStateDiffInBlock".into(),
+            (BlockNumberOrTag::Number(0),),
+        )
+        .await?;
+    assert_eq!(genesis_diff[&sender].nonce, Delta::Added(U64::ZERO));
+    assert!(!genesis_diff.contains_key(&recipient));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_subscribe_to_logs_from_block() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let modules =
+        RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Reth, RethRpcModule::Anvil]);
+    let mut rpc = RpcServerArgs::default()
+        .with_unused_ports()
+        .with_http()
+        .with_http_api(modules.clone())
+        .with_ws();
+    rpc.ws_api = Some(modules);
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_rpc(rpc);
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+
+    let url = node.rpc_server_handle().http_url().unwrap().parse()?;
+    let provider = ProviderBuilder::new().connect_http(url);
+    let wallet = Wallet::default().inner;
+    provider
+        .raw_request::<_, ()>("anvil_setBalance".into(), (wallet.address(), U256::from(ETH_TO_WEI)))
+        .await?;
+
+    // deploys a contract whose constructor emits an empty log
+    let deploy = async |nonce: u64, max_fee_per_gas: u128| -> eyre::Result<_> {
+        let tx = TransactionRequest::default()
+            .with_deploy_code(hex!("60006000a000"))
+            .with_nonce(nonce)
+            .with_chain_id(custom_chain().chain.id())
+            .with_gas_limit(60_000)
+            .with_max_fee_per_gas(max_fee_per_gas)
+            .with_max_priority_fee_per_gas(1_000_000);
+        let tx = TransactionTestContext::sign_tx(wallet.clone(), tx).await;
+        Ok(provider.send_raw_transaction(&tx.encoded_2718()).await?.get_receipt().await?)
+    };
+
+    let first = deploy(0, 1_000_000_000).await?;
+    let client = node.rpc_server_handle().ws_client().await.unwrap();
+    let mut logs = RethApiClient::reth_subscribe_chain_notifications(
+        &client,
+        Some(Filter::new()),
+        Some(BlockNumberOrTag::Earliest),
+    )
+    .await?
+    .map(|item| match item? {
+        ChainNotification::Log(log) => Ok(log),
+        ChainNotification::Notification(_) => eyre::bail!("expected a log"),
+    });
+
+    // the log of the first deployment is replayed from the database
+    let log = logs.next().await.unwrap()?;
+    assert_eq!(log.address(), first.contract_address.unwrap());
+    assert_eq!(log.block_number, first.block_number);
+    assert_eq!(log.transaction_hash, Some(first.transaction_hash));
+    assert!(!log.removed);
+
+    // the log of the second one is sent once it is mined
+    let snapshot = provider.raw_request::<_, U256>("anvil_snapshot".into(), ()).await?;
+    let second = deploy(1, 1_000_000_000).await?;
+    let log = logs.next().await.unwrap()?;
+    assert_eq!(log.address(), second.contract_address.unwrap());
+    assert_eq!(log.block_number, second.block_number);
+    assert!(!log.removed);
+
+    // the logs of a block that was unwound are sent as removed, followed by the logs of the block
+    // replacing it
+    assert!(provider.raw_request::<_, bool>("anvil_revert".into(), (snapshot,)).await?);
+    let replaced = deploy(1, 2_000_000_000).await?;
+    let log = logs.next().await.unwrap()?;
+    assert_eq!(log.transaction_hash, Some(second.transaction_hash));
+    assert!(log.removed);
+    let log = logs.next().await.unwrap()?;
+    assert_eq!(log.block_number, second.block_number);
+    assert_eq!(log.transaction_hash, Some(replaced.transaction_hash));
+    assert!(!log.removed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_subscribe_to_trace_chain() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let modules = RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Debug]);
+    let mut rpc = RpcServerArgs::default()
+        .with_unused_ports()
+        .with_http()
+        .with_http_api(modules.clone())
+        .with_ws();
+    rpc.ws_api = Some(modules);
+    // fund the wallet in the genesis, state changes of the anvil API can't be replayed
+    let wallet = Wallet::default().inner;
+    let genesis = custom_chain().genesis.clone().extend_accounts([(
+        wallet.address(),
+        GenesisAccount::default().with_balance(U256::from(ETH_TO_WEI)),
+    )]);
+    let chain: Arc<ChainSpec> = Arc::new(genesis.into());
+    let node_config = NodeConfig::test()
+        .with_chain(chain.clone())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_rpc(rpc);
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+
+    let url = node.rpc_server_handle().http_url().unwrap().parse()?;
+    let provider = ProviderBuilder::new().connect_http(url);
+
+    // mines one block per transfer
+    let mut receipts = Vec::new();
+    for nonce in 0..2 {
+        let tx = TransactionRequest::default()
+            .with_to(Address::with_last_byte(42))
+            .with_value(U256::from(1))
+            .with_nonce(nonce)
+            .with_chain_id(chain.chain.id())
+            .with_gas_limit(21_000)
+            .with_max_fee_per_gas(1_000_000_000)
+            .with_max_priority_fee_per_gas(1_000_000);
+        let tx = TransactionTestContext::sign_tx(wallet.clone(), tx).await;
+        receipts
+            .push(provider.send_raw_transaction(&tx.encoded_2718()).await?.get_receipt().await?);
+    }
+    let end = receipts.last().unwrap().block_number.unwrap();
+
+    let client = node.rpc_server_handle().ws_client().await.unwrap();
+    let opts = GethDebugTracingOptions::default()
+        .with_tracer(GethDebugBuiltInTracerType::PreStateTracer.into());
+    let mut traces = DebugApiClient::debug_trace_chain(
+        &client,
+        DebugSubscriptionKind::TraceChain,
+        BlockNumberOrTag::Number(0),
+        BlockNumberOrTag::Number(end),
+        Some(opts.clone()),
+    )
+    .await?;
+
+    for number in 1..=end {
+        let block = traces.next().await.unwrap()?;
+        let header = provider.get_block_by_number(number.into()).await?.unwrap().header;
+        assert_eq!(block.block, U256::from(number));
+        assert_eq!(block.hash, header.hash);
+
+        // tracing on top of the state carried over the range equals tracing the single block
+        let expected = DebugApiClient::debug_trace_block_by_number(
+            &client,
+            BlockNumberOrTag::Number(number),
+            Some(opts.clone()),
+        )
+        .await?;
+        assert_eq!(block.traces, expected);
+
+        let receipt = receipts.iter().find(|receipt| receipt.block_number == Some(number));
+        assert_eq!(block.traces.len(), receipt.is_some() as usize);
+        let Some(receipt) = receipt else { continue };
+        let TraceResult::Success {
+            result: GethTrace::PreStateTracer(PreStateFrame::Default(prestate)),
+            tx_hash,
+        } = &block.traces[0]
+        else {
+            panic!("unexpected trace {:?}", block.traces[0])
+        };
+        assert_eq!(*tx_hash, Some(receipt.transaction_hash));
+
+        // the block is traced on top of the state left by the previous block
+        let balance = provider.get_balance(wallet.address()).number(number - 1).await?;
+        assert_eq!(prestate.0[&wallet.address()].balance, Some(balance));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_dump_state_in_pages() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let modules = RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Debug]);
+    let rpc = RpcServerArgs::default().with_unused_ports().with_http().with_http_api(modules);
+    let wallet = Wallet::default().inner;
+    let contract = Address::with_last_byte(0xc0);
+    let contract_storage = (1..=3u8)
+        .map(|slot| (B256::with_last_byte(slot), B256::with_last_byte(slot * 10)))
+        .collect::<BTreeMap<_, _>>();
+    let genesis = custom_chain().genesis.clone().extend_accounts([
+        (wallet.address(), GenesisAccount::default().with_balance(U256::from(ETH_TO_WEI))),
+        (
+            contract,
+            GenesisAccount::default()
+                .with_code(Some(Bytes::from_static(&[0x00])))
+                .with_storage(Some(contract_storage.clone())),
+        ),
+    ]);
+    let chain: Arc<ChainSpec> = Arc::new(genesis.into());
+    let node_config = NodeConfig::test()
+        .with_chain(chain.clone())
+        .with_dev(DevArgs { dev: true, ..Default::default() })
+        .with_rpc(rpc);
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(exec.clone())
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+
+    let url = node.rpc_server_handle().http_url().unwrap().parse()?;
+    let provider = ProviderBuilder::new().connect_http(url);
+    let tx = TransactionRequest::default()
+        .with_to(Address::with_last_byte(42))
+        .with_value(U256::from(1))
+        .with_nonce(0)
+        .with_chain_id(chain.chain.id())
+        .with_gas_limit(21_000)
+        .with_max_fee_per_gas(1_000_000_000)
+        .with_max_priority_fee_per_gas(1_000_000);
+    let tx = TransactionTestContext::sign_tx(wallet, tx).await;
+    let receipt = provider.send_raw_transaction(&tx.encoded_2718()).await?.get_receipt().await?;
+    let number = receipt.block_number.unwrap();
+    let block_hash = receipt.block_hash.unwrap();
+
+    let client = node.rpc_server_handle().http_client().unwrap();
+    let dump = DebugApiClient::debug_dump_block(&client, number.into()).await?;
+    assert_eq!(dump.next, None);
+    assert_eq!(dump.accounts[&contract].storage, contract_storage);
+
+    // the accounts can be read one by one
+    let mut accounts = BTreeMap::new();
+    let mut start = Bytes::new();
+    loop {
+        let page = DebugApiClient::debug_account_range(
+            &client,
+            number.into(),
+            start,
+            1,
+            false,
+            false,
+            false,
+        )
+        .await?;
+        assert_eq!(page.root, dump.root);
+        assert_eq!(page.accounts.len(), 1);
+        accounts.extend(page.accounts);
+        let Some(next) = page.next else { break };
+        start = Bytes::copy_from_slice(next.as_slice());
+    }
+    assert_eq!(accounts, dump.accounts);
+
+    // so can the storage slots
+    let mut storage = BTreeMap::new();
+    let mut key_start = B256::ZERO;
+    loop {
+        let page =
+            DebugApiClient::debug_storage_range_at(&client, block_hash, 0, contract, key_start, 1)
+                .await?;
+        assert_eq!(page.storage.len(), 1);
+        storage.extend(page.storage.into_values().map(|entry| (entry.key.unwrap(), entry.value)));
+        let Some(next) = page.next_key else { break };
+        key_start = next;
+    }
+    assert_eq!(storage, contract_storage);
+
+    Ok(())
+}
+
 async fn assert_chain_advances<N, AddOns>(node: FullNode<N, AddOns>)
 where
     N: FullNodeComponents<Provider: CanonStateSubscriptions>,
@@ -1,5 +1,8 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+use alloy_primitives::Bytes;
+use reth_payload_builder_primitives::MinerConfig;
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
+use reth_revm::state_changes::PendingStateChanges;
 
 /// Settings for the Ethereum builder.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -9,6 +12,11 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// State changes that are committed before the transactions of a payload, used by dev chains.
+    pub pending_state_changes: Option<PendingStateChanges>,
+    /// Settings that can be changed while the node is running and take precedence over this
+    /// configuration.
+    pub miner_config: Option<MinerConfig>,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +28,12 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            pending_state_changes: None,
+            miner_config: None,
+        }
     }
 
     /// Set desired gas limit.
@@ -35,13 +48,44 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Commits the [`PendingStateChanges`] staged for the parent of a payload before its
+    /// transactions.
+    pub fn with_pending_state_changes(
+        mut self,
+        pending_state_changes: PendingStateChanges,
+    ) -> Self {
+        self.pending_state_changes = Some(pending_state_changes);
+        self
+    }
+
+    /// Sets the [`MinerConfig`] that can override this configuration while the node is running.
+    pub fn with_miner_config(mut self, miner_config: MinerConfig) -> Self {
+        self.miner_config = Some(miner_config);
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
     /// Returns the gas limit for the next block based
     /// on parent and desired gas limits.
     pub fn gas_limit(&self, parent_gas_limit: u64) -> u64 {
-        calculate_block_gas_limit(parent_gas_limit, self.desired_gas_limit)
+        let desired_gas_limit = self
+            .miner_config
+            .as_ref()
+            .and_then(MinerConfig::gas_limit)
+            .unwrap_or(self.desired_gas_limit);
+        calculate_block_gas_limit(parent_gas_limit, desired_gas_limit)
+    }
+
+    /// Returns the extra data that overrides the extra data of the EVM configuration, if any.
+    pub fn extra_data(&self) -> Option<Bytes> {
+        self.miner_config.as_ref().and_then(MinerConfig::extra_data)
+    }
+
+    /// Returns the minimum priority fee per gas of included transactions.
+    pub fn min_tip(&self) -> u128 {
+        self.miner_config.as_ref().and_then(MinerConfig::min_tip).unwrap_or_default()
     }
 }
 
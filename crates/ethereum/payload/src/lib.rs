@@ -15,6 +15,7 @@ use reth_basic_payload_builder::{
     is_better_payload, BuildArguments, BuildOutcome, MissingPayloadBehaviour, PayloadBuilder,
     PayloadConfig,
 };
+use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_errors::{BlockExecutionError, BlockValidationError};
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
@@ -22,7 +23,8 @@ use reth_evm::{
     execute::{BlockBuilder, BlockBuilderOutcome},
     ConfigureEvm, Evm, NextBlockEnvAttributes,
 };
-use reth_evm_ethereum::EthEvmConfig;
+use reth_evm_ethereum::{ConfigureExtraData, EthEvmConfig};
+use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{BlobSidecars, EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
@@ -76,7 +78,8 @@ impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
 // Default implementation of [PayloadBuilder] for unit type
 impl<Pool, Client, EvmConfig> PayloadBuilder for EthereumPayloadBuilder<Pool, Client, EvmConfig>
 where
-    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>
+        + ConfigureExtraData,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
 {
@@ -134,7 +137,7 @@ where
 /// a result indicating success with the payload or an error in case of failure.
 #[inline]
 pub fn default_ethereum_payload<EvmConfig, Client, Pool, F>(
-    evm_config: EvmConfig,
+    mut evm_config: EvmConfig,
     client: Client,
     pool: Pool,
     builder_config: EthereumBuilderConfig,
@@ -142,7 +145,8 @@ pub fn default_ethereum_payload<EvmConfig, Client, Pool, F>(
     best_txs: F,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
-    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>
+        + ConfigureExtraData,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
     F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
@@ -155,6 +159,22 @@ where
     let mut db =
         State::builder().with_database(cached_reads.as_db_mut(state)).with_bundle_update().build();
 
+    // commit the state changes of a dev chain, so they're part of the payload's state
+    if let Some(changes) = builder_config
+        .pending_state_changes
+        .as_ref()
+        .and_then(|changes| changes.staged(parent_header.hash()))
+    {
+        debug!(target: "payload_builder", id=%attributes.id, "committing pending state changes");
+        changes.commit(&mut db)?;
+    }
+
+    // the extra data can be changed while the node is running, so it takes precedence over the
+    // extra data of the EVM config
+    if let Some(extra_data) = builder_config.extra_data() {
+        evm_config.set_extra_data(extra_data);
+    }
+
     let mut builder = evm_config
         .builder_for_next_block(
             &mut db,
@@ -198,6 +218,8 @@ where
     let max_blob_count =
         blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
+    let min_tip = builder_config.min_tip();
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -211,6 +233,13 @@ where
             continue
         }
 
+        // skip transactions that pay less than the minimum tip, and their descendants
+        if pool_tx.transaction.effective_tip_per_gas(base_fee).unwrap_or_default() < min_tip {
+            trace!(target: "payload_builder", tx=?pool_tx.hash(), %min_tip, "skipping transaction below the minimum tip");
+            best_txs.mark_invalid(&pool_tx, InvalidPoolTransactionError::Underpriced);
+            continue
+        }
+
         // check if the job was cancelled, if so we can exit early
         if cancel.is_cancelled() {
             return Ok(BuildOutcome::Cancelled)
@@ -327,18 +356,36 @@ where
         return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
     }
 
-    let BlockBuilderOutcome { execution_result, block, .. } = builder.finish(&state_provider)?;
+    let BlockBuilderOutcome { execution_result, hashed_state, trie_updates, block } =
+        builder.finish(&state_provider)?;
 
     let requests = chain_spec
         .is_prague_active_at_timestamp(attributes.timestamp)
-        .then_some(execution_result.requests);
+        .then_some(execution_result.requests.clone());
 
     let sealed_block = Arc::new(block.sealed_block().clone());
     debug!(target: "payload_builder", id=%attributes.id, sealed_block_header = ?sealed_block.sealed_header(), "sealed built block");
 
+    // create the executed block data, so the engine doesn't need to execute the block again
+    let execution_outcome = ExecutionOutcome::new(
+        db.take_bundle(),
+        vec![execution_result.receipts],
+        block.number,
+        vec![execution_result.requests],
+    );
+    let executed = ExecutedBlockWithTrieUpdates {
+        block: ExecutedBlock {
+            recovered_block: Arc::new(block),
+            execution_output: Arc::new(execution_outcome),
+            hashed_state: Arc::new(hashed_state),
+        },
+        trie: ExecutedTrieUpdates::Present(Arc::new(trie_updates)),
+    };
+
     let payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees, requests)
         // add blob sidecars from the executed txs
-        .with_sidecars(blob_sidecars);
+        .with_sidecars(blob_sidecars)
+        .with_executed_block(executed);
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
@@ -618,16 +618,20 @@ impl reth_codecs::Compact for TransactionSigned {
                     // TODO: enforce that zstd is only present at a "top" level type
 
                     let transaction_type = (bitflags & 0b110) >> 1;
-                    let (transaction, _) =
-                        Transaction::from_compact(decompressor.decompress(buf), transaction_type);
+                    let (transaction, _) = Transaction::from_compact(
+                        decompressor.decompress(buf).expect("Failed to decompress"),
+                        transaction_type,
+                    );
 
                     (transaction, buf)
                 })
             } else {
                 let mut decompressor = reth_zstd_compressors::create_tx_decompressor();
                 let transaction_type = (bitflags & 0b110) >> 1;
-                let (transaction, _) =
-                    Transaction::from_compact(decompressor.decompress(buf), transaction_type);
+                let (transaction, _) = Transaction::from_compact(
+                    decompressor.decompress(buf).expect("Failed to decompress"),
+                    transaction_type,
+                );
 
                 (transaction, buf)
             }
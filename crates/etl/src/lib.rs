@@ -17,13 +17,18 @@
 use std::{
     cmp::Reverse,
     collections::BinaryHeap,
-    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    io::{self, BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write},
     path::{Path, PathBuf},
 };
 
 /// Key len and Value len encode use [`usize::to_be_bytes()`] the length is 8.
 const KV_LEN: usize = 8;
 
+/// Target size in bytes of the uncompressed blocks of entries in compressed ETL files.
+///
+/// Every file being merged holds one decompressed block in memory.
+const BLOCK_SIZE: usize = 128 * 1024;
+
 use rayon::prelude::*;
 use reth_db_api::table::{Compress, Encode, Key, Value};
 use tempfile::{NamedTempFile, TempDir};
@@ -57,6 +62,12 @@ where
     buffer: Vec<(<K as Encode>::Encoded, <V as Compress>::Compressed)>,
     /// Total number of elements in the collector, including all files
     len: usize,
+    /// Zstd compression level of the files, if they're compressed
+    compression_level: Option<i32>,
+    /// Total size in bytes of the files
+    disk_usage: u64,
+    /// Maximum total size in bytes of the files, flushing fails if it would be exceeded
+    max_disk_usage: Option<u64>,
 }
 
 impl<K, V> Collector<K, V>
@@ -76,9 +87,34 @@ where
             buffer_capacity_bytes,
             buffer: Vec::new(),
             len: 0,
+            compression_level: None,
+            disk_usage: 0,
+            max_disk_usage: None,
         }
     }
 
+    /// Compresses the flushed files with zstd at the given level, or doesn't compress them if
+    /// `None`.
+    ///
+    /// The entries are compressed in blocks, in parallel.
+    pub const fn with_compression_level(mut self, compression_level: Option<i32>) -> Self {
+        self.compression_level = compression_level;
+        self
+    }
+
+    /// Sets the maximum total size in bytes of the flushed files. Inserting or iterating returns an
+    /// error of kind [`io::ErrorKind::StorageFull`] instead of flushing a file that would exceed
+    /// it.
+    pub const fn with_max_disk_usage(mut self, max_disk_usage: Option<u64>) -> Self {
+        self.max_disk_usage = max_disk_usage;
+        self
+    }
+
+    /// Returns the total size in bytes of the files flushed to disk.
+    pub const fn disk_usage(&self) -> u64 {
+        self.disk_usage
+    }
+
     /// Returns number of elements currently in the collector.
     pub const fn len(&self) -> usize {
         self.len
@@ -97,6 +133,7 @@ where
         self.buffer = Vec::new();
         self.buffer_size_bytes = 0;
         self.len = 0;
+        self.disk_usage = 0;
     }
 
     /// Insert an entry into the collector.
@@ -131,13 +168,35 @@ where
     }
 
     fn flush(&mut self) -> io::Result<()> {
+        let compression = self.compression_level.map(|level| {
+            // Aim for blocks of `BLOCK_SIZE` bytes based on the average size of the entries
+            let entries_per_block = BLOCK_SIZE * self.buffer.len() / self.buffer_size_bytes.max(1);
+            (level, entries_per_block.max(1))
+        });
+
         self.buffer_size_bytes = 0;
         self.buffer.par_sort_unstable_by(|a, b| a.0.cmp(&b.0));
         let mut buf = Vec::with_capacity(self.buffer.len());
         std::mem::swap(&mut buf, &mut self.buffer);
 
+        let max_size = self
+            .max_disk_usage
+            .map(|max_disk_usage| max_disk_usage.saturating_sub(self.disk_usage));
         let path = self.dir()?.path().to_path_buf();
-        self.files.push(EtlFile::new(path.as_path(), buf)?);
+        let file = EtlFile::new(path.as_path(), buf, compression, max_size).map_err(|err| {
+            match (err.kind(), self.max_disk_usage) {
+                (io::ErrorKind::StorageFull, Some(max_disk_usage)) => io::Error::new(
+                    err.kind(),
+                    format!(
+                        "ETL files would exceed the maximum of {max_disk_usage} bytes, {} bytes are already used",
+                        self.disk_usage
+                    ),
+                ),
+                _ => err,
+            }
+        })?;
+        self.disk_usage += file.size;
+        self.files.push(file);
 
         Ok(())
     }
@@ -217,38 +276,92 @@ impl Iterator for EtlIter<'_> {
 }
 
 /// A temporary ETL file.
+///
+/// Entries are written as the lengths of the key and value followed by the key and value. In
+/// compressed files, the entries are grouped in zstd compressed blocks, each preceded by its
+/// compressed and decompressed length.
 #[derive(Debug)]
 struct EtlFile {
     file: BufReader<NamedTempFile>,
     len: usize,
+    /// Size of the file in bytes.
+    size: u64,
+    /// Whether the entries are grouped in compressed blocks.
+    compressed: bool,
+    /// The decompressed block that is being read, if the file is compressed.
+    block: Cursor<Vec<u8>>,
 }
 
 impl EtlFile {
     /// Create a new file with the given data (which should be pre-sorted) at the given path.
     ///
+    /// If a compression level and number of entries per block are given, the blocks of entries are
+    /// compressed in parallel.
+    ///
+    /// If the file would be larger than `max_size` bytes, nothing is written and an error of kind
+    /// [`io::ErrorKind::StorageFull`] is returned.
+    ///
     /// The file will be a temporary file.
-    pub(crate) fn new<K, V>(dir: &Path, buffer: Vec<(K, V)>) -> std::io::Result<Self>
+    pub(crate) fn new<K, V>(
+        dir: &Path,
+        buffer: Vec<(K, V)>,
+        compression: Option<(i32, usize)>,
+        max_size: Option<u64>,
+    ) -> std::io::Result<Self>
     where
         Self: Sized,
-        K: AsRef<[u8]>,
-        V: AsRef<[u8]>,
+        K: AsRef<[u8]> + Sync,
+        V: AsRef<[u8]> + Sync,
     {
+        // Blocks are compressed in memory, so the size of the file is known before writing it
+        let blocks = compression
+            .map(|(level, entries_per_block)| {
+                buffer
+                    .par_chunks(entries_per_block)
+                    .map(|entries| {
+                        let mut block = Vec::new();
+                        for (k, v) in entries {
+                            write_entry(&mut block, k.as_ref(), v.as_ref())?;
+                        }
+                        Ok((block.len(), zstd::bulk::compress(&block, level)?))
+                    })
+                    .collect::<io::Result<Vec<_>>>()
+            })
+            .transpose()?;
+        let size: usize = match &blocks {
+            Some(blocks) => blocks.iter().map(|(_, block)| 2 * KV_LEN + block.len()).sum(),
+            None => {
+                buffer.iter().map(|(k, v)| 2 * KV_LEN + k.as_ref().len() + v.as_ref().len()).sum()
+            }
+        };
+        if let Some(max_size) = max_size {
+            if size as u64 > max_size {
+                return Err(io::Error::new(
+                    io::ErrorKind::StorageFull,
+                    format!("ETL file of {size} bytes exceeds the maximum of {max_size} bytes"),
+                ))
+            }
+        }
+
         let file = NamedTempFile::new_in(dir)?;
         let mut w = BufWriter::new(file);
-        for entry in &buffer {
-            let k = entry.0.as_ref();
-            let v = entry.1.as_ref();
-
-            w.write_all(&k.len().to_be_bytes())?;
-            w.write_all(&v.len().to_be_bytes())?;
-            w.write_all(k)?;
-            w.write_all(v)?;
+        if let Some(blocks) = blocks {
+            for (len, block) in blocks {
+                w.write_all(&block.len().to_be_bytes())?;
+                w.write_all(&len.to_be_bytes())?;
+                w.write_all(&block)?;
+            }
+        } else {
+            for (k, v) in &buffer {
+                write_entry(&mut w, k.as_ref(), v.as_ref())?;
+            }
         }
 
         let mut file = BufReader::new(w.into_inner()?);
+        let size = file.get_ref().as_file().metadata()?.len();
         file.seek(SeekFrom::Start(0))?;
         let len = buffer.len();
-        Ok(Self { file, len })
+        Ok(Self { file, len, size, compressed: compression.is_some(), block: Cursor::default() })
     }
 
     /// Read the next entry in the file.
@@ -259,26 +372,64 @@ impl EtlFile {
             return Ok(None)
         }
 
-        let mut buffer_key_length = [0; KV_LEN];
-        let mut buffer_value_length = [0; KV_LEN];
+        let entry = if self.compressed {
+            if self.block.position() == self.block.get_ref().len() as u64 {
+                self.read_block()?;
+            }
+            read_entry(&mut self.block)?
+        } else {
+            read_entry(&mut self.file)?
+        };
+
+        self.len -= 1;
 
-        self.file.read_exact(&mut buffer_key_length)?;
-        self.file.read_exact(&mut buffer_value_length)?;
+        Ok(Some(entry))
+    }
 
-        let key_length = usize::from_be_bytes(buffer_key_length);
-        let value_length = usize::from_be_bytes(buffer_value_length);
-        let mut key = vec![0; key_length];
-        let mut value = vec![0; value_length];
+    /// Reads and decompresses the next block of entries.
+    fn read_block(&mut self) -> std::io::Result<()> {
+        let mut buffer_compressed_length = [0; KV_LEN];
+        let mut buffer_length = [0; KV_LEN];
 
-        self.file.read_exact(&mut key)?;
-        self.file.read_exact(&mut value)?;
+        self.file.read_exact(&mut buffer_compressed_length)?;
+        self.file.read_exact(&mut buffer_length)?;
 
-        self.len -= 1;
+        let mut compressed = vec![0; usize::from_be_bytes(buffer_compressed_length)];
+        self.file.read_exact(&mut compressed)?;
+        self.block =
+            Cursor::new(zstd::bulk::decompress(&compressed, usize::from_be_bytes(buffer_length))?);
 
-        Ok(Some((key, value)))
+        Ok(())
     }
 }
 
+/// Writes an entry as the lengths of the key and value, followed by the key and value.
+fn write_entry(w: &mut impl Write, k: &[u8], v: &[u8]) -> std::io::Result<()> {
+    w.write_all(&k.len().to_be_bytes())?;
+    w.write_all(&v.len().to_be_bytes())?;
+    w.write_all(k)?;
+    w.write_all(v)
+}
+
+/// Reads an entry written by [`write_entry`].
+fn read_entry(r: &mut impl Read) -> std::io::Result<(Vec<u8>, Vec<u8>)> {
+    let mut buffer_key_length = [0; KV_LEN];
+    let mut buffer_value_length = [0; KV_LEN];
+
+    r.read_exact(&mut buffer_key_length)?;
+    r.read_exact(&mut buffer_value_length)?;
+
+    let key_length = usize::from_be_bytes(buffer_key_length);
+    let value_length = usize::from_be_bytes(buffer_value_length);
+    let mut key = vec![0; key_length];
+    let mut value = vec![0; value_length];
+
+    r.read_exact(&mut key)?;
+    r.read_exact(&mut value)?;
+
+    Ok((key, value))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -316,4 +467,51 @@ mod tests {
         assert!(collector.is_empty());
         assert!(!temp_dir_path.exists());
     }
+
+    #[test]
+    fn etl_hashes_compressed() {
+        let mut entries: Vec<_> =
+            (0..10_000).map(|id| (TxHash::random(), id as TxNumber)).collect();
+
+        // Large enough for files with multiple blocks
+        let mut collector = Collector::new(256 * 1024, None).with_compression_level(Some(1));
+        let mut uncompressed = Collector::new(256 * 1024, None);
+        for (k, v) in entries.clone() {
+            collector.insert(k, v).unwrap();
+            uncompressed.insert(k, v).unwrap();
+        }
+        entries.sort_unstable_by_key(|entry| entry.0);
+
+        let iter = collector.iter().unwrap();
+        assert!(iter.files.len() > 1);
+        for (id, entry) in iter.enumerate() {
+            let expected = entries[id];
+            assert_eq!(
+                entry.unwrap(),
+                (expected.0.encode().to_vec(), expected.1.compress().clone())
+            );
+        }
+
+        // Hashes don't compress, but the lengths of the entries do
+        uncompressed.iter().unwrap();
+        assert!(collector.disk_usage() < uncompressed.disk_usage());
+    }
+
+    #[test]
+    fn etl_max_disk_usage() {
+        let mut collector = Collector::new(1024, None).with_max_disk_usage(Some(4096));
+
+        let result = (0..1_000)
+            .map(|id| collector.insert(TxHash::random(), id as TxNumber))
+            .collect::<io::Result<Vec<_>>>();
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::StorageFull);
+
+        // The file exceeding the maximum wasn't written
+        assert!(collector.disk_usage() <= 4096);
+        let files_size = std::fs::read_dir(collector.dir.as_ref().unwrap().path())
+            .unwrap()
+            .map(|entry| entry.unwrap().metadata().unwrap().len())
+            .sum::<u64>();
+        assert_eq!(files_size, collector.disk_usage());
+    }
 }
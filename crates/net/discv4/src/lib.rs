@@ -341,6 +341,15 @@ impl Discv4 {
         self.send_to_service(cmd);
     }
 
+    /// Requests the EIP-868 record of the node, which is emitted as [`DiscoveryUpdate::Enr`].
+    ///
+    /// The node has to be in the table, and only answers once it has verified the endpoint of the
+    /// local node, so requests sent right after the node was added may be ignored.
+    pub fn request_enr(&self, node_record: NodeRecord) {
+        let cmd = Discv4Command::RequestEnr(node_record);
+        self.send_to_service(cmd);
+    }
+
     /// Adds the peer and id to the ban list.
     ///
     /// This will prevent any future inclusion in the table
@@ -1337,6 +1346,7 @@ impl Discv4Service {
                     }
                     _ => return,
                 };
+                self.notify(DiscoveryUpdate::Enr(record, msg.enr));
                 match (fork_id, old_fork_id) {
                     (Some(new), Some(old)) => {
                         if new != old {
@@ -1714,6 +1724,9 @@ impl Discv4Service {
                     Discv4Command::Add(enr) => {
                         self.add_node(enr);
                     }
+                    Discv4Command::RequestEnr(record) => {
+                        self.send_enr_request(record);
+                    }
                     Discv4Command::Lookup { node_id, tx } => {
                         let node_id = node_id.unwrap_or(self.local_node_record.id);
                         self.lookup_with(node_id, tx);
@@ -2030,6 +2043,7 @@ impl Default for ReceiveCache {
 /// The commands sent from the frontend [Discv4] to the service [`Discv4Service`].
 enum Discv4Command {
     Add(NodeRecord),
+    RequestEnr(NodeRecord),
     SetTcpPort(u16),
     SetEIP868RLPPair { key: Vec<u8>, rlp: Bytes },
     Ban(PeerId, IpAddr),
@@ -2385,6 +2399,8 @@ pub enum DiscoveryUpdate {
     DiscoveredAtCapacity(NodeRecord),
     /// Received a [`ForkId`] via EIP-868 for the given [`NodeRecord`].
     EnrForkId(NodeRecord, ForkId),
+    /// Received the signed record of the given [`NodeRecord`] via EIP-868.
+    Enr(NodeRecord, Enr<SecretKey>),
     /// Node that was removed from the table
     Removed(PeerId),
     /// A series of updates
@@ -3011,6 +3027,41 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_enr_update() {
+        reth_tracing::init_test_tracing();
+        let config = Discv4Config::builder().external_ip_resolver(None).build();
+        let (_, service_1) = create_discv4_with_config(config.clone()).await;
+        let record_1 = service_1.local_node_record;
+        service_1.spawn();
+
+        let (discv4, mut service_2) = create_discv4_with_config(config).await;
+        let mut updates = service_2.update_stream();
+        service_2.spawn();
+        discv4.add_node(record_1);
+
+        // The node answers ENR requests once it verified the endpoint of the requesting node
+        let enr = tokio::time::timeout(Duration::from_secs(5), async {
+            let mut interval = tokio::time::interval(Duration::from_millis(100));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => discv4.request_enr(record_1),
+                    Some(update) = updates.next() => {
+                        if let DiscoveryUpdate::Enr(record, enr) = update {
+                            if record.id == record_1.id {
+                                return enr
+                            }
+                        }
+                    }
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(pk2id(&enr.public_key()), record_1.id);
+        assert_eq!(enr.udp4(), Some(record_1.udp_port));
+    }
+
     #[tokio::test]
     async fn test_bootnode_not_in_update_stream() {
         reth_tracing::init_test_tracing();
@@ -0,0 +1,319 @@
+//! Building of [EIP-1459](https://eips.ethereum.org/EIPS/eip-1459) ENR trees, to publish a list of
+//! nodes via DNS.
+//!
+//! The node records and the links to other trees are the leaves of two subtrees. Every entry is
+//! published as a TXT record at the subdomain of its hash, and the root entry at the domain of the
+//! tree.
+
+use crate::tree::{BranchEntry, DnsEntry, LinkEntry, NodeEntry, TreeRootEntry};
+use alloy_primitives::{keccak256, Bytes};
+use data_encoding::BASE32_NOPAD;
+use enr::Enr;
+use secp256k1::SecretKey;
+use std::{collections::BTreeMap, fmt::Write};
+
+/// Maximum number of children of a branch entry.
+///
+/// A branch with this many hashes still fits into a TXT record of 370 bytes.
+pub const MAX_BRANCH_CHILDREN: usize = 13;
+
+/// Number of bytes of the hash of an entry that make up its subdomain.
+const HASH_ABBREV_SIZE: usize = 16;
+
+/// Maximum length of a single string of a TXT record.
+const MAX_TXT_STRING_LEN: usize = 255;
+
+/// Returns the subdomain an entry is published at: the base32 encoding of the first 16 bytes of
+/// the keccak256 hash of its text.
+pub fn entry_hash(entry: &DnsEntry<SecretKey>) -> String {
+    BASE32_NOPAD.encode(&keccak256(entry.to_string().as_bytes())[..HASH_ABBREV_SIZE])
+}
+
+/// An ENR tree of node records and links to other trees, ready to be published.
+#[derive(Debug, Clone)]
+pub struct DnsTree {
+    /// The root entry, published at the domain of the tree.
+    root: TreeRootEntry,
+    /// All other entries, by the subdomain they're published at.
+    entries: BTreeMap<String, DnsEntry<SecretKey>>,
+}
+
+// === impl DnsTree ===
+
+impl DnsTree {
+    /// Builds the tree of the node records and the links.
+    ///
+    /// Records of the same node are deduplicated, keeping the one with the highest sequence
+    /// number. The root entry is unsigned until the tree is [signed](Self::sign).
+    pub fn new(
+        sequence_number: u64,
+        enrs: impl IntoIterator<Item = Enr<SecretKey>>,
+        links: impl IntoIterator<Item = LinkEntry>,
+    ) -> Self {
+        let mut nodes = BTreeMap::new();
+        for enr in enrs {
+            let node_id = enr.node_id().raw();
+            if nodes
+                .get(&node_id)
+                .is_none_or(|existing: &Enr<SecretKey>| existing.seq() < enr.seq())
+            {
+                nodes.insert(node_id, enr);
+            }
+        }
+        let mut links = links.into_iter().collect::<Vec<_>>();
+        links.sort_by_cached_key(ToString::to_string);
+        links.dedup();
+
+        let mut entries = BTreeMap::new();
+        let enr_root = build_subtree(
+            &mut entries,
+            nodes.into_values().map(|enr| DnsEntry::Node(NodeEntry { enr })).collect(),
+        );
+        let link_root =
+            build_subtree(&mut entries, links.into_iter().map(DnsEntry::Link).collect());
+
+        let root = TreeRootEntry {
+            enr_root: insert_entry(&mut entries, enr_root),
+            link_root: insert_entry(&mut entries, link_root),
+            sequence_number,
+            signature: Bytes::new(),
+        };
+        Self { root, entries }
+    }
+
+    /// Signs the root entry with the key of the tree.
+    pub fn sign(&mut self, key: &SecretKey) {
+        self.root.sign_recoverable(key);
+    }
+
+    /// Returns the root entry.
+    pub const fn root(&self) -> &TreeRootEntry {
+        &self.root
+    }
+
+    /// Returns all entries other than the root, by the subdomain they're published at.
+    pub const fn entries(&self) -> &BTreeMap<String, DnsEntry<SecretKey>> {
+        &self.entries
+    }
+
+    /// Returns the node records of the tree.
+    pub fn nodes(&self) -> impl Iterator<Item = &Enr<SecretKey>> + '_ {
+        self.entries.values().filter_map(|entry| match entry {
+            DnsEntry::Node(node) => Some(&node.enr),
+            _ => None,
+        })
+    }
+
+    /// Returns the content of the TXT records of the tree published at the domain, by their
+    /// names.
+    pub fn txt_records(&self, domain: &str) -> BTreeMap<String, String> {
+        let domain = domain.trim_end_matches('.');
+        std::iter::once((domain.to_string(), self.root.to_string()))
+            .chain(
+                self.entries
+                    .iter()
+                    .map(|(hash, entry)| (format!("{hash}.{domain}"), entry.to_string())),
+            )
+            .collect()
+    }
+
+    /// Returns the TXT records of the tree published at the domain, in the zone file format.
+    ///
+    /// Records longer than a single string of a TXT record are split into several strings.
+    pub fn to_zone_file(&self, domain: &str, ttl: u32) -> String {
+        let mut zone = format!(
+            "; EIP-1459 node list of {} nodes, sequence number {}\n",
+            self.nodes().count(),
+            self.root.sequence_number
+        );
+        for (name, content) in self.txt_records(domain) {
+            let _ = writeln!(zone, "{name}.\t{ttl}\tIN\tTXT\t{}", txt_strings(&content));
+        }
+        zone
+    }
+}
+
+/// Builds the subtree of the leaves, and returns its root.
+///
+/// All entries below the root are inserted into the entries of the tree.
+fn build_subtree(
+    entries: &mut BTreeMap<String, DnsEntry<SecretKey>>,
+    mut leaves: Vec<DnsEntry<SecretKey>>,
+) -> DnsEntry<SecretKey> {
+    if leaves.len() == 1 {
+        return leaves.pop().expect("single leaf")
+    }
+    if leaves.len() <= MAX_BRANCH_CHILDREN {
+        let children = leaves.into_iter().map(|leaf| insert_entry(entries, leaf)).collect();
+        return DnsEntry::Branch(BranchEntry { children })
+    }
+
+    let mut subtrees = Vec::with_capacity(leaves.len().div_ceil(MAX_BRANCH_CHILDREN));
+    while !leaves.is_empty() {
+        let rest = leaves.split_off(leaves.len().min(MAX_BRANCH_CHILDREN));
+        subtrees.push(build_subtree(entries, std::mem::replace(&mut leaves, rest)));
+    }
+    build_subtree(entries, subtrees)
+}
+
+/// Inserts the entry, and returns its hash.
+fn insert_entry(
+    entries: &mut BTreeMap<String, DnsEntry<SecretKey>>,
+    entry: DnsEntry<SecretKey>,
+) -> String {
+    let hash = entry_hash(&entry);
+    entries.insert(hash.clone(), entry);
+    hash
+}
+
+/// Returns the content as quoted strings of a TXT record.
+fn txt_strings(content: &str) -> String {
+    let mut strings = Vec::new();
+    let mut rest = content;
+    while !rest.is_empty() {
+        let mut end = rest.len().min(MAX_TXT_STRING_LEN);
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        let (string, remaining) = rest.split_at(end);
+        strings.push(format!("\"{string}\""));
+        rest = remaining;
+    }
+    strings.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DnsDiscoveryConfig, DnsDiscoveryEvent, DnsDiscoveryService, MapResolver};
+    use enr::EnrKey;
+    use secp256k1::rand::thread_rng;
+    use std::{collections::HashSet, net::Ipv4Addr, num::NonZeroUsize, sync::Arc};
+    use tokio_stream::StreamExt;
+
+    fn enrs(n: u8) -> Vec<Enr<SecretKey>> {
+        (0..n)
+            .map(|i| {
+                Enr::builder()
+                    .ip4(Ipv4Addr::new(10, 0, 0, i))
+                    .tcp4(30303)
+                    .udp4(30303)
+                    .build(&SecretKey::new(&mut thread_rng()))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn build_single_node_tree() {
+        let enrs = enrs(1);
+        let tree = DnsTree::new(1, enrs.clone(), []);
+
+        // The single record is the root of the subtree, and there are no links
+        assert_eq!(tree.entries().len(), 2);
+        let DnsEntry::Node(node) = &tree.entries()[&tree.root().enr_root] else { unreachable!() };
+        assert_eq!(node.enr, enrs[0]);
+        let DnsEntry::Branch(links) = &tree.entries()[&tree.root().link_root] else {
+            unreachable!()
+        };
+        assert!(links.children.is_empty());
+    }
+
+    #[test]
+    fn build_nested_tree() {
+        let enrs = enrs(30);
+        let tree = DnsTree::new(1, enrs.clone(), []);
+
+        let nodes = tree.nodes().map(Enr::to_base64).collect::<HashSet<_>>();
+        assert_eq!(nodes, enrs.iter().map(Enr::to_base64).collect());
+        for (hash, entry) in tree.entries() {
+            assert_eq!(*hash, entry_hash(entry));
+            if let DnsEntry::Branch(branch) = entry {
+                assert!(branch.children.len() <= MAX_BRANCH_CHILDREN);
+                assert!(branch.children.iter().all(|child| tree.entries().contains_key(child)));
+            }
+        }
+    }
+
+    #[test]
+    fn deduplicate_node_records() {
+        let key = SecretKey::new(&mut thread_rng());
+        let mut enr = Enr::builder().ip4(Ipv4Addr::LOCALHOST).tcp4(30303).build(&key).unwrap();
+        let old = enr.clone();
+        enr.set_tcp4(30304, &key).unwrap();
+
+        let tree = DnsTree::new(1, [enr.clone(), old], []);
+        assert_eq!(tree.nodes().collect::<Vec<_>>(), vec![&enr]);
+    }
+
+    #[test]
+    fn zone_file_round_trip() {
+        let key = SecretKey::new(&mut thread_rng());
+        let link: LinkEntry = format!(
+            "enrtree://{}@other.example.org",
+            BASE32_NOPAD.encode(&key.public().serialize())
+        )
+        .parse()
+        .unwrap();
+        // A record that's longer than a string of a TXT record
+        let long_enr = Enr::builder()
+            .ip4(Ipv4Addr::LOCALHOST)
+            .tcp4(30303)
+            .add_value(b"data", &Bytes::from(vec![0xab; 150]))
+            .build(&SecretKey::new(&mut thread_rng()))
+            .unwrap();
+        assert!(long_enr.to_base64().len() > MAX_TXT_STRING_LEN);
+        let mut tree = DnsTree::new(7, enrs(20).into_iter().chain([long_enr]), [link]);
+        tree.sign(&key);
+
+        let zone = tree.to_zone_file("nodes.example.org", 300);
+        let mut records = BTreeMap::new();
+        for line in zone.lines().filter(|line| !line.starts_with(';')) {
+            let mut fields = line.splitn(5, '\t');
+            let name = fields.next().unwrap().trim_end_matches('.').to_string();
+            let strings = fields.nth(3).unwrap().split('"').skip(1).step_by(2).collect::<Vec<_>>();
+            assert!(strings.iter().all(|string| string.len() <= MAX_TXT_STRING_LEN));
+            records.insert(name, strings.concat());
+        }
+        assert_eq!(records, tree.txt_records("nodes.example.org"));
+
+        let root: TreeRootEntry = records["nodes.example.org"].parse().unwrap();
+        assert_eq!(&root, tree.root());
+        assert!(root.verify::<SecretKey>(&key.public()));
+        for (hash, entry) in tree.entries() {
+            let parsed: DnsEntry<SecretKey> =
+                records[&format!("{hash}.nodes.example.org")].parse().unwrap();
+            assert_eq!(parsed.to_string(), entry.to_string());
+            assert_eq!(entry_hash(&parsed), *hash);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sync_published_tree() {
+        let key = SecretKey::new(&mut thread_rng());
+        let enrs = enrs(30);
+        let mut tree = DnsTree::new(1, enrs.clone(), []);
+        tree.sign(&key);
+
+        let resolver = MapResolver::default();
+        for (name, content) in tree.txt_records("nodes.example.org") {
+            resolver.insert(name, content);
+        }
+        let config = DnsDiscoveryConfig {
+            max_requests_per_sec: NonZeroUsize::new(100).unwrap(),
+            ..Default::default()
+        };
+        let mut service = DnsDiscoveryService::new(Arc::new(resolver), config);
+        service.sync_tree_with_link(LinkEntry {
+            domain: "nodes.example.org".to_string(),
+            pubkey: key.public(),
+        });
+
+        let mut discovered = HashSet::new();
+        while discovered.len() < enrs.len() {
+            let Some(DnsDiscoveryEvent::Enr(enr)) = service.next().await else { unreachable!() };
+            discovered.insert(enr.to_base64());
+        }
+        assert_eq!(discovered, enrs.iter().map(Enr::to_base64).collect());
+    }
+}
@@ -24,8 +24,9 @@ impl<P: ConnectionProvider> Resolver for hickory_resolver::Resolver<P> {
             }
             Ok(lookup) => {
                 let txt = lookup.into_iter().next()?;
-                let entry = txt.iter().next()?;
-                String::from_utf8(entry.to_vec()).ok()
+                // Long entries are split into several strings of the record
+                let entry = txt.iter().flat_map(|data| data.iter().copied()).collect();
+                String::from_utf8(entry).ok()
             }
         }
     }
@@ -21,10 +21,10 @@ use crate::error::{
     ParseDnsEntryError::{FieldNotFound, UnknownEntry},
     ParseEntryResult,
 };
-use alloy_primitives::{hex, Bytes};
+use alloy_primitives::{hex, keccak256, Bytes};
 use data_encoding::{BASE32_NOPAD, BASE64URL_NOPAD};
 use enr::{Enr, EnrKey, EnrKeyUnambiguous, EnrPublicKey, Error as EnrError};
-use secp256k1::SecretKey;
+use secp256k1::{Message, SecretKey, SECP256K1};
 #[cfg(feature = "serde")]
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 use std::{
@@ -141,6 +141,22 @@ impl TreeRootEntry {
         Ok(())
     }
 
+    /// Signs the content with the given key, with the recovery id appended to the 64 bytes of the
+    /// signature.
+    ///
+    /// This is the 65 byte signature other clients require in published trees.
+    pub fn sign_recoverable(&mut self, key: &SecretKey) {
+        let (recovery_id, signature) = SECP256K1
+            .sign_ecdsa_recoverable(
+                &Message::from_digest(keccak256(self.content().as_bytes()).0),
+                key,
+            )
+            .serialize_compact();
+        let mut bytes = signature.to_vec();
+        bytes.push(i32::from(recovery_id) as u8);
+        self.signature = bytes.into();
+    }
+
     /// Verify the signature of the record.
     #[must_use]
     pub fn verify<K: EnrKey>(&self, pubkey: &K::PublicKey) -> bool {
@@ -210,6 +226,11 @@ impl BranchEntry {
             Ok(hash.to_string())
         }
 
+        // The branch of an empty subtree, like the links of a tree without links
+        if input.trim().is_empty() {
+            return Ok(Self { children: Vec::new() })
+        }
+
         let children =
             input.trim().split(',').map(ensure_valid_hash).collect::<ParseEntryResult<Vec<_>>>()?;
         Ok(Self { children })
@@ -406,6 +427,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_empty_branch_entry() {
+        let s = "enrtree-branch:";
+        let entry: BranchEntry = s.parse().unwrap();
+        assert!(entry.children.is_empty());
+        assert_eq!(entry.to_string(), s);
+    }
+
+    #[test]
+    fn sign_recoverable_root_entry() {
+        let secret_key = SecretKey::new(&mut secp256k1::rand::thread_rng());
+        let s = "enrtree-root:v1 e=QFT4PBCRX4XQCV3VUYJ6BTCEPU l=JGUFMSAGI7KZYB3P7IZW4S5Y3A seq=3 sig=3FmXuVwpa8Y7OstZTx9PIb1mt8FrW7VpDOFv4AaGCsZ2EIHmhraWhe4NxYhQDlw5MjeFXYMbJjsPeKlHzmJREQE";
+        let mut root: TreeRootEntry = s.parse().unwrap();
+        root.sign_recoverable(&secret_key);
+
+        assert_eq!(root.signature.len(), 65);
+        assert!(root.verify::<SecretKey>(&secret_key.public()));
+        let parsed: TreeRootEntry = root.to_string().parse().unwrap();
+        assert_eq!(parsed, root);
+    }
+
     #[test]
     fn parse_invalid_branch_entry() {
         let s = "enrtree-branch:1,2";
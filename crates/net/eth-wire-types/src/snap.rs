@@ -51,8 +51,9 @@ pub struct GetAccountRangeMessage {
 }
 
 /// Account data in the response.
-#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
-#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+///
+/// The body is the RLP encoded account, which is embedded as is instead of as a byte string.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[add_arbitrary_tests(rlp)]
 pub struct AccountData {
     /// Hash of the account address (trie path)
@@ -61,6 +62,45 @@ pub struct AccountData {
     pub body: Bytes,
 }
 
+impl Encodable for AccountData {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.hash.length() + self.body.len() }
+            .encode(out);
+        self.hash.encode(out);
+        out.put_slice(&self.body);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.hash.length() + self.body.len();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for AccountData {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut payload = alloy_rlp::Header::decode_bytes(buf, true)?;
+        let hash = B256::decode(&mut payload)?;
+
+        // Make sure the body is a single RLP item
+        let mut body = payload;
+        let header = alloy_rlp::Header::decode(&mut body)?;
+        if body.len() != header.payload_length {
+            return Err(alloy_rlp::Error::UnexpectedLength)
+        }
+
+        Ok(Self { hash, body: Bytes::copy_from_slice(payload) })
+    }
+}
+
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> arbitrary::Arbitrary<'a> for AccountData {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // The body must be valid RLP to roundtrip
+        let body: Vec<Bytes> = u.arbitrary()?;
+        Ok(Self { hash: u.arbitrary()?, body: alloy_rlp::encode(body).into() })
+    }
+}
+
 /// Response containing a number of consecutive accounts and the Merkle proofs for the entire range.
 // http://github.com/ethereum/devp2p/blob/master/caps/snap.md#accountrange-0x01
 #[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
@@ -149,7 +189,11 @@ pub struct ByteCodesMessage {
 }
 
 /// Path in the trie for an account and its storage
-#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+///
+/// Encoded as a flat list of paths. Without storage paths, the account path is the compact encoded
+/// path of an account trie node. Otherwise, it's the full hash of the account whose storage trie
+/// nodes are requested at the compact encoded storage paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[add_arbitrary_tests(rlp)]
 pub struct TriePath {
@@ -159,6 +203,39 @@ pub struct TriePath {
     pub slot_paths: Vec<Bytes>,
 }
 
+impl TriePath {
+    fn payload_length(&self) -> usize {
+        self.account_path.length() + self.slot_paths.iter().map(Encodable::length).sum::<usize>()
+    }
+}
+
+impl Encodable for TriePath {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.account_path.encode(out);
+        for slot_path in &self.slot_paths {
+            slot_path.encode(out);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for TriePath {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut payload = alloy_rlp::Header::decode_bytes(buf, true)?;
+        let account_path = Bytes::decode(&mut payload)?;
+        let mut slot_paths = Vec::new();
+        while !payload.is_empty() {
+            slot_paths.push(Bytes::decode(&mut payload)?);
+        }
+        Ok(Self { account_path, slot_paths })
+    }
+}
+
 /// Request a number of state (either account or storage) Merkle trie nodes by path
 // https://github.com/ethereum/devp2p/blob/master/caps/snap.md#gettrienodes-0x06
 #[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
@@ -356,7 +433,7 @@ mod tests {
             request_id: 42,
             accounts: vec![AccountData {
                 hash: b256_from_u64(123),
-                body: Bytes::from(vec![1, 2, 3]),
+                body: alloy_rlp::encode(vec![Bytes::from(vec![1, 2, 3])]).into(),
             }],
             proof: vec![Bytes::from(vec![4, 5, 6])],
         }));
@@ -406,6 +483,34 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn test_account_data_raw_body() {
+        let body = alloy_rlp::encode(vec![Bytes::from(vec![1, 2, 3])]);
+        let data = AccountData { hash: b256_from_u64(1), body: body.clone().into() };
+
+        // The body is embedded in the list as is
+        let encoded = alloy_rlp::encode(&data);
+        assert!(encoded.ends_with(&body));
+        assert_eq!(encoded.len(), data.length());
+        assert_eq!(AccountData::decode(&mut &encoded[..]).unwrap(), data);
+    }
+
+    #[test]
+    fn test_trie_path_flat_encoding() {
+        let path = TriePath {
+            account_path: Bytes::from(vec![1, 2, 3]),
+            slot_paths: vec![Bytes::from(vec![4, 5]), Bytes::from(vec![6])],
+        };
+
+        // Paths are encoded as a single list of byte strings
+        let encoded = alloy_rlp::encode(&path);
+        let mut paths = vec![path.account_path.clone()];
+        paths.extend(path.slot_paths.iter().cloned());
+        assert_eq!(encoded, alloy_rlp::encode(paths));
+        assert_eq!(encoded.len(), path.length());
+        assert_eq!(TriePath::decode(&mut &encoded[..]).unwrap(), path);
+    }
+
     #[test]
     fn test_unknown_message_id() {
         // Create some random data
@@ -25,7 +25,7 @@ use test_utils::PeersHandleProvider;
 
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 pub use reth_network_p2p::{BlockClient, HeadersClient};
-pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+pub use reth_network_types::{BannedPeers, PeerKind, Reputation, ReputationChangeKind};
 
 pub use downloaders::BlockDownloaderProvider;
 pub use error::NetworkError;
@@ -39,7 +39,12 @@ use reth_eth_wire_types::{
 };
 use reth_network_p2p::sync::NetworkSyncUpdater;
 use reth_network_peers::NodeRecord;
-use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
+use std::{
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// The `PeerId` type.
 pub type PeerId = alloy_primitives::B512;
@@ -214,6 +219,43 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Bans the peer indefinitely or for the given duration and disconnects it if connected.
+    ///
+    /// Returns `true` if the peer wasn't banned yet, an existing ban is replaced.
+    fn ban_peer(
+        &self,
+        peer_id: PeerId,
+        duration: Option<Duration>,
+    ) -> impl Future<Output = Result<bool, NetworkError>> + Send;
+
+    /// Lifts the ban of the peer.
+    ///
+    /// Returns `true` if the peer was banned.
+    fn unban_peer(
+        &self,
+        peer_id: PeerId,
+    ) -> impl Future<Output = Result<bool, NetworkError>> + Send;
+
+    /// Bans the IP indefinitely or for the given duration and disconnects all peers connected
+    /// from it.
+    ///
+    /// Returns `true` if the IP wasn't banned yet, an existing ban is replaced.
+    ///
+    /// Note: non-global IPs are never banned.
+    fn ban_ip(
+        &self,
+        ip: IpAddr,
+        duration: Option<Duration>,
+    ) -> impl Future<Output = Result<bool, NetworkError>> + Send;
+
+    /// Lifts the ban of the IP.
+    ///
+    /// Returns `true` if the IP was banned.
+    fn unban_ip(&self, ip: IpAddr) -> impl Future<Output = Result<bool, NetworkError>> + Send;
+
+    /// Returns all banned peers and IPs.
+    fn banned_peers(&self) -> impl Future<Output = Result<BannedPeers, NetworkError>> + Send;
 }
 
 /// Info about an active peer session.
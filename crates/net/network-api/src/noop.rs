@@ -4,7 +4,10 @@
 //! generic over it.
 
 use core::{fmt, marker::PhantomData};
-use std::net::{IpAddr, SocketAddr};
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
 use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
@@ -13,7 +16,7 @@ use reth_eth_wire_types::{
 };
 use reth_network_p2p::{sync::NetworkSyncUpdater, NoopFullBlockClient};
 use reth_network_peers::NodeRecord;
-use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{BannedPeers, PeerKind, Reputation, ReputationChangeKind};
 use reth_tokio_util::{EventSender, EventStream};
 use tokio::sync::{mpsc, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -159,6 +162,30 @@ where
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    async fn ban_peer(
+        &self,
+        _peer_id: PeerId,
+        _duration: Option<Duration>,
+    ) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn unban_peer(&self, _peer_id: PeerId) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn ban_ip(&self, _ip: IpAddr, _duration: Option<Duration>) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn unban_ip(&self, _ip: IpAddr) -> Result<bool, NetworkError> {
+        Ok(false)
+    }
+
+    async fn banned_peers(&self) -> Result<BannedPeers, NetworkError> {
+        Ok(BannedPeers::default())
+    }
 }
 
 impl<Net> BlockDownloaderProvider for NoopNetwork<Net>
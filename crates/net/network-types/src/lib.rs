@@ -25,6 +25,9 @@ pub use backoff::BackoffKind;
 pub use peers::{
     addr::PeerAddr,
     kind::PeerKind,
+    persistence::{
+        persisted_peers_file, BannedPeers, PersistedPeers, DEFAULT_PERSIST_PEERS_INTERVAL,
+    },
     reputation::{
         is_banned_reputation, is_connection_failed_reputation, ReputationChangeOutcome,
         DEFAULT_REPUTATION,
@@ -1,17 +1,17 @@
 //! Configuration for peering.
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     io::{self, ErrorKind},
     path::Path,
     time::Duration,
 };
 
 use reth_net_banlist::BanList;
-use reth_network_peers::{NodeRecord, TrustedPeer};
+use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
 use tracing::info;
 
-use crate::{BackoffKind, ReputationChangeWeights};
+use crate::{BackoffKind, PersistedPeers, Reputation, ReputationChangeWeights};
 
 /// Maximum number of available slots for outbound sessions.
 pub const DEFAULT_MAX_COUNT_PEERS_OUTBOUND: u32 = 100;
@@ -153,6 +153,9 @@ pub struct PeersConfig {
     /// Restrictions on `PeerIds` and Ips.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub ban_list: BanList,
+    /// Reputations of peers restored from a previous run.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub reputations: HashMap<PeerId, Reputation>,
     /// Restrictions on connections.
     pub connection_info: ConnectionsConfig,
     /// How to weigh reputation changes.
@@ -175,6 +178,7 @@ impl Default for PeersConfig {
             connection_info: Default::default(),
             reputation_weights: Default::default(),
             ban_list: Default::default(),
+            reputations: Default::default(),
             // Ban peers for 12h
             ban_duration: Duration::from_secs(60 * 60 * 12),
             backoff_durations: Default::default(),
@@ -259,6 +263,20 @@ impl PeersConfig {
         self
     }
 
+    /// Reputations to restore for known peers.
+    pub fn with_reputations(mut self, reputations: HashMap<PeerId, Reputation>) -> Self {
+        self.reputations = reputations;
+        self
+    }
+
+    /// Restores the reputations and bans of a previous run.
+    ///
+    /// Bans are added to the configured [`BanList`], bans that have expired are ignored.
+    pub fn with_persisted_peers(mut self, persisted: PersistedPeers) -> Self {
+        persisted.banned.extend_ban_list(&mut self.ban_list);
+        self.with_reputations(persisted.reputations.into_iter().collect())
+    }
+
     /// Configures the max allowed backoff count.
     pub const fn with_max_backoff_count(mut self, max_backoff_count: u8) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -286,6 +304,9 @@ impl PeersConfig {
     }
 
     /// Read from file nodes available at launch. Ignored if None.
+    ///
+    /// This also restores the reputations and bans that were saved next to the peers file, see
+    /// [`persisted_peers_file`](crate::persisted_peers_file).
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
@@ -298,7 +319,45 @@ impl PeersConfig {
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
         let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        #[cfg(feature = "serde")]
+        let this = self.with_persisted_peers_from_file(file_path.as_ref());
+        #[cfg(not(feature = "serde"))]
+        let this = self;
+        Ok(this.with_basic_nodes(nodes))
+    }
+
+    /// Restores the reputations and bans that were saved next to the given peers file.
+    ///
+    /// A missing or malformed file is ignored.
+    #[cfg(feature = "serde")]
+    pub fn with_persisted_peers_from_file(self, peers_file: &Path) -> Self {
+        let file_path = crate::persisted_peers_file(peers_file);
+        let persisted = match std::fs::File::open(&file_path) {
+            Ok(file) => serde_json::from_reader::<_, PersistedPeers>(io::BufReader::new(file)),
+            Err(_) => return self,
+        };
+        match persisted {
+            Ok(persisted) => {
+                info!(
+                    target: "net::peers",
+                    file = %file_path.display(),
+                    reputations = persisted.reputations.len(),
+                    banned_peers = persisted.banned.peers.len(),
+                    banned_ips = persisted.banned.ips.len(),
+                    "Loading saved peer reputations and bans"
+                );
+                self.with_persisted_peers(persisted)
+            }
+            Err(err) => {
+                tracing::warn!(
+                    target: "net::peers",
+                    file = %file_path.display(),
+                    %err,
+                    "Failed to load saved peer reputations and bans"
+                );
+                self
+            }
+        }
     }
 
     /// Returns settings for testing
@@ -1,10 +1,14 @@
 pub mod addr;
 pub mod config;
 pub mod kind;
+pub mod persistence;
 pub mod reputation;
 pub mod state;
 
 pub use config::{ConnectionsConfig, PeersConfig};
+pub use persistence::{
+    persisted_peers_file, BannedPeers, PersistedPeers, DEFAULT_PERSIST_PEERS_INTERVAL,
+};
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
 
 use alloy_eip2124::ForkId;
@@ -0,0 +1,153 @@
+//! Peer state that is persisted across restarts.
+
+use std::{
+    collections::BTreeMap,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use reth_net_banlist::BanList;
+use reth_network_peers::PeerId;
+
+use crate::Reputation;
+
+/// How often the [`PersistedPeers`] of a running node are saved, so that they survive an unclean
+/// shutdown.
+pub const DEFAULT_PERSIST_PEERS_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Returns the path of the file that stores the [`PersistedPeers`] next to the given peers file.
+///
+/// For a peers file `known-peers.json` this is `known-peers.reputation.json`.
+pub fn persisted_peers_file(peers_file: &Path) -> PathBuf {
+    peers_file.with_extension("reputation.json")
+}
+
+/// Peers and ips that are banned, with the unix timestamp in seconds at which the ban expires.
+///
+/// A ban without an expiry is indefinite.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BannedPeers {
+    /// Banned peers.
+    pub peers: BTreeMap<PeerId, Option<u64>>,
+    /// Banned ip addresses.
+    pub ips: BTreeMap<IpAddr, Option<u64>>,
+}
+
+impl BannedPeers {
+    /// Collects all entries of the [`BanList`], converting their expiry to unix timestamps.
+    pub fn from_ban_list(ban_list: &BanList) -> Self {
+        let now = Instant::now();
+        let unix_now = unix_now();
+        let expiry = |until: Option<Instant>| {
+            until.map(|until| {
+                unix_now.saturating_add(until.saturating_duration_since(now).as_secs())
+            })
+        };
+        Self {
+            peers: ban_list.banned_peers().map(|(peer, until)| (peer, expiry(until))).collect(),
+            ips: ban_list.banned_ips().map(|(ip, until)| (ip, expiry(until))).collect(),
+        }
+    }
+
+    /// Adds all entries that have not expired yet to the [`BanList`].
+    ///
+    /// Entries expiring beyond the range of [`Instant`] are banned indefinitely.
+    pub fn extend_ban_list(&self, ban_list: &mut BanList) {
+        let now = Instant::now();
+        let unix_now = unix_now();
+        let until = |expiry: Option<u64>| match expiry {
+            None => Some(None),
+            Some(expiry) if expiry > unix_now => {
+                Some(now.checked_add(Duration::from_secs(expiry - unix_now)))
+            }
+            Some(_) => None,
+        };
+        for (peer, expiry) in &self.peers {
+            if let Some(until) = until(*expiry) {
+                ban_list.ban_peer_with(*peer, until);
+            }
+        }
+        for (ip, expiry) in &self.ips {
+            if let Some(until) = until(*expiry) {
+                ban_list.ban_ip_with(*ip, until);
+            }
+        }
+    }
+
+    /// Returns `true` if there are no banned peers or ips.
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty() && self.ips.is_empty()
+    }
+}
+
+/// Reputations and bans of peers that are saved alongside the known peers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PersistedPeers {
+    /// Reputations of peers that differ from the default reputation.
+    pub reputations: BTreeMap<PeerId, Reputation>,
+    /// Banned peers and ips.
+    pub banned: BannedPeers,
+}
+
+/// Returns the current unix timestamp in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ban_list_roundtrip() {
+        let peer = PeerId::with_last_byte(1);
+        let expired = PeerId::with_last_byte(2);
+        let ip = IpAddr::from([1, 1, 1, 1]);
+
+        let mut ban_list = BanList::default();
+        ban_list.ban_peer_until(peer, Instant::now() + Duration::from_secs(60 * 60));
+        ban_list.ban_ip(ip);
+
+        let mut banned = BannedPeers::from_ban_list(&ban_list);
+        assert_eq!(banned.ips.get(&ip), Some(&None));
+        assert!(banned.peers[&peer].unwrap() > unix_now());
+        banned.peers.insert(expired, Some(unix_now() - 1));
+
+        let mut restored = BanList::default();
+        banned.extend_ban_list(&mut restored);
+        assert!(restored.is_banned_peer(&peer));
+        assert!(restored.is_banned_ip(&ip));
+        assert!(!restored.is_banned_peer(&expired));
+    }
+
+    #[test]
+    fn ban_list_overflowing_expiry() {
+        let peer = PeerId::with_last_byte(1);
+        let ip = IpAddr::from([1, 1, 1, 1]);
+        let mut banned = BannedPeers::default();
+        banned.peers.insert(peer, Some(u64::MAX));
+        banned.ips.insert(ip, Some(u64::MAX));
+
+        let mut restored = BanList::default();
+        banned.extend_ban_list(&mut restored);
+        assert_eq!(BannedPeers::from_ban_list(&restored).peers.get(&peer), Some(&None));
+        assert_eq!(BannedPeers::from_ban_list(&restored).ips.get(&ip), Some(&None));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn persisted_peers_serde_roundtrip() {
+        let mut persisted = PersistedPeers::default();
+        persisted.reputations.insert(PeerId::with_last_byte(1), -1024);
+        persisted.banned.peers.insert(PeerId::with_last_byte(2), Some(1_700_000_000));
+        persisted.banned.ips.insert(IpAddr::from([1, 1, 1, 1]), None);
+
+        let json = serde_json::to_string(&persisted).unwrap();
+        assert_eq!(serde_json::from_str::<PersistedPeers>(&json).unwrap(), persisted);
+    }
+}
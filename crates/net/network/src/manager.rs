@@ -426,10 +426,18 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
 
     /// Collect the peers from the [`NetworkManager`] and write them to the given
     /// `persistent_peers_file`.
+    ///
+    /// The reputations and bans of the peers are written next to it, see
+    /// [`persisted_peers_file`](reth_network_types::persisted_peers_file).
     pub fn write_peers_to_file(&self, persistent_peers_file: &Path) -> Result<(), FsPathError> {
         let known_peers = self.all_peers().collect::<Vec<_>>();
         persistent_peers_file.parent().map(fs::create_dir_all).transpose()?;
         reth_fs_util::write_json_file(persistent_peers_file, &known_peers)?;
+        #[cfg(feature = "serde")]
+        reth_fs_util::write_json_file(
+            &reth_network_types::persisted_peers_file(persistent_peers_file),
+            &self.swarm.state().peers().persisted_peers(),
+        )?;
         Ok(())
     }
 
@@ -684,6 +692,24 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::GetReputationById(peer_id, tx) => {
                 let _ = tx.send(self.swarm.state_mut().peers().get_reputation(&peer_id));
             }
+            NetworkHandleMessage::BanPeer(peer_id, duration, tx) => {
+                let _ = tx.send(self.swarm.state_mut().peers_mut().ban_peer_for(peer_id, duration));
+            }
+            NetworkHandleMessage::UnbanPeer(peer_id, tx) => {
+                let _ = tx.send(self.swarm.state_mut().peers_mut().lift_peer_ban(peer_id));
+            }
+            NetworkHandleMessage::BanIp(ip, duration, tx) => {
+                let _ = tx.send(self.swarm.state_mut().peers_mut().ban_ip_for(ip, duration));
+            }
+            NetworkHandleMessage::UnbanIp(ip, tx) => {
+                let _ = tx.send(self.swarm.state_mut().peers_mut().lift_ip_ban(ip));
+            }
+            NetworkHandleMessage::GetBannedPeers(tx) => {
+                let _ = tx.send(self.swarm.state().peers().banned_peers());
+            }
+            NetworkHandleMessage::GetPersistedPeers(tx) => {
+                let _ = tx.send(self.swarm.state().peers().persisted_peers());
+            }
             NetworkHandleMessage::FetchClient(tx) => {
                 let _ = tx.send(self.fetch_client());
             }
@@ -22,15 +22,18 @@ use reth_network_api::{
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::{PeerAddr, PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{
+    BannedPeers, PeerAddr, PeerKind, PersistedPeers, Reputation, ReputationChangeKind,
+};
 use reth_tokio_util::{EventSender, EventStream};
 use secp256k1::SecretKey;
 use std::{
-    net::SocketAddr,
+    net::{IpAddr, SocketAddr},
     sync::{
         atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
 };
 use tokio::sync::{
     mpsc::{self, UnboundedSender},
@@ -152,6 +155,13 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         rx.await.unwrap()
     }
 
+    /// Send message to get the reputations and bans that should be persisted across restarts.
+    pub async fn persisted_peers(&self) -> Result<PersistedPeers, oneshot::error::RecvError> {
+        let (tx, rx) = oneshot::channel();
+        self.send_message(NetworkHandleMessage::GetPersistedPeers(tx));
+        rx.await
+    }
+
     /// Send message to gracefully shutdown node.
     ///
     /// This will disconnect all active and pending sessions and prevent
@@ -356,6 +366,46 @@ impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    /// Sends a message to the [`NetworkManager`](crate::NetworkManager) to ban the given peer.
+    async fn ban_peer(
+        &self,
+        peer_id: PeerId,
+        duration: Option<Duration>,
+    ) -> Result<bool, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::BanPeer(peer_id, duration, tx));
+        Ok(rx.await?)
+    }
+
+    /// Sends a message to the [`NetworkManager`](crate::NetworkManager) to lift the ban of the
+    /// given peer.
+    async fn unban_peer(&self, peer_id: PeerId) -> Result<bool, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::UnbanPeer(peer_id, tx));
+        Ok(rx.await?)
+    }
+
+    /// Sends a message to the [`NetworkManager`](crate::NetworkManager) to ban the given IP.
+    async fn ban_ip(&self, ip: IpAddr, duration: Option<Duration>) -> Result<bool, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::BanIp(ip, duration, tx));
+        Ok(rx.await?)
+    }
+
+    /// Sends a message to the [`NetworkManager`](crate::NetworkManager) to lift the ban of the
+    /// given IP.
+    async fn unban_ip(&self, ip: IpAddr) -> Result<bool, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::UnbanIp(ip, tx));
+        Ok(rx.await?)
+    }
+
+    async fn banned_peers(&self) -> Result<BannedPeers, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetBannedPeers(tx));
+        Ok(rx.await?)
+    }
 }
 
 impl<N: NetworkPrimitives> PeersHandleProvider for NetworkHandle<N> {
@@ -534,6 +584,20 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     GetPeerInfosByPeerKind(PeerKind, oneshot::Sender<Vec<PeerInfo>>),
     /// Gets the reputation for a specific peer via a oneshot sender.
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
+    /// Bans a peer indefinitely or for the given duration, and returns whether it wasn't banned
+    /// yet via a oneshot sender.
+    BanPeer(PeerId, Option<Duration>, oneshot::Sender<bool>),
+    /// Lifts the ban of a peer, and returns whether it was banned via a oneshot sender.
+    UnbanPeer(PeerId, oneshot::Sender<bool>),
+    /// Bans an IP indefinitely or for the given duration, and returns whether it wasn't banned
+    /// yet via a oneshot sender.
+    BanIp(IpAddr, Option<Duration>, oneshot::Sender<bool>),
+    /// Lifts the ban of an IP, and returns whether it was banned via a oneshot sender.
+    UnbanIp(IpAddr, oneshot::Sender<bool>),
+    /// Gets all banned peers and IPs via a oneshot sender.
+    GetBannedPeers(oneshot::Sender<BannedPeers>),
+    /// Gets the reputations and bans to persist via a oneshot sender.
+    GetPersistedPeers(oneshot::Sender<PersistedPeers>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
     GetTransactionsHandle(oneshot::Sender<Option<TransactionsHandle<N>>>),
     /// Initiates a graceful shutdown of the network via a oneshot sender.
@@ -14,13 +14,13 @@ use reth_net_banlist::BanList;
 use reth_network_api::test_utils::{PeerCommand, PeersHandle};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
-    is_connection_failed_reputation,
+    is_banned_reputation, is_connection_failed_reputation,
     peers::{
         config::PeerBackoffDurations,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
-    ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    BannedPeers, ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
+    PersistedPeers, ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
@@ -108,6 +108,7 @@ impl PeersManager {
             basic_nodes,
             max_backoff_count,
             incoming_ip_throttle_duration,
+            reputations,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -138,6 +139,17 @@ impl PeersManager {
             });
         }
 
+        // restore the reputations of a previous run, a banned reputation is only restored if the
+        // ban itself is still active, otherwise the peer would never be unbanned
+        for (peer_id, reputation) in reputations {
+            if is_banned_reputation(reputation) && !ban_list.is_banned_peer(&peer_id) {
+                continue
+            }
+            if let Some(peer) = peers.get_mut(&peer_id) {
+                peer.reputation = reputation;
+            }
+        }
+
         Self {
             peers,
             trusted_peer_ids,
@@ -422,6 +434,106 @@ impl PeersManager {
         self.queued_actions.push_back(PeerAction::UnBanPeer { peer_id });
     }
 
+    /// Bans the peer indefinitely or for the given duration and disconnects it if connected.
+    ///
+    /// A duration that exceeds the range of [`Instant`](std::time::Instant) bans the peer
+    /// indefinitely.
+    ///
+    /// Returns `true` if the peer wasn't banned yet, an existing ban is replaced.
+    ///
+    /// Note: This is invoked on demand via an external command received by the manager
+    pub(crate) fn ban_peer_for(&mut self, peer_id: PeerId, duration: Option<Duration>) -> bool {
+        let newly_banned = !self.ban_list.is_banned_peer(&peer_id);
+        let until = duration.and_then(|duration| std::time::Instant::now().checked_add(duration));
+        self.ban_list.ban_peer_with(peer_id, until);
+        self.disconnect_banned(|id, _| *id == peer_id);
+        self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
+        newly_banned
+    }
+
+    /// Lifts the ban of the peer and resets a banned reputation.
+    ///
+    /// Returns `true` if the peer was banned.
+    ///
+    /// Note: This is invoked on demand via an external command received by the manager
+    pub(crate) fn lift_peer_ban(&mut self, peer_id: PeerId) -> bool {
+        let mut was_banned = self.ban_list.is_banned_peer(&peer_id);
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            if peer.is_banned() {
+                peer.unban();
+                was_banned = true;
+            }
+        }
+        if was_banned {
+            self.unban_peer(peer_id);
+        }
+        was_banned
+    }
+
+    /// Bans the IP indefinitely or for the given duration and disconnects all peers connected
+    /// from that IP.
+    ///
+    /// A duration that exceeds the range of [`Instant`](std::time::Instant) bans the IP
+    /// indefinitely. This does not ban non-global IPs.
+    ///
+    /// Returns `true` if the IP wasn't banned yet, an existing ban is replaced.
+    ///
+    /// Note: This is invoked on demand via an external command received by the manager
+    pub(crate) fn ban_ip_for(&mut self, ip: IpAddr, duration: Option<Duration>) -> bool {
+        let was_banned = self.ban_list.is_banned_ip(&ip);
+        let until = duration.and_then(|duration| std::time::Instant::now().checked_add(duration));
+        self.ban_list.ban_ip_with(ip, until);
+        if !self.ban_list.is_banned_ip(&ip) {
+            return false
+        }
+        self.disconnect_banned(|_, peer| peer.addr.tcp().ip() == ip);
+        !was_banned
+    }
+
+    /// Lifts the ban of the IP.
+    ///
+    /// Returns `true` if the IP was banned.
+    ///
+    /// Note: This is invoked on demand via an external command received by the manager
+    pub(crate) fn lift_ip_ban(&mut self, ip: IpAddr) -> bool {
+        let was_banned = self.ban_list.is_banned_ip(&ip);
+        self.ban_list.unban_ip(&ip);
+        was_banned
+    }
+
+    /// Disconnects all connected peers that match the filter.
+    fn disconnect_banned(&mut self, mut filter: impl FnMut(&PeerId, &Peer) -> bool) {
+        for (peer_id, peer) in &mut self.peers {
+            if peer.state.is_connected() && filter(peer_id, peer) {
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id: *peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                });
+            }
+        }
+    }
+
+    /// Returns all banned peers and ips.
+    pub(crate) fn banned_peers(&self) -> BannedPeers {
+        BannedPeers::from_ban_list(&self.ban_list)
+    }
+
+    /// Returns the reputations and bans that should be persisted across restarts.
+    ///
+    /// Only reputations that differ from the [`DEFAULT_REPUTATION`] are included.
+    pub(crate) fn persisted_peers(&self) -> PersistedPeers {
+        PersistedPeers {
+            reputations: self
+                .peers
+                .iter()
+                .filter(|(_, peer)| peer.reputation != DEFAULT_REPUTATION)
+                .map(|(peer_id, peer)| (*peer_id, peer.reputation))
+                .collect(),
+            banned: self.banned_peers(),
+        }
+    }
+
     /// Tick function to update reputation of all connected peers.
     /// Peers are rewarded with reputation increases for the time they are connected since the last
     /// tick. This is to prevent peers from being disconnected eventually due to slashed
@@ -885,9 +997,12 @@ impl PeersManager {
     ///
     /// Returns `None` if no peer is available.
     fn best_unconnected(&mut self) -> Option<(PeerId, &mut Peer)> {
-        let mut unconnected = self.peers.iter_mut().filter(|(_, peer)| {
+        // peers banned only in the ban list, e.g. via `admin_banPeer` or a ban restored from a
+        // previous run, keep their reputation and must not be dialed until the ban is lifted
+        let mut unconnected = self.peers.iter_mut().filter(|(peer_id, peer)| {
             !peer.is_backed_off() &&
                 !peer.is_banned() &&
+                !self.ban_list.is_banned(peer_id, &peer.addr.tcp().ip()) &&
                 peer.state.is_unconnected() &&
                 (!self.trusted_nodes_only || peer.is_trusted())
         });
@@ -1211,11 +1326,13 @@ mod tests {
     };
     use reth_net_banlist::BanList;
     use reth_network_api::Direction;
-    use reth_network_peers::{PeerId, TrustedPeer};
+    use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, ReputationChangeKind,
+        peers::reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION},
+        BackoffKind, Peer, ReputationChangeKind,
     };
     use std::{
+        collections::HashSet,
         future::{poll_fn, Future},
         io,
         net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -1366,6 +1483,129 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_ban_peer_for() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_and_connect(peer, PeerAddr::from_tcp(socket_addr), None);
+        assert!(matches!(event!(peers), PeerAction::Connect { .. }));
+        peers.on_active_outgoing_established(peer);
+
+        assert!(peers.ban_peer_for(peer, Some(Duration::from_secs(60))));
+
+        match event!(peers) {
+            PeerAction::Disconnect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        match event!(peers) {
+            PeerAction::BanPeer { peer_id } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        peers.on_active_session_gracefully_closed(peer);
+        assert!(peers.best_unconnected().is_none());
+        assert!(peers.banned_peers().peers.contains_key(&peer));
+
+        // banning again only replaces the ban
+        assert!(!peers.ban_peer_for(peer, None));
+        assert_eq!(peers.banned_peers().peers.get(&peer), Some(&None));
+        assert!(matches!(event!(peers), PeerAction::BanPeer { .. }));
+
+        assert!(peers.lift_peer_ban(peer));
+
+        match event!(peers) {
+            PeerAction::UnBanPeer { peer_id } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        assert!(peers.banned_peers().is_empty());
+        assert!(!peers.lift_peer_ban(peer));
+        assert_eq!(peers.best_unconnected().unwrap().0, peer);
+    }
+
+    #[tokio::test]
+    async fn test_ban_ip_for() {
+        let peer = PeerId::random();
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+        let socket_addr = SocketAddr::new(ip, 8008);
+        let mut peers = PeersManager::default();
+        peers.add_and_connect(peer, PeerAddr::from_tcp(socket_addr), None);
+        assert!(matches!(event!(peers), PeerAction::Connect { .. }));
+        peers.on_active_outgoing_established(peer);
+
+        assert!(peers.ban_ip_for(ip, None));
+        assert!(!peers.ban_ip_for(IpAddr::V4(Ipv4Addr::LOCALHOST), None));
+
+        match event!(peers) {
+            PeerAction::Disconnect { peer_id, .. } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(peers.banned_peers().ips.get(&ip), Some(&None));
+        assert_eq!(
+            peers.on_incoming_pending_session(ip).unwrap_err(),
+            InboundConnectionError::IpBanned
+        );
+
+        assert!(!peers.ban_ip_for(ip, None));
+        assert!(peers.lift_ip_ban(ip));
+        assert!(peers.banned_peers().is_empty());
+        assert!(!peers.lift_ip_ban(ip));
+    }
+
+    #[tokio::test]
+    async fn test_ban_for_overflowing_duration() {
+        let peer = PeerId::random();
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+        let mut peers = PeersManager::default();
+
+        // durations beyond the range of `Instant` ban indefinitely
+        assert!(peers.ban_peer_for(peer, Some(Duration::from_secs(u64::MAX))));
+        assert!(peers.ban_ip_for(ip, Some(Duration::MAX)));
+        assert_eq!(peers.banned_peers().peers.get(&peer), Some(&None));
+        assert_eq!(peers.banned_peers().ips.get(&ip), Some(&None));
+    }
+
+    #[tokio::test]
+    async fn test_restore_persisted_peers() {
+        let peer = PeerId::random();
+        let banned = PeerId::random();
+        let ban_expired = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let basic_nodes = [peer, banned, ban_expired]
+            .into_iter()
+            .map(|id| NodeRecord::new(socket_addr, id))
+            .collect::<HashSet<_>>();
+
+        let mut peers =
+            PeersManager::new(PeersConfig::test().with_basic_nodes(basic_nodes.clone()));
+        peers.peers.get_mut(&peer).unwrap().reputation = -1000;
+        peers.peers.get_mut(&banned).unwrap().reputation = BANNED_REPUTATION - 1;
+        peers.peers.get_mut(&ban_expired).unwrap().reputation = BANNED_REPUTATION - 1;
+        peers.ban_peer_for(banned, Some(Duration::from_secs(60 * 60)));
+
+        let persisted = peers.persisted_peers();
+        assert_eq!(persisted.reputations.len(), 3);
+        assert_eq!(persisted.banned.peers.len(), 1);
+
+        let restored = PeersManager::new(
+            PeersConfig::test().with_basic_nodes(basic_nodes).with_persisted_peers(persisted),
+        );
+        assert_eq!(restored.get_reputation(&peer), Some(-1000));
+        assert_eq!(restored.get_reputation(&banned), Some(BANNED_REPUTATION - 1));
+        assert!(restored.ban_list.is_banned_peer(&banned));
+        // the ban was not persisted, so the banned reputation must not be restored
+        assert_eq!(restored.get_reputation(&ban_expired), Some(DEFAULT_REPUTATION));
+        assert_eq!(restored.persisted_peers().reputations.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_backoff_on_busy() {
         let peer = PeerId::random();
@@ -2164,6 +2404,14 @@ mod tests {
         // await for the ban to expire
         tokio::time::sleep(peers.backoff_durations.medium).await;
 
+        // the peer is only dialed again once the expired ban is lifted
+        match event!(peers) {
+            PeerAction::UnBanPeer { peer_id } => {
+                assert_eq!(peer_id, peer);
+            }
+            err => unreachable!("{err:?}"),
+        }
+
         match event!(peers) {
             PeerAction::Connect { peer_id, remote_addr } => {
                 assert_eq!(peer_id, peer);
@@ -0,0 +1,235 @@
+//! Client sending snap requests to the peers of the snap connections.
+
+use parking_lot::Mutex;
+use reth_eth_wire::{
+    GetAccountRangeMessage, GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage,
+    SnapProtocolMessage,
+};
+use reth_network_api::PeerId;
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    priority::Priority,
+    snap::client::{SnapClient, SnapResponse},
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Sleep,
+};
+use tracing::debug;
+
+/// The default timeout of a snap request.
+pub const SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A request for a peer, with the channel to send its response to.
+#[derive(Debug)]
+pub(crate) struct OutgoingSnapRequest {
+    /// The request, whose request id is assigned by the connection.
+    pub(crate) message: SnapProtocolMessage,
+    /// Sender for the response.
+    pub(crate) response: oneshot::Sender<SnapResponse>,
+}
+
+/// A peer with a snap connection.
+#[derive(Debug)]
+struct SnapPeer {
+    /// Sender for requests to the connection.
+    requests: mpsc::UnboundedSender<OutgoingSnapRequest>,
+    /// Number of requests waiting for a response.
+    inflight: Arc<AtomicUsize>,
+    /// Number of requests sent, to spread requests over peers that are equally busy.
+    sent: u64,
+}
+
+/// The peers with a snap connection, shared between the connections and the clients.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SnapPeers(Arc<Mutex<HashMap<PeerId, SnapPeer>>>);
+
+impl SnapPeers {
+    /// Registers the connection with the peer, and returns the receiver for its requests.
+    pub(crate) fn register(&self, peer_id: PeerId) -> mpsc::UnboundedReceiver<OutgoingSnapRequest> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.0
+            .lock()
+            .insert(peer_id, SnapPeer { requests: tx, inflight: Default::default(), sent: 0 });
+        rx
+    }
+
+    /// Removes the peer if its connection is closed.
+    pub(crate) fn remove_closed(&self, peer_id: &PeerId) {
+        let mut peers = self.0.lock();
+        if peers.get(peer_id).is_some_and(|peer| peer.requests.is_closed()) {
+            peers.remove(peer_id);
+        }
+    }
+
+    /// Removes the peer, so that no more requests are sent to it.
+    fn remove(&self, peer_id: &PeerId) {
+        self.0.lock().remove(peer_id);
+    }
+
+    /// Returns the number of peers.
+    fn len(&self) -> usize {
+        self.0.lock().len()
+    }
+
+    /// Sends the request to the peer with the fewest requests in flight.
+    ///
+    /// Among equally busy peers, the one that received the fewest requests is chosen.
+    fn send(&self, message: SnapProtocolMessage) -> Result<InflightRequest, RequestError> {
+        let mut peers = self.0.lock();
+        let (peer_id, peer) = peers
+            .iter_mut()
+            .filter(|(_, peer)| !peer.requests.is_closed())
+            .min_by_key(|(_, peer)| (peer.inflight.load(Ordering::Relaxed), peer.sent))
+            .ok_or(RequestError::ConnectionDropped)?;
+
+        let (tx, rx) = oneshot::channel();
+        peer.requests.send(OutgoingSnapRequest { message, response: tx })?;
+        peer.inflight.fetch_add(1, Ordering::Relaxed);
+        peer.sent += 1;
+        Ok(InflightRequest { peer_id: *peer_id, response: rx, inflight: peer.inflight.clone() })
+    }
+}
+
+/// A request waiting for the response of a peer.
+#[derive(Debug)]
+struct InflightRequest {
+    peer_id: PeerId,
+    response: oneshot::Receiver<SnapResponse>,
+    /// Counter of the requests in flight of the peer, decremented on drop.
+    inflight: Arc<AtomicUsize>,
+}
+
+impl Drop for InflightRequest {
+    fn drop(&mut self) {
+        self.inflight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Future resolving to the response of a snap request, or an error if the peer doesn't respond
+/// in time.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct SnapResponseFuture {
+    /// The request, or the error that prevented sending it.
+    request: Result<InflightRequest, Option<RequestError>>,
+    timeout: Duration,
+    /// The deadline for the response, started on the first poll.
+    deadline: Option<Pin<Box<Sleep>>>,
+}
+
+impl Future for SnapResponseFuture {
+    type Output = PeerRequestResult<SnapResponse>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let request = match &mut this.request {
+            Ok(request) => request,
+            Err(err) => return Poll::Ready(Err(err.take().expect("polled after completion"))),
+        };
+
+        if let Poll::Ready(response) = Pin::new(&mut request.response).poll(cx) {
+            // The connection was closed before the response arrived
+            let response = response.map_err(|_| RequestError::ConnectionDropped)?;
+            return Poll::Ready(Ok((request.peer_id, response).into()))
+        }
+
+        let timeout = this.timeout;
+        let deadline = this.deadline.get_or_insert_with(|| Box::pin(tokio::time::sleep(timeout)));
+        if deadline.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Err(RequestError::Timeout))
+        }
+        Poll::Pending
+    }
+}
+
+/// Client sending snap requests to the connected peers that support the snap protocol.
+///
+/// Requests are sent to the peer with the fewest requests in flight. A peer that's reported for a
+/// bad message doesn't receive any more requests until it reconnects.
+#[derive(Debug, Clone)]
+pub struct SnapFetchClient {
+    peers: SnapPeers,
+    timeout: Duration,
+}
+
+impl SnapFetchClient {
+    /// Creates a new client for the peers.
+    pub(crate) const fn new(peers: SnapPeers) -> Self {
+        Self { peers, timeout: SNAP_REQUEST_TIMEOUT }
+    }
+
+    /// Sets the timeout of requests.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sends the request to a peer, and returns the future for its response.
+    fn request(&self, message: SnapProtocolMessage) -> SnapResponseFuture {
+        SnapResponseFuture {
+            request: self.peers.send(message).map_err(Some),
+            timeout: self.timeout,
+            deadline: None,
+        }
+    }
+}
+
+impl DownloadClient for SnapFetchClient {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        debug!(target: "net::snap", %peer_id, "Removing snap peer after bad message");
+        self.peers.remove(&peer_id);
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.peers.len()
+    }
+}
+
+impl SnapClient for SnapFetchClient {
+    type Output = SnapResponseFuture;
+
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRangeMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.request(SnapProtocolMessage::GetAccountRange(request))
+    }
+
+    fn get_storage_ranges_with_priority(
+        &self,
+        request: GetStorageRangesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.request(SnapProtocolMessage::GetStorageRanges(request))
+    }
+
+    fn get_byte_codes_with_priority(
+        &self,
+        request: GetByteCodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.request(SnapProtocolMessage::GetByteCodes(request))
+    }
+
+    fn get_trie_nodes_with_priority(
+        &self,
+        request: GetTrieNodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.request(SnapProtocolMessage::GetTrieNodes(request))
+    }
+}
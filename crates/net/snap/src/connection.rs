@@ -0,0 +1,370 @@
+//! `RLPx` sub-protocol handler for snap connections.
+
+use crate::{
+    client::{OutgoingSnapRequest, SnapPeers},
+    server::IncomingSnapRequest,
+    SnapFetchClient,
+};
+use alloy_primitives::bytes::BytesMut;
+use futures::{future::BoxFuture, stream::FuturesOrdered, FutureExt, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+    AccountRangeMessage, ByteCodesMessage, Capability, SnapMessageId, SnapProtocolMessage,
+    StorageRangesMessage, TrieNodesMessage,
+};
+use reth_network::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use reth_network_api::{Direction, PeerId};
+use reth_network_p2p::snap::client::SnapResponse;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::trace;
+
+/// Maximum number of requests of a peer that are served concurrently.
+///
+/// Further requests are dropped until a response is sent, so the peer times them out.
+pub const MAX_CONCURRENT_REQUESTS: usize = 16;
+
+/// Capacity of the channel for the requests of all snap connections.
+pub const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
+/// Returns the snap/1 protocol.
+pub const fn snap_protocol() -> Protocol {
+    Protocol::new(Capability::new_static("snap", 1), SnapMessageId::TrieNodes as u8 + 1)
+}
+
+/// The protocol handler for snap connections.
+///
+/// Requests of peers are delegated to the [`SnapRequestHandler`](crate::SnapRequestHandler), and
+/// requests for peers are sent by the [`SnapFetchClient`] of the handler.
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// Sender for the requests of all snap connections, if they're served.
+    requests: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// The peers that requests can be sent to.
+    peers: SnapPeers,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new protocol handler that sends incoming requests to the given channel.
+    ///
+    /// If no channel is given, requests of peers are answered with empty responses.
+    pub fn new(requests: Option<mpsc::Sender<IncomingSnapRequest>>) -> Self {
+        Self { requests, peers: Default::default() }
+    }
+
+    /// Returns a client that sends requests to the peers of the snap connections.
+    pub fn client(&self) -> SnapFetchClient {
+        SnapFetchClient::new(self.peers.clone())
+    }
+
+    fn connection_handler(&self) -> SnapConnectionHandler {
+        SnapConnectionHandler { requests: self.requests.clone(), peers: self.peers.clone() }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+}
+
+/// The connection handler for a snap connection.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    requests: Option<mpsc::Sender<IncomingSnapRequest>>,
+    peers: SnapPeers,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        snap_protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        // Snap is a satellite protocol of eth, the session is still useful without it
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let outgoing = self.peers.register(peer_id);
+        SnapConnection {
+            conn,
+            peer_id,
+            requests: self.requests,
+            responses: FuturesOrdered::new(),
+            peers: self.peers,
+            outgoing: Some(outgoing),
+            pending: HashMap::default(),
+            next_request_id: 0,
+        }
+    }
+}
+
+/// A snap connection with a peer, which serves the requests of the peer and sends the requests
+/// of the [`SnapFetchClient`] to it.
+///
+/// Responses are sent in the order of the requests.
+#[must_use = "Connection does nothing unless polled."]
+pub struct SnapConnection {
+    conn: ProtocolConnection,
+    peer_id: PeerId,
+    requests: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// Responses to the requests that are being served.
+    responses: FuturesOrdered<BoxFuture<'static, Option<SnapProtocolMessage>>>,
+    /// The peers of all snap connections, that this connection is removed from on drop.
+    peers: SnapPeers,
+    /// Requests for the peer, or `None` once the channel is closed.
+    outgoing: Option<mpsc::UnboundedReceiver<OutgoingSnapRequest>>,
+    /// Senders for the responses of the peer, by request id.
+    pending: HashMap<u64, oneshot::Sender<SnapResponse>>,
+    /// The request id of the next request for the peer.
+    next_request_id: u64,
+}
+
+impl std::fmt::Debug for SnapConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("responses", &self.responses.len())
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for SnapConnection {
+    fn drop(&mut self) {
+        if let Some(outgoing) = &mut self.outgoing {
+            outgoing.close();
+        }
+        self.peers.remove_closed(&self.peer_id);
+    }
+}
+
+impl SnapConnection {
+    /// Handles a message of the peer, which is either a request for the request handler, or the
+    /// response to a request of the client.
+    fn on_message(&mut self, message: SnapProtocolMessage) {
+        let (request_id, response) = match message {
+            SnapProtocolMessage::AccountRange(response) => {
+                (response.request_id, SnapResponse::AccountRange(response))
+            }
+            SnapProtocolMessage::StorageRanges(response) => {
+                (response.request_id, SnapResponse::StorageRanges(response))
+            }
+            SnapProtocolMessage::ByteCodes(response) => {
+                (response.request_id, SnapResponse::ByteCodes(response))
+            }
+            SnapProtocolMessage::TrieNodes(response) => {
+                (response.request_id, SnapResponse::TrieNodes(response))
+            }
+            request => return self.on_request(request),
+        };
+
+        // Responses to unknown or abandoned requests are ignored
+        if let Some(tx) = self.pending.remove(&request_id) {
+            let _ = tx.send(response);
+        } else {
+            trace!(target: "net::snap", peer_id=%self.peer_id, request_id, "Ignoring unexpected response");
+        }
+    }
+
+    /// Delegates a request to the request handler.
+    fn on_request(&mut self, message: SnapProtocolMessage) {
+        let Some(requests) = &self.requests else {
+            // Nothing is served, but the peer gets a response instead of timing out
+            if let Some(response) = empty_response(message) {
+                self.responses.push_back(futures::future::ready(Some(response)).boxed());
+            }
+            return
+        };
+
+        if self.responses.len() >= MAX_CONCURRENT_REQUESTS {
+            trace!(
+                target: "net::snap",
+                peer_id=%self.peer_id,
+                "Dropping request over concurrency limit"
+            );
+            return
+        }
+
+        let peer_id = self.peer_id;
+        let (request, response) = match message {
+            SnapProtocolMessage::GetAccountRange(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response: tx },
+                    rx.map(|res| res.ok().map(SnapProtocolMessage::AccountRange)).boxed(),
+                )
+            }
+            SnapProtocolMessage::GetStorageRanges(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response: tx },
+                    rx.map(|res| res.ok().map(SnapProtocolMessage::StorageRanges)).boxed(),
+                )
+            }
+            SnapProtocolMessage::GetByteCodes(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response: tx },
+                    rx.map(|res| res.ok().map(SnapProtocolMessage::ByteCodes)).boxed(),
+                )
+            }
+            SnapProtocolMessage::GetTrieNodes(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response: tx },
+                    rx.map(|res| res.ok().map(SnapProtocolMessage::TrieNodes)).boxed(),
+                )
+            }
+            // Responses are handled by `on_message`
+            SnapProtocolMessage::AccountRange(_) |
+            SnapProtocolMessage::StorageRanges(_) |
+            SnapProtocolMessage::ByteCodes(_) |
+            SnapProtocolMessage::TrieNodes(_) => return,
+        };
+
+        if requests.try_send(request).is_ok() {
+            self.responses.push_back(response);
+        } else {
+            trace!(
+                target: "net::snap",
+                peer_id=%self.peer_id,
+                "Dropping request, request handler is busy"
+            );
+        }
+    }
+
+    /// Assigns a request id to the request, and returns the message to send to the peer.
+    fn on_outgoing_request(&mut self, request: OutgoingSnapRequest) -> SnapProtocolMessage {
+        let OutgoingSnapRequest { mut message, response } = request;
+        let request_id = self.next_request_id;
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+        match &mut message {
+            SnapProtocolMessage::GetAccountRange(request) => request.request_id = request_id,
+            SnapProtocolMessage::GetStorageRanges(request) => request.request_id = request_id,
+            SnapProtocolMessage::GetByteCodes(request) => request.request_id = request_id,
+            SnapProtocolMessage::GetTrieNodes(request) => request.request_id = request_id,
+            SnapProtocolMessage::AccountRange(_) |
+            SnapProtocolMessage::StorageRanges(_) |
+            SnapProtocolMessage::ByteCodes(_) |
+            SnapProtocolMessage::TrieNodes(_) => {}
+        }
+
+        // Forget the requests whose futures were dropped, e.g. after a timeout
+        self.pending.retain(|_, tx| !tx.is_closed());
+        self.pending.insert(request_id, response);
+        message
+    }
+}
+
+/// Returns the empty response to a request.
+fn empty_response(request: SnapProtocolMessage) -> Option<SnapProtocolMessage> {
+    Some(match request {
+        SnapProtocolMessage::GetAccountRange(request) => {
+            SnapProtocolMessage::AccountRange(AccountRangeMessage {
+                request_id: request.request_id,
+                accounts: Vec::new(),
+                proof: Vec::new(),
+            })
+        }
+        SnapProtocolMessage::GetStorageRanges(request) => {
+            SnapProtocolMessage::StorageRanges(StorageRangesMessage {
+                request_id: request.request_id,
+                slots: Vec::new(),
+                proof: Vec::new(),
+            })
+        }
+        SnapProtocolMessage::GetByteCodes(request) => {
+            SnapProtocolMessage::ByteCodes(ByteCodesMessage {
+                request_id: request.request_id,
+                codes: Vec::new(),
+            })
+        }
+        SnapProtocolMessage::GetTrieNodes(request) => {
+            SnapProtocolMessage::TrieNodes(TrieNodesMessage {
+                request_id: request.request_id,
+                nodes: Vec::new(),
+            })
+        }
+        SnapProtocolMessage::AccountRange(_) |
+        SnapProtocolMessage::StorageRanges(_) |
+        SnapProtocolMessage::ByteCodes(_) |
+        SnapProtocolMessage::TrieNodes(_) => return None,
+    })
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.responses.poll_next_unpin(cx) {
+                match response {
+                    Some(response) => {
+                        return Poll::Ready(Some(BytesMut::from(&response.encode()[..])))
+                    }
+                    // The request handler is gone, nothing can be served anymore
+                    None => return Poll::Ready(None),
+                }
+            }
+
+            if let Some(outgoing) = &mut this.outgoing {
+                match outgoing.poll_recv(cx) {
+                    Poll::Ready(Some(request)) => {
+                        let message = this.on_outgoing_request(request);
+                        return Poll::Ready(Some(BytesMut::from(&message.encode()[..])))
+                    }
+                    Poll::Ready(None) => this.outgoing = None,
+                    Poll::Pending => {}
+                }
+            }
+
+            let Poll::Ready(message) = this.conn.poll_next_unpin(cx) else { return Poll::Pending };
+            let Some(message) = message else { return Poll::Ready(None) };
+
+            let Some((&id, mut payload)) = message.split_first() else { return Poll::Ready(None) };
+            match SnapProtocolMessage::decode(id, &mut payload) {
+                Ok(message) => this.on_message(message),
+                Err(err) => {
+                    trace!(
+                        target: "net::snap",
+                        peer_id=%this.peer_id,
+                        %err,
+                        "Failed to decode snap message"
+                    );
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Encodings specific to the snap protocol.
+
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_rlp::{RlpDecodable, RlpEncodable};
+use reth_primitives_traits::Account;
+use reth_trie::{encode_path_leaf, Nibbles, TrieAccount, EMPTY_ROOT_HASH};
+
+/// Account in the slim format, which leaves out the storage root and code hash if they're empty.
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct SlimAccount {
+    nonce: u64,
+    balance: U256,
+    storage_root: Bytes,
+    code_hash: Bytes,
+}
+
+/// Encodes the account in the slim format of the snap protocol.
+pub fn encode_slim_account(account: Account, storage_root: B256) -> Bytes {
+    let account = account.into_trie_account(storage_root);
+    let slim = SlimAccount {
+        nonce: account.nonce,
+        balance: account.balance,
+        storage_root: if account.storage_root == EMPTY_ROOT_HASH {
+            Bytes::new()
+        } else {
+            account.storage_root.into()
+        },
+        code_hash: if account.code_hash == alloy_consensus::constants::KECCAK_EMPTY {
+            Bytes::new()
+        } else {
+            account.code_hash.into()
+        },
+    };
+    alloy_rlp::encode(slim).into()
+}
+
+/// Decodes an account in the slim format of the snap protocol.
+pub fn decode_slim_account(mut buf: &[u8]) -> alloy_rlp::Result<TrieAccount> {
+    let slim = <SlimAccount as alloy_rlp::Decodable>::decode(&mut buf)?;
+    let hash_or = |bytes: Bytes, empty: B256| match bytes.len() {
+        0 => Ok(empty),
+        32 => Ok(B256::from_slice(&bytes)),
+        _ => Err(alloy_rlp::Error::UnexpectedLength),
+    };
+    Ok(TrieAccount {
+        nonce: slim.nonce,
+        balance: slim.balance,
+        storage_root: hash_or(slim.storage_root, EMPTY_ROOT_HASH)?,
+        code_hash: hash_or(slim.code_hash, alloy_consensus::constants::KECCAK_EMPTY)?,
+    })
+}
+
+/// Encodes a trie path in the compact format used by `GetTrieNodes`.
+pub fn encode_path(path: &Nibbles) -> Bytes {
+    Bytes::copy_from_slice(&encode_path_leaf(path, false))
+}
+
+/// Decodes a trie path in the compact format used by `GetTrieNodes`.
+///
+/// Returns `None` if the path is malformed or longer than a full key.
+pub fn decode_path(path: &[u8]) -> Option<Nibbles> {
+    let (&first, rest) = path.split_first()?;
+    let flag = first >> 4;
+    if flag > 3 || rest.len() > 32 {
+        return None
+    }
+
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // An odd number of nibbles is marked by the lowest bit of the flag, with the first nibble in
+    // the lower half of the first byte
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    } else if first & 0x0f != 0 {
+        return None
+    }
+    nibbles.extend(rest.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+
+    (nibbles.len() <= 64).then(|| Nibbles::from_nibbles_unchecked(nibbles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn slim_account_roundtrip() {
+        let empty = Account { nonce: 1, balance: U256::from(2), bytecode_hash: None };
+        let encoded = encode_slim_account(empty, EMPTY_ROOT_HASH);
+        // Empty storage root and code hash are encoded as empty strings
+        assert_eq!(encoded[..], hex!("c401028080"));
+        assert_eq!(
+            decode_slim_account(&encoded).unwrap(),
+            empty.into_trie_account(EMPTY_ROOT_HASH)
+        );
+
+        let storage_root = B256::repeat_byte(1);
+        let contract =
+            Account { nonce: 1, balance: U256::from(2), bytecode_hash: Some(B256::repeat_byte(2)) };
+        let encoded = encode_slim_account(contract, storage_root);
+        assert_eq!(
+            decode_slim_account(&encoded).unwrap(),
+            contract.into_trie_account(storage_root)
+        );
+    }
+
+    #[test]
+    fn path_roundtrip() {
+        for nibbles in [vec![], vec![0x1], vec![0xa, 0xb], vec![0xf; 64]] {
+            let path = Nibbles::from_nibbles_unchecked(&nibbles);
+            assert_eq!(decode_path(&encode_path(&path)), Some(path));
+        }
+
+        // Leaf flags are accepted as well
+        assert_eq!(
+            decode_path(&[0x3a, 0xbc]),
+            Some(Nibbles::from_nibbles_unchecked([0xa, 0xb, 0xc]))
+        );
+        assert_eq!(decode_path(&[]), None);
+        assert_eq!(decode_path(&[0x40]), None);
+        assert_eq!(decode_path(&[0x01]), None);
+    }
+}
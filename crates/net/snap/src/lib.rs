@@ -0,0 +1,38 @@
+//! Support for the [snap protocol](https://github.com/ethereum/devp2p/blob/master/caps/snap.md).
+//!
+//! The [`SnapProtocolHandler`] adds snap/1 as an `RLPx` sub-protocol to the network, and delegates
+//! the requests of peers to the [`SnapRequestHandler`], which serves the state of recent blocks
+//! from the hashed state and trie tables.
+//!
+//! Requests for peers are sent by the [`SnapFetchClient`], which the [`SnapSyncStage`] uses to
+//! download the state of the pivot block during initial sync.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod client;
+pub use client::{SnapFetchClient, SnapResponseFuture, SNAP_REQUEST_TIMEOUT};
+
+mod connection;
+pub use connection::{
+    snap_protocol, SnapConnection, SnapConnectionHandler, SnapProtocolHandler,
+    MAX_CONCURRENT_REQUESTS, SNAP_REQUEST_CHANNEL_CAPACITY,
+};
+
+pub mod encoding;
+
+mod metrics;
+
+mod server;
+pub use server::{
+    IncomingSnapRequest, SnapRequestHandler, MAX_CODE_LOOKUPS, MAX_TRIE_NODE_LOOKUPS,
+    SERVED_STATES, SOFT_RESPONSE_LIMIT,
+};
+
+mod sync;
+pub use sync::{SnapSyncError, SnapSyncStage};
@@ -0,0 +1,24 @@
+use reth_metrics::{metrics::Counter, Metrics};
+
+/// Metrics for the [`SnapRequestHandler`](crate::SnapRequestHandler).
+#[derive(Metrics)]
+#[metrics(scope = "network.snap")]
+pub(crate) struct SnapRequestHandlerMetrics {
+    /// Number of `GetAccountRange` requests received
+    pub(crate) account_range_requests_received_total: Counter,
+
+    /// Number of `GetStorageRanges` requests received
+    pub(crate) storage_ranges_requests_received_total: Counter,
+
+    /// Number of `GetByteCodes` requests received
+    pub(crate) byte_codes_requests_received_total: Counter,
+
+    /// Number of `GetTrieNodes` requests received
+    pub(crate) trie_nodes_requests_received_total: Counter,
+
+    /// Number of requests for state roots that aren't served
+    pub(crate) unknown_root_requests_total: Counter,
+
+    /// Number of requests that failed with an error
+    pub(crate) failed_requests_total: Counter,
+}
@@ -0,0 +1,1020 @@
+//! Serves snap requests from the hashed state and trie tables.
+
+use crate::{
+    encoding::{decode_path, encode_slim_account},
+    metrics::SnapRequestHandlerMetrics,
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{
+    keccak256,
+    map::{B256Map, B256Set},
+    BlockNumber, Bytes, B256, U256,
+};
+use alloy_trie::proof::ProofNodes;
+use futures::StreamExt;
+use reth_db_api::{tables, transaction::DbTx};
+use reth_eth_wire::{
+    AccountData, AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage,
+    GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage, StorageData,
+    StorageRangesMessage, TrieNodesMessage,
+};
+use reth_network_api::PeerId;
+use reth_stages_types::StageId;
+use reth_storage_api::{
+    ChangeSetReader, DBProvider, DatabaseProviderFactory, HeaderProvider, StorageChangeSetReader,
+};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{
+    hashed_cursor::{
+        HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory, HashedStorageCursor,
+    },
+    prefix_set::{PrefixSetMut, TriePrefixSetsMut},
+    proof::{Proof, StorageProof},
+    HashedPostState, HashedPostStateSorted, HashedStorage, MultiProofTargets, Nibbles,
+    EMPTY_ROOT_HASH,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::debug;
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/v1.15.11/eth/protocols/snap/handler.go#L35-L58>
+
+/// Maximum size of replies to data retrievals: 2MB
+pub const SOFT_RESPONSE_LIMIT: u64 = 2 * 1024 * 1024;
+
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Number of recent blocks whose state is served.
+///
+/// Matches the number of state layers kept in memory by geth, which peers expect to be available.
+pub const SERVED_STATES: u64 = 128;
+
+/// Number of reverted states that are cached.
+const STATE_CACHE_SIZE: u32 = 8;
+
+/// Manages snap requests on top of the hashed state and trie tables.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<F> {
+    /// The provider factory of the database that's served.
+    provider_factory: F,
+    /// Incoming requests from the snap connections.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// The served state roots and the tip they were loaded at.
+    served_roots: Option<ServedRoots>,
+    /// States of blocks before the tip, by block number.
+    states: LruMap<BlockNumber, Arc<RevertedState>>,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+impl<F> SnapRequestHandler<F> {
+    /// Create a new instance
+    pub fn new(provider_factory: F, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self {
+            provider_factory,
+            incoming_requests: ReceiverStream::new(incoming),
+            served_roots: None,
+            states: LruMap::new(ByLength::new(STATE_CACHE_SIZE)),
+            metrics: Default::default(),
+        }
+    }
+}
+
+impl<F> SnapRequestHandler<F>
+where
+    F: DatabaseProviderFactory + HeaderProvider + ChangeSetReader + StorageChangeSetReader,
+{
+    /// Returns the state with the given root, if it's one of the served states.
+    ///
+    /// The state is described as the changes that revert the hashed state tables to it.
+    fn state_by_root(
+        &mut self,
+        provider: &F::Provider,
+        root: B256,
+    ) -> ProviderResult<Option<Arc<RevertedState>>> {
+        // The hashed state and trie tables are consistent with the last block of a finished sync
+        let tip = provider
+            .tx_ref()
+            .get::<tables::StageCheckpoints>(StageId::Finish.to_string())?
+            .unwrap_or_default()
+            .block_number;
+
+        self.update_tip(tip)?;
+
+        let Some(block) = self.served_roots.as_ref().and_then(|served| served.roots.get(&root))
+        else {
+            return Ok(None)
+        };
+        let block = *block;
+        if block == tip {
+            return Ok(Some(Default::default()))
+        }
+
+        if let Some(state) = self.states.get(&block) {
+            return Ok(Some(state.clone()))
+        }
+
+        let state = Arc::new(RevertedState::new(self.reverted_changes(block + 1..=tip)?));
+        self.states.insert(block, state.clone());
+        Ok(Some(state))
+    }
+
+    /// Moves the served states to a new tip.
+    ///
+    /// If the chain was extended, the roots and cached states that are still served are kept, and
+    /// the cached states are extended by the changes of the new blocks. After a reorg, they are
+    /// loaded again.
+    fn update_tip(&mut self, tip: BlockNumber) -> ProviderResult<()> {
+        if self.served_roots.as_ref().is_some_and(|served| served.tip == tip) {
+            return Ok(())
+        }
+
+        let first = tip.saturating_sub(SERVED_STATES - 1);
+        let mut served = match self.served_roots.take() {
+            Some(served)
+                if served.tip < tip &&
+                    self.provider_factory
+                        .sealed_header(served.tip)?
+                        .is_some_and(|header| header.hash() == served.tip_hash) =>
+            {
+                served
+            }
+            _ => {
+                self.states.clear();
+                ServedRoots::default()
+            }
+        };
+
+        // Evict the states that are no longer served, and extend the others to the new tip
+        if served.tip + SERVED_STATES > tip && !self.states.is_empty() {
+            let changes = self.reverted_changes(served.tip + 1..=tip)?;
+            let mut states =
+                std::mem::replace(&mut self.states, LruMap::new(ByLength::new(STATE_CACHE_SIZE)));
+            while let Some((block, state)) = states.pop_oldest() {
+                if block >= first {
+                    self.states.insert(block, Arc::new(state.extend_tip(&changes)));
+                }
+            }
+        } else {
+            self.states.clear();
+        }
+        served.roots.retain(|_, block| *block >= first);
+
+        // Empty blocks share the state of their parent, which is the cheapest to serve at the
+        // highest block
+        let from = if served.roots.is_empty() { first } else { (served.tip + 1).max(first) };
+        for block in from..=tip {
+            if let Some(header) = self.provider_factory.header_by_number(block)? {
+                served.roots.insert(header.state_root(), block);
+            }
+        }
+        served.tip = tip;
+        served.tip_hash = self
+            .provider_factory
+            .sealed_header(tip)?
+            .map(|header| header.hash())
+            .unwrap_or_default();
+        self.served_roots = Some(served);
+        Ok(())
+    }
+
+    /// Returns the changes that revert the hashed state to the state before the given blocks.
+    fn reverted_changes(
+        &self,
+        blocks: std::ops::RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<HashedPostState> {
+        // Record the value before the first occurring change of every account and slot
+        let mut accounts = B256Map::default();
+        let mut storages = B256Map::<B256Map<U256>>::default();
+        for block in blocks {
+            for account in self.provider_factory.account_block_changeset(block)? {
+                accounts.entry(keccak256(account.address)).or_insert(account.info);
+            }
+            for (block_address, entry) in self.provider_factory.storage_changeset(block)? {
+                storages
+                    .entry(keccak256(block_address.address()))
+                    .or_default()
+                    .entry(keccak256(entry.key))
+                    .or_insert(entry.value);
+            }
+        }
+
+        Ok(HashedPostState {
+            accounts,
+            storages: storages
+                .into_iter()
+                .map(|(hashed_address, storage)| {
+                    (hashed_address, HashedStorage::from_iter(false, storage))
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns the requested range of accounts with the proofs of its boundaries.
+    fn get_account_range(
+        &mut self,
+        request: &GetAccountRangeMessage,
+    ) -> ProviderResult<Option<AccountRangeMessage>> {
+        let provider = self.provider_factory.database_provider_ro()?;
+        let Some(state) = self.state_by_root(&provider, request.root_hash)? else {
+            return Ok(None)
+        };
+        let response_bytes = request.response_bytes.min(SOFT_RESPONSE_LIMIT);
+
+        let tx = provider.tx_ref();
+        let hashed_cursor_factory =
+            HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state.state);
+        let mut cursor = hashed_cursor_factory.hashed_account_cursor()?;
+
+        let mut accounts = Vec::new();
+        let mut size = 0;
+        let mut entry = cursor.seek(request.starting_hash)?;
+        while let Some((hashed_address, account)) = entry {
+            // The size of the storage root only depends on whether the storage is empty
+            let has_storage =
+                !hashed_cursor_factory.hashed_storage_cursor(hashed_address)?.is_storage_empty()?;
+            let body = encode_slim_account(
+                account,
+                if has_storage { B256::ZERO } else { EMPTY_ROOT_HASH },
+            );
+            size += (B256::len_bytes() + body.len()) as u64;
+            accounts.push((hashed_address, account));
+            if hashed_address >= request.limit_hash || size > response_bytes {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        // Storage roots are part of the accounts, and retrieved with the proof of every account.
+        let multiproof = Proof::new(DatabaseTrieCursorFactory::new(tx), hashed_cursor_factory)
+            .with_prefix_sets_mut(state.prefix_sets.clone())
+            .multiproof(MultiProofTargets::accounts(
+                accounts
+                    .iter()
+                    .map(|(hashed_address, _)| *hashed_address)
+                    .chain([request.starting_hash]),
+            ))?;
+
+        let accounts = accounts
+            .into_iter()
+            .map(|(hashed_address, account)| {
+                let storage_root = multiproof
+                    .storages
+                    .get(&hashed_address)
+                    .map_or(EMPTY_ROOT_HASH, |storage| storage.root);
+                AccountData {
+                    hash: hashed_address,
+                    body: encode_slim_account(account, storage_root),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let last = accounts.last().map(|account| account.hash);
+        let proof = range_proof(&multiproof.account_subtree, request.starting_hash, last);
+        Ok(Some(AccountRangeMessage { request_id: request.request_id, accounts, proof }))
+    }
+
+    /// Returns the requested ranges of storage slots, with the proofs of the boundaries of the
+    /// last range if it's incomplete.
+    fn get_storage_ranges(
+        &mut self,
+        request: &GetStorageRangesMessage,
+    ) -> ProviderResult<Option<StorageRangesMessage>> {
+        let provider = self.provider_factory.database_provider_ro()?;
+        let Some(state) = self.state_by_root(&provider, request.root_hash)? else {
+            return Ok(None)
+        };
+        let response_bytes = request.response_bytes.min(SOFT_RESPONSE_LIMIT);
+        // Ranges of slots aren't split before this limit, to avoid sending proofs
+        let hard_limit = response_bytes + response_bytes * 15 / 100;
+
+        let tx = provider.tx_ref();
+        let hashed_cursor_factory =
+            HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state.state);
+
+        let mut slots = Vec::new();
+        let mut proof = Vec::new();
+        let mut size = 0;
+        for (index, hashed_address) in request.account_hashes.iter().enumerate() {
+            if size >= response_bytes {
+                break
+            }
+
+            // Only the range of the first account can start and end at other hashes
+            let (origin, limit) = if index == 0 {
+                (request.starting_hash, request.limit_hash)
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let mut cursor = hashed_cursor_factory.hashed_storage_cursor(*hashed_address)?;
+            let mut storage = Vec::new();
+            let mut aborted = false;
+            let mut entry = cursor.seek(origin)?;
+            while let Some((hashed_slot, value)) = entry {
+                if size >= hard_limit {
+                    aborted = true;
+                    break
+                }
+
+                let data = Bytes::from(alloy_rlp::encode(value));
+                size += (B256::len_bytes() + data.len()) as u64;
+                storage.push(StorageData { hash: hashed_slot, data });
+                if hashed_slot >= limit {
+                    break
+                }
+                entry = cursor.next()?;
+            }
+
+            // Proofs are only needed if the range doesn't cover the complete storage
+            let last = storage.last().map(|slot| slot.hash);
+            let needs_proof = origin != B256::ZERO || (aborted && last.is_some());
+            if !storage.is_empty() {
+                slots.push(storage);
+            }
+            if needs_proof {
+                let storage_proof = StorageProof::new_hashed(
+                    DatabaseTrieCursorFactory::new(tx),
+                    hashed_cursor_factory.clone(),
+                    *hashed_address,
+                )
+                .with_prefix_set_mut(state.storage_prefix_set(hashed_address))
+                .storage_multiproof(std::iter::once(origin).chain(last).collect::<B256Set>())?;
+                proof = range_proof(&storage_proof.subtree, origin, last);
+                break
+            }
+        }
+
+        Ok(Some(StorageRangesMessage { request_id: request.request_id, slots, proof }))
+    }
+
+    /// Returns the requested bytecodes.
+    fn get_byte_codes(&self, request: &GetByteCodesMessage) -> ProviderResult<ByteCodesMessage> {
+        let provider = self.provider_factory.database_provider_ro()?;
+        let response_bytes = request.response_bytes.min(SOFT_RESPONSE_LIMIT);
+
+        let mut codes = Vec::new();
+        let mut size = 0;
+        for hash in request.hashes.iter().take(MAX_CODE_LOOKUPS) {
+            if *hash == alloy_consensus::constants::KECCAK_EMPTY {
+                codes.push(Bytes::new());
+            } else if let Some(code) = provider.tx_ref().get::<tables::Bytecodes>(*hash)? {
+                let code = code.original_bytes();
+                size += code.len() as u64;
+                codes.push(code);
+            }
+
+            if size > response_bytes {
+                break
+            }
+        }
+
+        Ok(ByteCodesMessage { request_id: request.request_id, codes })
+    }
+
+    /// Returns the requested trie nodes, or empty nodes where the trie has none.
+    fn get_trie_nodes(
+        &mut self,
+        request: &GetTrieNodesMessage,
+    ) -> ProviderResult<Option<TrieNodesMessage>> {
+        let provider = self.provider_factory.database_provider_ro()?;
+        let Some(state) = self.state_by_root(&provider, request.root_hash)? else {
+            return Ok(None)
+        };
+        let response_bytes = request.response_bytes.min(SOFT_RESPONSE_LIMIT);
+
+        // Decode the paths within the lookup limit
+        let mut lookups = 0;
+        let mut paths = Vec::new();
+        for path in &request.paths {
+            if lookups >= MAX_TRIE_NODE_LOOKUPS {
+                break
+            }
+            if path.slot_paths.is_empty() {
+                lookups += 1;
+                paths.push((None, vec![decode_path(&path.account_path)]));
+            } else {
+                // A malformed account hash is treated as an account without storage
+                let hashed_address = if path.account_path.len() == 32 {
+                    B256::from_slice(&path.account_path)
+                } else {
+                    B256::ZERO
+                };
+                let slot_paths = path
+                    .slot_paths
+                    .iter()
+                    .take(MAX_TRIE_NODE_LOOKUPS - lookups)
+                    .map(|path| decode_path(path))
+                    .collect::<Vec<_>>();
+                lookups += slot_paths.len();
+                paths.push((Some(hashed_address), slot_paths));
+            }
+        }
+
+        let tx = provider.tx_ref();
+        let hashed_cursor_factory =
+            HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state.state);
+
+        // The node at a path is part of the proof of any key starting with it
+        let node_target = |path: &Nibbles| {
+            let mut key = path.clone();
+            key.extend_from_slice_unchecked(&[0; 64][..64 - path.len()]);
+            B256::from_slice(&key.pack())
+        };
+
+        let account_paths = paths
+            .iter()
+            .filter(|(hashed_address, _)| hashed_address.is_none())
+            .flat_map(|(_, paths)| paths.iter().flatten())
+            .collect::<Vec<_>>();
+        let account_nodes = if account_paths.is_empty() {
+            ProofNodes::default()
+        } else {
+            Proof::new(DatabaseTrieCursorFactory::new(tx), hashed_cursor_factory.clone())
+                .with_prefix_sets_mut(state.prefix_sets.clone())
+                .multiproof(MultiProofTargets::accounts(
+                    account_paths.into_iter().map(node_target),
+                ))?
+                .account_subtree
+        };
+
+        let mut nodes = Vec::new();
+        let mut size = 0;
+        for (hashed_address, paths) in paths {
+            let storage_nodes;
+            let trie_nodes = match hashed_address {
+                None => &account_nodes,
+                Some(hashed_address) => {
+                    storage_nodes = StorageProof::new_hashed(
+                        DatabaseTrieCursorFactory::new(tx),
+                        hashed_cursor_factory.clone(),
+                        hashed_address,
+                    )
+                    .with_prefix_set_mut(state.storage_prefix_set(&hashed_address))
+                    .storage_multiproof(paths.iter().flatten().map(node_target).collect())?
+                    .subtree;
+                    &storage_nodes
+                }
+            };
+
+            for path in paths {
+                let node = path.and_then(|path| trie_nodes.get(&path).cloned()).unwrap_or_default();
+                size += node.len() as u64;
+                nodes.push(node);
+            }
+
+            if size > response_bytes {
+                break
+            }
+        }
+
+        Ok(Some(TrieNodesMessage { request_id: request.request_id, nodes }))
+    }
+
+    fn on_account_range_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetAccountRangeMessage,
+        response: oneshot::Sender<AccountRangeMessage>,
+    ) {
+        self.metrics.account_range_requests_received_total.increment(1);
+        let result = self.get_account_range(&request);
+        let response_message = self.unwrap_or_empty(peer_id, result, || AccountRangeMessage {
+            request_id: request.request_id,
+            accounts: Vec::new(),
+            proof: Vec::new(),
+        });
+        let _ = response.send(response_message);
+    }
+
+    fn on_storage_ranges_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetStorageRangesMessage,
+        response: oneshot::Sender<StorageRangesMessage>,
+    ) {
+        self.metrics.storage_ranges_requests_received_total.increment(1);
+        let result = self.get_storage_ranges(&request);
+        let response_message = self.unwrap_or_empty(peer_id, result, || StorageRangesMessage {
+            request_id: request.request_id,
+            slots: Vec::new(),
+            proof: Vec::new(),
+        });
+        let _ = response.send(response_message);
+    }
+
+    fn on_byte_codes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetByteCodesMessage,
+        response: oneshot::Sender<ByteCodesMessage>,
+    ) {
+        self.metrics.byte_codes_requests_received_total.increment(1);
+        let result = self.get_byte_codes(&request).map(Some);
+        let response_message = self.unwrap_or_empty(peer_id, result, || ByteCodesMessage {
+            request_id: request.request_id,
+            codes: Vec::new(),
+        });
+        let _ = response.send(response_message);
+    }
+
+    fn on_trie_nodes_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetTrieNodesMessage,
+        response: oneshot::Sender<TrieNodesMessage>,
+    ) {
+        self.metrics.trie_nodes_requests_received_total.increment(1);
+        let result = self.get_trie_nodes(&request);
+        let response_message = self.unwrap_or_empty(peer_id, result, || TrieNodesMessage {
+            request_id: request.request_id,
+            nodes: Vec::new(),
+        });
+        let _ = response.send(response_message);
+    }
+
+    /// Returns the response, or an empty response if the state isn't served or the request
+    /// failed.
+    fn unwrap_or_empty<T>(
+        &self,
+        peer_id: PeerId,
+        result: ProviderResult<Option<T>>,
+        empty: impl FnOnce() -> T,
+    ) -> T {
+        match result {
+            Ok(Some(response)) => response,
+            Ok(None) => {
+                self.metrics.unknown_root_requests_total.increment(1);
+                empty()
+            }
+            Err(err) => {
+                self.metrics.failed_requests_total.increment(1);
+                debug!(target: "net::snap", %peer_id, %err, "Failed to serve snap request");
+                empty()
+            }
+        }
+    }
+}
+
+/// An endless future.
+///
+/// Serving a request reads the database and builds proofs synchronously, so this should be spawned
+/// on a blocking thread, e.g. with `TaskSpawner::spawn_critical_blocking`.
+impl<F> Future for SnapRequestHandler<F>
+where
+    F: DatabaseProviderFactory + HeaderProvider + ChangeSetReader + StorageChangeSetReader + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while let Poll::Ready(incoming) = this.incoming_requests.poll_next_unpin(cx) {
+            let Some(incoming) = incoming else {
+                // All connections and the protocol handler are gone
+                return Poll::Ready(())
+            };
+
+            match incoming {
+                IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                    this.on_account_range_request(peer_id, request, response)
+                }
+                IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                    this.on_storage_ranges_request(peer_id, request, response)
+                }
+                IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                    this.on_byte_codes_request(peer_id, request, response)
+                }
+                IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                    this.on_trie_nodes_request(peer_id, request, response)
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+/// The state roots of the served blocks.
+#[derive(Debug, Default)]
+struct ServedRoots {
+    /// The tip the roots were loaded at.
+    tip: BlockNumber,
+    /// The hash of the tip, to detect reorgs.
+    tip_hash: B256,
+    /// The served state roots and their block numbers.
+    roots: B256Map<BlockNumber>,
+}
+
+/// Changes that revert the hashed state tables to an earlier state.
+#[derive(Debug, Default)]
+struct RevertedState {
+    /// The reverted accounts and storage slots.
+    state: HashedPostStateSorted,
+    /// The trie paths that are changed by the revert.
+    prefix_sets: TriePrefixSetsMut,
+    /// The unsorted changes, kept to extend the revert to a later tip.
+    changes: HashedPostState,
+}
+
+impl RevertedState {
+    /// Creates the revert from the changes.
+    fn new(changes: HashedPostState) -> Self {
+        Self {
+            state: changes.clone().into_sorted(),
+            prefix_sets: changes.construct_prefix_sets(),
+            changes,
+        }
+    }
+
+    /// Returns the revert to the same state from a later tip, given the changes that revert the
+    /// later tip to the current one.
+    fn extend_tip(&self, later: &HashedPostState) -> Self {
+        // The earlier values of this revert take precedence
+        let mut changes = later.clone();
+        changes.extend_ref(&self.changes);
+        Self::new(changes)
+    }
+
+    /// Returns the storage trie paths of the account that are changed by the revert.
+    fn storage_prefix_set(&self, hashed_address: &B256) -> PrefixSetMut {
+        self.prefix_sets.storage_prefix_sets.get(hashed_address).cloned().unwrap_or_default()
+    }
+}
+
+/// Returns the nodes proving the boundaries of a range, without duplicates.
+fn range_proof(proof_nodes: &ProofNodes, origin: B256, last: Option<B256>) -> Vec<Bytes> {
+    std::iter::once(origin)
+        .chain(last)
+        .flat_map(|key| proof_nodes.matching_nodes(&Nibbles::unpack(key)))
+        .collect::<BTreeMap<_, _>>()
+        .into_values()
+        .collect()
+}
+
+/// All snap requests delegated by the snap connections.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested range of accounts.
+        request: GetAccountRangeMessage,
+        /// The channel sender for the response containing the accounts.
+        response: oneshot::Sender<AccountRangeMessage>,
+    },
+    /// Request ranges of storage slots from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested ranges of storage slots.
+        request: GetStorageRangesMessage,
+        /// The channel sender for the response containing the storage slots.
+        response: oneshot::Sender<StorageRangesMessage>,
+    },
+    /// Request bytecodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested bytecode hashes.
+        request: GetByteCodesMessage,
+        /// The channel sender for the response containing the bytecodes.
+        response: oneshot::Sender<ByteCodesMessage>,
+    },
+    /// Request trie nodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested trie node paths.
+        request: GetTrieNodesMessage,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<TrieNodesMessage>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{decode_slim_account, encode_path};
+    use alloy_consensus::Header;
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use alloy_primitives::{Address, B256};
+    use alloy_rlp::Decodable;
+    use reth_chainspec::ChainSpec;
+    use reth_db_api::{models::AccountBeforeTx, transaction::DbTxMut};
+    use reth_db_common::init::init_genesis;
+    use reth_eth_wire::TriePath;
+    use reth_primitives_traits::Account;
+    use reth_provider::{
+        providers::{BlockchainProvider, StaticFileWriter},
+        test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
+        StageCheckpointWriter, StaticFileProviderFactory, StaticFileSegment, TrieWriter,
+    };
+    use reth_stages_types::StageCheckpoint;
+    use reth_trie::{root, StateRoot};
+    use reth_trie_db::{DatabaseProof, DatabaseStateRoot};
+    use std::{collections::BTreeMap, sync::Arc};
+    use tokio::sync::mpsc;
+
+    const CONTRACT: Address = Address::with_last_byte(0xff);
+
+    fn code() -> Bytes {
+        Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3])
+    }
+
+    /// Creates a database with a genesis state of a few accounts and a contract with storage.
+    fn setup() -> (SnapRequestHandler<BlockchainProvider<MockNodeTypesWithDB>>, B256) {
+        let mut alloc = (1..=20)
+            .map(|i| {
+                (
+                    Address::with_last_byte(i),
+                    GenesisAccount { balance: U256::from(i), ..Default::default() },
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        alloc.insert(
+            CONTRACT,
+            GenesisAccount {
+                code: Some(code()),
+                storage: Some(
+                    (1..=50).map(|i| (B256::with_last_byte(i), B256::with_last_byte(i))).collect(),
+                ),
+                ..Default::default()
+            },
+        );
+        let chain_spec = Arc::new(ChainSpec::from(Genesis { alloc, ..Default::default() }));
+
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(&factory).unwrap();
+        let state_root = factory.header_by_number(0).unwrap().unwrap().state_root;
+
+        let (_, rx) = mpsc::channel(1);
+        (SnapRequestHandler::new(BlockchainProvider::new(factory).unwrap(), rx), state_root)
+    }
+
+    /// Commits the next block, which changes the balance of an account.
+    fn advance(
+        handler: &SnapRequestHandler<BlockchainProvider<MockNodeTypesWithDB>>,
+        number: BlockNumber,
+    ) -> B256 {
+        let address = Address::with_last_byte(1);
+        let hashed_address = keccak256(address);
+        let account = Account { balance: U256::from(1000 + number), ..Default::default() };
+
+        let provider_rw = handler.provider_factory.database_provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        let before = tx.get::<tables::HashedAccounts>(hashed_address).unwrap();
+        tx.put::<tables::AccountChangeSets>(number, AccountBeforeTx { address, info: before })
+            .unwrap();
+        let (state_root, updates) = StateRoot::overlay_root_with_updates(
+            tx,
+            HashedPostState::default().with_accounts([(hashed_address, Some(account))]),
+        )
+        .unwrap();
+        tx.put::<tables::HashedAccounts>(hashed_address, account).unwrap();
+        provider_rw.write_trie_updates(&updates).unwrap();
+
+        let header = Header { number, state_root, ..Default::default() };
+        let static_file_provider = provider_rw.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        writer.append_header(&header, U256::ZERO, &header.hash_slow()).unwrap();
+        writer.commit().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(number)).unwrap();
+        provider_rw.commit().unwrap();
+
+        state_root
+    }
+
+    fn account_range(
+        handler: &mut SnapRequestHandler<BlockchainProvider<MockNodeTypesWithDB>>,
+        root_hash: B256,
+        response_bytes: u64,
+    ) -> AccountRangeMessage {
+        handler
+            .get_account_range(&GetAccountRangeMessage {
+                request_id: 1,
+                root_hash,
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes,
+            })
+            .unwrap()
+            .unwrap()
+    }
+
+    /// Returns the root of the trie of the accounts in the response.
+    fn accounts_root(response: &AccountRangeMessage) -> B256 {
+        root::state_root(
+            response
+                .accounts
+                .iter()
+                .map(|account| (account.hash, decode_slim_account(&account.body).unwrap())),
+        )
+    }
+
+    #[test]
+    fn serve_complete_account_range() {
+        let (mut handler, state_root) = setup();
+
+        let response = account_range(&mut handler, state_root, SOFT_RESPONSE_LIMIT);
+        assert_eq!(response.accounts.len(), 21);
+        assert_eq!(accounts_root(&response), state_root);
+        // The proof starts at the root node
+        assert_eq!(keccak256(&response.proof[0]), state_root);
+    }
+
+    #[test]
+    fn serve_partial_account_range() {
+        let (mut handler, state_root) = setup();
+
+        let response = account_range(&mut handler, state_root, 1);
+        assert_eq!(response.accounts.len(), 1);
+
+        // The proof contains the proof of the returned account
+        let address = (1..=20)
+            .map(Address::with_last_byte)
+            .chain([CONTRACT])
+            .find(|address| keccak256(address) == response.accounts[0].hash)
+            .unwrap();
+        let provider = handler.provider_factory.database_provider_ro().unwrap();
+        let account_proof = Proof::from_tx(provider.tx_ref()).account_proof(address, &[]).unwrap();
+        account_proof.verify(state_root).unwrap();
+        assert!(account_proof.proof.iter().all(|node| response.proof.contains(node)));
+    }
+
+    #[test]
+    fn serve_reverted_account_range() {
+        let (mut handler, genesis_root) = setup();
+        let state_root = advance(&handler, 1);
+        assert_ne!(state_root, genesis_root);
+
+        let response = account_range(&mut handler, state_root, SOFT_RESPONSE_LIMIT);
+        assert_eq!(accounts_root(&response), state_root);
+
+        // The state of the parent block is still served
+        let response = account_range(&mut handler, genesis_root, SOFT_RESPONSE_LIMIT);
+        assert_eq!(accounts_root(&response), genesis_root);
+        assert_eq!(keccak256(&response.proof[0]), genesis_root);
+    }
+
+    #[test]
+    fn extend_reverted_states_to_new_tip() {
+        let (mut handler, genesis_root) = setup();
+        let first_root = advance(&handler, 1);
+        account_range(&mut handler, genesis_root, SOFT_RESPONSE_LIMIT);
+        assert!(handler.states.peek(&0).is_some());
+
+        // The cached state of the genesis block is kept and reverts both blocks
+        let second_root = advance(&handler, 2);
+        assert_ne!(second_root, first_root);
+        let response = account_range(&mut handler, genesis_root, SOFT_RESPONSE_LIMIT);
+        assert_eq!(accounts_root(&response), genesis_root);
+        assert_eq!(keccak256(&response.proof[0]), genesis_root);
+        assert_eq!(handler.states.len(), 1);
+
+        let response = account_range(&mut handler, first_root, SOFT_RESPONSE_LIMIT);
+        assert_eq!(accounts_root(&response), first_root);
+        assert_eq!(handler.served_roots.as_ref().unwrap().roots.len(), 3);
+    }
+
+    #[test]
+    fn serve_storage_ranges() {
+        let (mut handler, state_root) = setup();
+        let hashed_address = keccak256(CONTRACT);
+        let storage_root =
+            root::storage_root_unhashed((1..=50).map(|i| (B256::with_last_byte(i), U256::from(i))));
+
+        let mut request = GetStorageRangesMessage {
+            request_id: 1,
+            root_hash: state_root,
+            account_hashes: vec![hashed_address, keccak256(Address::with_last_byte(1))],
+            starting_hash: B256::ZERO,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: SOFT_RESPONSE_LIMIT,
+        };
+
+        // The complete storage is returned without a proof, and accounts without storage are
+        // left out
+        let response = handler.get_storage_ranges(&request).unwrap().unwrap();
+        assert_eq!(response.slots.len(), 1);
+        assert!(response.proof.is_empty());
+        let slots = response.slots[0]
+            .iter()
+            .map(|slot| (slot.hash, U256::decode(&mut &slot.data[..]).unwrap()));
+        assert_eq!(root::storage_root(slots), storage_root);
+
+        // A range that doesn't start at the first slot is proven
+        request.starting_hash = response.slots[0][10].hash;
+        let response = handler.get_storage_ranges(&request).unwrap().unwrap();
+        assert_eq!(response.slots[0].len(), 40);
+        assert_eq!(keccak256(&response.proof[0]), storage_root);
+    }
+
+    #[test]
+    fn serve_byte_codes() {
+        let (handler, _) = setup();
+
+        let response = handler
+            .get_byte_codes(&GetByteCodesMessage {
+                request_id: 1,
+                hashes: vec![
+                    keccak256(code()),
+                    alloy_consensus::constants::KECCAK_EMPTY,
+                    B256::repeat_byte(1),
+                ],
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap();
+        // Unknown codes are left out
+        assert_eq!(response.codes, vec![code(), Bytes::new()]);
+    }
+
+    #[test]
+    fn serve_trie_nodes() {
+        let (mut handler, state_root) = setup();
+        let storage_root_node = handler
+            .get_storage_ranges(&GetStorageRangesMessage {
+                request_id: 1,
+                root_hash: state_root,
+                account_hashes: vec![keccak256(CONTRACT)],
+                starting_hash: B256::with_last_byte(1),
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap()
+            .unwrap()
+            .proof[0]
+            .clone();
+
+        let root_path = encode_path(&Nibbles::default());
+        let response = handler
+            .get_trie_nodes(&GetTrieNodesMessage {
+                request_id: 1,
+                root_hash: state_root,
+                paths: vec![
+                    TriePath { account_path: root_path.clone(), slot_paths: vec![] },
+                    TriePath {
+                        account_path: keccak256(CONTRACT).into(),
+                        slot_paths: vec![root_path],
+                    },
+                    // There are no nodes that deep in a trie of a few accounts
+                    TriePath {
+                        account_path: encode_path(&Nibbles::from_nibbles_unchecked([0; 10])),
+                        slot_paths: vec![],
+                    },
+                ],
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(response.nodes.len(), 3);
+        assert_eq!(keccak256(&response.nodes[0]), state_root);
+        assert_eq!(response.nodes[1], storage_root_node);
+        assert!(response.nodes[2].is_empty());
+    }
+
+    #[test]
+    fn unknown_root() {
+        let (mut handler, _) = setup();
+        assert!(handler
+            .get_account_range(&GetAccountRangeMessage {
+                request_id: 1,
+                root_hash: B256::repeat_byte(1),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: SOFT_RESPONSE_LIMIT,
+            })
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,1061 @@
+//! Stage that downloads the state of a recent block over the snap protocol.
+
+use crate::encoding::{decode_slim_account, encode_path};
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
+use alloy_primitives::{
+    keccak256,
+    map::{B256Map, B256Set, HashSet},
+    BlockNumber, Bytes, B256, U256,
+};
+use alloy_rlp::Decodable;
+use alloy_trie::nodes::TrieNode;
+use futures::{stream::FuturesUnordered, FutureExt, StreamExt};
+use reth_config::config::SnapSyncConfig;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_eth_wire::{
+    AccountData, AccountRangeMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, GetTrieNodesMessage, StorageData, StorageRangesMessage, TriePath,
+};
+use reth_network_api::PeerId;
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::PeerRequestResult,
+    snap::client::{SnapClient, SnapResponse},
+};
+use reth_primitives_traits::{Account, Bytecode, StorageEntry};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+use reth_stages_api::{
+    EntitiesCheckpoint, ExecInput, ExecOutput, SnapSyncCheckpoint, Stage, StageCheckpoint,
+    StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_storage_api::{
+    DBProvider, HeaderProvider, PruneCheckpointWriter, StageCheckpointReader,
+    StageCheckpointWriter, TrieWriter,
+};
+use reth_storage_errors::provider::ProviderError;
+use reth_trie::{
+    prefix_set::{PrefixSetMut, TriePrefixSetsMut},
+    BranchNodeCompact, Nibbles, StateRoot, StateRootProgress, StorageRoot, StoredNibbles,
+    TrieAccount, EMPTY_ROOT_HASH,
+};
+use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::time::Sleep;
+use tracing::{debug, info, trace};
+
+/// Maximum number of requests that are sent at once.
+const MAX_INFLIGHT_REQUESTS: usize = 16;
+
+/// Maximum number of accounts whose storage is requested at once.
+const MAX_STORAGE_ACCOUNTS: usize = 64;
+
+/// Maximum number of bytecodes requested at once.
+const MAX_CODES: usize = 64;
+
+/// Maximum number of trie nodes requested at once.
+const MAX_TRIE_NODES: usize = 128;
+
+/// Delay before failed requests are sent again.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The stages whose work is replaced by the download of the state, and that continue from the
+/// pivot block.
+const REPLACED_STAGES: [StageId; 6] = [
+    StageId::Execution,
+    StageId::AccountHashing,
+    StageId::StorageHashing,
+    StageId::MerkleExecute,
+    StageId::IndexAccountHistory,
+    StageId::IndexStorageHistory,
+];
+
+/// Errors of the [`SnapSyncStage`].
+#[derive(Debug, thiserror::Error)]
+pub enum SnapSyncError {
+    /// Receipts pruning is required, since the blocks before the pivot block aren't executed.
+    #[error(
+        "snap sync requires receipts pruning, receipts before the pivot block aren't available"
+    )]
+    ReceiptsPruningRequired,
+    /// The state before the pivot block isn't available to unwind to.
+    #[error("cannot unwind to block {unwind_to}, the state was downloaded at block {pivot}")]
+    UnwindBelowPivot {
+        /// The block whose state was downloaded.
+        pivot: BlockNumber,
+        /// The block to unwind to.
+        unwind_to: BlockNumber,
+    },
+}
+
+/// The snap sync stage downloads the state of the target block from peers over the snap
+/// protocol, instead of executing all blocks since genesis.
+///
+/// The state of the target block, the pivot, is downloaded into the hashed state tables:
+///
+/// - [`HashedAccounts`][reth_db_api::tables::HashedAccounts]
+/// - [`HashedStorages`][reth_db_api::tables::HashedStorages]
+/// - [`Bytecodes`][reth_db_api::tables::Bytecodes]
+///
+/// Once all accounts are downloaded, the trie is rebuilt and its root is verified against the
+/// state root of the pivot. Parts of the state that were downloaded for an older pivot, or that
+/// were served incorrectly, are healed by walking the trie of the pivot with `GetTrieNodes` and
+/// downloading the accounts below the nodes that differ from the local trie again.
+///
+/// When the root matches, the checkpoints of the execution, hashing, merkle and history index
+/// stages are moved to the pivot, and the pipeline continues by executing the blocks after it.
+///
+/// If no peer serves the state of the pivot anymore, the stage finishes at block zero so the
+/// following stages do nothing, and the download continues with the target of the next pipeline
+/// run as pivot.
+///
+/// The stage only runs on a node that hasn't executed any blocks, and requires receipts pruning.
+pub struct SnapSyncStage<C: SnapClient> {
+    /// The client for snap requests.
+    client: C,
+    /// The configuration of the stage.
+    config: SnapSyncConfig,
+    /// Requests waiting for their responses.
+    requests: FuturesUnordered<RequestFuture<C::Output>>,
+    /// Responses that are processed by the next execution.
+    responses: Vec<(Request, PeerRequestResult<SnapResponse>)>,
+    /// Delay before the next requests, after a request failed.
+    retry: Option<Pin<Box<Sleep>>>,
+    /// The download state of the current pivot.
+    state: Option<SyncState>,
+}
+
+impl<C: SnapClient> std::fmt::Debug for SnapSyncStage<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapSyncStage")
+            .field("config", &self.config)
+            .field("requests", &self.requests.len())
+            .field("responses", &self.responses.len())
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: SnapClient> SnapSyncStage<C> {
+    /// Creates a new snap sync stage that downloads the state with the given client.
+    pub fn new(client: C, config: SnapSyncConfig) -> Self {
+        Self {
+            client,
+            config,
+            requests: FuturesUnordered::new(),
+            responses: Vec::new(),
+            retry: None,
+            state: None,
+        }
+    }
+
+    /// Sends the request for the tasks to a peer.
+    fn send(&self, root: B256, request: Request) {
+        let response_bytes = self.config.response_bytes;
+        let response = match &request {
+            Request::AccountRange { origin, limit } => {
+                self.client.get_account_range(GetAccountRangeMessage {
+                    request_id: 0,
+                    root_hash: root,
+                    starting_hash: *origin,
+                    limit_hash: *limit,
+                    response_bytes,
+                })
+            }
+            Request::StorageRanges(tasks) => {
+                self.client.get_storage_ranges(GetStorageRangesMessage {
+                    request_id: 0,
+                    root_hash: root,
+                    account_hashes: tasks.iter().map(|task| task.hashed_address).collect(),
+                    starting_hash: tasks[0].origin,
+                    limit_hash: B256::repeat_byte(0xff),
+                    response_bytes,
+                })
+            }
+            Request::ByteCodes(hashes) => self.client.get_byte_codes(GetByteCodesMessage {
+                request_id: 0,
+                hashes: hashes.clone(),
+                response_bytes,
+            }),
+            Request::TrieNodes(nodes) => self.client.get_trie_nodes(GetTrieNodesMessage {
+                request_id: 0,
+                root_hash: root,
+                paths: nodes
+                    .iter()
+                    .map(|(path, _)| TriePath {
+                        account_path: encode_path(path),
+                        slot_paths: vec![],
+                    })
+                    .collect(),
+                response_bytes,
+            }),
+        };
+        self.requests.push(RequestFuture { request: Some(request), response });
+    }
+}
+
+impl<C, Provider> Stage<Provider> for SnapSyncStage<C>
+where
+    C: SnapClient + 'static,
+    Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + PruneCheckpointWriter
+        + TrieWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::SnapSync
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        _input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if let Some(retry) = &mut self.retry {
+            ready!(retry.as_mut().poll(cx));
+            self.retry = None;
+        }
+
+        while let Poll::Ready(Some(response)) = self.requests.poll_next_unpin(cx) {
+            self.responses.push(response);
+        }
+
+        if self.requests.is_empty() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        let target = input.target();
+        let checkpoint = input.checkpoint();
+        if checkpoint.snap_sync_pivot().is_some() {
+            return Ok(ExecOutput::done(StageCheckpoint { block_number: target, ..checkpoint }))
+        }
+
+        let tx = provider.tx_ref();
+        let mut unit = match checkpoint.snap_sync_stage_checkpoint() {
+            Some(unit) => unit,
+            // Only the execution of the blocks since genesis can be replaced
+            None if target == 0 ||
+                provider
+                    .get_stage_checkpoint(StageId::Execution)?
+                    .unwrap_or_default()
+                    .block_number >
+                    0 =>
+            {
+                return Ok(ExecOutput::done(StageCheckpoint::new(target)))
+            }
+            None => {
+                if !provider.prune_modes_ref().has_receipts_pruning() {
+                    return Err(StageError::Fatal(Box::new(SnapSyncError::ReceiptsPruningRequired)))
+                }
+
+                info!(target: "sync::stages::snap_sync", "Clearing state for snap sync");
+                tx.clear::<tables::PlainAccountState>()?;
+                tx.clear::<tables::PlainStorageState>()?;
+                tx.clear::<tables::HashedAccounts>()?;
+                tx.clear::<tables::HashedStorages>()?;
+                tx.clear::<tables::AccountsTrie>()?;
+                tx.clear::<tables::StoragesTrie>()?;
+                SnapSyncCheckpoint {
+                    pivot: target,
+                    next_account: Some(B256::ZERO),
+                    progress: EntitiesCheckpoint { processed: 0, total: u64::MAX },
+                }
+            }
+        };
+
+        if self.state.as_ref().is_none_or(|state| state.pivot != target) {
+            let root = provider
+                .header_by_number(target)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(target.into()))?
+                .state_root();
+            info!(target: "sync::stages::snap_sync", previous = unit.pivot, pivot = target, ?root, "Downloading state of pivot block");
+            // Responses for the previous root can't be checked against the new one
+            self.responses.clear();
+            match &mut self.state {
+                Some(state) => state.set_pivot(target, root),
+                None => self.state = Some(SyncState::new(target, root)),
+            }
+            unit.pivot = target;
+        }
+        let state = self.state.as_mut().expect("state is set");
+
+        let mut failed = false;
+        for (request, response) in std::mem::take(&mut self.responses) {
+            failed |= !state.on_response(tx, &self.client, request, response)?;
+        }
+
+        // The state isn't served anymore, a new pipeline run brings a newer pivot
+        let unavailable = state.unavailable.len();
+        if unavailable > 0 && unavailable >= self.client.num_connected_peers() {
+            info!(target: "sync::stages::snap_sync", pivot = state.pivot, unavailable, "State of pivot block is no longer served");
+            state.unavailable.clear();
+            return Ok(ExecOutput::done(checkpoint.with_snap_sync_stage_checkpoint(unit)))
+        }
+
+        if state.batch.as_ref().is_some_and(AccountBatch::is_complete) {
+            let batch = state.batch.take().expect("batch exists");
+            let (end, complete) = (batch.end, batch.complete);
+            state.commit(tx, batch)?;
+            if state.phase == Phase::Download {
+                unit.next_account = if complete { None } else { next_key(end) };
+                unit.progress.processed = unit.next_account.map_or(u64::MAX, key_progress);
+            }
+        }
+
+        if failed {
+            self.retry = Some(Box::pin(tokio::time::sleep(RETRY_DELAY)));
+            return Ok(ExecOutput::in_progress(checkpoint.with_snap_sync_stage_checkpoint(unit)))
+        }
+
+        let root = state.root;
+        let mut requests = Vec::new();
+        if let Some(batch) = &mut state.batch {
+            batch.next_requests(&mut requests);
+        } else if state.phase == Phase::Download {
+            if let Some(origin) = unit.next_account {
+                requests.push(Request::AccountRange { origin, limit: B256::repeat_byte(0xff) });
+            } else {
+                info!(target: "sync::stages::snap_sync", pivot = state.pivot, "Rebuilding trie of downloaded state");
+                let local_root = rebuild_trie(provider)?;
+                if local_root == root {
+                    return finish(provider, unit)
+                }
+
+                info!(target: "sync::stages::snap_sync", pivot = state.pivot, ?local_root, ?root, "Healing downloaded state");
+                state.heal();
+            }
+        }
+
+        if requests.is_empty() && state.batch.is_none() && state.phase == Phase::Heal {
+            if let Some((origin, limit)) = state.heal_ranges.pop_front() {
+                requests.push(Request::AccountRange { origin, limit });
+            } else if !state.heal_nodes.is_empty() {
+                while !state.heal_nodes.is_empty() && requests.len() < MAX_INFLIGHT_REQUESTS {
+                    let count = state.heal_nodes.len().min(MAX_TRIE_NODES);
+                    requests.push(Request::TrieNodes(state.heal_nodes.drain(..count).collect()));
+                }
+            } else {
+                let prefix_sets = std::mem::take(&mut state.prefix_sets);
+                let healed = !prefix_sets.is_empty();
+                let (local_root, updates) = StateRoot::from_tx(tx)
+                    .with_prefix_sets(prefix_sets.freeze())
+                    .root_with_updates()
+                    .map_err(|err| StageError::Fatal(Box::new(err)))?;
+                provider.write_trie_updates(&updates)?;
+                if local_root == root {
+                    return finish(provider, unit)
+                }
+
+                if healed {
+                    debug!(target: "sync::stages::snap_sync", ?local_root, ?root, "Continuing to heal state");
+                    state.heal();
+                } else {
+                    // The trie can't be healed from its nodes, so every account is compared again
+                    info!(target: "sync::stages::snap_sync", ?local_root, ?root, "Downloading all accounts again");
+                    state.phase = Phase::Download;
+                    unit.next_account = Some(B256::ZERO);
+                    unit.progress.processed = 0;
+                }
+            }
+        }
+
+        for request in requests {
+            trace!(target: "sync::stages::snap_sync", ?request, "Sending snap request");
+            self.send(root, request);
+        }
+
+        Ok(ExecOutput::in_progress(checkpoint.with_snap_sync_stage_checkpoint(unit)))
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        _provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        if let Some(pivot) = input.checkpoint.snap_sync_pivot() {
+            if input.unwind_to < pivot {
+                return Err(StageError::Fatal(Box::new(SnapSyncError::UnwindBelowPivot {
+                    pivot,
+                    unwind_to: input.unwind_to,
+                })))
+            }
+        }
+
+        Ok(UnwindOutput {
+            checkpoint: StageCheckpoint { block_number: input.unwind_to, ..input.checkpoint },
+        })
+    }
+}
+
+/// Rebuilds the trie tables from the hashed state tables, and returns the state root.
+fn rebuild_trie<Provider>(provider: &Provider) -> Result<B256, StageError>
+where
+    Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+{
+    let tx = provider.tx_ref();
+    tx.clear::<tables::AccountsTrie>()?;
+    tx.clear::<tables::StoragesTrie>()?;
+
+    let mut intermediate_state = None;
+    loop {
+        let progress = StateRoot::from_tx(tx)
+            .with_intermediate_state(intermediate_state.take())
+            .root_with_progress()
+            .map_err(|err| StageError::Fatal(Box::new(err)))?;
+        match progress {
+            StateRootProgress::Progress(state, _, updates) => {
+                provider.write_trie_updates(&updates)?;
+                intermediate_state = Some(*state);
+            }
+            StateRootProgress::Complete(root, _, updates) => {
+                provider.write_trie_updates(&updates)?;
+                return Ok(root)
+            }
+        }
+    }
+}
+
+/// Moves the stages replaced by the download of the state to the pivot block.
+fn finish<Provider>(
+    provider: &Provider,
+    mut unit: SnapSyncCheckpoint,
+) -> Result<ExecOutput, StageError>
+where
+    Provider: StageCheckpointWriter + PruneCheckpointWriter,
+{
+    let pivot = unit.pivot;
+    info!(target: "sync::stages::snap_sync", pivot, "Downloaded state of pivot block");
+
+    for stage_id in REPLACED_STAGES {
+        provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(pivot))?;
+    }
+    // The history before the pivot doesn't exist, which is the same as if it was pruned
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        provider.save_prune_checkpoint(
+            segment,
+            PruneCheckpoint {
+                block_number: Some(pivot),
+                tx_number: None,
+                prune_mode: PruneMode::Before(pivot + 1),
+            },
+        )?;
+    }
+
+    unit.next_account = None;
+    unit.progress.processed = unit.progress.total;
+    Ok(ExecOutput::done(StageCheckpoint::new(pivot).with_snap_sync_stage_checkpoint(unit)))
+}
+
+/// Returns the key after the given key, or `None` if it's the last key.
+fn next_key(key: B256) -> Option<B256> {
+    U256::from_be_bytes(key.0).checked_add(U256::from(1)).map(|key| B256::from(key.to_be_bytes()))
+}
+
+/// Returns the progress of the download up to the given key, in the units of
+/// [`SnapSyncCheckpoint::progress`].
+fn key_progress(key: B256) -> u64 {
+    u64::from_be_bytes(key[..8].try_into().expect("slice has 8 bytes"))
+}
+
+/// Returns the range of keys below the trie node at the path.
+fn path_range(path: &Nibbles) -> (B256, B256) {
+    let pad = |nibble| {
+        let mut key = path.clone();
+        key.extend_from_slice_unchecked(&[nibble; 64][..64 - path.len()]);
+        B256::from_slice(&key.pack())
+    };
+    (pad(0), pad(0xf))
+}
+
+/// Returns the storage root of the account from the hashed storage table.
+fn storage_root<TX: DbTx>(tx: &TX, hashed_address: B256) -> Result<B256, StageError> {
+    if tx.cursor_dup_read::<tables::HashedStorages>()?.seek_exact(hashed_address)?.is_none() {
+        return Ok(EMPTY_ROOT_HASH)
+    }
+
+    StorageRoot::from_tx_hashed(tx, hashed_address)
+        .with_prefix_set(PrefixSetMut::all().freeze())
+        .root()
+        .map_err(|err| StageError::Fatal(Box::new(err)))
+}
+
+/// Deletes the storage of the account and its trie.
+fn delete_storage<TX: DbTxMut + DbTx>(tx: &TX, hashed_address: B256) -> Result<(), StageError> {
+    let mut storage_cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+    if storage_cursor.seek_exact(hashed_address)?.is_some() {
+        storage_cursor.delete_current_duplicates()?;
+    }
+    let mut trie_cursor = tx.cursor_dup_write::<tables::StoragesTrie>()?;
+    if trie_cursor.seek_exact(hashed_address)?.is_some() {
+        trie_cursor.delete_current_duplicates()?;
+    }
+    Ok(())
+}
+
+/// Request for a peer, with the tasks it's sent for.
+#[derive(Debug)]
+enum Request {
+    /// Request for the accounts in the range.
+    AccountRange { origin: B256, limit: B256 },
+    /// Request for the storages of accounts, where only the first one may continue from a slot.
+    StorageRanges(Vec<StorageTask>),
+    /// Request for bytecodes by hash.
+    ByteCodes(Vec<B256>),
+    /// Request for account trie nodes by path, with their expected hashes.
+    TrieNodes(Vec<(Nibbles, B256)>),
+}
+
+/// Future resolving to the response of a request, together with the request.
+#[derive(Debug)]
+struct RequestFuture<F> {
+    request: Option<Request>,
+    response: F,
+}
+
+impl<F> Future for RequestFuture<F>
+where
+    F: Future<Output = PeerRequestResult<SnapResponse>> + Unpin,
+{
+    type Output = (Request, PeerRequestResult<SnapResponse>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let response = ready!(this.response.poll_unpin(cx));
+        Poll::Ready((this.request.take().expect("polled after completion"), response))
+    }
+}
+
+/// The storage of an account to download.
+#[derive(Debug, Clone, Copy)]
+struct StorageTask {
+    hashed_address: B256,
+    /// The storage root of the account in the pivot state.
+    root: B256,
+    /// The next slot to download.
+    origin: B256,
+}
+
+/// The phase of the download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Accounts are downloaded from the next account of the checkpoint.
+    Download,
+    /// The differences to the trie of the pivot are healed.
+    Heal,
+}
+
+/// A downloaded range of accounts, which is written once their storages and bytecodes are
+/// downloaded.
+#[derive(Debug)]
+struct AccountBatch {
+    /// The first key of the range.
+    origin: B256,
+    /// The requested last key of the range.
+    limit: B256,
+    /// The last key of the range.
+    end: B256,
+    /// Whether the range covers all keys up to the requested limit.
+    complete: bool,
+    accounts: Vec<(B256, Account)>,
+    /// Storages that need to be downloaded.
+    storages: VecDeque<StorageTask>,
+    /// Bytecodes that need to be downloaded.
+    codes: Vec<B256>,
+}
+
+impl AccountBatch {
+    /// Returns `true` if nothing needs to be downloaded anymore.
+    fn is_complete(&self) -> bool {
+        self.storages.is_empty() && self.codes.is_empty()
+    }
+
+    /// Adds the requests for the storages and bytecodes of the accounts.
+    fn next_requests(&mut self, requests: &mut Vec<Request>) {
+        while requests.len() < MAX_INFLIGHT_REQUESTS && !self.codes.is_empty() {
+            let count = self.codes.len().min(MAX_CODES);
+            requests.push(Request::ByteCodes(self.codes.drain(..count).collect()));
+        }
+
+        while requests.len() < MAX_INFLIGHT_REQUESTS {
+            let Some(task) = self.storages.pop_front() else { break };
+            let mut tasks = vec![task];
+            // Storages continuing from a slot are requested on their own
+            if task.origin.is_zero() {
+                while tasks.len() < MAX_STORAGE_ACCOUNTS &&
+                    self.storages.front().is_some_and(|task| task.origin.is_zero())
+                {
+                    tasks.extend(self.storages.pop_front());
+                }
+            }
+            requests.push(Request::StorageRanges(tasks));
+        }
+    }
+}
+
+/// The download state of a pivot, which is lost on restart and rebuilt from the checkpoint.
+#[derive(Debug)]
+struct SyncState {
+    pivot: BlockNumber,
+    /// The state root of the pivot.
+    root: B256,
+    phase: Phase,
+    /// The downloaded accounts that aren't written yet.
+    batch: Option<AccountBatch>,
+    /// Ranges of accounts to download again.
+    heal_ranges: VecDeque<(B256, B256)>,
+    /// Paths of the trie nodes of the pivot to compare with the local trie, with their hashes.
+    heal_nodes: Vec<(Nibbles, B256)>,
+    /// Changes to the hashed state that the trie isn't updated with yet, while healing.
+    prefix_sets: TriePrefixSetsMut,
+    /// Peers that didn't serve the state of the pivot.
+    unavailable: HashSet<PeerId>,
+}
+
+impl SyncState {
+    fn new(pivot: BlockNumber, root: B256) -> Self {
+        Self {
+            pivot,
+            root,
+            phase: Phase::Download,
+            batch: None,
+            heal_ranges: VecDeque::new(),
+            heal_nodes: Vec::new(),
+            prefix_sets: TriePrefixSetsMut::default(),
+            unavailable: HashSet::default(),
+        }
+    }
+
+    /// Continues the download with a new pivot.
+    ///
+    /// Data that was downloaded for the previous pivot is kept and healed later on.
+    fn set_pivot(&mut self, pivot: BlockNumber, root: B256) {
+        self.pivot = pivot;
+        self.root = root;
+        self.unavailable.clear();
+        // The nodes are those of the previous trie
+        self.heal_nodes.clear();
+        if let Some(batch) = self.batch.take() {
+            if self.phase == Phase::Heal {
+                self.heal_ranges.push_front((batch.origin, batch.limit));
+            }
+        }
+    }
+
+    /// Starts to heal the state from the root of the pivot.
+    fn heal(&mut self) {
+        self.phase = Phase::Heal;
+        self.heal_nodes.push((Nibbles::default(), self.root));
+    }
+
+    /// Processes the response to a request, and returns `false` if the request failed.
+    fn on_response<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        client: &impl DownloadClient,
+        request: Request,
+        response: PeerRequestResult<SnapResponse>,
+    ) -> Result<bool, StageError> {
+        let response = match response {
+            Ok(response) => response,
+            Err(err) => {
+                debug!(target: "sync::stages::snap_sync", %err, "Snap request failed");
+                self.requeue(request);
+                return Ok(false)
+            }
+        };
+
+        let (peer_id, response) = response.split();
+        let success = match (request, response) {
+            (Request::AccountRange { origin, limit }, SnapResponse::AccountRange(response)) => {
+                self.on_account_range(tx, client, peer_id, origin, limit, response)?
+            }
+            (Request::StorageRanges(tasks), SnapResponse::StorageRanges(response)) => {
+                self.on_storage_ranges(tx, client, peer_id, tasks, response)?
+            }
+            (Request::ByteCodes(hashes), SnapResponse::ByteCodes(response)) => {
+                if response.codes.is_empty() {
+                    self.unavailable.insert(peer_id);
+                }
+
+                let mut codes = response
+                    .codes
+                    .into_iter()
+                    .map(|code| (keccak256(&code), code))
+                    .collect::<B256Map<_>>();
+                let mut missing = Vec::new();
+                for hash in hashes {
+                    match codes.remove(&hash) {
+                        Some(code) => tx.put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?,
+                        None => missing.push(hash),
+                    }
+                }
+
+                let success = missing.is_empty();
+                self.requeue(Request::ByteCodes(missing));
+                success
+            }
+            (Request::TrieNodes(nodes), SnapResponse::TrieNodes(response)) => {
+                if response.nodes.is_empty() {
+                    self.unavailable.insert(peer_id);
+                }
+
+                let mut missing = Vec::new();
+                let mut responses = response.nodes.into_iter();
+                for (path, hash) in nodes {
+                    let node = responses.next().filter(|node| !node.is_empty());
+                    match node {
+                        Some(node) if keccak256(&node) != hash => {
+                            debug!(target: "sync::stages::snap_sync", %peer_id, ?path, "Received trie node with wrong hash");
+                            client.report_bad_message(peer_id);
+                            missing.push((path, hash));
+                        }
+                        Some(node) => {
+                            if !self.on_trie_node(tx, path.clone(), &node)? {
+                                client.report_bad_message(peer_id);
+                                missing.push((path, hash));
+                            }
+                        }
+                        None => missing.push((path, hash)),
+                    }
+                }
+
+                let success = missing.is_empty();
+                self.requeue(Request::TrieNodes(missing));
+                success
+            }
+            (request, _) => {
+                debug!(target: "sync::stages::snap_sync", %peer_id, "Received response of wrong type");
+                client.report_bad_message(peer_id);
+                self.requeue(request);
+                false
+            }
+        };
+
+        Ok(success)
+    }
+
+    /// Puts the tasks of the request back into the queue.
+    fn requeue(&mut self, request: Request) {
+        match request {
+            // Accounts of the download are requested again from the checkpoint
+            Request::AccountRange { origin, limit } => {
+                if self.phase == Phase::Heal {
+                    self.heal_ranges.push_front((origin, limit));
+                }
+            }
+            Request::StorageRanges(tasks) => {
+                if let Some(batch) = &mut self.batch {
+                    for task in tasks.into_iter().rev() {
+                        batch.storages.push_front(task);
+                    }
+                }
+            }
+            Request::ByteCodes(hashes) => {
+                if let Some(batch) = &mut self.batch {
+                    batch.codes.extend(hashes);
+                }
+            }
+            Request::TrieNodes(nodes) => self.heal_nodes.extend(nodes),
+        }
+    }
+
+    /// Processes a range of accounts, and returns `false` if the response is invalid.
+    fn on_account_range<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        client: &impl DownloadClient,
+        peer_id: PeerId,
+        origin: B256,
+        limit: B256,
+        response: AccountRangeMessage,
+    ) -> Result<bool, StageError> {
+        if response.accounts.is_empty() && response.proof.is_empty() {
+            self.unavailable.insert(peer_id);
+            self.requeue(Request::AccountRange { origin, limit });
+            return Ok(false)
+        }
+
+        // Without a proof, the response contains all accounts after the origin
+        let mut complete = response.proof.is_empty();
+        let mut accounts = Vec::with_capacity(response.accounts.len());
+        for AccountData { hash, body } in response.accounts {
+            if hash > limit {
+                complete = true;
+                break
+            }
+
+            let account = decode_slim_account(&body)
+                .ok()
+                .filter(|_| hash >= origin && accounts.last().is_none_or(|(last, _)| hash > *last));
+            let Some(account) = account else {
+                debug!(target: "sync::stages::snap_sync", %peer_id, "Received invalid account range");
+                client.report_bad_message(peer_id);
+                self.requeue(Request::AccountRange { origin, limit });
+                return Ok(false)
+            };
+            accounts.push((hash, account));
+        }
+
+        let end = match accounts.last() {
+            Some((last, _)) if !complete && *last != limit => *last,
+            _ => {
+                complete = true;
+                limit
+            }
+        };
+        self.batch = Some(self.new_batch(tx, (origin, limit), end, complete, accounts)?);
+        Ok(true)
+    }
+
+    /// Creates the batch for the downloaded accounts, with the storages and bytecodes that differ
+    /// from the local state.
+    fn new_batch<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        (origin, limit): (B256, B256),
+        end: B256,
+        complete: bool,
+        accounts: Vec<(B256, TrieAccount)>,
+    ) -> Result<AccountBatch, StageError> {
+        let mut storages = VecDeque::new();
+        let mut codes = B256Set::default();
+        let mut batch_accounts = Vec::with_capacity(accounts.len());
+        for (hashed_address, account) in accounts {
+            if storage_root(tx, hashed_address)? != account.storage_root {
+                if account.storage_root == EMPTY_ROOT_HASH {
+                    delete_storage(tx, hashed_address)?;
+                    self.storage_changed(hashed_address);
+                } else {
+                    storages.push_back(StorageTask {
+                        hashed_address,
+                        root: account.storage_root,
+                        origin: B256::ZERO,
+                    });
+                }
+            }
+
+            if account.code_hash != KECCAK_EMPTY &&
+                tx.get::<tables::Bytecodes>(account.code_hash)?.is_none()
+            {
+                codes.insert(account.code_hash);
+            }
+
+            batch_accounts.push((
+                hashed_address,
+                Account {
+                    nonce: account.nonce,
+                    balance: account.balance,
+                    bytecode_hash: Some(account.code_hash).filter(|hash| *hash != KECCAK_EMPTY),
+                },
+            ));
+        }
+
+        Ok(AccountBatch {
+            origin,
+            limit,
+            end,
+            complete,
+            accounts: batch_accounts,
+            storages,
+            codes: codes.into_iter().collect(),
+        })
+    }
+
+    /// Processes storage ranges, and returns `false` if the response is invalid.
+    fn on_storage_ranges<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        client: &impl DownloadClient,
+        peer_id: PeerId,
+        tasks: Vec<StorageTask>,
+        response: StorageRangesMessage,
+    ) -> Result<bool, StageError> {
+        if response.slots.is_empty() && response.proof.is_empty() {
+            self.unavailable.insert(peer_id);
+            self.requeue(Request::StorageRanges(tasks));
+            return Ok(false)
+        }
+
+        // A proof without slots ends the storage of the first account
+        let served = response.slots.len().max(usize::from(!response.proof.is_empty()));
+        let last = served - 1;
+        let mut slots = response.slots.into_iter();
+        let mut tasks = tasks.into_iter();
+        let mut valid = true;
+        let mut requeue = Vec::new();
+        for (index, mut task) in tasks.by_ref().take(served).enumerate() {
+            let slots = slots.next().unwrap_or_default();
+            let partial = index == last && !response.proof.is_empty() && !slots.is_empty();
+            let Some(next_origin) = self.write_slots(tx, &task, slots)? else {
+                valid = false;
+                requeue.push(StorageTask { origin: B256::ZERO, ..task });
+                continue
+            };
+
+            if let Some(origin) = next_origin.filter(|_| partial) {
+                requeue.push(StorageTask { origin, ..task });
+                continue
+            }
+
+            task.origin = B256::ZERO;
+            if storage_root(tx, task.hashed_address)? != task.root {
+                debug!(target: "sync::stages::snap_sync", %peer_id, hashed_address = ?task.hashed_address, "Downloaded storage doesn't match storage root");
+                valid = false;
+                requeue.push(task);
+            }
+        }
+        requeue.extend(tasks);
+
+        if !valid {
+            client.report_bad_message(peer_id);
+        }
+        self.requeue(Request::StorageRanges(requeue));
+        Ok(valid)
+    }
+
+    /// Writes the slots of a storage task.
+    ///
+    /// Returns the slot after the last written slot, or `None` if the slots are invalid.
+    fn write_slots<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        task: &StorageTask,
+        slots: Vec<StorageData>,
+    ) -> Result<Option<Option<B256>>, StageError> {
+        let mut entries = Vec::with_capacity(slots.len());
+        for StorageData { hash, data } in slots {
+            let value = U256::decode(&mut &data[..]).ok().filter(|_| {
+                hash >= task.origin &&
+                    entries.last().is_none_or(|entry: &StorageEntry| hash > entry.key)
+            });
+            let Some(value) = value else { return Ok(None) };
+            entries.push(StorageEntry { key: hash, value });
+        }
+
+        // The first range replaces the storage
+        if task.origin.is_zero() {
+            delete_storage(tx, task.hashed_address)?;
+        }
+        self.storage_changed(task.hashed_address);
+
+        let next_origin = entries.last().map_or(Some(task.origin), |entry| next_key(entry.key));
+        let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+        for entry in entries {
+            if !entry.value.is_zero() {
+                cursor.upsert(task.hashed_address, &entry)?;
+            }
+        }
+        Ok(Some(next_origin))
+    }
+
+    /// Writes the accounts of the batch, replacing the local accounts in its range.
+    fn commit<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        batch: AccountBatch,
+    ) -> Result<(), StageError> {
+        let accounts =
+            batch.accounts.iter().map(|(hashed_address, _)| *hashed_address).collect::<B256Set>();
+
+        let mut cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+        let mut entry = cursor.seek(batch.origin)?;
+        while let Some((hashed_address, _)) = entry.filter(|(key, _)| *key <= batch.end) {
+            if !accounts.contains(&hashed_address) {
+                cursor.delete_current()?;
+                delete_storage(tx, hashed_address)?;
+                if self.phase == Phase::Heal {
+                    self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+                    self.prefix_sets.destroyed_accounts.insert(hashed_address);
+                }
+            }
+            entry = cursor.next()?;
+        }
+
+        for (hashed_address, account) in batch.accounts {
+            cursor.upsert(hashed_address, &account)?;
+            if self.phase == Phase::Heal {
+                self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the change of the storage of the account, while healing.
+    fn storage_changed(&mut self, hashed_address: B256) {
+        if self.phase == Phase::Heal {
+            self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+            self.prefix_sets.storage_prefix_sets.insert(hashed_address, PrefixSetMut::all());
+        }
+    }
+
+    /// Compares the trie node of the pivot at the path with the local trie, and queues the
+    /// differences to heal.
+    ///
+    /// Returns `false` if the node can't be decoded.
+    fn on_trie_node<TX: DbTx>(
+        &mut self,
+        tx: &TX,
+        path: Nibbles,
+        node: &Bytes,
+    ) -> Result<bool, StageError> {
+        let Ok(node) = TrieNode::decode(&mut &node[..]) else { return Ok(false) };
+        let TrieNode::Branch(branch) = node else {
+            // Leaves and extensions are small enough to download the accounts below them
+            self.heal_ranges.push_back(path_range(&path));
+            return Ok(true)
+        };
+
+        // The root node isn't stored, so its children are compared instead
+        let local = tx.get::<tables::AccountsTrie>(StoredNibbles(path.clone()))?;
+        if local.is_none() && !path.is_empty() {
+            self.heal_ranges.push_back(path_range(&path));
+            return Ok(true)
+        }
+
+        let local_child = |nibble: u8| {
+            local.as_ref().map(|local: &BranchNodeCompact| {
+                let hash =
+                    local.hash_mask.is_bit_set(nibble).then(|| local.hash_for_nibble(nibble));
+                (local.state_mask.is_bit_set(nibble), local.tree_mask.is_bit_set(nibble), hash)
+            })
+        };
+
+        for (nibble, child) in branch.as_ref().children() {
+            let mut child_path = path.clone();
+            child_path.push(nibble);
+
+            let remote_hash = child.and_then(|child| child.as_hash());
+            match (child, remote_hash, local_child(nibble)) {
+                // Neither trie has the child
+                (None, _, None | Some((false, _, _))) => {}
+                // Children are compared by their hashes
+                (Some(_), Some(hash), Some((_, _, Some(local_hash)))) if hash == local_hash => {}
+                // Branches below the child are compared node by node
+                (Some(_), Some(hash), None | Some((_, true, _))) => {
+                    self.heal_nodes.push((child_path, hash));
+                }
+                _ => self.heal_ranges.push_back(path_range(&child_path)),
+            }
+        }
+
+        Ok(true)
+    }
+}
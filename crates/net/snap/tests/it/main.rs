@@ -0,0 +1,5 @@
+#![allow(missing_docs)]
+
+mod sync;
+
+const fn main() {}
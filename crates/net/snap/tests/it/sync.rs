@@ -0,0 +1,283 @@
+//! Snap sync between peers of a local network.
+
+use alloy_consensus::{BlockHeader, Header};
+use alloy_genesis::{Genesis, GenesisAccount};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use reth_chainspec::ChainSpec;
+use reth_config::config::SnapSyncConfig;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::{AccountBeforeTx, BlockNumberAddress},
+    table::Table,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_db_common::init::init_genesis;
+use reth_net_snap::{SnapFetchClient, SnapProtocolHandler, SnapRequestHandler, SnapSyncStage};
+use reth_network::test_utils::{Testnet, TestnetHandle};
+use reth_network_p2p::download::DownloadClient;
+use reth_primitives_traits::{Account, StorageEntry};
+use reth_provider::{
+    providers::{BlockchainProvider, StaticFileWriter},
+    test_utils::{
+        create_test_provider_factory_with_chain_spec, MockEthProvider, MockNodeTypesWithDB,
+    },
+    DatabaseProviderFactory, HeaderProvider, ProviderFactory, StageCheckpointReader,
+    StageCheckpointWriter, StateWriter, StaticFileProviderFactory, StaticFileSegment, TrieWriter,
+};
+use reth_prune_types::{PruneMode, PruneModes};
+use reth_stages_api::{ExecInput, ExecOutput, Stage, StageCheckpoint, StageId};
+use reth_transaction_pool::test_utils::TestPool;
+use reth_trie::{HashedPostState, HashedStorage, StateRoot};
+use reth_trie_db::DatabaseStateRoot;
+use std::{collections::BTreeMap, future::poll_fn, sync::Arc, time::Duration};
+use tokio::sync::mpsc;
+
+type Factory = ProviderFactory<MockNodeTypesWithDB>;
+
+type ProviderRW = <Factory as DatabaseProviderFactory>::ProviderRW;
+
+const CONTRACT: Address = Address::with_last_byte(0xff);
+
+fn address(i: u64) -> Address {
+    Address::from_word(B256::from(U256::from(i)))
+}
+
+fn slot(i: u64) -> B256 {
+    B256::from(U256::from(i))
+}
+
+/// Returns a chain spec whose genesis has enough accounts and storage to need several requests
+/// with small responses.
+fn chain_spec() -> Arc<ChainSpec> {
+    let mut alloc = (1..=200)
+        .map(|i| (address(i), GenesisAccount { balance: U256::from(i), ..Default::default() }))
+        .collect::<BTreeMap<_, _>>();
+    alloc.insert(
+        CONTRACT,
+        GenesisAccount {
+            code: Some(Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3])),
+            storage: Some((1..=500).map(|i| (slot(i), slot(i))).collect()),
+            ..Default::default()
+        },
+    );
+    Arc::new(ChainSpec::from(Genesis { alloc, ..Default::default() }))
+}
+
+/// Appends the header of the next block, with the given state root.
+fn append_header(factory: &Factory, number: u64, state_root: B256) {
+    let parent = factory.header_by_number(number - 1).unwrap().unwrap();
+    let header =
+        Header { number, parent_hash: parent.hash_slow(), state_root, ..Default::default() };
+
+    let provider_rw = factory.database_provider_rw().unwrap();
+    let static_file_provider = provider_rw.static_file_provider();
+    let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+    writer.append_header(&header, U256::ZERO, &header.hash_slow()).unwrap();
+    writer.commit().unwrap();
+    provider_rw.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(number)).unwrap();
+    provider_rw.commit().unwrap();
+}
+
+/// Commits block 2 on the serving node, which changes the balances of some accounts, removes an
+/// account and changes a storage slot of the contract. Returns the new state root.
+fn advance(server: &Factory) -> B256 {
+    let provider_rw = server.database_provider_rw().unwrap();
+    let tx = provider_rw.tx_ref();
+
+    let mut state = HashedPostState::default();
+    for i in 1..=20 {
+        let info = tx.get::<tables::PlainAccountState>(address(i)).unwrap();
+        tx.put::<tables::AccountChangeSets>(2, AccountBeforeTx { address: address(i), info })
+            .unwrap();
+        let account =
+            (i != 20).then(|| Account { balance: U256::from(1000 + i), ..Default::default() });
+        state.accounts.insert(keccak256(address(i)), account);
+    }
+    tx.put::<tables::StorageChangeSets>(
+        BlockNumberAddress((2, CONTRACT)),
+        StorageEntry { key: slot(1), value: U256::from(1) },
+    )
+    .unwrap();
+    state.storages.insert(
+        keccak256(CONTRACT),
+        HashedStorage::from_iter(false, [(keccak256(slot(1)), U256::from(1000))]),
+    );
+
+    let (state_root, updates) = StateRoot::overlay_root_with_updates(tx, state.clone()).unwrap();
+    provider_rw.write_hashed_state(&state.into_sorted()).unwrap();
+    provider_rw.write_trie_updates(&updates).unwrap();
+    provider_rw.commit().unwrap();
+
+    append_header(server, 2, state_root);
+    let provider_rw = server.database_provider_rw().unwrap();
+    provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(2)).unwrap();
+    provider_rw.commit().unwrap();
+
+    state_root
+}
+
+/// Returns all the entries of the table.
+fn entries<T: Table>(factory: &Factory) -> Vec<(T::Key, T::Value)> {
+    let provider = factory.database_provider_ro().unwrap();
+    provider.tx_ref().cursor_read::<T>().unwrap().walk(None).unwrap().map(Result::unwrap).collect()
+}
+
+/// A serving node that has finished syncing block 1, and a syncing node that has downloaded its
+/// header, connected over a local network.
+struct Setup {
+    server: Factory,
+    syncing: Factory,
+    client: SnapFetchClient,
+    _network: TestnetHandle<MockEthProvider, TestPool>,
+}
+
+impl Setup {
+    async fn new() -> Self {
+        let server = create_test_provider_factory_with_chain_spec(chain_spec());
+        init_genesis(&server).unwrap();
+        let genesis_root = server.header_by_number(0).unwrap().unwrap().state_root();
+        append_header(&server, 1, genesis_root);
+        let provider_rw = server.database_provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(1)).unwrap();
+        provider_rw.commit().unwrap();
+
+        // Snap sync requires receipts pruning
+        let syncing = create_test_provider_factory_with_chain_spec(chain_spec())
+            .with_prune_modes(PruneModes { receipts: Some(PruneMode::Full), ..Default::default() });
+        init_genesis(&syncing).unwrap();
+        append_header(&syncing, 1, genesis_root);
+
+        let mut net = Testnet::create_with(2, MockEthProvider::default()).await;
+
+        let (tx, rx) = mpsc::channel(100);
+        tokio::spawn(SnapRequestHandler::new(BlockchainProvider::new(server.clone()).unwrap(), rx));
+        net.peers_mut()[0].add_rlpx_sub_protocol(SnapProtocolHandler::new(Some(tx)));
+
+        let handler = SnapProtocolHandler::new(None);
+        let client = handler.client();
+        net.peers_mut()[1].add_rlpx_sub_protocol(handler);
+
+        let network = net.spawn();
+        network.connect_peers().await;
+
+        // Wait for the snap connection with the serving node
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while client.num_connected_peers() == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        Self { server, syncing, client, _network: network }
+    }
+
+    fn stage(&self) -> SnapSyncStage<SnapFetchClient> {
+        SnapSyncStage::new(
+            self.client.clone(),
+            SnapSyncConfig { enabled: true, response_bytes: 1024 },
+        )
+    }
+
+    /// Runs the stage like the pipeline does, until `stop` returns true for its output.
+    async fn run(
+        &self,
+        stage: &mut SnapSyncStage<SnapFetchClient>,
+        mut input: ExecInput,
+        stop: impl Fn(&ExecOutput) -> bool,
+    ) -> ExecOutput {
+        tokio::time::timeout(Duration::from_secs(60), async {
+            loop {
+                poll_fn(|cx| <_ as Stage<ProviderRW>>::poll_execute_ready(stage, cx, input))
+                    .await
+                    .unwrap();
+                let provider_rw = self.syncing.database_provider_rw().unwrap();
+                let output = stage.execute(&provider_rw, input).unwrap();
+                provider_rw.commit().unwrap();
+                if stop(&output) {
+                    return output
+                }
+                input.checkpoint = Some(output.checkpoint);
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Asserts that the state of the syncing node matches the state of the serving node.
+    fn assert_synced(&self, pivot: u64) {
+        assert_eq!(
+            entries::<tables::HashedAccounts>(&self.syncing),
+            entries::<tables::HashedAccounts>(&self.server)
+        );
+        assert_eq!(
+            entries::<tables::HashedStorages>(&self.syncing),
+            entries::<tables::HashedStorages>(&self.server)
+        );
+        assert_eq!(
+            entries::<tables::Bytecodes>(&self.syncing),
+            entries::<tables::Bytecodes>(&self.server)
+        );
+
+        let provider = self.syncing.database_provider_ro().unwrap();
+        let state_root = provider.header_by_number(pivot).unwrap().unwrap().state_root;
+        assert_eq!(StateRoot::from_tx(provider.tx_ref()).root().unwrap(), state_root);
+
+        // The stages replaced by snap sync continue after the pivot
+        for stage_id in [StageId::Execution, StageId::AccountHashing, StageId::MerkleExecute] {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap().unwrap().block_number,
+                pivot
+            );
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn snap_sync_from_peer() {
+    reth_tracing::init_test_tracing();
+    let setup = Setup::new().await;
+
+    let mut stage = setup.stage();
+    let input = ExecInput { target: Some(1), checkpoint: None };
+    let output = setup.run(&mut stage, input, |output| output.done).await;
+    assert_eq!(output.checkpoint.block_number, 1);
+    assert_eq!(output.checkpoint.snap_sync_pivot(), Some(1));
+
+    setup.assert_synced(1);
+    assert_eq!(entries::<tables::HashedStorages>(&setup.syncing).len(), 500);
+    let provider = setup.syncing.database_provider_ro().unwrap();
+    assert!(provider
+        .tx_ref()
+        .get::<tables::HashedAccounts>(keccak256(CONTRACT))
+        .unwrap()
+        .is_some_and(|account| account.has_bytecode()));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn snap_sync_heals_after_pivot_change() {
+    reth_tracing::init_test_tracing();
+    let setup = Setup::new().await;
+
+    // Download part of the state of block 1
+    let mut stage = setup.stage();
+    let input = ExecInput { target: Some(1), checkpoint: None };
+    let output = setup
+        .run(&mut stage, input, |output| {
+            assert!(!output.done);
+            output.checkpoint.snap_sync_stage_checkpoint().unwrap().progress.processed > 0
+        })
+        .await;
+
+    // The state changes at block 2, the accounts already downloaded are healed
+    let state_root = advance(&setup.server);
+    append_header(&setup.syncing, 2, state_root);
+
+    let input = ExecInput { target: Some(2), checkpoint: Some(output.checkpoint) };
+    let output = setup.run(&mut stage, input, |output| output.done).await;
+    assert_eq!(output.checkpoint.block_number, 2);
+    assert_eq!(output.checkpoint.snap_sync_pivot(), Some(2));
+
+    setup.assert_synced(2);
+}
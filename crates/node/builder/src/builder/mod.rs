@@ -15,11 +15,13 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli_util::get_secret_key;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_exex::ExExContext;
+use reth_net_snap::SnapFetchClient;
 use reth_network::{
     transactions::{TransactionPropagationPolicy, TransactionsManagerConfig},
     NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
     NetworkPrimitives,
 };
+use reth_network_types::{persisted_peers_file, DEFAULT_PERSIST_PEERS_INTERVAL};
 use reth_node_api::{
     FullNodePrimitives, FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes,
     NodeTypesWithDBAdapter,
@@ -30,14 +32,19 @@ use reth_node_core::{
     node_config::NodeConfig,
     primitives::Head,
 };
+use reth_payload_builder_primitives::MinerConfig;
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     ChainSpecProvider, FullProvider,
 };
+use reth_revm::state_changes::PendingStateChanges;
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
 use secp256k1::SecretKey;
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    fmt::Debug,
+    sync::{Arc, OnceLock},
+};
 use tracing::{info, trace, warn};
 
 pub mod add_ons;
@@ -599,17 +606,31 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) executor: TaskExecutor,
     /// Config container
     pub(crate) config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    /// State changes committed in the next block mined in --dev mode.
+    pub(crate) pending_state_changes: PendingStateChanges,
+    /// Payload builder settings that can be changed while the node is running.
+    pub(crate) miner_config: MinerConfig,
+    /// The snap client registered by the network builder.
+    pub(crate) snap_client: Arc<OnceLock<SnapFetchClient>>,
 }
 
 impl<Node: FullNodeTypes> BuilderContext<Node> {
     /// Create a new instance of [`BuilderContext`]
-    pub const fn new(
+    pub fn new(
         head: Head,
         provider: Node::Provider,
         executor: TaskExecutor,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        Self {
+            head,
+            provider,
+            executor,
+            config_container,
+            pending_state_changes: PendingStateChanges::default(),
+            miner_config: MinerConfig::default(),
+            snap_client: Default::default(),
+        }
     }
 
     /// Returns the configured provider to interact with the blockchain.
@@ -649,6 +670,29 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().dev.dev
     }
 
+    /// Returns the state changes that are committed in the next block mined in --dev mode.
+    ///
+    /// Payload builders of --dev nodes should commit the changes staged for the parent of a
+    /// payload, so that state changes requested over RPC, like `anvil_setBalance`, take effect.
+    pub const fn pending_state_changes(&self) -> &PendingStateChanges {
+        &self.pending_state_changes
+    }
+
+    /// Returns the payload builder settings that can be changed while the node is running.
+    ///
+    /// Payload builders that apply these settings allow changing them via the `miner_` API.
+    pub const fn miner_config(&self) -> &MinerConfig {
+        &self.miner_config
+    }
+
+    /// Sets the client the pipeline downloads the state with if snap sync is enabled.
+    ///
+    /// Network builders that register the snap protocol should set it, otherwise the pipeline
+    /// executes all blocks. Only the first client that is set is used.
+    pub fn set_snap_client(&self, client: SnapFetchClient) {
+        let _ = self.snap_client.set(client);
+    }
+
     /// Returns the transaction pool config of the node.
     pub fn pool_config(&self) -> PoolConfig {
         self.config().txpool.pool_config()
@@ -727,6 +771,26 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
 
         let default_peers_path = self.config().datadir().known_peers();
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
+
+        // The known peers are written on shutdown, but reputations and bans are also saved
+        // periodically so that abusive peers stay banned after a crash
+        if let Some(peers_file) = &known_peers_file {
+            let network_handle = handle.clone();
+            let file = persisted_peers_file(peers_file);
+            self.executor.spawn(Box::pin(async move {
+                let mut interval = tokio::time::interval(DEFAULT_PERSIST_PEERS_INTERVAL);
+                // the first tick completes immediately
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    let Ok(persisted) = network_handle.persisted_peers().await else { break };
+                    if let Err(err) = reth_fs_util::write_json_file(&file, &persisted) {
+                        warn!(target: "reth::cli", %err, "Failed to write peer reputations and bans to file");
+                    }
+                }
+            }));
+        }
+
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
             |shutdown| {
@@ -821,6 +885,9 @@ impl<Node: FullNodeTypes> std::fmt::Debug for BuilderContext<Node> {
             .field("provider", &std::any::type_name::<Node::Provider>())
             .field("executor", &self.executor)
             .field("config", &self.config())
+            .field("pending_state_changes", &self.pending_state_changes)
+            .field("miner_config", &self.miner_config)
+            .field("snap_client", &self.snap_client)
             .finish()
     }
 }
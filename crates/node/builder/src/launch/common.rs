@@ -20,6 +20,7 @@ use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlo
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_fs_util as fs;
 use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+use reth_net_snap::SnapFetchClient;
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::{
@@ -31,6 +32,7 @@ use reth_node_core::{
         BUILD_PROFILE_NAME, CARGO_PKG_VERSION, VERGEN_BUILD_TIMESTAMP, VERGEN_CARGO_FEATURES,
         VERGEN_CARGO_TARGET_TRIPLE, VERGEN_GIT_SHA,
     },
+    zstd_dictionaries::load_zstd_dictionaries,
 };
 use reth_node_metrics::{
     chain::ChainSpecInfo,
@@ -39,12 +41,15 @@ use reth_node_metrics::{
     server::{MetricServer, MetricServerConfig},
     version::VersionInfo,
 };
+use reth_payload_builder_primitives::MinerConfig;
 use reth_provider::{
     providers::{NodeTypesForProvider, ProviderNodeTypes, StaticFileProvider},
     BlockHashReader, BlockNumReader, ChainSpecProvider, ProviderError, ProviderFactory,
-    ProviderResult, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
+    ProviderResult, StageCheckpointReader, StageCheckpointWriter, StateProviderFactory,
+    StaticFileProviderFactory,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
+use reth_revm::state_changes::PendingStateChanges;
 use reth_rpc_api::clients::EthApiClient;
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
@@ -387,6 +392,8 @@ where
         N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
         Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
     {
+        load_zstd_dictionaries(&self.data_dir().zstd_dictionaries())?;
+
         let factory = ProviderFactory::new(
             self.right().clone(),
             self.chain_spec(),
@@ -542,13 +549,40 @@ where
 
     /// Convenience function to [`Self::init_genesis`]
     pub fn with_genesis(self) -> Result<Self, InitStorageError> {
-        init_genesis(self.provider_factory())?;
+        self.init_genesis()?;
         Ok(self)
     }
 
     /// Write the genesis block and state if it has not already been written
     pub fn init_genesis(&self) -> Result<B256, InitStorageError> {
-        init_genesis(self.provider_factory())
+        let genesis_hash = init_genesis(self.provider_factory())?;
+        self.init_optional_stage_checkpoints()?;
+        Ok(genesis_hash)
+    }
+
+    /// Initializes the checkpoints of the enabled optional stages if the chain is still at
+    /// genesis.
+    ///
+    /// Genesis initialization only sets the checkpoints of [`StageId::ALL`]. The data of optional
+    /// stages is maintained by the engine only once they have a checkpoint, and the pipeline
+    /// doesn't run for a chain at genesis to create one.
+    fn init_optional_stage_checkpoints(&self) -> ProviderResult<()> {
+        if !self.toml_config().stages.index_address_transactions.enabled {
+            return Ok(())
+        }
+
+        let provider_rw = self.provider_factory().provider_rw()?;
+        let finish_checkpoint =
+            provider_rw.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default();
+        if finish_checkpoint.block_number == 0 &&
+            provider_rw.get_stage_checkpoint(StageId::IndexAddressTransactions)?.is_none()
+        {
+            provider_rw
+                .save_stage_checkpoint(StageId::IndexAddressTransactions, Default::default())?;
+            provider_rw.commit()?;
+        }
+
+        Ok(())
     }
 
     /// Creates a new `WithMeteredProvider` container and attaches it to the
@@ -706,6 +740,9 @@ where
             },
             node_adapter,
             head,
+            pending_state_changes: builder_ctx.pending_state_changes,
+            miner_config: builder_ctx.miner_config,
+            snap_client: builder_ctx.snap_client.get().cloned(),
         };
 
         let ctx = LaunchContextWith {
@@ -758,6 +795,24 @@ where
         self.right().head
     }
 
+    /// Returns the state changes that are committed in the next block mined in --dev mode, see
+    /// [`BuilderContext::pending_state_changes`].
+    pub const fn pending_state_changes(&self) -> &PendingStateChanges {
+        &self.right().pending_state_changes
+    }
+
+    /// Returns the payload builder settings that can be changed while the node is running, see
+    /// [`BuilderContext::miner_config`].
+    pub const fn miner_config(&self) -> &MinerConfig {
+        &self.right().miner_config
+    }
+
+    /// Returns the snap client set by the network builder, see
+    /// [`BuilderContext::set_snap_client`].
+    pub const fn snap_client(&self) -> Option<&SnapFetchClient> {
+        self.right().snap_client.as_ref()
+    }
+
     /// Returns the configured `NodeAdapter`.
     pub const fn node_adapter(&self) -> &NodeAdapter<T, CB::Components> {
         &self.right().node_adapter
@@ -840,8 +895,14 @@ where
             .block_number;
 
         // Skip the first stage as we've already retrieved it and comparing all other checkpoints
-        // against it.
-        for stage_id in StageId::ALL.iter().skip(1) {
+        // against it. Optional stages are only checked if they're enabled.
+        let optional_stages = self
+            .toml_config()
+            .stages
+            .index_address_transactions
+            .enabled
+            .then_some(StageId::IndexAddressTransactions);
+        for stage_id in StageId::ALL.iter().skip(1).chain(optional_stages.as_ref()) {
             let stage_checkpoint = self
                 .blockchain_db()
                 .get_stage_checkpoint(*stage_id)?
@@ -1048,6 +1109,9 @@ where
     db_provider_container: WithMeteredProvider<NodeTypesWithDBAdapter<T::Types, T::DB>>,
     node_adapter: NodeAdapter<T, CB::Components>,
     head: Head,
+    pending_state_changes: PendingStateChanges,
+    miner_config: MinerConfig,
+    snap_client: Option<SnapFetchClient>,
 }
 
 #[cfg(test)]
@@ -1088,6 +1152,9 @@ mod tests {
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    address_transactions_full: false,
+                    address_transactions_distance: None,
+                    address_transactions_before: None,
                     receipts_log_filter: None,
                 },
                 ..NodeConfig::test()
@@ -12,7 +12,7 @@ use alloy_consensus::BlockHeader;
 use futures::{future::Either, stream, stream_select, StreamExt};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
-use reth_engine_local::{LocalMiner, LocalPayloadAttributesBuilder};
+use reth_engine_local::{LocalMiner, LocalPayload, LocalPayloadAttributesBuilder};
 use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
@@ -23,8 +23,8 @@ use reth_exex::ExExManagerHandle;
 use reth_network::{NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
-    BeaconConsensusEngineHandle, BuiltPayload, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
-    PayloadAttributesBuilder, PayloadTypes,
+    BeaconConsensusEngineHandle, BeaconEngineMessage, BuiltPayload, FullNodeTypes, NodeTypes,
+    NodeTypesWithDBAdapter, PayloadAttributesBuilder, PayloadTypes,
 };
 use reth_node_core::{
     args::DefaultEraHost,
@@ -37,7 +37,7 @@ use reth_provider::providers::{BlockchainProvider, NodeTypesForProvider};
 use reth_stages::stages::EraImportSource;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
-use reth_tracing::tracing::{debug, error, info};
+use reth_tracing::tracing::{debug, error, info, warn};
 use std::sync::Arc;
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -90,7 +90,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, mut add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -170,9 +170,21 @@ where
             None
         };
 
+        // The snap client is registered by the network builder, if it supports snap
+        let snap_client = if ctx.toml_config().stages.snap_sync.enabled {
+            let snap_client = ctx.snap_client().cloned();
+            if snap_client.is_none() {
+                warn!(target: "reth::cli", "Snap sync is enabled, but the network doesn't support snap");
+            }
+            snap_client
+        } else {
+            None
+        };
+
         let pipeline = build_networked_pipeline(
             &ctx.toml_config().stages,
             network_client.clone(),
+            snap_client,
             consensus.clone(),
             ctx.provider_factory().clone(),
             ctx.task_executor(),
@@ -206,12 +218,30 @@ where
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        // locally mined payloads are inserted and submitted in order by the engine loop below
+        let (local_payload_tx, local_payload_rx) = unbounded_channel();
+        let local_miner = ctx.is_dev().then(|| {
+            LocalMiner::new(
+                ctx.blockchain_db().clone(),
+                LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
+                beacon_engine_handle.clone(),
+                ctx.dev_mining_mode(ctx.components().pool()),
+                ctx.components().payload_builder_handle().clone(),
+            )
+            .with_pending_state_changes(ctx.pending_state_changes().clone())
+            .with_local_payloads(local_payload_tx)
+        });
+        if let Some(local_miner) = &local_miner {
+            add_ons.set_local_miner(local_miner.handle());
+        }
+
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            miner_config: ctx.miner_config().clone(),
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
@@ -248,18 +278,8 @@ where
             ctx.components().evm_config().clone(),
         );
 
-        if ctx.is_dev() {
-            ctx.task_executor().spawn_critical(
-                "local engine",
-                LocalMiner::new(
-                    ctx.blockchain_db().clone(),
-                    LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
-                    beacon_engine_handle.clone(),
-                    ctx.dev_mining_mode(ctx.components().pool()),
-                    ctx.components().payload_builder_handle().clone(),
-                )
-                .run(),
-            );
+        if let Some(local_miner) = local_miner {
+            ctx.task_executor().spawn_critical("local engine", local_miner.run());
         }
 
         info!(target: "reth::cli", "Consensus engine initialized");
@@ -301,6 +321,8 @@ where
             .map_err(|e| eyre::eyre!("Failed to subscribe to payload builder events: {:?}", e))?
             .into_built_payload_stream()
             .fuse();
+        let mut local_payloads = UnboundedReceiverStream::new(local_payload_rx).fuse();
+        let is_dev = ctx.is_dev();
         let chainspec = ctx.chain_spec();
         let (exit, rx) = oneshot::channel();
         let terminate_after_backfill = ctx.terminate_after_initial_backfill();
@@ -318,11 +340,24 @@ where
             loop {
                 tokio::select! {
                     payload = built_payloads.select_next_some() => {
-                        if let Some(executed_block) = payload.executed_block() {
+                        // in --dev mode built payloads are inserted along with their newPayload
+                        if let Some(executed_block) = payload.executed_block().filter(|_| !is_dev) {
                             debug!(target: "reth::cli", block=?executed_block.recovered_block().num_hash(),  "inserting built payload");
                             engine_service.orchestrator_mut().handler_mut().handler_mut().on_event(EngineApiRequest::InsertExecutedBlock(executed_block).into());
                         }
                     }
+                    LocalPayload { payload, tx } = local_payloads.select_next_some() => {
+                        // Inserting the executed block before the payload ensures the engine
+                        // never re-executes it, as locally mined blocks can carry state changes
+                        // that only the payload builder applies.
+                        let handler = engine_service.orchestrator_mut().handler_mut().handler_mut();
+                        if let Some(executed_block) = payload.executed_block() {
+                            debug!(target: "reth::cli", block=?executed_block.recovered_block().num_hash(),  "inserting locally mined payload");
+                            handler.on_event(EngineApiRequest::InsertExecutedBlock(executed_block).into());
+                        }
+                        let payload = <Types::Payload as PayloadTypes>::block_to_payload(payload.block().clone());
+                        handler.on_event(EngineApiRequest::Beacon(BeaconEngineMessage::NewPayload { payload, tx }).into());
+                    }
                     event = engine_service.next() => {
                         let Some(event) = event else { break };
                         debug!(target: "reth::cli", "Event: {event}");
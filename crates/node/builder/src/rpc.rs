@@ -6,6 +6,7 @@ use alloy_rpc_types_engine::ExecutionData;
 use jsonrpsee::RpcModule;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
+use reth_engine_local::LocalMinerHandle;
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineTypes, EngineValidator, FullNodeComponents, FullNodeTypes,
     NodeAddOns, NodeTypes, PayloadTypes, ReceiptTy,
@@ -15,12 +16,19 @@ use reth_node_core::{
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
-use reth_rpc::eth::{EthApiTypes, FullEthApiServer};
-use reth_rpc_api::{eth::helpers::AddDevSigners, IntoEngineApiRpcModule};
+use reth_rpc::{
+    eth::{EthApiTypes, FullEthApiServer},
+    AnvilApi,
+};
+use reth_rpc_api::{
+    eth::helpers::AddDevSigners, AnvilApiServer, DebugApiServer, HardhatApiServer,
+    IntoEngineApiRpcModule,
+};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle,
+    TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
@@ -426,6 +434,8 @@ pub struct RpcAddOns<
     engine_validator_builder: EV,
     /// Builder for `EngineApi`
     engine_api_builder: EB,
+    /// Handle to the miner of a --dev node, backs the dev namespaces.
+    local_miner: Option<LocalMinerHandle>,
 }
 
 impl<Node, EthB, EV, EB> Debug for RpcAddOns<Node, EthB, EV, EB>
@@ -441,6 +451,7 @@ where
             .field("eth_api_builder", &"...")
             .field("engine_validator_builder", &self.engine_validator_builder)
             .field("engine_api_builder", &self.engine_api_builder)
+            .field("local_miner", &self.local_miner)
             .finish()
     }
 }
@@ -461,13 +472,20 @@ where
             eth_api_builder,
             engine_validator_builder,
             engine_api_builder,
+            local_miner: None,
         }
     }
 
     /// Maps the [`EngineApiBuilder`] builder type.
     pub fn with_engine_api<T>(self, engine_api_builder: T) -> RpcAddOns<Node, EthB, EV, T> {
-        let Self { hooks, eth_api_builder, engine_validator_builder, .. } = self;
-        RpcAddOns { hooks, eth_api_builder, engine_validator_builder, engine_api_builder }
+        let Self { hooks, eth_api_builder, engine_validator_builder, local_miner, .. } = self;
+        RpcAddOns {
+            hooks,
+            eth_api_builder,
+            engine_validator_builder,
+            engine_api_builder,
+            local_miner,
+        }
     }
 
     /// Maps the [`EngineValidatorBuilder`] builder type.
@@ -475,8 +493,14 @@ where
         self,
         engine_validator_builder: T,
     ) -> RpcAddOns<Node, EthB, T, EB> {
-        let Self { hooks, eth_api_builder, engine_api_builder, .. } = self;
-        RpcAddOns { hooks, eth_api_builder, engine_validator_builder, engine_api_builder }
+        let Self { hooks, eth_api_builder, engine_api_builder, local_miner, .. } = self;
+        RpcAddOns {
+            hooks,
+            eth_api_builder,
+            engine_validator_builder,
+            engine_api_builder,
+            local_miner,
+        }
     }
 
     /// Sets the hook that is run once the rpc server is started.
@@ -631,10 +655,11 @@ where
     where
         F: FnOnce(RpcModuleContainer<'_, N, EthB::EthApi>) -> eyre::Result<()>,
     {
-        let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
+        let Self { eth_api_builder, engine_api_builder, hooks, local_miner, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events, .. } =
+            ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -669,6 +694,27 @@ where
             .with_consensus(node.consensus().clone())
             .build_with_auth_server(module_config, engine_api, eth_api);
 
+        // `debug_setHead` unwinds through the engine, which the registry has no handle to, and the
+        // profiling methods write to the datadir
+        let debug_api = registry
+            .debug_api()
+            .with_engine_handle(beacon_engine_handle.clone())
+            .with_profiles_dir(config.datadir().profiles());
+        modules.replace_if_module_configured(RethRpcModule::Debug, debug_api.into_rpc())?;
+
+        // the dev namespaces control the local miner, which only exists in dev mode
+        if let Some(local_miner) = local_miner {
+            let anvil_api = AnvilApi::new(registry.eth_api().clone(), Some(local_miner));
+            modules.replace_if_module_configured(
+                RethRpcModule::Anvil,
+                AnvilApiServer::into_rpc(anvil_api.clone()),
+            )?;
+            modules.replace_if_module_configured(
+                RethRpcModule::Hardhat,
+                HardhatApiServer::into_rpc(anvil_api),
+            )?;
+        }
+
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             registry.eth_api().with_dev_accounts();
@@ -785,6 +831,13 @@ pub trait RethRpcAddOns<N: FullNodeComponents>:
 
     /// Returns a mutable reference to RPC hooks.
     fn hooks_mut(&mut self) -> &mut RpcHooks<N, Self::EthApi>;
+
+    /// Sets the handle to the miner of a --dev node, which the dev namespaces (`anvil`,
+    /// `hardhat`) control.
+    ///
+    /// This is called by the launcher before the add-ons are launched. Add-ons without the dev
+    /// namespaces can ignore it.
+    fn set_local_miner(&mut self, _miner: LocalMinerHandle) {}
 }
 
 impl<N: FullNodeComponents, EthB, EV, EB> RethRpcAddOns<N> for RpcAddOns<N, EthB, EV, EB>
@@ -797,6 +850,10 @@ where
     fn hooks_mut(&mut self) -> &mut RpcHooks<N, Self::EthApi> {
         &mut self.hooks
     }
+
+    fn set_local_miner(&mut self, miner: LocalMinerHandle) {
+        self.local_miner = Some(miner);
+    }
 }
 
 /// `EthApiCtx` struct
@@ -12,6 +12,7 @@ use reth_downloaders::{
 };
 use reth_evm::ConfigureEvm;
 use reth_exex::ExExManagerHandle;
+use reth_net_snap::{SnapFetchClient, SnapSyncStage};
 use reth_network_p2p::{
     bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
 };
@@ -20,7 +21,7 @@ use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
 use reth_stages::{
     prelude::DefaultStages,
     stages::{EraImportSource, ExecutionStage},
-    Pipeline, StageSet,
+    Pipeline, StageId, StageSet,
 };
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
@@ -28,10 +29,14 @@ use reth_tracing::tracing::debug;
 use tokio::sync::watch;
 
 /// Constructs a [Pipeline] that's wired to the network
+///
+/// If a snap client is given, the state is downloaded with the [`SnapSyncStage`] instead of
+/// executing the blocks since genesis.
 #[expect(clippy::too_many_arguments)]
 pub fn build_networked_pipeline<N, Client, Evm>(
     config: &StageConfig,
     client: Client,
+    snap_client: Option<SnapFetchClient>,
     consensus: Arc<dyn FullConsensus<N::Primitives, Error = ConsensusError>>,
     provider_factory: ProviderFactory<N>,
     task_executor: &TaskExecutor,
@@ -62,6 +67,7 @@ where
         config,
         header_downloader,
         body_downloader,
+        snap_client,
         consensus,
         max_block,
         metrics_tx,
@@ -82,6 +88,7 @@ pub fn build_pipeline<N, H, B, Evm>(
     stage_config: &StageConfig,
     header_downloader: H,
     body_downloader: B,
+    snap_client: Option<SnapFetchClient>,
     consensus: Arc<dyn FullConsensus<N::Primitives, Error = ConsensusError>>,
     max_block: Option<u64>,
     metrics_tx: reth_stages::MetricEventsSender,
@@ -108,29 +115,33 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut stages = DefaultStages::new(
+        provider_factory.clone(),
+        tip_rx,
+        Arc::clone(&consensus),
+        header_downloader,
+        body_downloader,
+        evm_config.clone(),
+        stage_config.clone(),
+        prune_modes,
+        era_import_source,
+    )
+    .set(ExecutionStage::new(
+        evm_config,
+        consensus,
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        exex_manager_handle,
+    ));
+    if let Some(snap_client) = snap_client {
+        stages = stages
+            .add_after(SnapSyncStage::new(snap_client, stage_config.snap_sync), StageId::Bodies);
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
-        .add_stages(
-            DefaultStages::new(
-                provider_factory.clone(),
-                tip_rx,
-                Arc::clone(&consensus),
-                header_downloader,
-                body_downloader,
-                evm_config.clone(),
-                stage_config.clone(),
-                prune_modes,
-                era_import_source,
-            )
-            .set(ExecutionStage::new(
-                evm_config,
-                consensus,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                exex_manager_handle,
-            )),
-        )
+        .add_stages(stages)
         .build(provider_factory, static_file_producer);
 
     Ok(pipeline)
@@ -161,6 +161,12 @@ pub struct NetworkArgs {
     /// The policy determines which peers transactions are gossiped to.
     #[arg(long = "tx-propagation-policy", default_value_t = TransactionPropagationKind::All)]
     pub tx_propagation_policy: TransactionPropagationKind,
+
+    /// Serve the state of recent blocks to peers over the snap protocol.
+    ///
+    /// The state of the last 128 blocks is served, which allows peers to snap sync from this node.
+    #[arg(long, verbatim_doc_comment)]
+    pub serve_snap: bool,
 }
 
 impl NetworkArgs {
@@ -342,7 +348,8 @@ impl Default for NetworkArgs {
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
             net_if: None,
-            tx_propagation_policy: TransactionPropagationKind::default()
+            tx_propagation_policy: TransactionPropagationKind::default(),
+            serve_snap: false,
         }
     }
 }
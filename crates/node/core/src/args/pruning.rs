@@ -86,6 +86,19 @@ pub struct PruningArgs {
     /// pruned.
     #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
     pub storage_history_before: Option<BlockNumber>,
+
+    // Address Transactions
+    /// Prunes all address transactions index data.
+    #[arg(long = "prune.addresstransactions.full", conflicts_with_all = &["address_transactions_distance", "address_transactions_before"])]
+    pub address_transactions_full: bool,
+    /// Prune address transactions index before the `head-N` block number. In other words, keep
+    /// last N + 1 blocks.
+    #[arg(long = "prune.addresstransactions.distance", value_name = "BLOCKS", conflicts_with_all = &["address_transactions_full", "address_transactions_before"])]
+    pub address_transactions_distance: Option<u64>,
+    /// Prune address transactions index before the specified block number. The specified block
+    /// number is not pruned.
+    #[arg(long = "prune.addresstransactions.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["address_transactions_full", "address_transactions_distance"])]
+    pub address_transactions_before: Option<BlockNumber>,
 }
 
 impl PruningArgs {
@@ -99,11 +112,14 @@ impl PruningArgs {
             config = PruneConfig {
                 block_interval: config.block_interval,
                 segments: PruneModes {
-                    sender_recovery: Some(PruneMode::Full),
+                    // Senders are kept in static files, which is cheaper than recovering them on
+                    // every historical block access.
+                    sender_recovery: None,
                     transaction_lookup: None,
                     receipts: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    address_transactions: None,
                     receipts_log_filter: Default::default(),
                 },
             }
@@ -128,6 +144,9 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.address_transactions_prune_mode() {
+            config.segments.address_transactions = Some(mode);
+        }
         if let Some(receipt_logs) =
             self.receipts_log_filter.as_ref().filter(|c| !c.is_empty()).cloned()
         {
@@ -199,6 +218,18 @@ impl PruningArgs {
             None
         }
     }
+
+    const fn address_transactions_prune_mode(&self) -> Option<PruneMode> {
+        if self.address_transactions_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.address_transactions_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.address_transactions_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
 }
 
 /// Parses `,` separated pruning info into [`ReceiptsLogPruneConfig`].
@@ -273,6 +304,22 @@ mod tests {
         assert_eq!(args.receipts_log_filter, Some(config));
     }
 
+    #[test]
+    fn full_node_keeps_senders() {
+        let args = CommandParser::<PruningArgs>::parse_from(["reth", "--full"]).args;
+        let config = args.prune_config().unwrap();
+        assert_eq!(config.segments.sender_recovery, None);
+
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--full",
+            "--prune.senderrecovery.full",
+        ])
+        .args;
+        let config = args.prune_config().unwrap();
+        assert_eq!(config.segments.sender_recovery, Some(PruneMode::Full));
+    }
+
     #[test]
     fn parse_receiptslogfilter() {
         let default_args = PruningArgs::default();
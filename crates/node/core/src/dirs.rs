@@ -351,10 +351,24 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the directory the profiling RPC methods write their profiles to.
+    ///
+    /// `<DIR>/<CHAIN_ID>/profiles`
+    pub fn profiles(&self) -> PathBuf {
+        self.data_dir().join("profiles")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
     }
+
+    /// Returns the path to the trained zstd dictionaries directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/zstd-dictionaries`
+    pub fn zstd_dictionaries(&self) -> PathBuf {
+        self.data_dir().join("zstd-dictionaries")
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
@@ -15,6 +15,7 @@ pub mod exit;
 pub mod node_config;
 pub mod utils;
 pub mod version;
+pub mod zstd_dictionaries;
 
 /// Re-exported primitive types
 pub mod primitives {
@@ -0,0 +1,101 @@
+//! Zstd dictionaries trained by `reth db train-dictionary`.
+
+use alloy_primitives::{BlockNumber, Bytes};
+use eyre::ensure;
+use reth_static_file_types::StaticFileSegment;
+use reth_zstd_compressors::DictionaryKind;
+use serde::{Deserialize, Serialize};
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Current version of the trained dictionary file format.
+pub const DICTIONARY_VERSION: u64 = 1;
+
+/// A zstd dictionary trained by `reth db train-dictionary`.
+///
+/// Stored as `<segment>-<id>.json` in the
+/// [`zstd_dictionaries`](crate::dirs::ChainPath::zstd_dictionaries) directory of the datadir. Once
+/// loaded, it takes the place of the dictionary embedded for its segment when encoding values, so
+/// it must be kept for as long as values compressed with it are stored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrainedDictionary {
+    /// Version of the dictionary file format.
+    pub version: u64,
+    /// Static file segment the dictionary was trained for.
+    pub segment: StaticFileSegment,
+    /// Dictionary ID, as embedded in the dictionary itself.
+    pub id: u32,
+    /// Unix timestamp in seconds of when the dictionary was trained.
+    pub created_at: u64,
+    /// Block range of the static files the samples were taken from.
+    pub block_range: RangeInclusive<BlockNumber>,
+    /// Number of values the dictionary was trained on.
+    pub samples: usize,
+    /// The raw zstd dictionary.
+    pub dictionary: Bytes,
+}
+
+impl TrainedDictionary {
+    /// Loads a dictionary file written by [`Self::save`].
+    pub fn load(path: &Path) -> eyre::Result<Self> {
+        let dictionary: Self = serde_json::from_slice(&reth_fs_util::read(path)?)?;
+        ensure!(
+            dictionary.version == DICTIONARY_VERSION,
+            "Unsupported dictionary version {}, expected {DICTIONARY_VERSION}",
+            dictionary.version
+        );
+        ensure!(
+            dictionary.kind().is_some(),
+            "Dictionaries are not supported for segment {}",
+            dictionary.segment
+        );
+        Ok(dictionary)
+    }
+
+    /// Writes the dictionary to the given directory and returns the path of the file.
+    pub fn save(&self, dir: &Path) -> eyre::Result<PathBuf> {
+        reth_fs_util::create_dir_all(dir)?;
+        let path = dir.join(format!("{}-{}.json", self.segment, self.id));
+        reth_fs_util::write(&path, serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// Returns the kind of values the dictionary compresses.
+    pub const fn kind(&self) -> Option<DictionaryKind> {
+        match self.segment {
+            StaticFileSegment::Receipts => Some(DictionaryKind::Receipt),
+            StaticFileSegment::Transactions => Some(DictionaryKind::Transaction),
+            _ => None,
+        }
+    }
+}
+
+/// Loads all dictionaries of the given directory in place of the embedded ones.
+///
+/// Values are compressed with the dictionary trained last for their segment, and the others are
+/// kept to decompress values compressed with them.
+pub fn load_zstd_dictionaries(dir: &Path) -> eyre::Result<()> {
+    if !dir.exists() {
+        return Ok(())
+    }
+
+    let mut dictionaries = Vec::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "json") {
+            dictionaries.push(TrainedDictionary::load(&path)?);
+        }
+    }
+    dictionaries.sort_by_key(|dictionary| dictionary.created_at);
+
+    for dictionary in dictionaries {
+        let kind = dictionary.kind().expect("checked on load");
+        info!(target: "reth::cli", segment = %dictionary.segment, id = dictionary.id, "Loaded zstd dictionary");
+        reth_zstd_compressors::load_dictionary(kind, dictionary.dictionary.to_vec());
+    }
+
+    Ok(())
+}
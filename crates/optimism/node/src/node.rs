@@ -479,6 +479,10 @@ where
     fn hooks_mut(&mut self) -> &mut reth_node_builder::rpc::RpcHooks<N, Self::EthApi> {
         self.rpc_add_ons.hooks_mut()
     }
+
+    fn set_local_miner(&mut self, miner: reth_engine_local::LocalMinerHandle) {
+        self.rpc_add_ons.set_local_miner(miner)
+    }
 }
 
 impl<N, NetworkT, EV, EB> EngineValidatorAddOn<N> for OpAddOns<N, OpEthApiBuilder<NetworkT>, EV, EB>
@@ -467,7 +467,7 @@ impl reth_codecs::Compact for OpTransactionSigned {
                     // TODO: enforce that zstd is only present at a "top" level type
                     let transaction_type = (bitflags & 0b110) >> 1;
                     let (transaction, _) = OpTypedTransaction::from_compact(
-                        decompressor.decompress(buf),
+                        decompressor.decompress(buf).expect("Failed to decompress"),
                         transaction_type,
                     );
 
@@ -477,7 +477,7 @@ impl reth_codecs::Compact for OpTransactionSigned {
                 let mut decompressor = reth_zstd_compressors::create_tx_decompressor();
                 let transaction_type = (bitflags & 0b110) >> 1;
                 let (transaction, _) = OpTypedTransaction::from_compact(
-                    decompressor.decompress(buf),
+                    decompressor.decompress(buf).expect("Failed to decompress"),
                     transaction_type,
                 );
 
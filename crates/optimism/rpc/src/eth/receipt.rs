@@ -1,6 +1,6 @@
 //! Loads and formats OP receipt RPC response.
 
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::transaction::{Recovered, TransactionMeta};
 use alloy_eips::eip2718::Encodable2718;
 use alloy_rpc_types_eth::{Log, TransactionReceipt};
 use op_alloy_consensus::{OpDepositReceipt, OpDepositReceiptWithBloom, OpReceiptEnvelope};
@@ -25,7 +25,7 @@ where
 {
     async fn build_transaction_receipt(
         &self,
-        tx: OpTransactionSigned,
+        tx: Recovered<OpTransactionSigned>,
         meta: TransactionMeta,
         receipt: OpReceipt,
     ) -> Result<RpcReceipt<Self::NetworkTypes>, Self::Error> {
@@ -45,7 +45,7 @@ where
 
         Ok(OpReceiptBuilder::new(
             &self.inner.eth_api.provider().chain_spec(),
-            &tx,
+            tx.as_recovered_ref(),
             meta,
             &receipt,
             &receipts,
@@ -223,7 +223,7 @@ impl OpReceiptBuilder {
     /// Returns a new builder.
     pub fn new(
         chain_spec: &impl OpHardforks,
-        transaction: &OpTransactionSigned,
+        transaction: Recovered<&OpTransactionSigned>,
         meta: TransactionMeta,
         receipt: &OpReceipt,
         all_receipts: &[OpReceipt],
@@ -252,7 +252,7 @@ impl OpReceiptBuilder {
             })?;
 
         let op_receipt_fields = OpReceiptFieldsBuilder::new(timestamp, block_number)
-            .l1_block_info(chain_spec, transaction, l1_block_info)?
+            .l1_block_info(chain_spec, transaction.inner(), l1_block_info)?
             .build();
 
         Ok(Self { core_receipt, op_receipt_fields })
@@ -11,4 +11,7 @@
 mod events;
 pub use crate::events::{Events, PayloadEvents};
 
+mod miner;
+pub use crate::miner::MinerConfig;
+
 pub use reth_payload_primitives::PayloadBuilderError;
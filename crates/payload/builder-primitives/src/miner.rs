@@ -0,0 +1,65 @@
+use alloy_primitives::Bytes;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// Settings of the payload builder that can be changed while the node is running, e.g. via the
+/// `miner_` API.
+///
+/// Every setting that is not set falls back to the static configuration of the payload builder.
+/// Changes apply to payload jobs that are started afterwards.
+///
+/// This type is shareable, all clones see the same settings.
+#[derive(Debug, Clone, Default)]
+pub struct MinerConfig {
+    inner: Arc<RwLock<MinerConfigInner>>,
+}
+
+#[derive(Debug, Default)]
+struct MinerConfigInner {
+    /// Extra data to include in built blocks.
+    extra_data: Option<Bytes>,
+    /// Gas limit to target for built blocks.
+    gas_limit: Option<u64>,
+    /// Minimum priority fee per gas of included transactions.
+    min_tip: Option<u128>,
+}
+
+impl MinerConfig {
+    /// Returns the extra data to include in built blocks, if set.
+    pub fn extra_data(&self) -> Option<Bytes> {
+        self.inner.read().extra_data.clone()
+    }
+
+    /// Sets the extra data to include in built blocks.
+    pub fn set_extra_data(&self, extra_data: Bytes) {
+        self.inner.write().extra_data = Some(extra_data);
+    }
+
+    /// Returns the gas limit to target for built blocks, if set.
+    pub fn gas_limit(&self) -> Option<u64> {
+        self.inner.read().gas_limit
+    }
+
+    /// Sets the gas limit to target for built blocks.
+    pub fn set_gas_limit(&self, gas_limit: u64) {
+        self.inner.write().gas_limit = Some(gas_limit);
+    }
+
+    /// Returns the minimum priority fee per gas of included transactions, if set.
+    pub fn min_tip(&self) -> Option<u128> {
+        self.inner.read().min_tip
+    }
+
+    /// Sets the minimum priority fee per gas of included transactions.
+    pub fn set_min_tip(&self, min_tip: u128) {
+        self.inner.write().min_tip = Some(min_tip);
+    }
+}
+
+impl PartialEq for MinerConfig {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for MinerConfig {}
@@ -1,10 +1,11 @@
 use crate::{segments::SegmentSet, Pruner};
-use alloy_eips::eip2718::Encodable2718;
+use alloy_consensus::TxReceipt;
+use alloy_primitives::Log;
 use reth_chainspec::MAINNET_PRUNE_DELETE_LIMIT;
 use reth_config::PruneConfig;
 use reth_db_api::{table::Value, transaction::DbTxMut};
 use reth_exex_types::FinishedExExHeight;
-use reth_primitives_traits::NodePrimitives;
+use reth_primitives_traits::{NodePrimitives, SignedTransaction};
 use reth_provider::{
     providers::StaticFileProvider, BlockReader, DBProvider, DatabaseProviderFactory,
     NodePrimitivesProvider, PruneCheckpointWriter, StaticFileProviderFactory,
@@ -80,8 +81,10 @@ impl PrunerBuilder {
     where
         PF: DatabaseProviderFactory<
                 ProviderRW: PruneCheckpointWriter
-                                + BlockReader<Transaction: Encodable2718>
-                                + StaticFileProviderFactory<
+                                + BlockReader<
+                    Transaction: SignedTransaction,
+                    Receipt: TxReceipt<Log = Log>,
+                > + StaticFileProviderFactory<
                     Primitives: NodePrimitives<SignedTx: Value, Receipt: Value>,
                 >,
             > + StaticFileProviderFactory<
@@ -109,7 +112,7 @@ impl PrunerBuilder {
     where
         Provider: StaticFileProviderFactory<Primitives: NodePrimitives<SignedTx: Value, Receipt: Value>>
             + DBProvider<Tx: DbTxMut>
-            + BlockReader<Transaction: Encodable2718>
+            + BlockReader<Transaction: SignedTransaction, Receipt: TxReceipt<Log = Log>>
             + PruneCheckpointWriter,
     {
         let segments = SegmentSet::<Provider>::from_components(static_file_provider, self.segments);
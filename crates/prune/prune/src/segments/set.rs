@@ -1,17 +1,21 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, AddressTransactions, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
+    TransactionLookup, UserReceipts,
 };
-use alloy_eips::eip2718::Encodable2718;
+use alloy_consensus::TxReceipt;
+use alloy_primitives::Log;
 use reth_db_api::{table::Value, transaction::DbTxMut};
-use reth_primitives_traits::NodePrimitives;
+use reth_primitives_traits::{NodePrimitives, SignedTransaction};
 use reth_provider::{
     providers::StaticFileProvider, BlockReader, DBProvider, PruneCheckpointWriter,
     StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
 
-use super::{StaticFileHeaders, StaticFileReceipts, StaticFileTransactions};
+use super::{
+    StaticFileChangeSets, StaticFileHeaders, StaticFileReceipts, StaticFileSenders,
+    StaticFileTransactions,
+};
 
 /// Collection of [`Segment`]. Thread-safe, allocated on the heap.
 #[derive(Debug)]
@@ -50,7 +54,7 @@ where
     Provider: StaticFileProviderFactory<Primitives: NodePrimitives<SignedTx: Value, Receipt: Value>>
         + DBProvider<Tx: DbTxMut>
         + PruneCheckpointWriter
-        + BlockReader<Transaction: Encodable2718>,
+        + BlockReader<Transaction: SignedTransaction, Receipt: TxReceipt<Log = Log>>,
 {
     /// Creates a [`SegmentSet`] from an existing components, such as [`StaticFileProvider`] and
     /// [`PruneModes`].
@@ -64,6 +68,7 @@ where
             receipts,
             account_history,
             storage_history,
+            address_transactions,
             receipts_log_filter,
         } = prune_modes;
 
@@ -73,11 +78,19 @@ where
             // Static file transactions
             .segment(StaticFileTransactions::new(static_file_provider.clone()))
             // Static file receipts
-            .segment(StaticFileReceipts::new(static_file_provider))
+            .segment(StaticFileReceipts::new(static_file_provider.clone()))
+            // Static file senders
+            .segment(StaticFileSenders::new(static_file_provider.clone()))
+            // Static file account changesets
+            .segment(StaticFileChangeSets::accounts(static_file_provider.clone()))
+            // Static file storage changesets
+            .segment(StaticFileChangeSets::storages(static_file_provider))
             // Account history
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Address transactions, pruned before the receipts it reads the log emitters from
+            .segment_opt(address_transactions.map(AddressTransactions::new))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
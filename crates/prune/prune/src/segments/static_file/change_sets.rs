@@ -0,0 +1,116 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use reth_db_api::{models::BlockNumberAddress, tables, transaction::DbTxMut};
+use reth_provider::{providers::StaticFileProvider, DBProvider, StaticFileProviderFactory};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use reth_static_file_types::StaticFileSegment;
+use tracing::trace;
+
+/// Prunes the account or storage changesets that were moved to static files from the database.
+#[derive(Debug)]
+pub struct ChangeSets<N> {
+    static_file_provider: StaticFileProvider<N>,
+    kind: ChangeSetsKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ChangeSetsKind {
+    Accounts,
+    Storages,
+}
+
+impl<N> ChangeSets<N> {
+    /// Creates the segment pruning [`tables::AccountChangeSets`].
+    pub const fn accounts(static_file_provider: StaticFileProvider<N>) -> Self {
+        Self { static_file_provider, kind: ChangeSetsKind::Accounts }
+    }
+
+    /// Creates the segment pruning [`tables::StorageChangeSets`].
+    pub const fn storages(static_file_provider: StaticFileProvider<N>) -> Self {
+        Self { static_file_provider, kind: ChangeSetsKind::Storages }
+    }
+
+    const fn static_file_segment(&self) -> StaticFileSegment {
+        match self.kind {
+            ChangeSetsKind::Accounts => StaticFileSegment::AccountChangeSets,
+            ChangeSetsKind::Storages => StaticFileSegment::StorageChangeSets,
+        }
+    }
+}
+
+impl<Provider: StaticFileProviderFactory + DBProvider<Tx: DbTxMut>> Segment<Provider>
+    for ChangeSets<Provider::Primitives>
+{
+    fn segment(&self) -> PruneSegment {
+        match self.kind {
+            ChangeSetsKind::Accounts => PruneSegment::AccountChangeSets,
+            ChangeSetsKind::Storages => PruneSegment::StorageChangeSets,
+        }
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        self.static_file_provider
+            .get_highest_static_file_block(self.static_file_segment())
+            .map(PruneMode::before_inclusive)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::StaticFile
+    }
+
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let segment = self.static_file_segment();
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", %segment, "No changesets to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+        let mut last_pruned_block = None;
+        let (pruned, done) = match self.kind {
+            ChangeSetsKind::Accounts => {
+                provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
+                    range,
+                    &mut limiter,
+                    |_| false,
+                    |(block_number, _)| last_pruned_block = Some(block_number),
+                )?
+            }
+            ChangeSetsKind::Storages => {
+                provider.tx_ref().prune_table_with_range::<tables::StorageChangeSets>(
+                    BlockNumberAddress::range(range),
+                    &mut limiter,
+                    |_| false,
+                    |(block_address, _)| last_pruned_block = Some(block_address.block_number()),
+                )?
+            }
+        };
+        trace!(target: "pruner", %segment, %pruned, %done, "Pruned changesets");
+
+        let last_pruned_block = last_pruned_block
+            // If there's more changesets to prune, set the checkpoint block number to previous,
+            // so we could finish pruning its changesets on the next run.
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
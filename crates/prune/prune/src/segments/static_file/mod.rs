@@ -1,7 +1,11 @@
+mod change_sets;
 mod headers;
 mod receipts;
+mod senders;
 mod transactions;
 
+pub use change_sets::ChangeSets;
 pub use headers::Headers;
 pub use receipts::Receipts;
+pub use senders::Senders;
 pub use transactions::Transactions;
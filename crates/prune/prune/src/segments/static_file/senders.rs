@@ -0,0 +1,46 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use reth_db_api::transaction::DbTxMut;
+use reth_provider::{
+    providers::StaticFileProvider, BlockReader, DBProvider, StaticFileProviderFactory,
+    TransactionsProvider,
+};
+use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
+use reth_static_file_types::StaticFileSegment;
+
+#[derive(Debug)]
+pub struct Senders<N> {
+    static_file_provider: StaticFileProvider<N>,
+}
+
+impl<N> Senders<N> {
+    pub const fn new(static_file_provider: StaticFileProvider<N>) -> Self {
+        Self { static_file_provider }
+    }
+}
+
+impl<Provider> Segment<Provider> for Senders<Provider::Primitives>
+where
+    Provider:
+        StaticFileProviderFactory + DBProvider<Tx: DbTxMut> + TransactionsProvider + BlockReader,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::SenderRecovery
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        self.static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Senders)
+            .map(PruneMode::before_inclusive)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::StaticFile
+    }
+
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        crate::segments::senders::prune(provider, input)
+    }
+}
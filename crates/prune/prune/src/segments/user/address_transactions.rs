@@ -0,0 +1,259 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::Log;
+use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{transaction_addresses, BlockReader, DBProvider};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use rustc_hash::FxHashSet;
+use tracing::{instrument, trace};
+
+/// Prunes [`tables::AddressTransactions`].
+///
+/// The addresses to prune are looked up from the pruned transactions, their senders and their
+/// receipts. If the receipts were already pruned, the indices of the addresses that only emitted
+/// logs in these transactions are left untouched until the address shows up in a pruned range
+/// again.
+#[derive(Debug)]
+pub struct AddressTransactions {
+    mode: PruneMode,
+}
+
+impl AddressTransactions {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for AddressTransactions
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader<Transaction: SignedTransaction, Receipt: TxReceipt<Log = Log>>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressTransactions
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let (start, end) = match input.get_next_tx_num_range(provider)? {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No address transactions to prune");
+                return Ok(SegmentOutput::done())
+            }
+        }
+        .into_inner();
+        let tx_range = start..=
+            input
+                .limiter
+                .deleted_entries_limit_left()
+                .map_or(end, |left| end.min(start + left as u64 - 1));
+        let tx_range_end = *tx_range.end();
+
+        let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+
+        // Number of transactions retrieved from the database should match the tx range count
+        let tx_count = tx_range.clone().count();
+        if transactions.len() != tx_count {
+            return Err(PrunerError::InconsistentData(
+                "Unexpected number of transactions retrieved by transaction number range",
+            ))
+        }
+
+        let mut senders = provider.senders_by_tx_range(tx_range.clone())?;
+        if senders.len() != tx_count {
+            senders = transactions
+                .iter()
+                .map(|transaction| transaction.try_recover_unchecked())
+                .collect::<Result<_, _>>()
+                .map_err(|_| {
+                    PrunerError::InconsistentData("Failed to recover transaction sender")
+                })?;
+        }
+        let receipts =
+            tx_range.map(|tx_number| provider.receipt(tx_number)).collect::<Result<Vec<_>, _>>()?;
+
+        let mut addresses = FxHashSet::default();
+        for ((transaction, sender), receipt) in transactions.iter().zip(senders).zip(&receipts) {
+            addresses.extend(transaction_addresses(sender, transaction, receipt.as_ref()));
+        }
+
+        // Every address gets all of its indices up to the end of the range pruned.
+        let mut highest_sharded_keys = addresses
+            .into_iter()
+            .map(|address| ShardedKey::new(address, tx_range_end))
+            .collect::<Vec<_>>();
+        highest_sharded_keys.sort_unstable();
+        let outcomes = prune_history_indices::<Provider, tables::AddressTransactions, _>(
+            provider,
+            highest_sharded_keys,
+            |a, b| a.key == b.key,
+        )?;
+
+        let mut limiter = input.limiter;
+        limiter.increment_deleted_entries_count_by(tx_count);
+
+        let done = tx_range_end == end;
+        trace!(target: "pruner", ?outcomes, %done, "Pruned address transactions");
+
+        let last_pruned_block = provider
+            .transaction_block(tx_range_end)?
+            .ok_or(PrunerError::InconsistentData("Block for transaction is not found"))?
+            // If there's more address transactions to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its entries on the next run.
+            .checked_sub(if done { 0 } else { 1 });
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: tx_count,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: Some(tx_range_end),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AddressTransactions, PruneInput, PruneLimiter, Segment};
+    use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
+    use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut, BlockNumberList};
+    use reth_primitives_traits::SignedTransaction;
+    use reth_provider::{
+        transaction_addresses, DatabaseProviderFactory, PruneCheckpointReader,
+        PruneCheckpointWriter,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneProgress, PruneSegment};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_receipt, BlockRangeParams,
+    };
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            1..=10,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // Build the index of all transactions in a single shard per address.
+        let mut index = BTreeMap::<Address, Vec<TxNumber>>::new();
+        let mut receipts = Vec::new();
+        for (tx_number, transaction) in
+            blocks.iter().flat_map(|block| &block.body().transactions).enumerate()
+        {
+            let tx_number = tx_number as TxNumber;
+            let receipt = random_receipt(&mut rng, transaction, Some(1));
+            let sender = transaction.try_recover().unwrap();
+            for address in transaction_addresses(sender, transaction, Some(&receipt)) {
+                let indices = index.entry(address).or_default();
+                if indices.last() != Some(&tx_number) {
+                    indices.push(tx_number);
+                }
+            }
+            receipts.push((tx_number, receipt));
+        }
+        db.insert_receipts(receipts).expect("insert receipts");
+        db.commit(|tx| {
+            for (address, indices) in &index {
+                tx.put::<tables::AddressTransactions>(
+                    ShardedKey::last(*address),
+                    BlockNumberList::new(indices.iter().copied()).unwrap(),
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let test_prune = |to_block: BlockNumber| {
+            let prune_mode = PruneMode::Before(to_block);
+            let segment = AddressTransactions::new(prune_mode);
+            let input = PruneInput {
+                previous_checkpoint: db
+                    .factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::AddressTransactions)
+                    .unwrap(),
+                to_block,
+                limiter: PruneLimiter::default(),
+            };
+
+            let provider = db.factory.database_provider_rw().unwrap();
+            let result = segment.prune(&provider, input).unwrap();
+            assert_eq!(result.progress, PruneProgress::Finished);
+            provider
+                .save_prune_checkpoint(
+                    PruneSegment::AddressTransactions,
+                    result.checkpoint.unwrap().as_prune_checkpoint(prune_mode),
+                )
+                .unwrap();
+            provider.commit().expect("commit");
+
+            // Every index entry up to the last pruned transaction is gone.
+            let last_pruned_tx = blocks
+                .iter()
+                .take_while(|block| block.number <= to_block)
+                .map(|block| block.transaction_count())
+                .sum::<usize>() as TxNumber;
+            let expected = index
+                .iter()
+                .filter_map(|(address, indices)| {
+                    let indices = indices
+                        .iter()
+                        .copied()
+                        .filter(|tx_number| *tx_number >= last_pruned_tx)
+                        .collect::<Vec<_>>();
+                    (!indices.is_empty()).then_some((*address, indices))
+                })
+                .collect::<BTreeMap<_, _>>();
+            let actual = db
+                .table::<tables::AddressTransactions>()
+                .unwrap()
+                .into_iter()
+                .map(|(key, list)| (key.key, list.iter().collect::<Vec<_>>()))
+                .collect::<BTreeMap<_, _>>();
+            assert_eq!(actual, expected);
+
+            assert_eq!(
+                db.factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::AddressTransactions)
+                    .unwrap(),
+                Some(PruneCheckpoint {
+                    block_number: Some(to_block),
+                    tx_number: Some(last_pruned_tx - 1),
+                    prune_mode
+                })
+            );
+        };
+
+        test_prune(3);
+        test_prune(6);
+        test_prune(10);
+    }
+}
@@ -21,6 +21,10 @@ pub mod cancelled;
 /// Contains glue code for integrating reth database into revm's [Database].
 pub mod database;
 
+/// State changes that are committed without executing a transaction.
+#[cfg(feature = "std")]
+pub mod state_changes;
+
 pub use revm::{database as db, inspector};
 
 /// Common test helpers
@@ -0,0 +1,239 @@
+use alloc::{collections::BTreeMap, sync::Arc};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use parking_lot::Mutex;
+use revm::{
+    bytecode::Bytecode,
+    database::State,
+    state::{Account, EvmState, EvmStorageSlot},
+    Database, DatabaseCommit,
+};
+
+/// Changes to a single account that are applied as they are, without executing a transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountChanges {
+    /// The new balance of the account, if changed.
+    pub balance: Option<U256>,
+    /// The new nonce of the account, if changed.
+    pub nonce: Option<u64>,
+    /// The new code of the account, if changed.
+    pub code: Option<Bytes>,
+    /// The changed storage slots of the account.
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// A set of account changes, see [`AccountChanges`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateChanges(BTreeMap<Address, AccountChanges>);
+
+impl StateChanges {
+    /// Returns `true` if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the changes of the given account, creating them if they don't exist yet.
+    pub fn account_mut(&mut self, address: Address) -> &mut AccountChanges {
+        self.0.entry(address).or_default()
+    }
+
+    /// Merges the given changes into these changes, the given ones take precedence.
+    pub fn extend(&mut self, other: Self) {
+        for (address, changes) in other.0 {
+            let account = self.account_mut(address);
+            account.balance = changes.balance.or(account.balance);
+            account.nonce = changes.nonce.or(account.nonce);
+            account.code = changes.code.or_else(|| account.code.take());
+            account.storage.extend(changes.storage);
+        }
+    }
+
+    /// Commits the changes to the given [`State`].
+    ///
+    /// The changes are recorded like the state changes of a transaction, so they become part of the
+    /// bundle state and its reverts.
+    pub fn commit<DB: Database>(&self, state: &mut State<DB>) -> Result<(), DB::Error> {
+        let mut evm_state = EvmState::default();
+        for (address, changes) in &self.0 {
+            let mut account = Account::from(state.basic(*address)?.unwrap_or_default());
+            account.mark_touch();
+
+            if let Some(balance) = changes.balance {
+                account.info.balance = balance;
+            }
+            if let Some(nonce) = changes.nonce {
+                account.info.nonce = nonce;
+            }
+            if let Some(code) = &changes.code {
+                let code = Bytecode::new_raw(code.clone());
+                account.info.code_hash = code.hash_slow();
+                account.info.code = Some(code);
+            }
+            for (slot, value) in &changes.storage {
+                let original = state.storage(*address, *slot)?;
+                account.storage.insert(*slot, EvmStorageSlot::new_changed(original, *value));
+            }
+
+            evm_state.insert(*address, account);
+        }
+        state.commit(evm_state);
+        Ok(())
+    }
+}
+
+/// State changes that are waiting to be included in the next block built on top of a given
+/// parent, e.g. balances set over RPC on a dev chain.
+///
+/// Changes are first collected as pending. Before a block is built, they are staged for the
+/// block's parent with [`Self::stage`], so every payload built on top of that parent commits the
+/// same changes, see [`Self::staged`]. Once the block is part of the chain, the staged changes are
+/// dropped with [`Self::clear_staged`].
+///
+/// This is a cheaply cloneable handle, all clones share the same changes.
+#[derive(Debug, Clone, Default)]
+pub struct PendingStateChanges {
+    inner: Arc<Mutex<PendingStateChangesInner>>,
+}
+
+#[derive(Debug, Default)]
+struct PendingStateChangesInner {
+    /// Changes that haven't been staged for a block yet.
+    pending: StateChanges,
+    /// Changes that are staged for the block on top of the parent with the given hash.
+    staged: Option<(B256, StateChanges)>,
+}
+
+impl PendingStateChanges {
+    /// Sets the balance of the account.
+    pub fn set_balance(&self, address: Address, balance: U256) {
+        self.inner.lock().pending.account_mut(address).balance = Some(balance);
+    }
+
+    /// Sets the nonce of the account.
+    pub fn set_nonce(&self, address: Address, nonce: u64) {
+        self.inner.lock().pending.account_mut(address).nonce = Some(nonce);
+    }
+
+    /// Sets the code of the account.
+    pub fn set_code(&self, address: Address, code: Bytes) {
+        self.inner.lock().pending.account_mut(address).code = Some(code);
+    }
+
+    /// Sets a storage slot of the account.
+    pub fn set_storage(&self, address: Address, slot: U256, value: U256) {
+        self.inner.lock().pending.account_mut(address).storage.insert(slot, value);
+    }
+
+    /// Returns `true` if there are pending changes that are not staged yet.
+    pub fn has_pending(&self) -> bool {
+        !self.inner.lock().pending.is_empty()
+    }
+
+    /// Stages all pending changes for the block on top of the given parent.
+    ///
+    /// Changes that are already staged for the same parent are kept, changes staged for a
+    /// different parent are discarded.
+    pub fn stage(&self, parent_hash: B256) {
+        let mut inner = self.inner.lock();
+        let pending = core::mem::take(&mut inner.pending);
+        match &mut inner.staged {
+            Some((hash, staged)) if *hash == parent_hash => staged.extend(pending),
+            staged => *staged = (!pending.is_empty()).then_some((parent_hash, pending)),
+        }
+    }
+
+    /// Returns the changes staged for the block on top of the given parent, if any.
+    pub fn staged(&self, parent_hash: B256) -> Option<StateChanges> {
+        self.inner
+            .lock()
+            .staged
+            .as_ref()
+            .filter(|(hash, _)| *hash == parent_hash)
+            .map(|(_, changes)| changes.clone())
+    }
+
+    /// Drops the changes staged for the block on top of the given parent.
+    pub fn clear_staged(&self, parent_hash: B256) {
+        let mut inner = self.inner.lock();
+        if inner.staged.as_ref().is_some_and(|(hash, _)| *hash == parent_hash) {
+            inner.staged = None;
+        }
+    }
+
+    /// Drops all pending and staged changes.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock();
+        inner.pending = StateChanges::default();
+        inner.staged = None;
+    }
+}
+
+impl PartialEq for PendingStateChanges {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for PendingStateChanges {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{CacheDB, EmptyDB};
+    use revm::state::AccountInfo;
+
+    #[test]
+    fn stage_for_parent() {
+        let changes = PendingStateChanges::default();
+        let address = Address::with_last_byte(1);
+        let parent = B256::with_last_byte(1);
+
+        changes.set_balance(address, U256::from(1));
+        assert!(changes.has_pending());
+        changes.stage(parent);
+        assert!(!changes.has_pending());
+
+        // changes set after staging are merged into the staged changes of the same parent
+        changes.set_nonce(address, 2);
+        changes.stage(parent);
+        let staged = changes.staged(parent).unwrap();
+        assert_eq!(
+            staged.0[&address],
+            AccountChanges { balance: Some(U256::from(1)), nonce: Some(2), ..Default::default() }
+        );
+        assert_eq!(changes.staged(B256::with_last_byte(2)), None);
+
+        changes.clear_staged(parent);
+        assert_eq!(changes.staged(parent), None);
+    }
+
+    #[test]
+    fn commit_changes() {
+        let address = Address::with_last_byte(1);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(address, AccountInfo { nonce: 1, ..Default::default() });
+        db.insert_account_storage(address, U256::from(1), U256::from(10)).unwrap();
+        let mut state = State::builder().with_database(db).with_bundle_update().build();
+
+        let mut changes = StateChanges::default();
+        let account = changes.account_mut(address);
+        account.balance = Some(U256::from(100));
+        account.code = Some(Bytes::from_static(&[0x60, 0x00]));
+        account.storage.insert(U256::from(1), U256::from(20));
+        changes.commit(&mut state).unwrap();
+
+        let info = state.basic(address).unwrap().unwrap();
+        assert_eq!(info.balance, U256::from(100));
+        assert_eq!(info.nonce, 1);
+        assert_eq!(
+            info.code_hash,
+            Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00])).hash_slow()
+        );
+        assert_eq!(state.storage(address, U256::from(1)).unwrap(), U256::from(20));
+
+        state.merge_transitions(revm::database::states::bundle_state::BundleRetention::Reverts);
+        let bundle = state.take_bundle();
+        let account = bundle.account(&address).unwrap();
+        assert_eq!(account.storage_slot(U256::from(1)), Some(U256::from(20)));
+        assert_eq!(account.original_info.as_ref().unwrap().balance, U256::ZERO);
+    }
+}
@@ -1,6 +1,8 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_network_peers::{AnyNode, NodeRecord};
+use reth_network_types::BannedPeers;
+use std::net::IpAddr;
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -27,6 +29,42 @@ pub trait AdminApi {
     #[method(name = "removeTrustedPeer")]
     fn remove_trusted_peer(&self, record: AnyNode) -> RpcResult<bool>;
 
+    /// Bans a remote node and disconnects it if the connection exists.
+    ///
+    /// The ban expires after the given number of seconds, or never if no duration is given.
+    /// Bans are persisted across restarts.
+    ///
+    /// Returns true if the peer wasn't banned yet, an existing ban is replaced.
+    #[method(name = "banPeer")]
+    async fn ban_peer(&self, record: AnyNode, duration: Option<u64>) -> RpcResult<bool>;
+
+    /// Lifts the ban of a remote node.
+    ///
+    /// Returns true if the peer was banned.
+    #[method(name = "unbanPeer")]
+    async fn unban_peer(&self, record: AnyNode) -> RpcResult<bool>;
+
+    /// Bans an IP address and disconnects all peers connected from it.
+    ///
+    /// The ban expires after the given number of seconds, or never if no duration is given.
+    /// Bans are persisted across restarts.
+    ///
+    /// Returns true if the IP wasn't banned yet, an existing ban is replaced. IPs that are not
+    /// globally routable are never banned.
+    #[method(name = "banIp")]
+    async fn ban_ip(&self, ip: IpAddr, duration: Option<u64>) -> RpcResult<bool>;
+
+    /// Lifts the ban of an IP address.
+    ///
+    /// Returns true if the IP was banned.
+    #[method(name = "unbanIp")]
+    async fn unban_ip(&self, ip: IpAddr) -> RpcResult<bool>;
+
+    /// Returns all banned peers and IP addresses, with the unix timestamp at which their ban
+    /// expires.
+    #[method(name = "bannedPeers")]
+    async fn banned_peers(&self) -> RpcResult<BannedPeers>;
+
     /// The peers administrative property can be queried for all the information known about the
     /// connected remote nodes at the networking granularity. These include general information
     /// about the nodes themselves as participants of the devp2p P2P overlay protocol, as well as
@@ -1,13 +1,98 @@
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{Address, Bytes, B256, U64};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
-    BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
+    GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Subscription kind of the `debug_subscribe` method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DebugSubscriptionKind {
+    /// Streams the traces of a block range, see `debug_traceChain`.
+    TraceChain,
+}
+
+/// State dump of `debug_dumpBlock`, the same as geth's.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDump {
+    /// State root of the block.
+    pub root: B256,
+    /// All accounts of the state, or of the page of `debug_accountRange`.
+    pub accounts: BTreeMap<Address, DumpAccount>,
+    /// Address to pass as `start` to fetch the next page of `debug_accountRange`, `None` if this
+    /// is the last one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Address>,
+}
+
+/// An account of a [`StateDump`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// Balance in wei, as a decimal string.
+    pub balance: String,
+    /// Nonce of the account.
+    pub nonce: u64,
+    /// Root of the storage trie.
+    pub root: B256,
+    /// Hash of the bytecode.
+    pub code_hash: B256,
+    /// Bytecode of the account.
+    pub code: Bytes,
+    /// Non-zero storage slots, keyed by the unhashed slot.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<B256, B256>,
+    /// Address of the account.
+    pub address: Address,
+    /// Hashed address of the account.
+    pub key: B256,
+}
+
+/// Memory statistics of the allocator, returned by `debug_memStats`.
+///
+/// All values are in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemStats {
+    /// Bytes allocated by the application.
+    pub allocated: u64,
+    /// Bytes in active pages allocated by the application.
+    pub active: u64,
+    /// Bytes dedicated to allocator metadata.
+    pub metadata: u64,
+    /// Bytes in physically resident data pages mapped by the allocator.
+    pub resident: u64,
+    /// Bytes in active extents mapped by the allocator.
+    pub mapped: u64,
+    /// Bytes in virtual memory mappings that were retained rather than being returned to the OS.
+    pub retained: u64,
+}
+
+/// Result of `debug_storageRangeAt`, the same as geth's.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// Storage slots of the range, keyed by the hashed slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// Hashed slot to pass as `keyStart` to fetch the next page, `None` if this is the last one.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot of a [`StorageRangeResult`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The unhashed slot.
+    pub key: Option<B256>,
+    /// Value of the slot.
+    pub value: B256,
+}
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -39,14 +124,24 @@ pub trait DebugApi {
     #[method(name = "getBadBlocks")]
     async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
 
-    /// Returns the structured logs created during the execution of EVM between two blocks
-    /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
+    /// Creates a subscription that streams the structured logs created during the execution of
+    /// EVM between two blocks (excluding start), one
+    /// [`BlockTraceResult`](alloy_rpc_types_trace::geth::BlockTraceResult) per block.
+    ///
+    /// Same as geth, this is served as `debug_subscribe` with the [`DebugSubscriptionKind`]
+    /// followed by the method parameters, e.g. `["traceChain", start, end, opts]`.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = alloy_rpc_types_trace::geth::BlockTraceResult
+    )]
     async fn debug_trace_chain(
         &self,
+        kind: DebugSubscriptionKind,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -162,10 +257,10 @@ pub trait DebugApi {
     async fn debug_backtrace_at(&self, location: &str) -> RpcResult<()>;
 
     /// Enumerates all accounts at a given block with paging capability. `maxResults` are returned
-    /// in the page and the items have keys that come after the `start` key (hashed address).
+    /// in the page and the items have keys that come after the `start` key.
     ///
-    /// If incompletes is false, then accounts for which the key preimage (i.e: the address) doesn't
-    /// exist in db are skipped. NB: geth by default does not store preimages.
+    /// Unlike geth, the accounts are ordered by their address rather than its hash and `start` is
+    /// a prefix of an address. The address of every account is known, so `incompletes` is ignored.
     #[method(name = "accountRange")]
     async fn debug_account_range(
         &self,
@@ -175,7 +270,7 @@ pub trait DebugApi {
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StateDump>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -206,6 +301,8 @@ pub trait DebugApi {
     ) -> RpcResult<Option<Bytes>>;
 
     /// Turns on CPU profiling for the given duration and writes profile data to disk.
+    ///
+    /// The file is created in the profiles directory of the node and must not exist yet.
     #[method(name = "cpuProfile")]
     async fn debug_cpu_profile(&self, file: String, seconds: u64) -> RpcResult<()>;
 
@@ -226,7 +323,7 @@ pub trait DebugApi {
     /// Retrieves the state that corresponds to the block number and returns a list of accounts
     /// (including storage and code).
     #[method(name = "dumpBlock")]
-    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<()>;
+    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<StateDump>;
 
     /// Forces garbage collection.
     #[method(name = "freeOSMemory")]
@@ -259,17 +356,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -286,7 +384,7 @@ pub trait DebugApi {
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
-    async fn debug_mem_stats(&self) -> RpcResult<()>;
+    async fn debug_mem_stats(&self) -> RpcResult<MemStats>;
 
     /// Turns on mutex profiling for `nsec` seconds and writes profile data to file. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -319,7 +417,7 @@ pub trait DebugApi {
     /// Sets the current head of the local chain by block number. Note, this is a destructive action
     /// and may severely damage your chain. Use with extreme caution.
     #[method(name = "setHead")]
-    async fn debug_set_head(&self, number: u64) -> RpcResult<()>;
+    async fn debug_set_head(&self, number: U64) -> RpcResult<()>;
 
     /// Sets the rate of mutex profiling.
     #[method(name = "setMutexProfileFraction")]
@@ -331,9 +429,9 @@ pub trait DebugApi {
     #[method(name = "setTrieFlushInterval")]
     async fn debug_set_trie_flush_interval(&self, interval: String) -> RpcResult<()>;
 
-    /// Returns a printed representation of the stacks of all goroutines.
+    /// Returns a printed representation of the stacks of all threads and tasks.
     #[method(name = "stacks")]
-    async fn debug_stacks(&self) -> RpcResult<()>;
+    async fn debug_stacks(&self) -> RpcResult<String>;
 
     /// Used to obtain info about a block.
     #[method(name = "standardTraceBadBlockToFile")]
@@ -353,6 +451,8 @@ pub trait DebugApi {
     ) -> RpcResult<()>;
 
     /// Turns on CPU profiling indefinitely, writing to the given file.
+    ///
+    /// The file is created in the profiles directory of the node and must not exist yet.
     #[method(name = "startCPUProfile")]
     async fn debug_start_cpu_profile(&self, file: String) -> RpcResult<()>;
 
@@ -388,7 +488,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -405,7 +505,8 @@ pub trait DebugApi {
     #[method(name = "verbosity")]
     async fn debug_verbosity(&self, level: usize) -> RpcResult<()>;
 
-    /// Sets the logging verbosity pattern.
+    /// Sets the logging verbosity pattern, a comma-separated list of per-target directives like
+    /// `engine::tree=trace,net=debug`. An empty pattern resets logging to the startup settings.
     #[method(name = "vmodule")]
     async fn debug_vmodule(&self, pattern: String) -> RpcResult<()>;
 
@@ -414,6 +515,8 @@ pub trait DebugApi {
     async fn debug_write_block_profile(&self, file: String) -> RpcResult<()>;
 
     /// Writes an allocation profile to the given file.
+    ///
+    /// The file is created in the profiles directory of the node and must not exist yet.
     #[method(name = "writeMemProfile")]
     async fn debug_write_mem_profile(&self, file: String) -> RpcResult<()>;
 
@@ -31,6 +31,11 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use debug::{
+    DebugSubscriptionKind, DumpAccount, MemStats, StateDump, StorageRangeEntry, StorageRangeResult,
+};
+pub use reth::{AddressTransaction, AddressTransactionsPage, ChainNotification};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -38,8 +43,10 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::AnvilApiServer,
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
+        hardhat::HardhatApiServer,
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
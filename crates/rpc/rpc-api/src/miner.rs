@@ -11,7 +11,8 @@ pub trait MinerApi {
     #[method(name = "setExtra")]
     fn set_extra(&self, record: Bytes) -> RpcResult<bool>;
 
-    /// Sets the minimum accepted gas price for the miner.
+    /// Sets the minimum accepted gas price for the miner, i.e. the minimum priority fee per gas of
+    /// included transactions.
     #[method(name = "setGasPrice")]
     fn set_gas_price(&self, gas_price: U128) -> RpcResult<bool>;
 
@@ -1,9 +1,48 @@
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, B256, U256, U64};
+use alloy_rpc_types_eth::{Filter, Log};
+use alloy_rpc_types_trace::parity::StateDiff;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_chain_state::CanonStateNotification;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Result of `reth_getTransactionsByAddress`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransactionsPage {
+    /// Transactions of the page, in ascending order.
+    pub transactions: Vec<AddressTransaction>,
+    /// Cursor to pass to fetch the next page, `None` if this is the last one.
+    pub next_cursor: Option<U64>,
+}
+
+/// A transaction of an [`AddressTransactionsPage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressTransaction {
+    /// Hash of the transaction.
+    pub hash: B256,
+    /// Hash of the block the transaction is included in.
+    pub block_hash: B256,
+    /// Number of the block the transaction is included in.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Index of the transaction in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+}
+
+/// Item of `reth_subscribeChainNotifications`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ChainNotification {
+    /// A canonical state notification, sent if no filter was passed.
+    Notification(CanonStateNotification),
+    /// A log matching the filter.
+    Log(Log),
+}
+
 /// Reth API namespace for reth-specific methods
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
@@ -15,11 +54,53 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
 
+    /// Returns the state changes of a block: the balance, nonce, code and storage changes of
+    /// every account the block modified.
+    ///
+    /// The changes are read from the changesets of the block, without re-executing it.
+    #[method(name = "getStateDiffInBlock")]
+    async fn reth_get_state_diff_in_block(&self, block_id: BlockId) -> RpcResult<StateDiff>;
+
+    /// Returns the state changes of a transaction, in the same format as
+    /// [`Self::reth_get_state_diff_in_block`].
+    ///
+    /// The block of the transaction is re-executed up to and including the transaction, and the
+    /// diff matches the `stateDiff` returned by `trace_replayTransaction`.
+    #[method(name = "getStateDiffByTransaction")]
+    async fn reth_get_state_diff_by_transaction(&self, hash: B256) -> RpcResult<StateDiff>;
+
+    /// Returns the transactions the address took part in within the block range, i.e. sent,
+    /// received or emitted a log in.
+    ///
+    /// The block range defaults to the whole chain. Only persisted blocks are served, and only if
+    /// the address transactions index is enabled. Ranges that start at a block whose index entries
+    /// were pruned are rejected. Large results are split into pages: pass the `nextCursor` of a
+    /// page as `cursor` to fetch the next one.
+    #[method(name = "getTransactionsByAddress")]
+    async fn reth_get_transactions_by_address(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+        cursor: Option<U64>,
+    ) -> RpcResult<AddressTransactionsPage>;
+
     /// Subscribe to json `ChainNotifications`
+    ///
+    /// If a filter is passed, the logs matching it are sent instead of the whole notifications.
+    /// The matching logs of the blocks from `fromBlock` up to the current head are replayed from
+    /// the database first, followed by the logs of new canonical blocks. `fromBlock` can be at
+    /// most as many blocks behind the head as `eth_getLogs` can query at once. Logs that were sent
+    /// and belong to blocks removed by a reorg are sent again with `removed: true`. If `fromBlock`
+    /// is omitted, only the logs of new blocks are sent, like `eth_subscribe("logs")` does.
     #[subscription(
         name = "subscribeChainNotifications",
         unsubscribe = "unsubscribeChainNotifications",
-        item = CanonStateNotification
+        item = ChainNotification
     )]
-    async fn reth_subscribe_chain_notifications(&self) -> jsonrpsee::core::SubscriptionResult;
+    async fn reth_subscribe_chain_notifications(
+        &self,
+        filter: Option<Filter>,
+        from_block: Option<BlockNumberOrTag>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -145,6 +145,11 @@ where
     AdminApiClient::remove_peer(client, node.into()).await.unwrap();
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
+    AdminApiClient::ban_peer(client, node.into(), Some(60)).await.unwrap();
+    AdminApiClient::unban_peer(client, node.into()).await.unwrap();
+    AdminApiClient::ban_ip(client, [1, 1, 1, 1].into(), None).await.unwrap();
+    AdminApiClient::unban_ip(client, [1, 1, 1, 1].into()).await.unwrap();
+    AdminApiClient::banned_peers(client).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
 }
 
@@ -369,6 +374,8 @@ where
     DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
     DebugApiClient::raw_receipts(client, block_id).await.unwrap();
     DebugApiClient::bad_blocks(client).await.unwrap();
+    DebugApiClient::debug_get_modified_accounts_by_number(client, 1, Some(0)).await.unwrap_err();
+    DebugApiClient::debug_set_head(client, U64::ZERO).await.unwrap_err();
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -478,28 +485,38 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_before(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_after(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    OtterscanClient::<Transaction, Header>::search_transactions_before(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap_err();
+    OtterscanClient::<Transaction, Header>::search_transactions_before(
+        client,
+        address,
+        Default::default(),
+        page_size,
+    )
+    .await
+    .unwrap_err();
+    OtterscanClient::<Transaction, Header>::search_transactions_after(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap_err();
+    OtterscanClient::<Transaction, Header>::search_transactions_after(
+        client,
+        address,
+        Default::default(),
+        page_size,
+    )
+    .await
+    .unwrap_err();
     assert!(OtterscanClient::<Transaction, Header>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
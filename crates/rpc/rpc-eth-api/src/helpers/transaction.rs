@@ -7,7 +7,7 @@ use crate::{
     RpcNodeCore, RpcNodeCoreExt, RpcReceipt, RpcTransaction,
 };
 use alloy_consensus::{
-    transaction::{SignerRecoverable, TransactionMeta},
+    transaction::{Recovered, SignerRecoverable, TransactionMeta},
     BlockHeader, Transaction,
 };
 use alloy_dyn_abi::TypedData;
@@ -171,7 +171,11 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         hash: TxHash,
     ) -> impl Future<
         Output = Result<
-            Option<(ProviderTx<Self::Provider>, TransactionMeta, ProviderReceipt<Self::Provider>)>,
+            Option<(
+                Recovered<ProviderTx<Self::Provider>>,
+                TransactionMeta,
+                ProviderReceipt<Self::Provider>,
+            )>,
             Self::Error,
         >,
     > + Send
@@ -192,6 +196,8 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
                 Some(recpt) => recpt,
                 None => return Ok(None),
             };
+            let tx =
+                with_recorded_sender(&provider, hash, tx).map_err(Self::Error::from_eth_err)?;
 
             Ok(Some((tx, meta, receipt)))
         })
@@ -482,12 +488,8 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
                     {
                         None => Ok(None),
                         Some((tx, meta)) => {
-                            // Note: we assume this transaction is valid, because it's mined (or
-                            // part of pending block) and already. We don't need to
-                            // check for pre EIP-2 because this transaction could be pre-EIP-2.
-                            let transaction = tx
-                                .try_into_recovered_unchecked()
-                                .map_err(|_| EthApiError::InvalidTransactionSignature)?;
+                            let transaction = with_recorded_sender(this.provider(), hash, tx)
+                                .map_err(Self::Error::from_eth_err)?;
 
                             let tx = TransactionSource::Block {
                                 transaction,
@@ -572,3 +574,25 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
         }
     }
 }
+
+/// Returns the mined transaction with the sender that was recorded for it.
+///
+/// The recorded sender can differ from the signer of the transaction, e.g. for transactions sent
+/// from impersonated accounts in `--dev` mode. Falls back to recovering the sender if none is
+/// recorded.
+fn with_recorded_sender<P: TransactionsProvider>(
+    provider: &P,
+    hash: TxHash,
+    tx: P::Transaction,
+) -> Result<Recovered<P::Transaction>, EthApiError> {
+    if let Some(id) = provider.transaction_id(hash)? {
+        if let Some(sender) = provider.transaction_sender(id)? {
+            return Ok(Recovered::new_unchecked(tx, sender))
+        }
+    }
+
+    // Note: we assume this transaction is valid, because it's mined (or part of pending block)
+    // and already. We don't need to check for pre EIP-2 because this transaction could be
+    // pre-EIP-2.
+    tx.try_into_recovered_unchecked().map_err(|_| EthApiError::InvalidTransactionSignature)
+}
@@ -40,6 +40,10 @@ impl ToRpcError for jsonrpsee_types::ErrorObject<'static> {
 /// Result alias
 pub type EthResult<T> = Result<T, EthApiError>;
 
+/// Error code returned when the requested history was pruned, see
+/// <https://eips.ethereum.org/EIPS/eip-4444#json-rpc-changes>.
+pub const PRUNED_HISTORY_UNAVAILABLE_CODE: i32 = 4444;
+
 /// Errors that can occur when interacting with the `eth_` namespace
 #[derive(Debug, thiserror::Error)]
 pub enum EthApiError {
@@ -144,6 +148,9 @@ pub enum EthApiError {
     /// Error encountered when converting a transaction type
     #[error("Transaction conversion error")]
     TransactionConversionError,
+    /// The requested history is no longer available because it was pruned
+    #[error("pruned history unavailable")]
+    PrunedHistoryUnavailable,
     /// Error thrown when tracing with a muxTracer fails
     #[error(transparent)]
     MuxTracerError(#[from] MuxError),
@@ -227,6 +234,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             err @ EthApiError::TransactionInputError(_) => invalid_params_rpc_err(err.to_string()),
             EthApiError::Other(err) => err.to_rpc_error(),
             EthApiError::MuxTracerError(msg) => internal_rpc_err(msg.to_string()),
+            err @ EthApiError::PrunedHistoryUnavailable => {
+                rpc_error_with_code(PRUNED_HISTORY_UNAVAILABLE_CODE, err.to_string())
+            }
         }
     }
 }
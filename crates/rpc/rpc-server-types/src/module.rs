@@ -273,6 +273,10 @@ pub enum RethRpcModule {
     Miner,
     /// `mev_` module
     Mev,
+    /// `anvil_` module
+    Anvil,
+    /// `hardhat_` module
+    Hardhat,
 }
 
 // === impl RethRpcModule ===
@@ -324,6 +328,8 @@ impl FromStr for RethRpcModule {
             "flashbots" => Self::Flashbots,
             "miner" => Self::Miner,
             "mev" => Self::Mev,
+            "anvil" => Self::Anvil,
+            "hardhat" => Self::Hardhat,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc, time::Duration};
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
@@ -10,7 +10,7 @@ use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
 use reth_network_api::{NetworkInfo, Peers};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
-use reth_network_types::PeerKind;
+use reth_network_types::{BannedPeers, PeerKind};
 use reth_rpc_api::AdminApiServer;
 use reth_rpc_server_types::ToRpcResult;
 
@@ -64,6 +64,34 @@ where
         Ok(true)
     }
 
+    /// Handler for `admin_banPeer`
+    async fn ban_peer(&self, record: AnyNode, duration: Option<u64>) -> RpcResult<bool> {
+        self.network
+            .ban_peer(record.peer_id(), duration.map(Duration::from_secs))
+            .await
+            .to_rpc_result()
+    }
+
+    /// Handler for `admin_unbanPeer`
+    async fn unban_peer(&self, record: AnyNode) -> RpcResult<bool> {
+        self.network.unban_peer(record.peer_id()).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_banIp`
+    async fn ban_ip(&self, ip: IpAddr, duration: Option<u64>) -> RpcResult<bool> {
+        self.network.ban_ip(ip, duration.map(Duration::from_secs)).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_unbanIp`
+    async fn unban_ip(&self, ip: IpAddr) -> RpcResult<bool> {
+        self.network.unban_ip(ip).await.to_rpc_result()
+    }
+
+    /// Handler for `admin_bannedPeers`
+    async fn banned_peers(&self) -> RpcResult<BannedPeers> {
+        self.network.banned_peers().await.to_rpc_result()
+    }
+
     /// Handler for `admin_peers`
     async fn peers(&self) -> RpcResult<Vec<PeerInfo>> {
         let peers = self.network.get_all_peers().await.to_rpc_result()?;
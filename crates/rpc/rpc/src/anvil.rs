@@ -0,0 +1,524 @@
+use alloy_consensus::{SignableTransaction, TxEnvelope, TypedTransaction};
+use alloy_dyn_abi::TypedData;
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_primitives::{Address, Bytes, Signature, B256, U256};
+use alloy_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeInfo};
+use alloy_rpc_types_eth::{Block, TransactionRequest};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use parking_lot::RwLock;
+use reth_engine_local::{LocalMinerHandle, MiningMode};
+use reth_rpc_api::{AnvilApiServer, HardhatApiServer};
+use reth_rpc_eth_api::{
+    helpers::{signer::Result as SignResult, EthSigner, EthTransactions},
+    RpcNodeCore,
+};
+use reth_rpc_eth_types::{EthApiError, SignError};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_transaction_pool::TransactionPool;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// `anvil` and `hardhat` API implementation for --dev nodes.
+///
+/// Mining is controlled through the [`LocalMinerHandle`] of the node. State changes like
+/// `anvil_setBalance` are committed in the next mined block, which is mined right away if the
+/// node mines a block for every transaction.
+pub struct AnvilApi<Eth> {
+    inner: Arc<AnvilApiInner<Eth>>,
+}
+
+impl<Eth> AnvilApi<Eth> {
+    /// Creates a new instance of `AnvilApi`.
+    ///
+    /// Without a [`LocalMinerHandle`], only the impersonation and transaction pool methods are
+    /// available.
+    pub fn new(eth_api: Eth, miner: Option<LocalMinerHandle>) -> Self {
+        let inner = AnvilApiInner {
+            eth_api,
+            miner,
+            impersonated: Default::default(),
+            impersonation_signer_added: AtomicBool::new(false),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns the [`LocalMinerHandle`] or an error if the node is not a --dev node.
+    fn miner(&self) -> RpcResult<&LocalMinerHandle> {
+        self.inner
+            .miner
+            .as_ref()
+            .ok_or_else(|| internal_rpc_err("mining is only available on --dev nodes"))
+    }
+}
+
+impl<Eth> AnvilApi<Eth>
+where
+    Eth: EthTransactions + RpcNodeCore<Pool: TransactionPool>,
+{
+    /// Handler for `anvil_impersonateAccount`
+    fn impersonate_account(&self, address: Address) {
+        self.add_impersonation_signer();
+        self.inner.impersonated.accounts.write().insert(address);
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    fn stop_impersonating_account(&self, address: Address) {
+        self.inner.impersonated.accounts.write().remove(&address);
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    fn auto_impersonate_account(&self, enabled: bool) {
+        self.add_impersonation_signer();
+        self.inner.impersonated.auto.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Adds the [`ImpersonationSigner`] to the signers of the eth API, once.
+    ///
+    /// This is deferred until the first impersonation, because --dev nodes replace the signers
+    /// with the dev accounts at launch.
+    fn add_impersonation_signer(&self) {
+        if !self.inner.impersonation_signer_added.swap(true, Ordering::Relaxed) {
+            EthTransactions::signers(&self.inner.eth_api)
+                .write()
+                .push(Box::new(ImpersonationSigner { accounts: self.inner.impersonated.clone() }));
+        }
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn automine(&self) -> RpcResult<bool> {
+        self.miner()?.is_instant().await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setAutomine`
+    fn set_automine(&self, enabled: bool) -> RpcResult<()> {
+        let mode = if enabled {
+            MiningMode::instant(self.inner.eth_api.pool().clone())
+        } else {
+            MiningMode::Manual
+        };
+        self.miner()?.set_mining_mode(mode).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    ///
+    /// An interval of 0 disables interval mining.
+    fn set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        let mode = if interval == 0 {
+            MiningMode::Manual
+        } else {
+            MiningMode::interval(Duration::from_secs(interval))
+        };
+        self.miner()?.set_mining_mode(mode).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_mine`
+    async fn mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        let blocks = blocks
+            .map(u64::try_from)
+            .transpose()
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        let interval = interval
+            .map(u64::try_from)
+            .transpose()
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        self.miner()?
+            .mine(blocks.unwrap_or(1), interval)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn set_next_block_timestamp(&self, timestamp: u64) -> RpcResult<()> {
+        self.miner()?
+            .set_next_block_timestamp(timestamp)
+            .await
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn snapshot(&self) -> RpcResult<U256> {
+        let id = self.miner()?.snapshot().await.map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(U256::from(id))
+    }
+
+    /// Handler for `anvil_revert`
+    async fn revert(&self, id: U256) -> RpcResult<bool> {
+        let Ok(id) = u64::try_from(id) else { return Ok(false) };
+        self.miner()?.revert(id).await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Applies a state change through the [`LocalMinerHandle`] and mines a block if the node
+    /// mines a block for every transaction.
+    async fn change_state(
+        &self,
+        f: impl FnOnce(&reth_revm::state_changes::PendingStateChanges),
+    ) -> RpcResult<()> {
+        let miner = self.miner()?;
+        f(miner.pending_state_changes());
+        miner.mine_if_instant().await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.change_state(|changes| changes.set_balance(address, balance)).await
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.change_state(|changes| changes.set_code(address, code)).await
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        let nonce = u64::try_from(nonce).map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        self.change_state(|changes| changes.set_nonce(address, nonce)).await
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn set_storage_at(&self, address: Address, slot: U256, value: B256) -> RpcResult<()> {
+        self.change_state(|changes| changes.set_storage(address, slot, value.into())).await
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    fn drop_transaction(&self, tx_hash: B256) -> Option<B256> {
+        let removed = self.inner.eth_api.pool().remove_transactions(vec![tx_hash]);
+        (!removed.is_empty()).then_some(tx_hash)
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    fn remove_pool_transactions(&self, address: Address) {
+        self.inner.eth_api.pool().remove_transactions_by_sender(address);
+    }
+}
+
+#[async_trait]
+impl<Eth> AnvilApiServer for AnvilApi<Eth>
+where
+    Eth: EthTransactions + RpcNodeCore<Pool: TransactionPool> + Send + Sync + 'static,
+{
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.impersonate_account(address);
+        Ok(())
+    }
+
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.stop_impersonating_account(address);
+        Ok(())
+    }
+
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        self.auto_impersonate_account(enabled);
+        Ok(())
+    }
+
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        self.automine().await
+    }
+
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.mine(blocks, interval).await
+    }
+
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        self.set_automine(enabled)
+    }
+
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        self.set_interval_mining(interval)
+    }
+
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        Ok(self.drop_transaction(tx_hash))
+    }
+
+    async fn anvil_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_reset is not supported").into())
+    }
+
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setRpcUrl is not supported").into())
+    }
+
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.set_balance(address, balance).await
+    }
+
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.set_code(address, code).await
+    }
+
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        self.set_nonce(address, nonce).await
+    }
+
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        self.set_storage_at(address, slot, value).await?;
+        Ok(true)
+    }
+
+    async fn anvil_set_coinbase(&self, _address: Address) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setCoinbase is not supported").into())
+    }
+
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setChainId is not supported").into())
+    }
+
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setLoggingEnabled is not supported").into())
+    }
+
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setMinGasPrice is not supported").into())
+    }
+
+    async fn anvil_set_next_block_base_fee_per_gas(&self, _base_fee: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setNextBlockBaseFeePerGas is not supported").into())
+    }
+
+    async fn anvil_set_time(&self, _timestamp: u64) -> RpcResult<u64> {
+        Err(EthApiError::Unsupported("anvil_setTime is not supported").into())
+    }
+
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        Err(EthApiError::Unsupported("anvil_dumpState is not supported").into())
+    }
+
+    async fn anvil_load_state(&self, _state: Bytes) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("anvil_loadState is not supported").into())
+    }
+
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        Err(EthApiError::Unsupported("anvil_nodeInfo is not supported").into())
+    }
+
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        Err(EthApiError::Unsupported("anvil_metadata is not supported").into())
+    }
+
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        self.snapshot().await
+    }
+
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        self.revert(id).await
+    }
+
+    async fn anvil_increase_time(&self, _seconds: U256) -> RpcResult<i64> {
+        Err(EthApiError::Unsupported("anvil_increaseTime is not supported").into())
+    }
+
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        self.set_next_block_timestamp(seconds).await
+    }
+
+    async fn anvil_set_block_gas_limit(&self, _gas_limit: U256) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("anvil_setBlockGasLimit is not supported").into())
+    }
+
+    async fn anvil_set_block_timestamp_interval(&self, _seconds: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setBlockTimestampInterval is not supported").into())
+    }
+
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("anvil_removeBlockTimestampInterval is not supported").into())
+    }
+
+    async fn anvil_mine_detailed(&self, _opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        Err(EthApiError::Unsupported("anvil_mine_detailed is not supported").into())
+    }
+
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_enableTraces is not supported").into())
+    }
+
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        self.remove_pool_transactions(address);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Eth> HardhatApiServer for AnvilApi<Eth>
+where
+    Eth: EthTransactions + RpcNodeCore<Pool: TransactionPool> + Send + Sync + 'static,
+{
+    async fn hardhat_drop_transaction(&self, tx_hash: B256) -> RpcResult<bool> {
+        Ok(self.drop_transaction(tx_hash).is_some())
+    }
+
+    async fn hardhat_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.impersonate_account(address);
+        Ok(())
+    }
+
+    async fn hardhat_get_automine(&self) -> RpcResult<bool> {
+        self.automine().await
+    }
+
+    async fn hardhat_metadata(&self) -> RpcResult<Metadata> {
+        Err(EthApiError::Unsupported("hardhat_metadata is not supported").into())
+    }
+
+    async fn hardhat_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.mine(blocks, interval).await
+    }
+
+    async fn hardhat_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_reset is not supported").into())
+    }
+
+    async fn hardhat_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.set_balance(address, balance).await
+    }
+
+    async fn hardhat_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.set_code(address, code).await
+    }
+
+    async fn hardhat_set_coinbase(&self, _address: Address) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setCoinbase is not supported").into())
+    }
+
+    async fn hardhat_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setLoggingEnabled is not supported").into())
+    }
+
+    async fn hardhat_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setMinGasPrice is not supported").into())
+    }
+
+    async fn hardhat_set_next_block_base_fee_per_gas(
+        &self,
+        _base_fee_per_gas: U256,
+    ) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setNextBlockBaseFeePerGas is not supported").into())
+    }
+
+    async fn hardhat_set_prev_randao(&self, _prev_randao: B256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("hardhat_setPrevRandao is not supported").into())
+    }
+
+    async fn hardhat_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        self.set_nonce(address, nonce).await
+    }
+
+    async fn hardhat_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<()> {
+        self.set_storage_at(address, slot, value).await
+    }
+
+    async fn hardhat_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.stop_impersonating_account(address);
+        Ok(())
+    }
+}
+
+impl<Eth> std::fmt::Debug for AnvilApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilApi").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for AnvilApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct AnvilApiInner<Eth> {
+    /// The implementation of `eth` API
+    eth_api: Eth,
+    /// Handle to the miner of the --dev node.
+    miner: Option<LocalMinerHandle>,
+    /// The accounts transactions can be sent from without their key.
+    impersonated: Arc<ImpersonatedAccounts>,
+    /// Whether the [`ImpersonationSigner`] was added to the eth API.
+    impersonation_signer_added: AtomicBool,
+}
+
+/// The accounts that are impersonated, see [`ImpersonationSigner`].
+#[derive(Debug, Default)]
+struct ImpersonatedAccounts {
+    /// Explicitly impersonated accounts.
+    accounts: RwLock<HashSet<Address>>,
+    /// Whether all accounts are impersonated.
+    auto: AtomicBool,
+}
+
+/// Signs transactions of impersonated accounts with a placeholder signature.
+///
+/// The signature doesn't recover to the account. This works because the transaction is handed to
+/// the pool together with the account as its sender, and locally built blocks are not executed
+/// again by the engine. The account is recorded as the sender of the mined transaction, which RPC
+/// responses like `eth_getTransactionReceipt` report instead of recovering it.
+#[derive(Debug, Clone)]
+struct ImpersonationSigner {
+    accounts: Arc<ImpersonatedAccounts>,
+}
+
+impl ImpersonationSigner {
+    /// A valid, low-s signature. `r` is the x coordinate of the secp256k1 generator point, so the
+    /// signature recovers to some address for every transaction.
+    const SIGNATURE: Signature = Signature::new(
+        U256::from_be_bytes(alloy_primitives::hex!(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        )),
+        U256::from_limbs([1, 0, 0, 0]),
+        false,
+    );
+}
+
+#[async_trait]
+impl<T: Decodable2718> EthSigner<T> for ImpersonationSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.accounts.accounts.read().iter().copied().collect()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.accounts.auto.load(Ordering::Relaxed) || self.accounts.accounts.read().contains(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> SignResult<Signature> {
+        Err(SignError::NoAccount)
+    }
+
+    async fn sign_transaction(
+        &self,
+        request: TransactionRequest,
+        _address: &Address,
+    ) -> SignResult<T> {
+        let signature = Self::SIGNATURE;
+        let envelope: TxEnvelope =
+            match request.build_typed_tx().map_err(|_| SignError::InvalidTransactionRequest)? {
+                TypedTransaction::Legacy(tx) => tx.into_signed(signature).into(),
+                TypedTransaction::Eip2930(tx) => tx.into_signed(signature).into(),
+                TypedTransaction::Eip1559(tx) => tx.into_signed(signature).into(),
+                TypedTransaction::Eip4844(tx) => tx.into_signed(signature).into(),
+                TypedTransaction::Eip7702(tx) => tx.into_signed(signature).into(),
+            };
+
+        T::decode_2718(&mut envelope.encoded_2718().as_ref())
+            .map_err(|_| SignError::InvalidTransactionRequest)
+    }
+
+    fn sign_typed_data(&self, _address: Address, _payload: &TypedData) -> SignResult<Signature> {
+        Err(SignError::NoAccount)
+    }
+}
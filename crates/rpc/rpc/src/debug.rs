@@ -1,7 +1,8 @@
-use alloy_consensus::{transaction::SignerRecoverable, BlockHeader};
+use crate::profiling;
+use alloy_consensus::{proofs::storage_root_unhashed, transaction::SignerRecoverable, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, B256, U256, U64};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
@@ -14,42 +15,94 @@ use alloy_rpc_types_trace::geth::{
     NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::{future::BoxFuture, FutureExt};
+use jsonrpsee::{
+    core::RpcResult, types::ErrorObjectOwned, PendingSubscriptionSink, SubscriptionMessage,
+};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
-use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
+use reth_engine_primitives::{BeaconConsensusEngineHandle, BeaconSetHeadError};
+use reth_evm::{
+    execute::{BlockExecutor, Executor},
+    ConfigureEvm, EvmEnvFor, TxEnvFor,
+};
+use reth_node_api::PayloadTypes;
 use reth_primitives_traits::{
     Block as _, BlockBody, NodePrimitives, ReceiptWithBloom, RecoveredBlock, SignedTransaction,
 };
 use reth_revm::{
     database::StateProviderDatabase,
-    db::{CacheDB, State},
+    db::{
+        states::bundle_state::BundleRetention, AccountState, BundleState, Cache, CacheDB,
+        DbAccount, State,
+    },
     witness::ExecutionWitnessRecord,
 };
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    DebugApiServer, DebugSubscriptionKind, DumpAccount, MemStats, StateDump, StorageRangeEntry,
+    StorageRangeResult,
+};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
     EthApiTypes, FromEthApiError, RpcNodeCore,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_eth_types::{cache::db::StateProviderTraitObjWrapper, EthApiError, StateCacheDb};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
-    StateProofProvider, StateProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, HeaderProvider, PlainStateReader,
+    ProviderBlock, ReceiptProviderIdExt, StateProofProvider, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
-use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use reth_tracing::{tracing::level_filters::LevelFilter, tracing_subscriber::filter::Directive};
+use reth_trie_common::{updates::TrieUpdates, HashedPostState, HashedStorage};
 use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
-use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use std::{collections::BinaryHeap, path::PathBuf, sync::Arc};
+use tokio::sync::{mpsc, AcquireError, OwnedSemaphorePermit};
+
+/// The number of traced blocks of `debug_traceChain` that can be buffered before tracing waits for
+/// the subscriber to catch up.
+const TRACE_CHAIN_BUFFER: usize = 16;
+
+/// The number of accounts and storage slots `debug_traceChain` caches across blocks, beyond which
+/// the cache is dropped and the state is reloaded from the provider.
+const TRACE_CHAIN_MAX_CACHED_ENTRIES: usize = 1_000_000;
+
+/// The maximum number of accounts `debug_dumpBlock` returns, larger states are rejected instead of
+/// being loaded into memory and must be read in pages with `debug_accountRange`.
+const DUMP_BLOCK_MAX_ACCOUNTS: usize = 100_000;
+
+/// The maximum number of accounts of a `debug_accountRange` page, the same as geth's.
+const ACCOUNT_RANGE_MAX_RESULTS: usize = 256;
+
+/// The maximum number of storage slots of an account in a state dump, larger storages must be read
+/// in pages with `debug_storageRangeAt`.
+const DUMP_ACCOUNT_MAX_STORAGE: usize = 10_000;
+
+/// The maximum number of storage slots of a `debug_storageRangeAt` page.
+const STORAGE_RANGE_MAX_RESULTS: usize = 10_000;
+
+/// The number of storage slots `debug_storageRangeAt` reads from the database at once.
+const STORAGE_RANGE_READ_BATCH: usize = 10_000;
+
+/// Unwinds the node to the given block, see [`DebugApi::with_engine_handle`].
+type SetHeadFn =
+    Arc<dyn Fn(BlockNumber) -> BoxFuture<'static, Result<(), BeaconSetHeadError>> + Send + Sync>;
 
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Eth, BlockExecutor> {
     inner: Arc<DebugApiInner<Eth, BlockExecutor>>,
+    /// Backs `debug_setHead`, if the API is connected to the engine.
+    set_head: Option<SetHeadFn>,
+    /// The directory the profiling methods write their profiles to.
+    profiles_dir: Option<PathBuf>,
 }
 
 // === impl DebugApi ===
@@ -58,7 +111,36 @@ impl<Eth, Evm> DebugApi<Eth, Evm> {
     /// Create a new instance of the [`DebugApi`]
     pub fn new(eth: Eth, blocking_task_guard: BlockingTaskGuard, evm_config: Evm) -> Self {
         let inner = Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, evm_config });
-        Self { inner }
+        Self { inner, set_head: None, profiles_dir: None }
+    }
+
+    /// Connects the API to the engine, which enables `debug_setHead`.
+    pub fn with_engine_handle<Payload>(
+        mut self,
+        engine_handle: BeaconConsensusEngineHandle<Payload>,
+    ) -> Self
+    where
+        Payload: PayloadTypes,
+    {
+        self.set_head = Some(Arc::new(move |block_number| {
+            let engine_handle = engine_handle.clone();
+            async move { engine_handle.set_head(block_number).await }.boxed()
+        }));
+        self
+    }
+
+    /// Sets the directory the profiling methods write their profiles to.
+    ///
+    /// The file names given to the profiling methods are resolved in this directory, without it
+    /// profiles can't be written.
+    pub fn with_profiles_dir(mut self, profiles_dir: PathBuf) -> Self {
+        self.profiles_dir = Some(profiles_dir);
+        self
+    }
+
+    /// Returns the path in the profiles directory of the profile with the given file name.
+    fn profile_path(&self, file: &str) -> Result<PathBuf, profiling::ProfilingError> {
+        profiling::profile_path(self.profiles_dir.as_deref(), file)
     }
 
     /// Access the underlying `Eth` API.
@@ -97,44 +179,190 @@ where
         let this = self.clone();
         self.eth_api()
             .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
-                let mut results = Vec::with_capacity(block.body().transactions().len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                this.trace_block_with_db(&block, evm_env, &opts, &mut db)
+            })
+            .await
+    }
 
-                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+    /// Replays all transactions of the block on top of the given database and returns the trace
+    /// of each transaction.
+    ///
+    /// The state changes of all transactions are committed to the database, the post-execution
+    /// changes of the block are not.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    fn trace_block_with_db(
+        &self,
+        block: &RecoveredBlock<ProviderBlock<Eth::Provider>>,
+        evm_env: EvmEnvFor<Eth::Evm>,
+        opts: &GethDebugTracingOptions,
+        db: &mut StateCacheDb<'_>,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let mut results = Vec::with_capacity(block.body().transactions().len());
+
+        self.eth_api().apply_pre_execution_changes(block, db, &evm_env)?;
+
+        let mut inspector = None;
+        for (index, tx) in block.transactions_recovered().enumerate() {
+            let tx_hash = *tx.tx_hash();
+
+            let tx_env = self.eth_api().evm_config().tx_env(tx);
+
+            let (result, state_changes) = self.trace_transaction(
+                opts,
+                evm_env.clone(),
+                tx_env,
+                db,
+                Some(TransactionContext {
+                    block_hash: Some(block.hash()),
+                    tx_hash: Some(tx_hash),
+                    tx_index: Some(index),
+                }),
+                &mut inspector,
+            )?;
+
+            inspector = inspector.map(|insp| insp.fused());
+
+            results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+            // need to apply the state changes of this transaction before executing the next
+            // transaction
+            db.commit(state_changes)
+        }
 
-                let mut transactions = block.transactions_recovered().enumerate().peekable();
-                let mut inspector = None;
-                while let Some((index, tx)) = transactions.next() {
-                    let tx_hash = *tx.tx_hash();
+        Ok(results)
+    }
 
-                    let tx_env = this.eth_api().evm_config().tx_env(tx);
+    /// Resolves the block range of `debug_traceChain`.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> Result<(BlockNumber, BlockNumber), Eth::Error> {
+        let block_number = |number_or_tag: BlockNumberOrTag| -> Result<_, Eth::Error> {
+            Ok(self
+                .provider()
+                .convert_block_number(number_or_tag)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(number_or_tag.into()))?)
+        };
+        let start = block_number(start_exclusive)?;
+        let end = block_number(end_inclusive)?;
+
+        if end <= start {
+            return Err(EthApiError::InvalidParams(format!(
+                "end block (#{end}) needs to come after start block (#{start})"
+            ))
+            .into())
+        }
+        if self.provider().header_by_number(end).map_err(Eth::Error::from_eth_err)?.is_none() {
+            return Err(EthApiError::HeaderNotFound(end.into()).into())
+        }
 
-                    let (result, state_changes) = this.trace_transaction(
-                        &opts,
-                        evm_env.clone(),
-                        tx_env,
-                        &mut db,
-                        Some(TransactionContext {
-                            block_hash: Some(block.hash()),
-                            tx_hash: Some(tx_hash),
-                            tx_index: Some(index),
-                        }),
-                        &mut inspector,
-                    )?;
-
-                    inspector = inspector.map(|insp| insp.fused());
-
-                    results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
-                    if transactions.peek().is_some() {
-                        // need to apply the state changes of this transaction before executing the
-                        // next transaction
-                        db.commit(state_changes)
-                    }
-                }
+        Ok((start, end))
+    }
 
-                Ok(results)
-            })
-            .await
+    /// Applies the post-execution changes of the block, e.g. withdrawals, block rewards and system
+    /// calls, to the given database.
+    ///
+    /// Together with [`Self::trace_block_with_db`] this advances the database to the state after
+    /// the block without executing its transactions again, so the next block can be traced on top
+    /// of it.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    fn apply_post_execution_changes(
+        &self,
+        block: &RecoveredBlock<ProviderBlock<Eth::Provider>>,
+        db: &mut StateCacheDb<'_>,
+    ) -> Result<(), Eth::Error> {
+        let mut state = State::builder().with_database(&mut *db).with_bundle_update().build();
+        self.inner
+            .evm_config
+            .executor_for_block(&mut state, block.sealed_block())
+            .apply_post_execution_changes()
+            .map_err(|err| EthApiError::Internal(err.into()))?;
+        state.merge_transitions(BundleRetention::PlainState);
+        let bundle = state.take_bundle();
+
+        apply_bundle(db, bundle);
+        Ok(())
+    }
+
+    /// Replays the blocks after `start_exclusive` up to and including `end_inclusive` and sends
+    /// the traces of each block to the channel as soon as the block is traced.
+    ///
+    /// Every transaction is executed once, by the tracer. The state is loaded at the start of the
+    /// range and every block is traced on top of the state left by the previous one: the state
+    /// provider and the accounts cached on top of it are carried from block to block for the
+    /// whole range. Only once the cache exceeds [`TRACE_CHAIN_MAX_CACHED_ENTRIES`] it's dropped
+    /// and the state after the last traced block is loaded from the provider instead. Each block
+    /// is traced with its own tracing permit, so a long range doesn't starve other tracing calls.
+    /// Tracing stops early if the receiver is dropped.
+    ///
+    /// This doesn't use the `reth-exex` backfill jobs: they execute each block without an
+    /// inspector on a state opened at its parent, so tracing on top of them would execute every
+    /// block twice and reload the state for every block.
+    pub async fn debug_trace_chain(
+        &self,
+        start_exclusive: BlockNumber,
+        end_inclusive: BlockNumber,
+        opts: GethDebugTracingOptions,
+        results: mpsc::Sender<BlockTraceResult>,
+    ) -> Result<(), Eth::Error> {
+        // the state after the last traced block and the accounts cached on top of it, moved
+        // between the blocking tasks of the blocks
+        let mut state: Option<(StateProviderBox, Cache)> = None;
+
+        for number in start_exclusive + 1..=end_inclusive {
+            let _permit = self.acquire_trace_permit().await;
+
+            let this = self.clone();
+            let opts = opts.clone();
+            let (result, next_state) = self
+                .eth_api()
+                .spawn_tracing(move |eth_api| {
+                    let (provider, cache) = match state {
+                        Some(state) => state,
+                        None => (eth_api.state_at_block_id((number - 1).into())?, Cache::default()),
+                    };
+                    let block = this
+                        .provider()
+                        .recovered_block(number.into(), TransactionVariant::WithHash)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                    let evm_env = this.eth_api().evm_config().evm_env(block.header());
+
+                    let mut db = CacheDB {
+                        cache,
+                        db: StateProviderDatabase::new(StateProviderTraitObjWrapper(&*provider)),
+                    };
+                    let traces = this.trace_block_with_db(&block, evm_env, &opts, &mut db)?;
+                    this.apply_post_execution_changes(&block, &mut db)?;
+
+                    let cache = db.cache;
+                    let cached_entries = cache.accounts.len() +
+                        cache
+                            .accounts
+                            .values()
+                            .map(|account| account.storage.len())
+                            .sum::<usize>();
+                    let next_state = (cached_entries <= TRACE_CHAIN_MAX_CACHED_ENTRIES)
+                        .then_some((provider, cache));
+
+                    let result =
+                        BlockTraceResult { block: U256::from(number), hash: block.hash(), traces };
+                    Ok((result, next_state))
+                })
+                .await?;
+            state = next_state;
+
+            if results.send(result).await.is_err() {
+                // the receiver is gone, nothing left to trace for
+                break
+            }
+        }
+
+        Ok(())
     }
 
     /// Replays the given block and returns the trace of each transaction.
@@ -444,7 +672,7 @@ where
 
                     Ok(GethTrace::JS(res))
                 }
-            }
+            };
         }
 
         // default structlog tracer
@@ -761,7 +989,7 @@ where
                             .geth_builder()
                             .geth_call_traces(call_config, res.result.gas_used());
 
-                        return Ok((frame.into(), res.state))
+                        return Ok((frame.into(), res.state));
                     }
                     GethDebugBuiltInTracerType::PreStateTracer => {
                         let prestate_config = tracer_config
@@ -821,7 +1049,7 @@ where
                             .into_parity_builder()
                             .into_localized_transaction_traces(tx_info);
 
-                        return Ok((frame.into(), res.state));
+                        return Ok((frame.into(), res.state))
                     }
                 },
                 #[cfg(not(feature = "js-tracer"))]
@@ -847,7 +1075,7 @@ where
                         .map_err(Eth::Error::from_eth_err)?;
                     Ok((GethTrace::JS(result), state))
                 }
-            }
+            };
         }
 
         // default structlog tracer
@@ -884,10 +1112,292 @@ where
     }
 }
 
+impl<Eth, Evm> DebugApi<Eth, Evm>
+where
+    Eth: EthApiTypes + TraceExt + 'static,
+    Eth::Provider: PlainStateReader,
+    Evm: ConfigureEvm<Primitives: NodePrimitives<Block = ProviderBlock<Eth::Provider>>> + 'static,
+{
+    /// Returns the accounts that changed in the blocks after `start` up to and including `end`.
+    ///
+    /// Without `end`, returns the accounts that changed in the `start` block.
+    pub async fn debug_get_modified_accounts(
+        &self,
+        start: BlockNumber,
+        end: Option<BlockNumber>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let (start, end) = match end {
+            Some(end) if start >= end => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "start block ({start}) must be less than end block ({end})"
+                ))
+                .into())
+            }
+            Some(end) => (start, end),
+            None if start == 0 => {
+                return Err(
+                    EthApiError::InvalidParams("genesis block has no parent".to_string()).into()
+                )
+            }
+            None => (start - 1, start),
+        };
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let best_block =
+                    this.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+                if end > best_block {
+                    return Err(EthApiError::HeaderNotFound(end.into()).into())
+                }
+                let addresses = this
+                    .provider()
+                    .changed_addresses_with_range(start + 1..=end)
+                    .map_err(Eth::Error::from_eth_err)?;
+                Ok(addresses.into_iter().collect())
+            })
+            .await
+    }
+
+    /// Returns a geth-style dump of the entire state after the given block.
+    ///
+    /// Fails if the state holds more than [`DUMP_BLOCK_MAX_ACCOUNTS`] accounts or an account holds
+    /// more than [`DUMP_ACCOUNT_MAX_STORAGE`] storage slots.
+    pub async fn debug_dump_block(&self, block_id: BlockId) -> Result<StateDump, Eth::Error> {
+        let dump =
+            self.dump_state(block_id, Address::ZERO, DUMP_BLOCK_MAX_ACCOUNTS, false, false).await?;
+        if dump.next.is_some() {
+            return Err(EthApiError::InvalidParams(format!(
+                "state has more than {DUMP_BLOCK_MAX_ACCOUNTS} accounts, use debug_accountRange to \
+                 read it in pages"
+            ))
+            .into())
+        }
+        Ok(dump)
+    }
+
+    /// Returns a page of the geth-style dump of the state after the given block.
+    ///
+    /// The accounts are ordered by address, the page starts at the `start` address and holds at
+    /// most `max_results` accounts, capped at [`ACCOUNT_RANGE_MAX_RESULTS`]. Fails if an account
+    /// holds more than [`DUMP_ACCOUNT_MAX_STORAGE`] storage slots, unless `nostorage` is set.
+    pub async fn debug_account_range(
+        &self,
+        block_id: BlockId,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+    ) -> Result<StateDump, Eth::Error> {
+        if start.len() > Address::len_bytes() {
+            return Err(EthApiError::InvalidParams(format!(
+                "start key {start} is longer than an address"
+            ))
+            .into())
+        }
+        // the start key is a prefix of the address
+        let mut start_address = Address::ZERO;
+        start_address[..start.len()].copy_from_slice(&start);
+        let max_results = match usize::try_from(max_results) {
+            Ok(max_results @ 1..ACCOUNT_RANGE_MAX_RESULTS) => max_results,
+            _ => ACCOUNT_RANGE_MAX_RESULTS,
+        };
+
+        self.dump_state(block_id, start_address, max_results, nocode, nostorage).await
+    }
+
+    /// Dumps at most `limit` accounts of the state after the given block, starting at the `start`
+    /// address.
+    async fn dump_state(
+        &self,
+        block_id: BlockId,
+        start: Address,
+        limit: usize,
+        nocode: bool,
+        nostorage: bool,
+    ) -> Result<StateDump, Eth::Error> {
+        let header = self
+            .provider()
+            .sealed_header_by_id(block_id)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let root = header.state_root();
+        let block_number = header.number();
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+                let state = provider
+                    .history_by_block_number(block_number)
+                    .map_err(Eth::Error::from_eth_err)?;
+                let storage_limit = if nostorage { 0 } else { DUMP_ACCOUNT_MAX_STORAGE + 1 };
+                let mut accounts = provider
+                    .plain_state_at(block_number, start, limit + 1, storage_limit)
+                    .map_err(Eth::Error::from_eth_err)?;
+                let next = (accounts.len() > limit).then(|| accounts.pop_last()).flatten();
+
+                let accounts = accounts
+                    .into_iter()
+                    .map(|(address, account)| {
+                        if account.storage.len() > DUMP_ACCOUNT_MAX_STORAGE {
+                            return Err(EthApiError::InvalidParams(format!(
+                                "account {address} has more than {DUMP_ACCOUNT_MAX_STORAGE} \
+                                 storage slots, use debug_storageRangeAt to read them in pages"
+                            ))
+                            .into())
+                        }
+                        let code = match account.info.bytecode_hash {
+                            Some(code_hash) if !nocode => state
+                                .bytecode_by_hash(&code_hash)
+                                .map_err(Eth::Error::from_eth_err)?
+                                .map(|code| code.original_bytes())
+                                .unwrap_or_default(),
+                            _ => Bytes::new(),
+                        };
+                        let storage_root = if nostorage {
+                            state
+                                .storage_root(address, HashedStorage::default())
+                                .map_err(Eth::Error::from_eth_err)?
+                        } else {
+                            storage_root_unhashed(account.storage.clone())
+                        };
+                        let dump = DumpAccount {
+                            balance: account.info.balance.to_string(),
+                            nonce: account.info.nonce,
+                            root: storage_root,
+                            code_hash: account.info.get_bytecode_hash(),
+                            code,
+                            storage: account
+                                .storage
+                                .into_iter()
+                                .map(|(key, value)| (key, value.into()))
+                                .collect(),
+                            address,
+                            key: keccak256(address),
+                        };
+                        Ok((address, dump))
+                    })
+                    .collect::<Result<_, Eth::Error>>()?;
+                Ok(StateDump { root, accounts, next: next.map(|(address, _)| address) })
+            })
+            .await
+    }
+
+    /// Returns a page of the storage of the account right before the transaction at the given
+    /// index was executed.
+    ///
+    /// The slots are ordered by their hash, the page starts at `key_start` and holds at most
+    /// `max_result` slots, capped at [`STORAGE_RANGE_MAX_RESULTS`].
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let block = self
+            .eth_api()
+            .recovered_block(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let tx_count = block.body().transactions().len();
+        if tx_idx >= tx_count && !(tx_idx == 0 && tx_count == 0) {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {block_hash}"
+            ))
+            .into())
+        }
+        let (evm_env, _) = self.eth_api().evm_env_at(block_hash.into()).await?;
+        let parent_number = block.number().saturating_sub(1);
+        let max_result =
+            usize::try_from(max_result).unwrap_or(usize::MAX).min(STORAGE_RANGE_MAX_RESULTS);
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                // replay all transactions prior to the targeted transaction
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+                for tx in block.transactions_recovered().take(tx_idx) {
+                    let tx_env = this.eth_api().evm_config().tx_env(tx);
+                    let res = this.eth_api().transact(&mut db, evm_env.clone(), tx_env)?;
+                    db.commit(res.state);
+                }
+                let changed = db.cache.accounts.get(&address);
+
+                // the slots are not stored in hash order, so all of them are walked while only the
+                // `max_result + 1` smallest hashes past `key_start` are kept
+                let mut slots = BinaryHeap::with_capacity(max_result + 1);
+                let mut keep = |key: B256, value: U256| {
+                    let hashed_key = keccak256(key);
+                    if value.is_zero() || hashed_key < key_start {
+                        return
+                    }
+                    slots.push((hashed_key, key, value));
+                    if slots.len() > max_result + 1 {
+                        slots.pop();
+                    }
+                };
+
+                // the persisted slots are replaced by the ones of the replayed transactions
+                let cleared = changed.is_some_and(|account| {
+                    matches!(
+                        account.account_state,
+                        AccountState::NotExisting | AccountState::StorageCleared
+                    )
+                });
+                let mut start = Some(B256::ZERO);
+                while let Some(page_start) = start.take().filter(|_| !cleared) {
+                    let storage = this
+                        .provider()
+                        .plain_storage_at(
+                            address,
+                            parent_number,
+                            page_start,
+                            STORAGE_RANGE_READ_BATCH,
+                        )
+                        .map_err(Eth::Error::from_eth_err)?;
+                    if storage.len() == STORAGE_RANGE_READ_BATCH {
+                        start = storage
+                            .keys()
+                            .next_back()
+                            .and_then(|key| U256::from_be_bytes(key.0).checked_add(U256::from(1)))
+                            .map(B256::from);
+                    }
+                    for (key, value) in storage {
+                        if !changed.is_some_and(|account| {
+                            account.storage.contains_key(&U256::from_be_bytes(key.0))
+                        }) {
+                            keep(key, value);
+                        }
+                    }
+                }
+                if let Some(account) = changed {
+                    for (key, value) in &account.storage {
+                        keep(B256::from(*key), *value);
+                    }
+                }
+
+                let mut slots = slots.into_sorted_vec();
+                let next_key = slots.get(max_result).map(|(hashed_key, _, _)| *hashed_key);
+                slots.truncate(max_result);
+                let storage = slots
+                    .into_iter()
+                    .map(|(hashed_key, key, value)| {
+                        (hashed_key, StorageRangeEntry { key: Some(key), value: value.into() })
+                    })
+                    .collect();
+                Ok(StorageRangeResult { storage, next_key })
+            })
+            .await
+    }
+}
+
 #[async_trait]
 impl<Eth, Evm> DebugApiServer for DebugApi<Eth, Evm>
 where
     Eth: EthApiTypes + EthTransactions + TraceExt + 'static,
+    Eth::Provider: PlainStateReader,
     Evm: ConfigureEvm<Primitives: NodePrimitives<Block = ProviderBlock<Eth::Provider>>> + 'static,
 {
     /// Handler for `debug_getRawHeader`
@@ -963,13 +1473,42 @@ where
         Ok(vec![])
     }
 
-    /// Handler for `debug_traceChain`
+    /// Handler for `debug_subscribe("traceChain")`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        _kind: DebugSubscriptionKind,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let (start, end) = match self.trace_chain_range(start_exclusive, end_inclusive) {
+            Ok(range) => range,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+        let sink = pending.accept().await?;
+
+        let (tx, mut rx) = mpsc::channel(TRACE_CHAIN_BUFFER);
+        let trace = self.debug_trace_chain(start, end, opts.unwrap_or_default(), tx);
+        let forward = async move {
+            while let Some(result) = rx.recv().await {
+                let msg =
+                    SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &result)?;
+                if sink.send(msg).await.is_err() {
+                    // connection dropped
+                    break
+                }
+            }
+            Ok::<_, jsonrpsee::core::SubscriptionError>(())
+        };
+
+        let (traced, forwarded) = futures::join!(trace, forward);
+        forwarded?;
+        traced.map_err(Into::<ErrorObjectOwned>::into)?;
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -1067,14 +1606,17 @@ where
 
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
         _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<StateDump> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_account_range(self, block_number.into(), start, max_results, nocode, nostorage)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -1101,7 +1643,12 @@ where
         Self::debug_code_by_hash(self, hash, block_id).await.map_err(Into::into)
     }
 
-    async fn debug_cpu_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
+    async fn debug_cpu_profile(&self, file: String, seconds: u64) -> RpcResult<()> {
+        let duration = profiling::cpu_profile_duration(seconds)?;
+        // the profile is discarded if the call is cancelled while it's recorded
+        let profile = profiling::CpuProfileGuard::start(self.profile_path(&file)?)?;
+        tokio::time::sleep(duration).await;
+        profiling::spawn_blocking(move || profile.stop()).await?;
         Ok(())
     }
 
@@ -1117,8 +1664,9 @@ where
         Ok(())
     }
 
-    async fn debug_dump_block(&self, _number: BlockId) -> RpcResult<()> {
-        Ok(())
+    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<StateDump> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_dump_block(self, number).await.map_err(Into::into)
     }
 
     async fn debug_free_os_memory(&self) -> RpcResult<()> {
@@ -1143,18 +1691,27 @@ where
 
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        let block_number = |hash: B256| -> RpcResult<BlockNumber> {
+            Ok(self
+                .provider()
+                .block_number(hash)
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::HeaderNotFound(hash.into()))?)
+        };
+        let start = block_number(start_hash)?;
+        let end = end_hash.map(block_number).transpose()?;
+        Self::debug_get_modified_accounts(self, start, end).await.map_err(Into::into)
     }
 
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts(self, start_number, end_number).await.map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -1169,8 +1726,8 @@ where
         Ok(())
     }
 
-    async fn debug_mem_stats(&self) -> RpcResult<()> {
-        Ok(())
+    async fn debug_mem_stats(&self) -> RpcResult<MemStats> {
+        Ok(profiling::mem_stats()?)
     }
 
     async fn debug_mutex_profile(&self, _file: String, _nsec: u64) -> RpcResult<()> {
@@ -1197,8 +1754,14 @@ where
         Ok(())
     }
 
-    async fn debug_set_head(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
+    async fn debug_set_head(&self, number: U64) -> RpcResult<()> {
+        let Some(set_head) = &self.set_head else {
+            return Err(internal_rpc_err("debug_setHead is not connected to the engine"))
+        };
+        set_head(number.to()).await.map_err(|err| match err {
+            BeaconSetHeadError::InvalidTarget { .. } => invalid_params_rpc_err(err.to_string()),
+            err => internal_rpc_err(err.to_string()),
+        })
     }
 
     async fn debug_set_mutex_profile_fraction(&self, _rate: i32) -> RpcResult<()> {
@@ -1209,8 +1772,14 @@ where
         Ok(())
     }
 
-    async fn debug_stacks(&self) -> RpcResult<()> {
-        Ok(())
+    async fn debug_stacks(&self) -> RpcResult<String> {
+        let mut stacks = profiling::spawn_blocking(|| Ok(profiling::thread_stacks())).await?;
+        // the thread stacks are returned regardless, task dumps are only available in some builds
+        match profiling::task_stacks().await {
+            Ok(tasks) => stacks.push_str(&tasks),
+            Err(err) => stacks.push_str(&format!("task stacks are not available: {err}\n")),
+        }
+        Ok(stacks)
     }
 
     async fn debug_standard_trace_bad_block_to_file(
@@ -1229,8 +1798,8 @@ where
         Ok(())
     }
 
-    async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+    async fn debug_start_cpu_profile(&self, file: String) -> RpcResult<()> {
+        Ok(profiling::start_cpu_profile(self.profile_path(&file)?)?)
     }
 
     async fn debug_start_go_trace(&self, _file: String) -> RpcResult<()> {
@@ -1246,6 +1815,7 @@ where
     }
 
     async fn debug_stop_cpu_profile(&self) -> RpcResult<()> {
+        profiling::spawn_blocking(profiling::stop_cpu_profile).await?;
         Ok(())
     }
 
@@ -1255,13 +1825,23 @@ where
 
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_bad_block(
@@ -1272,20 +1852,49 @@ where
         Ok(())
     }
 
-    async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_verbosity`
+    ///
+    /// Levels follow geth: 0 disables logging, 1 to 5 enable error, warn, info, debug and trace.
+    async fn debug_verbosity(&self, level: usize) -> RpcResult<()> {
+        let level = match level {
+            0 => LevelFilter::OFF,
+            1 => LevelFilter::ERROR,
+            2 => LevelFilter::WARN,
+            3 => LevelFilter::INFO,
+            4 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        };
+        reth_tracing::set_log_verbosity(level).map_err(|err| internal_rpc_err(err.to_string()))
     }
 
-    async fn debug_vmodule(&self, _pattern: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_vmodule`
+    ///
+    /// The pattern is a comma-separated list of tracing directives, e.g.
+    /// `engine::tree=trace,net=debug`. An empty pattern resets the log filters to the ones the
+    /// node was started with.
+    async fn debug_vmodule(&self, pattern: String) -> RpcResult<()> {
+        if pattern.trim().is_empty() {
+            return reth_tracing::reset_log_filters()
+                .map_err(|err| internal_rpc_err(err.to_string()))
+        }
+        let directives = pattern
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .map(|directive| directive.parse::<Directive>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        reth_tracing::set_log_directives(directives)
+            .map_err(|err| internal_rpc_err(err.to_string()))
     }
 
     async fn debug_write_block_profile(&self, _file: String) -> RpcResult<()> {
         Ok(())
     }
 
-    async fn debug_write_mem_profile(&self, _file: String) -> RpcResult<()> {
-        Ok(())
+    async fn debug_write_mem_profile(&self, file: String) -> RpcResult<()> {
+        let path = self.profile_path(&file)?;
+        Ok(profiling::spawn_blocking(move || profiling::write_heap_profile(path)).await?)
     }
 
     async fn debug_write_mutex_profile(&self, _file: String) -> RpcResult<()> {
@@ -1301,7 +1910,11 @@ impl<Eth, Evm> std::fmt::Debug for DebugApi<Eth, Evm> {
 
 impl<Eth, Evm> Clone for DebugApi<Eth, Evm> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            set_head: self.set_head.clone(),
+            profiles_dir: self.profiles_dir.clone(),
+        }
     }
 }
 
@@ -1313,3 +1926,31 @@ struct DebugApiInner<Eth, Evm> {
     /// block executor for debug & trace apis
     evm_config: Evm,
 }
+
+/// Applies the changes of an executed block to the database, so it reflects the state after the
+/// block.
+///
+/// Unlike committing the [`EvmState`] of single transactions this also applies the pre- and
+/// post-execution changes of the block, and removes accounts that were destroyed in the block.
+fn apply_bundle<ExtDB>(db: &mut CacheDB<ExtDB>, bundle: BundleState) {
+    db.cache.contracts.extend(bundle.contracts);
+
+    for (address, account) in bundle.state {
+        let Some(info) = account.info else {
+            db.cache.accounts.insert(address, DbAccount::new_not_existing());
+            continue
+        };
+
+        let storage = account.storage.into_iter().map(|(slot, value)| (slot, value.present_value));
+        let db_account = db.cache.accounts.entry(address).or_default();
+        if account.status.was_destroyed() || db_account.account_state == AccountState::NotExisting {
+            // the storage of the account was wiped or the account did not exist before, so all
+            // of its storage is known
+            db_account.account_state = AccountState::StorageCleared;
+            db_account.storage = storage.collect();
+        } else {
+            db_account.storage.extend(storage);
+        }
+        db.insert_account_info(address, info);
+    }
+}
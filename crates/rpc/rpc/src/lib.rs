@@ -33,12 +33,14 @@ use pin_project as _;
 use tower as _;
 
 mod admin;
+mod anvil;
 mod debug;
 mod engine;
 pub mod eth;
 mod miner;
 mod net;
 mod otterscan;
+mod profiling;
 mod reth;
 mod rpc;
 mod trace;
@@ -47,6 +49,7 @@ mod validation;
 mod web3;
 
 pub use admin::AdminApi;
+pub use anvil::AnvilApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
@@ -1,25 +1,57 @@
+use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
 use alloy_primitives::{Bytes, U128};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_payload_builder_primitives::MinerConfig;
 use reth_rpc_api::MinerApiServer;
+use reth_rpc_server_types::result::invalid_params_rpc_err;
+use tracing::debug;
 
 /// `miner` API implementation.
 ///
 /// This type provides the functionality for handling `miner` related requests.
+///
+/// Without a [`MinerConfig`] every setting is rejected by returning `false`.
 #[derive(Clone, Debug, Default)]
-pub struct MinerApi {}
+pub struct MinerApi {
+    /// Settings of the payload builder, shared with the payload builder.
+    config: Option<MinerConfig>,
+}
+
+impl MinerApi {
+    /// Creates a new instance of `MinerApi` that updates the given [`MinerConfig`].
+    pub const fn new(config: MinerConfig) -> Self {
+        Self { config: Some(config) }
+    }
+}
 
 #[async_trait]
 impl MinerApiServer for MinerApi {
-    fn set_extra(&self, _record: Bytes) -> RpcResult<bool> {
-        Ok(false)
+    fn set_extra(&self, record: Bytes) -> RpcResult<bool> {
+        if record.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(invalid_params_rpc_err(format!(
+                "extra data exceeds {MAXIMUM_EXTRA_DATA_SIZE}-byte limit"
+            )))
+        }
+        let Some(config) = &self.config else { return Ok(false) };
+        debug!(target: "rpc", %record, "Setting extra data");
+        config.set_extra_data(record);
+        Ok(true)
     }
 
-    fn set_gas_price(&self, _gas_price: U128) -> RpcResult<bool> {
-        Ok(false)
+    fn set_gas_price(&self, gas_price: U128) -> RpcResult<bool> {
+        let Some(config) = &self.config else { return Ok(false) };
+        debug!(target: "rpc", %gas_price, "Setting minimum tip");
+        config.set_min_tip(gas_price.to());
+        Ok(true)
     }
 
-    fn set_gas_limit(&self, _gas_price: U128) -> RpcResult<bool> {
-        Ok(false)
+    fn set_gas_limit(&self, gas_limit: U128) -> RpcResult<bool> {
+        let gas_limit = u64::try_from(gas_limit)
+            .map_err(|_| invalid_params_rpc_err("gas limit exceeds u64::MAX"))?;
+        let Some(config) = &self.config else { return Ok(false) };
+        debug!(target: "rpc", %gas_limit, "Setting gas limit");
+        config.set_gas_limit(gas_limit);
+        Ok(true)
     }
 }
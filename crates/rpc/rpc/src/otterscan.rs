@@ -1,7 +1,7 @@
 use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, TxNumber, B256, U256};
 use alloy_rpc_types_eth::{BlockTransactions, TransactionReceipt};
 use alloy_rpc_types_trace::{
     otterscan::{
@@ -15,15 +15,20 @@ use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    FromEthApiError, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
+use reth_storage_api::{
+    address_transactions_indexed_blocks, AddressTransactionsReader, BlockBodyIndicesProvider,
+    BlockNumReader, PruneCheckpointReader, StageCheckpointReader, TransactionsProvider,
+};
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
     transfer::{TransferInspector, TransferKind},
 };
+use std::ops::Range;
 
 const API_LEVEL: u64 = 8;
 
@@ -62,6 +67,187 @@ where
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+        > + EthTransactions
+        + TraceExt
+        + 'static,
+    Eth::Provider: AddressTransactionsReader + StageCheckpointReader + PruneCheckpointReader,
+{
+    /// Returns the transactions the address took part in within the transaction number range, at
+    /// most one page of them, grouped by block.
+    ///
+    /// The page size is a soft limit: the transactions of the last block on the page are always
+    /// returned in full. The returned flag indicates whether there are more transactions in the
+    /// range.
+    ///
+    /// Returns an error if the page would be incomplete because the part of the range the search
+    /// starts from or, if the search reaches it, the part it ends in is not covered by the index.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        tx_range: Range<TxNumber>,
+        page_size: usize,
+        reverse: bool,
+    ) -> RpcResult<(Vec<(BlockNumber, Vec<usize>)>, bool)> {
+        let search = move |provider: &Eth::Provider, range: Range<TxNumber>, limit: usize| {
+            if range.is_empty() {
+                return Ok(Vec::new())
+            }
+            let range = range.start..=range.end - 1;
+            if reverse {
+                provider.address_transactions_rev(address, range, limit)
+            } else {
+                provider.address_transactions(address, range, limit)
+            }
+            .map_err(Eth::Error::from_eth_err)
+        };
+        let block_txs = |provider: &Eth::Provider, block: BlockNumber| {
+            let body = provider
+                .block_body_indices(block)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(block.into()))?;
+            Ok::<_, Eth::Error>(body.first_tx_num()..body.next_tx_num())
+        };
+        let block_body = move |provider: &Eth::Provider, tx_number: TxNumber| {
+            let block = provider
+                .transaction_block(tx_number)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or(EthApiError::TransactionNotFound)?;
+            Ok::<_, Eth::Error>((block, block_txs(provider, block)?))
+        };
+
+        self.eth
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+
+                // Only search the transactions the index covers.
+                let indexed_blocks = address_transactions_indexed_blocks(provider)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .ok_or(EthApiError::Unsupported(
+                        "the address transactions index is not enabled",
+                    ))?;
+                let tip = provider.best_block_number().map_err(Eth::Error::from_eth_err)?;
+                let indexed_txs = block_txs(provider, *indexed_blocks.start())?.start..
+                    block_txs(provider, *indexed_blocks.end())?.end;
+                let tx_range = tx_range.start..tx_range.end.min(block_txs(provider, tip)?.end);
+                let pruned = tx_range.start < indexed_txs.start;
+                let not_indexed_yet = tx_range.end > indexed_txs.end;
+                let tx_range =
+                    tx_range.start.max(indexed_txs.start)..tx_range.end.min(indexed_txs.end);
+                let (missing_start, missing_end) =
+                    if reverse { (not_indexed_yet, pruned) } else { (pruned, not_indexed_yet) };
+                let missing_error = |pruned| {
+                    if pruned {
+                        EthApiError::PrunedHistoryUnavailable
+                    } else {
+                        EthApiError::Unsupported(
+                            "the address transactions index does not cover the requested blocks yet",
+                        )
+                    }
+                };
+                if missing_start {
+                    return Err(missing_error(!reverse).into())
+                }
+
+                let mut tx_numbers = search(provider, tx_range.clone(), page_size)?;
+
+                // Complete the last block of the page and check whether there's anything left.
+                let has_more = if let Some(&last) =
+                    tx_numbers.last().filter(|_| tx_numbers.len() == page_size)
+                {
+                    let (_, block_txs) = block_body(provider, last)?;
+                    let (rest_of_block, rest_of_range) = if reverse {
+                        (block_txs.start..last, tx_range.start..block_txs.start)
+                    } else {
+                        (last + 1..block_txs.end, block_txs.end..tx_range.end)
+                    };
+                    tx_numbers.extend(search(provider, rest_of_block, usize::MAX)?);
+                    !search(provider, rest_of_range, 1)?.is_empty()
+                } else {
+                    false
+                };
+                if !has_more && missing_end {
+                    return Err(missing_error(reverse).into())
+                }
+
+                // Group the transactions by block, keeping their order.
+                let mut blocks = Vec::<(BlockNumber, Vec<usize>)>::new();
+                let mut current_block_txs = 0..0;
+                for tx_number in tx_numbers {
+                    if !current_block_txs.contains(&tx_number) {
+                        let (block, block_txs) = block_body(provider, tx_number)?;
+                        blocks.push((block, Vec::new()));
+                        current_block_txs = block_txs;
+                    }
+                    let index = (tx_number - current_block_txs.start) as usize;
+                    blocks.last_mut().expect("block was pushed").1.push(index);
+                }
+
+                Ok((blocks, has_more))
+            })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Returns the range of transaction numbers of the block.
+    async fn block_tx_range(&self, block_number: BlockNumber) -> RpcResult<Range<TxNumber>> {
+        self.eth
+            .spawn_blocking_io(move |this| {
+                let body = this
+                    .provider()
+                    .block_body_indices(block_number)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+                Ok(body.first_tx_num()..body.next_tx_num())
+            })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Collects the transactions at the given indices of the blocks, together with their
+    /// receipts.
+    async fn transactions_with_receipts(
+        &self,
+        blocks: Vec<(BlockNumber, Vec<usize>)>,
+    ) -> RpcResult<(Vec<RpcTransaction<Eth::NetworkTypes>>, Vec<OtsTransactionReceipt>)> {
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        for (block_number, indices) in blocks {
+            let block_id = block_number.into();
+            let block = self.eth.block_by_number(block_number.into(), true);
+            let block_receipts = self.eth.block_receipts(block_id);
+            let (block, block_receipts) = futures::try_join!(block, block_receipts)?;
+
+            let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+            let block_receipts = block_receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+            let BlockTransactions::Full(transactions) = block.transactions else {
+                return Err(internal_rpc_err("block is not full"));
+            };
+
+            let timestamp = Some(block.header.timestamp());
+            for index in indices {
+                let (Some(tx), Some(receipt)) =
+                    (transactions.get(index), block_receipts.get(index))
+                else {
+                    return Err(internal_rpc_err(
+                        "the number of transactions does not match the number of receipts",
+                    ))
+                };
+                receipts.push(ots_receipt(receipt, tx.ty(), timestamp));
+                txs.push(tx.clone());
+            }
+        }
+
+        Ok((txs, receipts))
+    }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
     for OtterscanApi<Eth>
@@ -74,6 +260,7 @@ where
         > + EthTransactions
         + TraceExt
         + 'static,
+    Eth::Provider: AddressTransactionsReader + StageCheckpointReader + PruneCheckpointReader,
 {
     /// Handler for `ots_getHeaderByNumber` and `erigon_getHeaderByNumber`
     async fn get_header_by_number(
@@ -245,32 +432,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_receipt(&receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -282,21 +444,63 @@ where
     /// Handler for `ots_searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        // `0` means searching from the tip of the chain.
+        let block_number = block_number.into_inner().as_number().unwrap_or_default();
+        let is_first_page = block_number == 0;
+
+        let tx_range = if is_first_page {
+            0..TxNumber::MAX
+        } else {
+            0..self.block_tx_range(block_number).await?.start
+        };
+
+        let (blocks, has_more) =
+            self.search_transactions(address, tx_range, page_size, true).await?;
+        let (txs, receipts) = self.transactions_with_receipts(blocks).await?;
+
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: is_first_page,
+            last_page: !has_more,
+        })
     }
 
     /// Handler for `ots_searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        // `0` means searching from genesis.
+        let block_number = block_number.into_inner().as_number().unwrap_or_default();
+        let is_last_page = block_number == 0;
+
+        let tx_range = if is_last_page {
+            0..TxNumber::MAX
+        } else {
+            self.block_tx_range(block_number).await?.end..TxNumber::MAX
+        };
+
+        let (blocks, has_more) =
+            self.search_transactions(address, tx_range, page_size, false).await?;
+        let (mut txs, mut receipts) = self.transactions_with_receipts(blocks).await?;
+
+        // Results are always returned in descending order.
+        txs.reverse();
+        receipts.reverse();
+
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: !has_more,
+            last_page: is_last_page,
+        })
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -377,3 +581,35 @@ where
         Ok(found)
     }
 }
+
+/// Converts a receipt into the trimmed down receipt otterscan expects.
+fn ots_receipt<R: ReceiptResponse>(
+    receipt: &R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
@@ -0,0 +1,654 @@
+//! In-process profilers backing the profiling methods of the `debug` namespace.
+
+use jsonrpsee::types::ErrorObjectOwned;
+use reth_rpc_api::MemStats;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use std::{
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+/// The longest CPU profile `debug_cpuProfile` records.
+pub(crate) const MAX_CPU_PROFILE_DURATION: Duration = Duration::from_secs(10 * 60);
+
+/// Errors of the in-process profilers.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ProfilingError {
+    /// A CPU profile is already being recorded.
+    #[error("CPU profiling already in progress")]
+    CpuProfileRunning,
+    /// No CPU profile is being recorded.
+    #[error("CPU profiling not in progress")]
+    CpuProfileNotRunning,
+    /// The profiler is not available in this build or environment.
+    #[error("{0}")]
+    Unavailable(&'static str),
+    /// The profile file name is not a plain file name.
+    #[error("invalid profile file name {0:?}, expected a file name without directories")]
+    InvalidFileName(String),
+    /// The requested profile duration exceeds [`MAX_CPU_PROFILE_DURATION`].
+    #[error("profile duration exceeds the maximum of {}s", MAX_CPU_PROFILE_DURATION.as_secs())]
+    DurationTooLong,
+    /// Failed to write the profile.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The blocking task of the profiler failed.
+    #[error(transparent)]
+    Task(#[from] tokio::task::JoinError),
+    /// Error of the CPU profiler.
+    #[cfg(unix)]
+    #[error(transparent)]
+    Pprof(#[from] pprof::Error),
+    /// Error reading or controlling jemalloc.
+    #[cfg(all(feature = "jemalloc", unix))]
+    #[error(transparent)]
+    Jemalloc(#[from] tikv_jemalloc_ctl::Error),
+}
+
+impl From<ProfilingError> for ErrorObjectOwned {
+    fn from(err: ProfilingError) -> Self {
+        match err {
+            ProfilingError::InvalidFileName(_) | ProfilingError::DurationTooLong => {
+                invalid_params_rpc_err(err.to_string())
+            }
+            err => internal_rpc_err(err.to_string()),
+        }
+    }
+}
+
+/// Returns the path in the profiles directory that a profile with the given file name is written
+/// to.
+///
+/// The name must be a plain file name, so profiles can't be written outside of the directory.
+pub(crate) fn profile_path(dir: Option<&Path>, file: &str) -> Result<PathBuf, ProfilingError> {
+    let dir = dir.ok_or(ProfilingError::Unavailable("no directory for profiles is configured"))?;
+    let mut components = Path::new(file).components();
+    if !matches!((components.next(), components.next()), (Some(Component::Normal(_)), None)) {
+        return Err(ProfilingError::InvalidFileName(file.to_string()))
+    }
+    std::fs::create_dir_all(dir)?;
+    Ok(dir.join(file))
+}
+
+/// Returns the duration of a CPU profile, which is capped at [`MAX_CPU_PROFILE_DURATION`].
+pub(crate) fn cpu_profile_duration(seconds: u64) -> Result<Duration, ProfilingError> {
+    let duration = Duration::from_secs(seconds);
+    if duration > MAX_CPU_PROFILE_DURATION {
+        return Err(ProfilingError::DurationTooLong)
+    }
+    Ok(duration)
+}
+
+/// Runs a blocking profiler operation on the blocking thread pool.
+pub(crate) async fn spawn_blocking<R, F>(f: F) -> Result<R, ProfilingError>
+where
+    R: Send + 'static,
+    F: FnOnce() -> Result<R, ProfilingError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await?
+}
+
+/// Starts recording a CPU profile that is written to the given path once stopped.
+///
+/// The profile is written as a flamegraph if the path has an `svg` extension and in the pprof
+/// format otherwise. The file must not exist yet.
+pub(crate) fn start_cpu_profile(path: PathBuf) -> Result<(), ProfilingError> {
+    // the profile outlives the call, it's stopped by `stop_cpu_profile`
+    CpuProfileGuard::start(path).map(|mut profile| profile.id = None)
+}
+
+/// Stops recording the CPU profile and writes it to the path it was started with.
+///
+/// Caution: this is blocking, the samples are symbolized before they are written.
+pub(crate) fn stop_cpu_profile() -> Result<PathBuf, ProfilingError> {
+    CpuProfileGuard { id: None }.stop()
+}
+
+/// A CPU profile that is discarded if it's dropped before it's stopped, e.g. because the RPC call
+/// that recorded it was cancelled.
+#[derive(Debug)]
+pub(crate) struct CpuProfileGuard {
+    /// The id of the profile, if it's still owned by the guard.
+    id: Option<u64>,
+}
+
+impl CpuProfileGuard {
+    /// Starts recording a CPU profile, see [`start_cpu_profile`].
+    pub(crate) fn start(path: PathBuf) -> Result<Self, ProfilingError> {
+        #[cfg(unix)]
+        return cpu::start(path).map(|id| Self { id: Some(id) });
+
+        #[cfg(not(unix))]
+        {
+            let _ = path;
+            Err(ProfilingError::Unavailable("CPU profiling is only supported on unix"))
+        }
+    }
+
+    /// Stops recording the profile and writes it, see [`stop_cpu_profile`].
+    ///
+    /// Caution: this is blocking, the samples are symbolized before they are written.
+    pub(crate) fn stop(mut self) -> Result<PathBuf, ProfilingError> {
+        let id = self.id.take();
+
+        #[cfg(unix)]
+        return cpu::stop(id);
+
+        #[cfg(not(unix))]
+        {
+            let _ = id;
+            Err(ProfilingError::CpuProfileNotRunning)
+        }
+    }
+}
+
+impl Drop for CpuProfileGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(id) = self.id.take() {
+            cpu::discard(id);
+        }
+    }
+}
+
+/// Returns the memory statistics of jemalloc.
+#[cfg_attr(not(all(feature = "jemalloc", unix)), expect(clippy::missing_const_for_fn))]
+pub(crate) fn mem_stats() -> Result<MemStats, ProfilingError> {
+    #[cfg(all(feature = "jemalloc", unix))]
+    {
+        use tikv_jemalloc_ctl::{epoch, stats};
+
+        // the statistics are cached, advancing the epoch refreshes them
+        epoch::advance()?;
+        Ok(MemStats {
+            allocated: stats::allocated::read()? as u64,
+            active: stats::active::read()? as u64,
+            metadata: stats::metadata::read()? as u64,
+            resident: stats::resident::read()? as u64,
+            mapped: stats::mapped::read()? as u64,
+            retained: stats::retained::read()? as u64,
+        })
+    }
+
+    #[cfg(not(all(feature = "jemalloc", unix)))]
+    Err(ProfilingError::Unavailable("memory statistics require the `jemalloc` feature"))
+}
+
+/// Writes a jemalloc heap profile to the given path, which can be analyzed with `jeprof`. The file
+/// must not exist yet.
+///
+/// Heap profiling must have been activated on startup with `_RJEM_MALLOC_CONF=prof:true`.
+pub(crate) fn write_heap_profile(path: PathBuf) -> Result<(), ProfilingError> {
+    #[cfg(all(feature = "jemalloc-prof", unix))]
+    {
+        use std::{ffi::CString, os::unix::ffi::OsStrExt};
+        use tikv_jemalloc_ctl::{profiling, raw};
+
+        if !profiling::prof::read()? {
+            return Err(ProfilingError::Unavailable(
+                "heap profiling is not active, start the node with `_RJEM_MALLOC_CONF=prof:true`",
+            ))
+        }
+        // jemalloc truncates the file, creating it first refuses to overwrite an existing one
+        std::fs::File::create_new(&path)?;
+        let path = CString::new(path.as_os_str().as_bytes()).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "path contains a nul byte")
+        })?;
+        // SAFETY: `prof.dump` takes a pointer to a nul-terminated path, which outlives the call
+        unsafe { raw::write(b"prof.dump\0", path.as_ptr()) }?;
+        Ok(())
+    }
+
+    #[cfg(not(all(feature = "jemalloc-prof", unix)))]
+    {
+        let _ = path;
+        Err(ProfilingError::Unavailable("heap profiling requires the `jemalloc-prof` feature"))
+    }
+}
+
+/// Returns the stack traces of all OS threads of the process.
+///
+/// Caution: this is blocking, every thread is interrupted in turn to capture its stack.
+pub(crate) fn thread_stacks() -> String {
+    let mut out = String::new();
+
+    #[cfg(target_os = "linux")]
+    threads::write_stacks(&mut out);
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        use std::fmt::Write;
+
+        let _ = writeln!(out, "stacks of other threads can only be captured on linux\n");
+        let _ = writeln!(out, "{}", std::backtrace::Backtrace::force_capture());
+    }
+
+    out
+}
+
+/// Returns the stack traces of all tokio tasks of the current runtime.
+///
+/// Task dumps are an unstable tokio feature, they are only available if the node was built with
+/// `RUSTFLAGS="--cfg tokio_unstable --cfg tokio_taskdump"`.
+#[expect(unexpected_cfgs)]
+pub(crate) async fn task_stacks() -> Result<String, ProfilingError> {
+    #[cfg(all(tokio_unstable, tokio_taskdump))]
+    {
+        use std::fmt::Write;
+
+        /// How long to wait for the runtime to capture the dump.
+        const TIMEOUT: Duration = Duration::from_secs(5);
+
+        let handle = tokio::runtime::Handle::try_current()
+            .map_err(|_| ProfilingError::Unavailable("not running on a tokio runtime"))?;
+        let dump = tokio::time::timeout(TIMEOUT, handle.dump())
+            .await
+            .map_err(|_| ProfilingError::Unavailable("task dump timed out, a worker is blocked"))?;
+        let mut out = String::new();
+        for task in dump.tasks().iter() {
+            let _ = writeln!(out, "task {}:\n{}\n", task.id(), task.trace());
+        }
+        Ok(out)
+    }
+
+    #[cfg(not(all(tokio_unstable, tokio_taskdump)))]
+    Err(ProfilingError::Unavailable(
+        "task dumps require building with RUSTFLAGS=\"--cfg tokio_unstable --cfg tokio_taskdump\"",
+    ))
+}
+
+/// The sampling CPU profiler.
+#[cfg(unix)]
+mod cpu {
+    use super::ProfilingError;
+    use parking_lot::Mutex;
+    use pprof::{protos::Message, ProfilerGuard, ProfilerGuardBuilder};
+    use std::{
+        fs::File,
+        io::{BufWriter, Write},
+        path::PathBuf,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    /// Sampling frequency of the profiler in Hz.
+    const FREQUENCY: i32 = 99;
+
+    /// Libraries that are never unwound, unwinding through them from the signal handler can
+    /// deadlock.
+    const BLOCKLIST: &[&str] = &["libc", "libgcc", "pthread", "vdso"];
+
+    /// The profile being recorded, there can only be one per process.
+    static PROFILE: Mutex<Option<CpuProfile>> = Mutex::new(None);
+
+    /// The id of the next profile.
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    /// A CPU profile being recorded.
+    struct CpuProfile {
+        id: u64,
+        guard: ProfilerGuard<'static>,
+        file: File,
+        path: PathBuf,
+    }
+
+    /// Starts recording a profile and returns its id.
+    pub(super) fn start(path: PathBuf) -> Result<u64, ProfilingError> {
+        let mut profile = PROFILE.lock();
+        if profile.is_some() {
+            return Err(ProfilingError::CpuProfileRunning)
+        }
+
+        // create the file upfront, so an invalid path or an existing file is reported right away
+        let file = File::create_new(&path)?;
+        let guard =
+            match ProfilerGuardBuilder::default().frequency(FREQUENCY).blocklist(BLOCKLIST).build()
+            {
+                Ok(guard) => guard,
+                Err(err) => {
+                    let _ = std::fs::remove_file(&path);
+                    return Err(err.into())
+                }
+            };
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        *profile = Some(CpuProfile { id, guard, file, path });
+        Ok(id)
+    }
+
+    /// Stops recording the profile and writes it.
+    ///
+    /// With an id, only the profile with that id is stopped.
+    pub(super) fn stop(id: Option<u64>) -> Result<PathBuf, ProfilingError> {
+        let CpuProfile { guard, file, path, .. } =
+            take(id).ok_or(ProfilingError::CpuProfileNotRunning)?;
+
+        let report = guard.report().build()?;
+        // sampling stops when the guard is dropped
+        drop(guard);
+
+        let mut writer = BufWriter::new(file);
+        if path.extension().is_some_and(|extension| extension == "svg") {
+            report.flamegraph(&mut writer)?;
+        } else {
+            writer.write_all(&report.pprof()?.encode_to_vec())?;
+        }
+        writer.flush()?;
+
+        Ok(path)
+    }
+
+    /// Stops recording the profile with the given id without writing it.
+    pub(super) fn discard(id: u64) {
+        if let Some(profile) = take(Some(id)) {
+            drop(profile.guard);
+            let _ = std::fs::remove_file(&profile.path);
+        }
+    }
+
+    fn take(id: Option<u64>) -> Option<CpuProfile> {
+        PROFILE.lock().take_if(|profile| id.is_none_or(|id| profile.id == id))
+    }
+}
+
+/// Captures the stacks of all threads of the process by interrupting them with a signal.
+///
+/// The signal handler unwinds the stack of the interrupted thread with
+/// [`backtrace::trace_unsynchronized`], the same way the sampling CPU profiler does, and leaves the
+/// symbolization to the dumping thread.
+#[cfg(target_os = "linux")]
+mod threads {
+    use parking_lot::Mutex;
+    use std::{
+        ffi::c_void,
+        fmt::Write,
+        sync::{
+            atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering},
+            Once,
+        },
+        time::{Duration, Instant},
+    };
+
+    /// Maximum number of frames captured per thread.
+    const MAX_FRAMES: usize = 128;
+
+    /// How long to wait for a thread to handle the signal.
+    const SIGNAL_TIMEOUT: Duration = Duration::from_millis(100);
+
+    /// The capture is not expected to be written.
+    const IDLE: i32 = 0;
+    /// The signal handler is writing the capture.
+    const WRITING: i32 = -1;
+    /// The signal handler wrote the capture.
+    const DONE: i32 = -2;
+
+    /// The stack captured by the signal handler of the thread being sampled.
+    ///
+    /// The handler can't allocate, so it writes the raw instruction pointers into this buffer.
+    struct Capture {
+        /// The tid of the thread that is expected to write the capture, or one of [`IDLE`],
+        /// [`WRITING`] and [`DONE`].
+        ///
+        /// The handler claims the capture by swapping its tid for [`WRITING`], so a late handler
+        /// of a thread that timed out can't overwrite the capture of the next one.
+        state: AtomicI32,
+        ips: [AtomicUsize; MAX_FRAMES],
+        len: AtomicUsize,
+    }
+
+    static CAPTURE: Capture = Capture {
+        state: AtomicI32::new(IDLE),
+        ips: [const { AtomicUsize::new(0) }; MAX_FRAMES],
+        len: AtomicUsize::new(0),
+    };
+
+    /// Serializes the dumps, there's only one capture buffer.
+    static DUMP: Mutex<()> = Mutex::new(());
+
+    static INSTALL_HANDLER: Once = Once::new();
+
+    /// Whether the handler was installed. It isn't if another handler already claimed the signal.
+    static HANDLER_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+    /// The signal used to interrupt the threads.
+    fn signal() -> libc::c_int {
+        libc::SIGRTMIN()
+    }
+
+    fn gettid() -> libc::pid_t {
+        // SAFETY: `gettid` has no preconditions and is async-signal-safe
+        unsafe { libc::syscall(libc::SYS_gettid) as libc::pid_t }
+    }
+
+    extern "C" fn capture_stack(_signal: libc::c_int) {
+        // SAFETY: the errno of the interrupted thread is restored before returning
+        let errno = unsafe { *libc::__errno_location() };
+
+        // signals that were not sent for a capture are ignored
+        if CAPTURE
+            .state
+            .compare_exchange(gettid(), WRITING, Ordering::AcqRel, Ordering::Relaxed)
+            .is_ok()
+        {
+            let handler = capture_stack as extern "C" fn(libc::c_int) as usize;
+            let mut len = 0;
+            // SAFETY: the dumping thread waits for the handler and doesn't unwind concurrently,
+            // this is how the sampling profiler unwinds in its signal handler as well
+            unsafe {
+                backtrace::trace_unsynchronized(|frame| {
+                    if frame.symbol_address() as usize == handler {
+                        // drop the frames of the handler itself, the interrupted stack follows
+                        len = 0;
+                    } else {
+                        CAPTURE.ips[len].store(frame.ip() as usize, Ordering::Relaxed);
+                        len += 1;
+                    }
+                    len < MAX_FRAMES
+                })
+            };
+            CAPTURE.len.store(len, Ordering::Relaxed);
+            CAPTURE.state.store(DONE, Ordering::Release);
+        }
+
+        // SAFETY: see above
+        unsafe { *libc::__errno_location() = errno };
+    }
+
+    /// Installs the signal handler, unless a handler for the signal is installed already.
+    fn install_handler() -> bool {
+        INSTALL_HANDLER.call_once(|| {
+            // SAFETY: the handler only touches atomics and unwinds the stack of its thread
+            unsafe {
+                let mut previous: libc::sigaction = std::mem::zeroed();
+                if libc::sigaction(signal(), std::ptr::null(), &raw mut previous) != 0 ||
+                    previous.sa_sigaction != libc::SIG_DFL
+                {
+                    return
+                }
+
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction =
+                    capture_stack as extern "C" fn(libc::c_int) as libc::sighandler_t;
+                action.sa_flags = libc::SA_RESTART;
+                libc::sigemptyset(&raw mut action.sa_mask);
+                if libc::sigaction(signal(), &raw const action, std::ptr::null_mut()) == 0 {
+                    HANDLER_INSTALLED.store(true, Ordering::Release);
+                }
+            }
+        });
+        HANDLER_INSTALLED.load(Ordering::Acquire)
+    }
+
+    /// Interrupts the thread and returns the instruction pointers of its stack.
+    fn capture(pid: libc::pid_t, tid: libc::pid_t) -> Option<Vec<usize>> {
+        CAPTURE.state.store(tid, Ordering::Release);
+
+        // SAFETY: `tgkill` has no memory safety requirements
+        let sent = unsafe { libc::syscall(libc::SYS_tgkill, pid, tid, signal()) } == 0;
+        let deadline = Instant::now() + SIGNAL_TIMEOUT;
+        let mut done = false;
+        while sent && !done && Instant::now() < deadline {
+            std::thread::yield_now();
+            done = CAPTURE.state.load(Ordering::Acquire) == DONE;
+        }
+        if !done &&
+            CAPTURE
+                .state
+                .compare_exchange(tid, IDLE, Ordering::AcqRel, Ordering::Acquire)
+                .is_err()
+        {
+            // the handler claimed the capture before it was released, it only unwinds its own
+            // stack and finishes shortly
+            while CAPTURE.state.load(Ordering::Acquire) != DONE {
+                std::thread::yield_now();
+            }
+            done = true;
+        }
+
+        let ips = done.then(|| {
+            let len = CAPTURE.len.load(Ordering::Relaxed);
+            CAPTURE.ips[..len].iter().map(|ip| ip.load(Ordering::Relaxed)).collect()
+        });
+        CAPTURE.state.store(IDLE, Ordering::Release);
+        ips
+    }
+
+    fn write_frames(out: &mut String, ips: &[usize]) {
+        for (index, ip) in ips.iter().enumerate() {
+            let mut resolved = false;
+            backtrace::resolve(*ip as *mut c_void, |symbol| {
+                resolved = true;
+                let name = symbol.name().map(|name| name.to_string());
+                let _ = writeln!(out, "{index:4}: {}", name.as_deref().unwrap_or("<unknown>"));
+                if let (Some(file), Some(line)) = (symbol.filename(), symbol.lineno()) {
+                    let _ = writeln!(out, "             at {}:{line}", file.display());
+                }
+            });
+            if !resolved {
+                let _ = writeln!(out, "{index:4}: {ip:#x}");
+            }
+        }
+    }
+
+    pub(super) fn write_stacks(out: &mut String) {
+        let _dump = DUMP.lock();
+        let installed = install_handler();
+        if !installed {
+            let _ = writeln!(
+                out,
+                "signal {} is handled elsewhere, only the current thread is captured\n",
+                signal()
+            );
+        }
+
+        let pid = std::process::id() as libc::pid_t;
+        let current = gettid();
+        let tids = match std::fs::read_dir("/proc/self/task") {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<libc::pid_t>().ok())
+                .collect::<Vec<_>>(),
+            Err(err) => {
+                let _ = writeln!(out, "failed to list threads: {err}");
+                return
+            }
+        };
+
+        for tid in tids {
+            let name =
+                std::fs::read_to_string(format!("/proc/self/task/{tid}/comm")).unwrap_or_default();
+            let _ = writeln!(out, "thread {tid} ({}):", name.trim());
+
+            let ips = if tid == current {
+                let mut ips = Vec::new();
+                backtrace::trace(|frame| {
+                    ips.push(frame.ip() as usize);
+                    ips.len() < MAX_FRAMES
+                });
+                Some(ips)
+            } else if installed {
+                capture(pid, tid)
+            } else {
+                None
+            };
+            match ips {
+                Some(ips) => write_frames(out, &ips),
+                None => {
+                    let _ = writeln!(out, "      <thread did not respond>");
+                }
+            }
+            let _ = writeln!(out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn captures_stacks_of_other_threads() {
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+        let thread = std::thread::Builder::new()
+            .name("stacks-test".to_string())
+            .spawn(move || rx.recv())
+            .unwrap();
+
+        let stacks = thread_stacks();
+        let (_, thread_stack) = stacks.split_once("(stacks-test):\n").unwrap();
+        let thread_stack = thread_stack.split("\n\n").next().unwrap();
+        // the stack is unwound past the signal handler into the blocked receive
+        assert!(thread_stack.contains("recv"), "{thread_stack}");
+
+        tx.send(()).unwrap();
+        thread.join().unwrap().unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn cpu_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cpu.pb");
+
+        assert!(matches!(stop_cpu_profile(), Err(ProfilingError::CpuProfileNotRunning)));
+        start_cpu_profile(path.clone()).unwrap();
+        assert!(matches!(
+            start_cpu_profile(dir.path().join("other.pb")),
+            Err(ProfilingError::CpuProfileRunning)
+        ));
+        assert_eq!(stop_cpu_profile().unwrap(), path);
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+
+        // existing files are not overwritten
+        assert!(matches!(start_cpu_profile(path), Err(ProfilingError::Io(_))));
+
+        // a dropped profile is discarded
+        let path = dir.path().join("dropped.pb");
+        drop(CpuProfileGuard::start(path.clone()).unwrap());
+        assert!(!path.exists());
+        assert!(matches!(stop_cpu_profile(), Err(ProfilingError::CpuProfileNotRunning)));
+
+        let path = dir.path().join("guarded.pb");
+        assert_eq!(CpuProfileGuard::start(path.clone()).unwrap().stop().unwrap(), path);
+    }
+
+    #[test]
+    fn profile_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let profiles = dir.path().join("profiles");
+
+        assert!(matches!(profile_path(None, "cpu.pb"), Err(ProfilingError::Unavailable(_))));
+        for file in ["", "../cpu.pb", "/tmp/cpu.pb", "sub/cpu.pb", "."] {
+            assert!(matches!(
+                profile_path(Some(&profiles), file),
+                Err(ProfilingError::InvalidFileName(_))
+            ));
+        }
+        assert_eq!(profile_path(Some(&profiles), "cpu.pb").unwrap(), profiles.join("cpu.pb"));
+        assert!(profiles.is_dir());
+
+        assert!(cpu_profile_duration(MAX_CPU_PROFILE_DURATION.as_secs()).is_ok());
+        assert!(matches!(
+            cpu_profile_duration(MAX_CPU_PROFILE_DURATION.as_secs() + 1),
+            Err(ProfilingError::DurationTooLong)
+        ));
+    }
+}
@@ -1,46 +1,99 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use crate::eth::filter::EthFilterError;
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    ops::{Range, RangeInclusive},
+    sync::Arc,
+};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_consensus::BlockHeader;
+use alloy_eips::{BlockId, BlockNumHash, BlockNumberOrTag};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxNumber, B256, U256, U64};
+use alloy_rpc_types_eth::{Filter, Log};
+use alloy_rpc_types_trace::parity::{AccountDiff, Delta, StateDiff};
 use async_trait::async_trait;
 use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use jsonrpsee_types::ErrorObject;
-use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
-use reth_errors::RethResult;
-use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::RethApiServer;
-use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_chain_state::{
+    CanonStateNotification, CanonStateNotificationStream, CanonStateSubscriptions,
+};
+use reth_errors::{ProviderResult, RethResult};
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{Account, NodePrimitives, SignedTransaction};
+use reth_revm::{database::StateProviderDatabase, db::CacheDB};
+use reth_rpc_api::{AddressTransaction, AddressTransactionsPage, RethApiServer};
+use reth_rpc_eth_api::{
+    helpers::{EthTransactions, TraceExt},
+    FromEthApiError,
+};
+use reth_rpc_eth_types::{
+    logs_utils::{append_matching_block_logs, matching_block_logs_with_tx_hashes, ProviderOrBlock},
+    EthApiError, EthConfig, EthResult,
+};
 use reth_rpc_server_types::result::internal_rpc_err;
-use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_storage_api::{
+    address_transactions_indexed_blocks, AddressTransactionsReader, BlockReaderIdExt,
+    ChangeSetReader, PruneCheckpointReader, StageCheckpointReader, StateProvider,
+    StateProviderFactory, StorageChangeSetReader,
+};
 use reth_tasks::TaskSpawner;
+use revm_inspectors::tracing::parity::populate_state_diff;
 use tokio::sync::oneshot;
 
+/// The maximum number of transactions returned by `reth_getTransactionsByAddress` at once.
+const MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE: usize = 1000;
+
+/// The maximum number of blocks whose logs are replayed at once by a log subscription.
+const MAX_LOGS_REPLAY_BLOCKS: u64 = 100;
+
+/// The number of most recent blocks whose sent logs a log subscription keeps, to send them again
+/// as removed if the blocks are reorged out.
+const MAX_LOGS_REORG_DEPTH: usize = 64;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
-    inner: Arc<RethApiInner<Provider>>,
+pub struct RethApi<Provider, Eth> {
+    inner: Arc<RethApiInner<Provider, Eth>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider, Eth> RethApi<Provider, Eth> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
+    /// Access the underlying `Eth` API, used to re-execute transactions.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
     /// Create a new instance of the [`RethApi`]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        task_spawner: Box<dyn TaskSpawner>,
+        eth_config: EthConfig,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner { provider, eth_api, task_spawner, eth_config });
         Self { inner }
     }
 }
 
-impl<Provider> RethApi<Provider>
+impl<Provider, Eth> RethApi<Provider, Eth>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + StateProviderFactory
+        + AddressTransactionsReader
+        + StageCheckpointReader
+        + PruneCheckpointReader
+        + 'static,
+    Eth: Send + Sync + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -88,16 +141,421 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the state changes of a block, read from its changesets.
+    pub async fn state_diff_in_block(&self, block_id: BlockId) -> EthResult<StateDiff> {
+        self.on_blocking_task(|this| async move { this.try_state_diff_in_block(block_id) }).await
+    }
+
+    fn try_state_diff_in_block(&self, block_id: BlockId) -> EthResult<StateDiff> {
+        let Some(block_number) = self.provider().block_number_for_id(block_id)? else {
+            return Err(EthApiError::HeaderNotFound(block_id))
+        };
+
+        let state = self.provider().state_by_block_id(block_id)?;
+        let mut state_diff = StateDiff::default();
+        for account_before in self.provider().account_block_changeset(block_number)? {
+            let address = account_before.address;
+            let account = state.basic_account(&address)?;
+            let account_diff = account_diff(&state, account_before.info, account)?;
+            state_diff.insert(address, account_diff);
+        }
+
+        for (block_address, storage_before) in self.provider().storage_changeset(block_number)? {
+            let address = block_address.address();
+            let value = state.storage(address, storage_before.key)?.unwrap_or_default();
+            if value == storage_before.value {
+                continue
+            }
+
+            // The storage of created and destroyed accounts is marked the same as the account.
+            let account_diff = state_diff.entry(address).or_default();
+            let delta = match account_diff.nonce {
+                Delta::Added(_) => Delta::Added(value.into()),
+                Delta::Removed(_) => Delta::Removed(storage_before.value.into()),
+                _ => Delta::changed(storage_before.value.into(), value.into()),
+            };
+            account_diff.storage.insert(storage_before.key, delta);
+        }
+
+        // Accounts that were only touched are not part of the diff.
+        state_diff.retain(|_, account_diff| {
+            !account_diff.balance.is_unchanged() ||
+                !account_diff.nonce.is_unchanged() ||
+                !account_diff.code.is_unchanged() ||
+                !account_diff.storage.is_empty()
+        });
+
+        Ok(state_diff)
+    }
+
+    /// Returns a page of the transactions the address took part in within the block range,
+    /// starting at the cursor.
+    pub async fn transactions_by_address(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+        cursor: Option<TxNumber>,
+    ) -> EthResult<AddressTransactionsPage> {
+        self.on_blocking_task(|this| async move {
+            this.try_transactions_by_address(address, from_block, to_block, cursor)
+        })
+        .await
+    }
+
+    fn try_transactions_by_address(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+        cursor: Option<TxNumber>,
+    ) -> EthResult<AddressTransactionsPage> {
+        // The index only covers the blocks after the pruned ones up to the stage checkpoint.
+        let Some(indexed_blocks) = address_transactions_indexed_blocks(self.provider())? else {
+            return Err(EthApiError::Unsupported("the address transactions index is not enabled"))
+        };
+
+        let block_number = |block: BlockNumberOrTag| {
+            self.provider()
+                .convert_block_number(block)?
+                .ok_or(EthApiError::HeaderNotFound(block.into()))
+        };
+        let from_block = block_number(from_block.unwrap_or(BlockNumberOrTag::Earliest))?;
+        if from_block < *indexed_blocks.start() {
+            return Err(EthApiError::PrunedHistoryUnavailable)
+        }
+        let to_block =
+            block_number(to_block.unwrap_or(BlockNumberOrTag::Latest))?.min(*indexed_blocks.end());
+        if from_block > to_block {
+            return Ok(AddressTransactionsPage::default())
+        }
+
+        let start = self.block_tx_range(from_block)?.start.max(cursor.unwrap_or_default());
+        let end = self.block_tx_range(to_block)?.end;
+        if start >= end {
+            return Ok(AddressTransactionsPage::default())
+        }
+
+        // Fetch one more transaction to find out whether there's a next page.
+        let mut tx_numbers = self.provider().address_transactions(
+            address,
+            start..=end - 1,
+            MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE + 1,
+        )?;
+        let next_cursor = if tx_numbers.len() > MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE {
+            tx_numbers.pop().map(U64::from)
+        } else {
+            None
+        };
+
+        let mut transactions = Vec::with_capacity(tx_numbers.len());
+        let mut block = None::<(BlockNumber, B256, Range<TxNumber>)>;
+        for tx_number in tx_numbers {
+            let hash = *self
+                .provider()
+                .transaction_by_id(tx_number)?
+                .ok_or(EthApiError::TransactionNotFound)?
+                .tx_hash();
+
+            if !block.as_ref().is_some_and(|(_, _, block_txs)| block_txs.contains(&tx_number)) {
+                let number = self
+                    .provider()
+                    .transaction_block(tx_number)?
+                    .ok_or(EthApiError::TransactionNotFound)?;
+                let hash = self
+                    .provider()
+                    .block_hash(number)?
+                    .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                block = Some((number, hash, self.block_tx_range(number)?));
+            }
+            let (block_number, block_hash, block_txs) = block.as_ref().expect("block was set");
+
+            transactions.push(AddressTransaction {
+                hash,
+                block_hash: *block_hash,
+                block_number: *block_number,
+                transaction_index: tx_number - block_txs.start,
+            });
+        }
+
+        Ok(AddressTransactionsPage { transactions, next_cursor })
+    }
+
+    /// Returns the range of transaction numbers of the block.
+    fn block_tx_range(&self, block_number: BlockNumber) -> EthResult<Range<TxNumber>> {
+        let body = self
+            .provider()
+            .block_body_indices(block_number)?
+            .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+        Ok(body.first_tx_num()..body.next_tx_num())
+    }
+
+    /// Returns the logs matching the filter of every block in the range.
+    async fn block_logs_in_range(
+        &self,
+        filter: Filter,
+        range: RangeInclusive<BlockNumber>,
+    ) -> EthResult<Vec<(BlockNumHash, Vec<Log>)>> {
+        self.on_blocking_task(|this| async move { this.try_block_logs_in_range(&filter, range) })
+            .await
+    }
+
+    fn try_block_logs_in_range(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<BlockNumber>,
+    ) -> EthResult<Vec<(BlockNumHash, Vec<Log>)>> {
+        let mut blocks = Vec::new();
+        for header in self.provider().sealed_headers_range(range)? {
+            let num_hash = header.num_hash();
+            let mut logs = Vec::new();
+            if filter.matches_block(&num_hash) && filter.matches_bloom(header.logs_bloom()) {
+                let receipts = self
+                    .provider()
+                    .receipts_by_block(num_hash.hash.into())?
+                    .ok_or(EthApiError::ReceiptsNotFound(num_hash.hash.into()))?;
+                append_matching_block_logs(
+                    &mut logs,
+                    ProviderOrBlock::Provider(self.provider()),
+                    filter,
+                    num_hash,
+                    &receipts,
+                    false,
+                    header.timestamp(),
+                )?;
+            }
+            blocks.push((num_hash, logs));
+        }
+        Ok(blocks)
+    }
+}
+
+impl<Provider, Eth> RethApi<Provider, Eth>
+where
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + StateProviderFactory
+        + AddressTransactionsReader
+        + StageCheckpointReader
+        + PruneCheckpointReader
+        + CanonStateSubscriptions
+        + 'static,
+    Eth: Send + Sync + 'static,
+{
+    /// Returns the number of the block a log subscription starts at.
+    ///
+    /// The block can be at most as many blocks behind the head as `eth_getLogs` can query.
+    fn logs_from_block(&self, block: BlockNumberOrTag) -> Result<BlockNumber, EthFilterError> {
+        let from_block = self
+            .provider()
+            .convert_block_number(block)?
+            .ok_or(EthApiError::HeaderNotFound(block.into()))?;
+        let best_block = self.provider().best_block_number()?;
+        let max_blocks = self.inner.eth_config.max_blocks_per_filter;
+        if best_block.saturating_sub(from_block) > max_blocks {
+            return Err(EthFilterError::QueryExceedsMaxBlocks(max_blocks))
+        }
+        Ok(from_block)
+    }
+
+    /// Sends the logs matching the filter to the subscription sink, starting at `from_block`, or at
+    /// the next block if `None`.
+    ///
+    /// The logs of the blocks up to the head are replayed before subscribing to the canonical
+    /// state notifications, so that a long replay can't make the notification channel lag, and the
+    /// blocks added in the meantime are replayed right after subscribing. The blocks of
+    /// notifications that are skipped because the channel lagged anyway are replayed as well.
+    async fn pipe_logs(
+        self,
+        sink: SubscriptionSink,
+        filter: Filter,
+        from_block: Option<BlockNumber>,
+    ) -> Result<(), ErrorObject<'static>> {
+        let next_block = match from_block {
+            Some(from_block) => from_block,
+            None => self.provider().best_block_number().map_err(EthApiError::from)? + 1,
+        };
+        let mut cursor = LogsCursor { next_block, sent: VecDeque::new() };
+
+        if !self.replay_logs(&sink, &filter, &mut cursor, None).await? {
+            return Ok(())
+        }
+        let mut stream = self.provider().canonical_state_stream();
+        if !self.replay_logs(&sink, &filter, &mut cursor, None).await? {
+            return Ok(())
+        }
+
+        loop {
+            tokio::select! {
+                _ = sink.closed() => {
+                    // connection dropped
+                    break Ok(())
+                }
+                maybe_notification = stream.next() => {
+                    let Some(notification) = maybe_notification else {
+                        // stream ended
+                        break Ok(())
+                    };
+                    // the stream silently skips the notifications it lagged behind on
+                    let first_block = notification.committed().first().number();
+                    if first_block > cursor.next_block &&
+                        !self.replay_logs(&sink, &filter, &mut cursor, Some(first_block - 1)).await?
+                    {
+                        break Ok(())
+                    }
+                    let logs = self.notification_logs(&filter, &notification, &mut cursor)?;
+                    if !send_logs(&sink, &logs).await? {
+                        break Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends the logs of the blocks from the cursor up to `to_block`, or up to the head if `None`.
+    ///
+    /// Returns `false` if the subscription was closed.
+    async fn replay_logs(
+        &self,
+        sink: &SubscriptionSink,
+        filter: &Filter,
+        cursor: &mut LogsCursor,
+        to_block: Option<BlockNumber>,
+    ) -> Result<bool, ErrorObject<'static>> {
+        // the chain may have been reorged since the last replay
+        if !send_logs(sink, &self.unwound_logs(cursor)?).await? {
+            return Ok(false)
+        }
+
+        let last_block = match to_block {
+            Some(to_block) => to_block,
+            None => self.provider().best_block_number().map_err(EthApiError::from)?,
+        };
+        while cursor.next_block <= last_block {
+            let to_block = last_block.min(cursor.next_block + MAX_LOGS_REPLAY_BLOCKS - 1);
+            let blocks =
+                self.block_logs_in_range(filter.clone(), cursor.next_block..=to_block).await?;
+            for (block, logs) in blocks {
+                if !send_logs(sink, &logs).await? {
+                    return Ok(false)
+                }
+                cursor.push(block, logs);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns the logs of a canonical state notification that still need to be sent, and
+    /// advances the cursor past its blocks.
+    ///
+    /// The sent logs of the blocks the notification reverts are returned as removed.
+    fn notification_logs(
+        &self,
+        filter: &Filter,
+        notification: &CanonStateNotification<Provider::Primitives>,
+        cursor: &mut LogsCursor,
+    ) -> EthResult<Vec<Log>> {
+        let mut logs = Vec::new();
+
+        if let Some(reverted) = notification.reverted() {
+            logs.extend(cursor.rewind(reverted.first().number()));
+        }
+
+        let committed = notification.committed();
+        if committed.first().number() < cursor.next_block {
+            // The blocks were either already replayed, or the chain was unwound without a reorg
+            // notification, in which case they replace the blocks that were sent.
+            logs.extend(self.unwound_logs(cursor)?);
+        }
+
+        for block_receipts in committed.receipts_with_attachment() {
+            if block_receipts.block.number >= cursor.next_block {
+                let block_logs = matching_block_logs_with_tx_hashes(
+                    filter,
+                    block_receipts.block,
+                    block_receipts.timestamp,
+                    block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
+                    false,
+                );
+                logs.extend(block_logs.iter().cloned());
+                cursor.push(block_receipts.block, block_logs);
+            }
+        }
+
+        Ok(logs)
+    }
+
+    /// Rewinds the cursor to the first sent block that is no longer canonical, and returns the
+    /// sent logs of the blocks from it on as removed.
+    fn unwound_logs(&self, cursor: &mut LogsCursor) -> EthResult<Vec<Log>> {
+        let mut fork_block = None;
+        for (block, _) in cursor.sent.iter().rev() {
+            if self.provider().block_hash(block.number)? == Some(block.hash) {
+                break
+            }
+            fork_block = Some(block.number);
+        }
+        Ok(fork_block.map(|fork_block| cursor.rewind(fork_block)).unwrap_or_default())
+    }
+}
+
+impl<Provider, Eth> RethApi<Provider, Eth>
+where
+    Eth: EthTransactions + TraceExt + 'static,
+{
+    /// Returns the state changes of a transaction.
+    ///
+    /// The block of the transaction is re-executed up to and including the transaction, since the
+    /// changesets only hold the state changes of whole blocks.
+    pub async fn state_diff_by_transaction(&self, hash: B256) -> Result<StateDiff, Eth::Error> {
+        let (transaction, block) = match self.eth_api().transaction_and_block(hash).await? {
+            None => return Err(EthApiError::TransactionNotFound.into()),
+            Some(res) => res,
+        };
+        let (evm_env, _) = self.eth_api().evm_env_at(block.hash().into()).await?;
+
+        // replay the block on top of the state of its parent
+        let eth_api = self.eth_api().clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let tx = transaction.into_recovered();
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                eth_api.apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+                eth_api.replay_transactions_until(
+                    &mut db,
+                    evm_env.clone(),
+                    block.transactions_recovered(),
+                    *tx.tx_hash(),
+                )?;
+
+                let tx_env = eth_api.evm_config().tx_env(&tx);
+                let res = eth_api.transact(&mut db, evm_env, tx_env)?;
+
+                let mut state_diff = StateDiff::default();
+                populate_state_diff(&mut state_diff, &db, res.state.iter())
+                    .map_err(Eth::Error::from_eth_err)?;
+                Ok(state_diff)
+            })
+            .await
+    }
 }
 
 #[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider, Eth> RethApiServer for RethApi<Provider, Eth>
 where
     Provider: BlockReaderIdExt
         + ChangeSetReader
+        + StorageChangeSetReader
         + StateProviderFactory
+        + AddressTransactionsReader
+        + StageCheckpointReader
+        + PruneCheckpointReader
         + CanonStateSubscriptions
         + 'static,
+    Eth: EthTransactions + TraceExt + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -107,21 +565,124 @@ where
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
 
+    /// Handler for `reth_getStateDiffInBlock`
+    async fn reth_get_state_diff_in_block(&self, block_id: BlockId) -> RpcResult<StateDiff> {
+        Ok(Self::state_diff_in_block(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getStateDiffByTransaction`
+    async fn reth_get_state_diff_by_transaction(&self, hash: B256) -> RpcResult<StateDiff> {
+        Self::state_diff_by_transaction(self, hash).await.map_err(Into::into)
+    }
+
+    /// Handler for `reth_getTransactionsByAddress`
+    async fn reth_get_transactions_by_address(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+        cursor: Option<U64>,
+    ) -> RpcResult<AddressTransactionsPage> {
+        Ok(self
+            .transactions_by_address(
+                address,
+                from_block,
+                to_block,
+                cursor.map(|cursor| cursor.to()),
+            )
+            .await?)
+    }
+
     /// Handler for `reth_subscribeChainNotifications`
     async fn reth_subscribe_chain_notifications(
         &self,
         pending: PendingSubscriptionSink,
+        filter: Option<Filter>,
+        from_block: Option<BlockNumberOrTag>,
     ) -> jsonrpsee::core::SubscriptionResult {
+        let Some(filter) = filter else {
+            let sink = pending.accept().await?;
+            let stream = self.provider().canonical_state_stream();
+            self.inner.task_spawner.spawn(Box::pin(async move {
+                let _ = pipe_from_stream(sink, stream).await;
+            }));
+            return Ok(())
+        };
+
+        let from_block = match from_block.map(|block| self.logs_from_block(block)).transpose() {
+            Ok(from_block) => from_block,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+
         let sink = pending.accept().await?;
-        let stream = self.provider().canonical_state_stream();
+        let this = self.clone();
         self.inner.task_spawner.spawn(Box::pin(async move {
-            let _ = pipe_from_stream(sink, stream).await;
+            let _ = this.pipe_logs(sink, filter, from_block).await;
         }));
 
         Ok(())
     }
 }
 
+/// Tracks the blocks whose logs were sent by a log subscription.
+#[derive(Debug)]
+struct LogsCursor {
+    /// The next block whose logs are sent.
+    next_block: BlockNumber,
+    /// The most recent blocks whose logs were sent, with those logs, at most
+    /// [`MAX_LOGS_REORG_DEPTH`] of them.
+    sent: VecDeque<(BlockNumHash, Vec<Log>)>,
+}
+
+impl LogsCursor {
+    /// Records that the logs of the block were sent.
+    fn push(&mut self, block: BlockNumHash, logs: Vec<Log>) {
+        self.next_block = block.number + 1;
+        if self.sent.len() == MAX_LOGS_REORG_DEPTH {
+            self.sent.pop_front();
+        }
+        self.sent.push_back((block, logs));
+    }
+
+    /// Rewinds the cursor to the given block, and returns the sent logs of the blocks from it on
+    /// as removed.
+    ///
+    /// The logs of blocks older than the kept ones can't be returned.
+    fn rewind(&mut self, block: BlockNumber) -> Vec<Log> {
+        let mut removed = Vec::new();
+        while self.sent.back().is_some_and(|(sent, _)| sent.number >= block) {
+            removed.push(self.sent.pop_back().expect("not empty").1);
+        }
+        self.next_block = self.next_block.min(block);
+        removed
+            .into_iter()
+            .rev()
+            .flatten()
+            .map(|mut log| {
+                log.removed = true;
+                log
+            })
+            .collect()
+    }
+}
+
+/// Sends the logs to the subscription sink.
+///
+/// Returns `false` if the subscription was closed.
+async fn send_logs(sink: &SubscriptionSink, logs: &[Log]) -> Result<bool, ErrorObject<'static>> {
+    for log in logs {
+        let msg = SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), log)
+            .map_err(|e| internal_rpc_err(e.to_string()))?;
+        if sink.send(msg).await.is_err() {
+            return Ok(false)
+        }
+    }
+    Ok(true)
+}
+
 /// Pipes all stream items to the subscription sink.
 async fn pipe_from_stream<N: NodePrimitives>(
     sink: SubscriptionSink,
@@ -152,21 +713,197 @@ async fn pipe_from_stream<N: NodePrimitives>(
     }
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+/// Returns the diff of the balance, nonce and code of an account.
+fn account_diff(
+    state: &impl StateProvider,
+    before: Option<Account>,
+    after: Option<Account>,
+) -> ProviderResult<AccountDiff> {
+    let code = |account: &Account| -> ProviderResult<Bytes> {
+        let Some(code_hash) = account.bytecode_hash else { return Ok(Bytes::new()) };
+        Ok(state
+            .bytecode_by_hash(&code_hash)?
+            .map(|bytecode| bytecode.original_bytes())
+            .unwrap_or_default())
+    };
+
+    let account_diff = match (before, after) {
+        (None, None) => AccountDiff::default(),
+        (None, Some(after)) => AccountDiff {
+            balance: Delta::Added(after.balance),
+            nonce: Delta::Added(U64::from(after.nonce)),
+            code: Delta::Added(code(&after)?),
+            storage: Default::default(),
+        },
+        (Some(before), None) => AccountDiff {
+            balance: Delta::Removed(before.balance),
+            nonce: Delta::Removed(U64::from(before.nonce)),
+            code: Delta::Removed(code(&before)?),
+            storage: Default::default(),
+        },
+        (Some(before), Some(after)) => AccountDiff {
+            balance: delta(before.balance, after.balance),
+            nonce: delta(U64::from(before.nonce), U64::from(after.nonce)),
+            code: if before.bytecode_hash == after.bytecode_hash {
+                Delta::Unchanged
+            } else {
+                Delta::changed(code(&before)?, code(&after)?)
+            },
+            storage: Default::default(),
+        },
+    };
+    Ok(account_diff)
+}
+
+/// Returns [`Delta::Unchanged`] if the values are equal, [`Delta::Changed`] otherwise.
+fn delta<T: PartialEq>(from: T, to: T) -> Delta<T> {
+    if from == to {
+        Delta::Unchanged
+    } else {
+        Delta::changed(from, to)
+    }
+}
+
+impl<Provider, Eth> std::fmt::Debug for RethApi<Provider, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider, Eth> Clone for RethApi<Provider, Eth> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider> {
+struct RethApiInner<Provider, Eth> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// The implementation of `eth` API.
+    eth_api: Eth,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// Configuration of the `eth` API, whose filter limits also apply to log subscriptions.
+    eth_config: EthConfig,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{
+        core::{server::Subscription, EmptyServerParams, SubscriptionResult},
+        RpcModule,
+    };
+    use reth_chain_state::{ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates, NewCanonicalChain};
+    use reth_ethereum_primitives::{Block, Receipt};
+    use reth_primitives_traits::SealedBlock;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory,
+        writer::UnifiedStorageWriter, Chain, DatabaseProviderFactory, ExecutionOutcome,
+        StaticFileProviderFactory, StaticFileSegment, StaticFileWriter,
+    };
+    use reth_tasks::TokioTaskExecutor;
+    use reth_testing_utils::generators::{self, random_block_range, random_log, BlockRangeParams};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn log_subscription_replays_skipped_notifications() {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+
+        let genesis = random_block_range(&mut rng, 0..=0, BlockRangeParams::default()).remove(0);
+        let provider_rw = factory.database_provider_rw().unwrap();
+        for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+            factory
+                .static_file_provider()
+                .latest_writer(segment)
+                .unwrap()
+                .increment_block(0)
+                .unwrap();
+        }
+        provider_rw.insert_historical_block(genesis.clone().try_recover().unwrap()).unwrap();
+        UnifiedStorageWriter::commit(provider_rw).unwrap();
+        let provider = BlockchainProvider::new(factory).unwrap();
+
+        let api = RethApi::new(
+            provider.clone(),
+            (),
+            Box::<TokioTaskExecutor>::default(),
+            EthConfig::default(),
+        );
+        let mut module = RpcModule::new(());
+        module
+            .register_subscription("subscribe", "logs", "unsubscribe", move |_, pending, _, _| {
+                let api = api.clone();
+                async move {
+                    let sink = pending.accept().await?;
+                    let _ = api.pipe_logs(sink, Filter::new(), None).await;
+                    SubscriptionResult::Ok(())
+                }
+            })
+            .unwrap();
+        let mut subscription =
+            module.subscribe_unbounded("subscribe", EmptyServerParams::new()).await.unwrap();
+
+        // one log per block, more blocks than the notification channel holds
+        let blocks = random_block_range(
+            &mut rng,
+            1..=300,
+            BlockRangeParams { parent: Some(genesis.hash()), tx_count: 1..2, ..Default::default() },
+        );
+        let mut logs = Vec::new();
+        let mut notify = |block: &SealedBlock<Block>| {
+            let receipt = Receipt {
+                tx_type: block.body().transactions[0].tx_type(),
+                success: true,
+                logs: vec![random_log(&mut rng, None, Some(1))],
+                ..Default::default()
+            };
+            logs.push(receipt.logs[0].clone());
+            let block = block.clone().try_recover().unwrap();
+            let outcome = ExecutionOutcome::new(
+                Default::default(),
+                vec![vec![receipt]],
+                block.number,
+                Vec::new(),
+            );
+            let in_memory_state = provider.canonical_in_memory_state();
+            in_memory_state.update_chain(NewCanonicalChain::Commit {
+                new: vec![ExecutedBlockWithTrieUpdates::new(
+                    Arc::new(block.clone()),
+                    Arc::new(outcome.clone()),
+                    Default::default(),
+                    ExecutedTrieUpdates::empty(),
+                )],
+            });
+            in_memory_state.set_canonical_head(block.clone_sealed_header());
+            in_memory_state.notify_canon_state(CanonStateNotification::Commit {
+                new: Arc::new(Chain::new([block], outcome, None)),
+            });
+        };
+
+        // the log of the first block shows that the subscription receives the notifications, the
+        // notifications of the remaining blocks are sent at once so the stream lags and skips some
+        notify(&blocks[0]);
+        let next_log = async |subscription: &mut Subscription| {
+            let next = tokio::time::timeout(Duration::from_secs(10), subscription.next::<Log>());
+            next.await.unwrap().unwrap().unwrap().0
+        };
+        let log = next_log(&mut subscription).await;
+        assert_eq!(log.block_number, Some(1));
+        for block in &blocks[1..] {
+            notify(block);
+        }
+
+        let mut received = vec![log];
+        while received.len() < blocks.len() {
+            received.push(next_log(&mut subscription).await);
+        }
+        for ((log, expected), block) in received.iter().zip(&logs).zip(&blocks) {
+            assert_eq!(log.block_number, Some(block.number));
+            assert_eq!(log.block_hash, Some(block.hash()));
+            assert_eq!(&log.inner, expected);
+            assert!(!log.removed);
+        }
+    }
 }
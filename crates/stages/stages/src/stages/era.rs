@@ -125,7 +125,9 @@ impl<Header, Body, F> EraStage<Header, Body, F> {
             source,
             item: None,
             stream: None,
-            hash_collector: Collector::new(etl_config.file_size, etl_config.dir),
+            hash_collector: Collector::new(etl_config.file_size, etl_config.dir)
+                .with_compression_level(etl_config.compression_level)
+                .with_max_disk_usage(etl_config.max_disk_usage),
         }
     }
 }
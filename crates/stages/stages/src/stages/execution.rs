@@ -11,9 +11,10 @@ use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
 use reth_primitives_traits::{format_gas_throughput, Block, BlockBody, NodePrimitives};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
-    BlockHashReader, BlockReader, DBProvider, ExecutionOutcome, HeaderProvider,
-    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateCommitmentProvider,
-    StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation, TransactionVariant,
+    BlockHashReader, BlockReader, DBProvider, ExecutionOutcome, HashedPostStateProvider,
+    HeaderProvider, LatestStateProviderRef, OriginalValuesKnown, ProviderError,
+    StageCheckpointReader, StateCommitmentProvider, StateWriter, StaticFileProviderFactory,
+    StatsReader, StorageLocation, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::{
@@ -31,7 +32,7 @@ use std::{
 };
 use tracing::*;
 
-use super::missing_static_data_error;
+use super::{is_snap_synced, missing_static_data_error};
 
 /// The execution stage executes all transactions and
 /// update history indexes.
@@ -55,6 +56,8 @@ use super::missing_static_data_error;
 /// - [`tables::Bytecodes`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+/// - [`tables::HashedAccounts`] and [`tables::HashedStorages`], if the state was downloaded by the
+///   `SnapSync` stage
 ///
 /// For unwinds we are accessing:
 /// - [`tables::BlockBodyIndices`] get tx index to know what needs to be unwinded
@@ -261,7 +264,8 @@ where
         + StatsReader
         + BlockHashReader
         + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
-        + StateCommitmentProvider,
+        + StateCommitmentProvider
+        + StageCheckpointReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -454,6 +458,13 @@ where
 
         // write output
         provider.write_state(&state, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)?;
+        if is_snap_synced(provider)? {
+            // The hashing stages can't rebuild the hashed state from the plain state of a snap
+            // synced node, so it's written together with the plain state.
+            let hashed_state =
+                LatestStateProviderRef::new(provider).hashed_post_state(&state.bundle);
+            provider.write_hashed_state(&hashed_state.into_sorted())?;
+        }
 
         let db_write_duration = time.elapsed();
         debug!(
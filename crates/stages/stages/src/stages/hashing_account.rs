@@ -9,7 +9,9 @@ use reth_db_api::{
 };
 use reth_etl::Collector;
 use reth_primitives_traits::Account;
-use reth_provider::{AccountExtReader, DBProvider, HashingWriter, StatsReader};
+use reth_provider::{
+    AccountExtReader, DBProvider, HashingWriter, StageCheckpointReader, StatsReader,
+};
 use reth_stages_api::{
     AccountHashingCheckpoint, EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint,
     StageError, StageId, UnwindInput, UnwindOutput,
@@ -22,6 +24,8 @@ use std::{
 };
 use tracing::*;
 
+use super::is_snap_synced;
+
 /// Maximum number of channels that can exist in memory.
 const MAXIMUM_CHANNELS: usize = 10_000;
 
@@ -134,7 +138,11 @@ impl Default for AccountHashingStage {
 
 impl<Provider> Stage<Provider> for AccountHashingStage
 where
-    Provider: DBProvider<Tx: DbTxMut> + HashingWriter + AccountExtReader + StatsReader,
+    Provider: DBProvider<Tx: DbTxMut>
+        + HashingWriter
+        + AccountExtReader
+        + StatsReader
+        + StageCheckpointReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -147,6 +155,11 @@ where
             return Ok(ExecOutput::done(input.checkpoint()))
         }
 
+        // The hashed state of a snap synced node is written by the execution stage.
+        if is_snap_synced(provider)? {
+            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+
         let (from_block, to_block) = input.next_block_range().into_inner();
 
         // if there are more blocks then threshold it is faster to go over Plain state and hash all
@@ -161,7 +174,9 @@ where
 
             let mut accounts_cursor = tx.cursor_read::<RawTable<tables::PlainAccountState>>()?;
             let mut collector =
-                Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+                Collector::new(self.etl_config.file_size, self.etl_config.dir.clone())
+                    .with_compression_level(self.etl_config.compression_level)
+                    .with_max_disk_usage(self.etl_config.max_disk_usage);
             let mut channels = Vec::with_capacity(MAXIMUM_CHANNELS);
 
             // channels used to return result of account hashing
@@ -10,7 +10,7 @@ use reth_db_api::{
 };
 use reth_etl::Collector;
 use reth_primitives_traits::StorageEntry;
-use reth_provider::{DBProvider, HashingWriter, StatsReader, StorageReader};
+use reth_provider::{DBProvider, HashingWriter, StageCheckpointReader, StatsReader, StorageReader};
 use reth_stages_api::{
     EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
     StorageHashingCheckpoint, UnwindInput, UnwindOutput,
@@ -22,6 +22,8 @@ use std::{
 };
 use tracing::*;
 
+use super::is_snap_synced;
+
 /// Maximum number of channels that can exist in memory.
 const MAXIMUM_CHANNELS: usize = 10_000;
 
@@ -64,7 +66,11 @@ impl Default for StorageHashingStage {
 
 impl<Provider> Stage<Provider> for StorageHashingStage
 where
-    Provider: DBProvider<Tx: DbTxMut> + StorageReader + HashingWriter + StatsReader,
+    Provider: DBProvider<Tx: DbTxMut>
+        + StorageReader
+        + HashingWriter
+        + StatsReader
+        + StageCheckpointReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -78,6 +84,11 @@ where
             return Ok(ExecOutput::done(input.checkpoint()))
         }
 
+        // The hashed state of a snap synced node is written by the execution stage.
+        if is_snap_synced(provider)? {
+            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+
         let (from_block, to_block) = input.next_block_range().into_inner();
 
         // if there are more blocks then threshold it is faster to go over Plain state and hash all
@@ -90,7 +101,9 @@ where
 
             let mut storage_cursor = tx.cursor_read::<tables::PlainStorageState>()?;
             let mut collector =
-                Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+                Collector::new(self.etl_config.file_size, self.etl_config.dir.clone())
+                    .with_compression_level(self.etl_config.compression_level)
+                    .with_max_disk_usage(self.etl_config.max_disk_usage);
             let mut channels = Vec::with_capacity(MAXIMUM_CHANNELS);
 
             for chunk in &storage_cursor.walk(None)?.chunks(WORKER_CHUNK_SIZE) {
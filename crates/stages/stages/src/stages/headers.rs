@@ -79,8 +79,12 @@ where
             downloader,
             tip,
             sync_gap: None,
-            hash_collector: Collector::new(etl_config.file_size / 2, etl_config.dir.clone()),
-            header_collector: Collector::new(etl_config.file_size / 2, etl_config.dir),
+            hash_collector: Collector::new(etl_config.file_size / 2, etl_config.dir.clone())
+                .with_compression_level(etl_config.compression_level)
+                .with_max_disk_usage(etl_config.max_disk_usage.map(|max| max / 2)),
+            header_collector: Collector::new(etl_config.file_size / 2, etl_config.dir)
+                .with_compression_level(etl_config.compression_level)
+                .with_max_disk_usage(etl_config.max_disk_usage.map(|max| max / 2)),
             is_etl_ready: false,
         }
     }
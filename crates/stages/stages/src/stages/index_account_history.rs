@@ -2,11 +2,15 @@ use super::{collect_history_indices, load_history_indices};
 use alloy_primitives::Address;
 use reth_config::config::{EtlConfig, IndexHistoryConfig};
 use reth_db_api::{models::ShardedKey, table::Decode, tables, transaction::DbTxMut};
-use reth_provider::{DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter};
+use reth_provider::{
+    ChangeSetReader, DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+    StaticFileProviderFactory,
+};
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
 use reth_stages_api::{
     ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
+use reth_static_file_types::StaticFileSegment;
 use std::fmt::Debug;
 use tracing::info;
 
@@ -43,8 +47,12 @@ impl Default for IndexAccountHistoryStage {
 
 impl<Provider> Stage<Provider> for IndexAccountHistoryStage
 where
-    Provider:
-        DBProvider<Tx: DbTxMut> + HistoryWriter + PruneCheckpointReader + PruneCheckpointWriter,
+    Provider: DBProvider<Tx: DbTxMut>
+        + HistoryWriter
+        + PruneCheckpointReader
+        + PruneCheckpointWriter
+        + ChangeSetReader
+        + StaticFileProviderFactory,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -106,6 +114,15 @@ where
             collect_history_indices::<_, tables::AccountChangeSets, tables::AccountsHistory, _>(
                 provider,
                 range.clone(),
+                StaticFileSegment::AccountChangeSets,
+                |block| {
+                    Ok(provider
+                        .account_block_changeset(block)?
+                        .into_iter()
+                        .map(|account_before| (block, account_before))
+                        .collect())
+                },
+                |block| block,
                 ShardedKey::new,
                 |(index, value)| (index, value.address),
                 &self.etl_config,
@@ -153,7 +170,7 @@ mod tests {
         cursor::DbCursorRO,
         models::{
             sharded_key, sharded_key::NUM_OF_INDICES_IN_SHARD, AccountBeforeTx,
-            StoredBlockBodyIndices,
+            StaticFileAccountChangeSet, StoredBlockBodyIndices,
         },
         transaction::DbTx,
         BlockNumberList,
@@ -260,6 +277,44 @@ mod tests {
         assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0])]));
     }
 
+    #[tokio::test]
+    async fn insert_index_from_static_file_changesets() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup
+        partial_setup(&db);
+
+        // move the changesets of the first blocks to static files
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.get_writer(0, StaticFileSegment::AccountChangeSets).unwrap();
+        for block in 0..=1 {
+            writer
+                .append_account_change_set(
+                    &StaticFileAccountChangeSet { changes: vec![acc()] },
+                    block,
+                )
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        db.commit(|tx| {
+            for block in 0..=1 {
+                tx.delete::<tables::AccountChangeSets>(block, None)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        // run
+        run(&db, 3, None);
+
+        // verify
+        let table = cast(db.table::<tables::AccountsHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1, 2, 3])]));
+    }
+
     #[tokio::test]
     async fn insert_index_to_not_empty_shard() {
         // init
@@ -0,0 +1,363 @@
+use super::load_history_indices;
+use alloy_primitives::Address;
+use reth_config::config::{EtlConfig, IndexAddressTransactionsConfig};
+use reth_db_api::{
+    models::ShardedKey, table::Decode, tables, transaction::DbTxMut, BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_provider::{
+    address_transactions_by_tx_range, BlockReader, DBProvider, HistoryWriter,
+    PruneCheckpointReader, PruneCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_storage_errors::provider::ProviderError;
+use tracing::info;
+
+/// The address transactions index stage.
+///
+/// This stage walks over existing transactions and indexes, for every address that took part in a
+/// transaction, the number of that transaction. An address takes part in a transaction if it's the
+/// sender, the recipient or the emitter of one of its logs. For more information on index sharding
+/// take a look at [`tables::AddressTransactions`].
+///
+/// The stage is optional and only added to the pipeline if enabled in
+/// [`IndexAddressTransactionsConfig`].
+#[derive(Debug, Clone)]
+pub struct IndexAddressTransactionsStage {
+    /// The maximum number of transactions to process before pushing the indices to
+    /// [`reth_etl::Collector`].
+    chunk_size: u64,
+    etl_config: EtlConfig,
+    prune_mode: Option<PruneMode>,
+}
+
+impl Default for IndexAddressTransactionsStage {
+    fn default() -> Self {
+        Self { chunk_size: 5_000_000, etl_config: EtlConfig::default(), prune_mode: None }
+    }
+}
+
+impl IndexAddressTransactionsStage {
+    /// Create new instance of [`IndexAddressTransactionsStage`].
+    pub const fn new(
+        config: IndexAddressTransactionsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { chunk_size: config.chunk_size, etl_config, prune_mode }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexAddressTransactionsStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader
+        + HistoryWriter
+        + PruneCheckpointReader
+        + PruneCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AddressTransactions,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::AddressTransactions)?.is_none() {
+                    let target_prunable_tx_number = provider
+                        .block_body_indices(target_prunable_block)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(target_prunable_block))?
+                        .last_tx_num();
+
+                    provider.save_prune_checkpoint(
+                        PruneSegment::AddressTransactions,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: Some(target_prunable_tx_number),
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we clear the table since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::AddressTransactions>()?;
+        }
+
+        let mut collector = Collector::new(self.etl_config.file_size, self.etl_config.dir.clone())
+            .with_compression_level(self.etl_config.compression_level)
+            .with_max_disk_usage(self.etl_config.max_disk_usage);
+
+        info!(
+            target: "sync::stages::index_address_transactions::exec",
+            ?first_sync,
+            block_range = ?input.next_block()..=input.target(),
+            "Collecting indices"
+        );
+
+        loop {
+            let (tx_range, block_range, is_final_range) =
+                input.next_block_range_with_transaction_threshold(provider, self.chunk_size)?;
+
+            info!(target: "sync::stages::index_address_transactions::exec", ?tx_range, "Collecting address transactions");
+
+            for (address, indices) in address_transactions_by_tx_range(provider, tx_range)? {
+                let last = *indices.last().expect("not empty");
+                collector.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(indices),
+                )?;
+            }
+
+            input.checkpoint = Some(StageCheckpoint::new(*block_range.end()));
+
+            // A chunk can end at the target without being the final range, when the transaction
+            // at the threshold belongs to the target block
+            if is_final_range || input.target_reached() {
+                break
+            }
+        }
+
+        info!(target: "sync::stages::index_address_transactions::exec", "Loading indices into database");
+        load_history_indices::<_, tables::AddressTransactions, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(input.target()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let end = input.checkpoint.block_number;
+        let next_tx_num = provider
+            .block_body_indices(end)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(end))?
+            .next_tx_num();
+
+        // The chunk size is a number of transactions, so the blocks to unwind in one go are
+        // found from the block of the transaction at the threshold, which is unwound entirely
+        let mut start = input.unwind_to + 1;
+        if let Some(threshold) = next_tx_num.checked_sub(self.chunk_size) {
+            if let Some(block) = provider.transaction_block(threshold)? {
+                start = start.max(block.min(end));
+            }
+        }
+        let unwind_to = start - 1;
+
+        let first_tx_num = provider
+            .block_body_indices(start)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(start))?
+            .first_tx_num();
+
+        let addresses = address_transactions_by_tx_range(provider, first_tx_num..next_tx_num)?
+            .into_iter()
+            .map(|(address, indices)| (address, indices[0]));
+        provider.unwind_address_transactions_indices(addresses)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::{BlockNumber, TxNumber};
+    use reth_ethereum_primitives::{Receipt, TransactionSigned};
+    use reth_primitives_traits::SignedTransaction;
+    use reth_provider::{
+        insert_transaction_addresses, transaction_addresses, BlockBodyIndicesProvider,
+        DatabaseProviderFactory,
+    };
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_receipt, BlockRangeParams,
+    };
+    use std::{
+        collections::BTreeMap,
+        ops::{Range, RangeInclusive},
+    };
+
+    /// Inserts random blocks with their receipts and returns every transaction with its sender and
+    /// receipt.
+    fn setup(
+        db: &TestStageDB,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> Vec<(Address, TransactionSigned, Receipt)> {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            block_range,
+            BlockRangeParams { parent: None, tx_count: 0..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        let transactions = blocks
+            .iter()
+            .flat_map(|block| block.body().transactions.iter().cloned())
+            .map(|transaction| {
+                let receipt = random_receipt(&mut rng, &transaction, Some(2));
+                (transaction.try_recover().unwrap(), transaction, receipt)
+            })
+            .collect::<Vec<_>>();
+        db.insert_receipts(
+            transactions
+                .iter()
+                .enumerate()
+                .map(|(tx_number, (_, _, receipt))| (tx_number as TxNumber, receipt.clone())),
+        )
+        .unwrap();
+
+        transactions
+    }
+
+    /// Builds the expected index of transactions within the range, ignoring the receipts of
+    /// transactions below `pruned_receipts`.
+    fn expected(
+        transactions: &[(Address, TransactionSigned, Receipt)],
+        tx_range: Range<TxNumber>,
+        pruned_receipts: TxNumber,
+    ) -> BTreeMap<Address, Vec<TxNumber>> {
+        let mut expected = BTreeMap::<Address, Vec<TxNumber>>::new();
+        for tx_number in tx_range {
+            let (sender, transaction, receipt) = &transactions[tx_number as usize];
+            let receipt = (tx_number >= pruned_receipts).then_some(receipt);
+            insert_transaction_addresses(
+                &mut expected,
+                tx_number,
+                transaction_addresses(*sender, transaction, receipt),
+            );
+        }
+        expected
+    }
+
+    fn table(db: &TestStageDB) -> BTreeMap<Address, Vec<TxNumber>> {
+        let mut table = BTreeMap::<Address, Vec<TxNumber>>::new();
+        for (key, list) in db.table::<tables::AddressTransactions>().unwrap() {
+            table.entry(key.key).or_default().extend(list.iter());
+        }
+        table
+    }
+
+    fn next_tx_num(db: &TestStageDB, block: BlockNumber) -> TxNumber {
+        db.factory.provider().unwrap().block_body_indices(block).unwrap().unwrap().next_tx_num()
+    }
+
+    fn run(db: &TestStageDB, stage: &mut IndexAddressTransactionsStage, from: u64, to: u64) {
+        let input = ExecInput { target: Some(to), checkpoint: Some(StageCheckpoint::new(from)) };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(to), done: true });
+        provider.commit().unwrap();
+    }
+
+    /// Unwinds like the pipeline does, and returns the number of unwind calls.
+    fn unwind(
+        db: &TestStageDB,
+        stage: &mut IndexAddressTransactionsStage,
+        from: u64,
+        to: u64,
+    ) -> usize {
+        let mut checkpoint = StageCheckpoint::new(from);
+        let mut calls = 0;
+        while checkpoint.block_number > to {
+            let input = UnwindInput { checkpoint, unwind_to: to, bad_block: None };
+            let provider = db.factory.database_provider_rw().unwrap();
+            let out = stage.unwind(&provider, input).unwrap();
+            assert!(out.checkpoint.block_number < checkpoint.block_number);
+            assert!(out.checkpoint.block_number >= to);
+            checkpoint = out.checkpoint;
+            provider.commit().unwrap();
+            calls += 1;
+        }
+        calls
+    }
+
+    #[tokio::test]
+    async fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let transactions = setup(&db, 0..=100);
+        let mut stage = IndexAddressTransactionsStage { chunk_size: 10, ..Default::default() };
+
+        // Genesis transactions are never indexed.
+        let first_tx_num = next_tx_num(&db, 0);
+
+        // Index the first half on first sync and the rest incrementally.
+        run(&db, &mut stage, 0, 50);
+        assert_eq!(table(&db), expected(&transactions, first_tx_num..next_tx_num(&db, 50), 0));
+        run(&db, &mut stage, 50, 100);
+        assert_eq!(table(&db), expected(&transactions, first_tx_num..next_tx_num(&db, 100), 0));
+
+        // Unwind back to the first half in chunks of ten transactions, plus the rest of the block
+        // at the threshold, which has at most two transactions.
+        let unwound = next_tx_num(&db, 100) - next_tx_num(&db, 50);
+        assert!(unwind(&db, &mut stage, 100, 50) as u64 >= unwound.div_ceil(12));
+        assert_eq!(table(&db), expected(&transactions, first_tx_num..next_tx_num(&db, 50), 0));
+
+        // Unwind everything at once.
+        assert_eq!(unwind(&db, &mut IndexAddressTransactionsStage::default(), 50, 0), 1);
+        assert_eq!(table(&db), BTreeMap::new());
+    }
+
+    #[tokio::test]
+    async fn execute_with_pruned_receipts() {
+        let db = TestStageDB::default();
+        let transactions = setup(&db, 0..=20);
+
+        // Only the sender and the recipient are indexed for transactions without receipts.
+        let pruned_receipts = next_tx_num(&db, 5);
+        db.commit(|tx| {
+            for tx_number in 0..pruned_receipts {
+                tx.delete::<tables::Receipts>(tx_number, None)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        run(&db, &mut IndexAddressTransactionsStage::default(), 0, 20);
+        assert_eq!(
+            table(&db),
+            expected(&transactions, next_tx_num(&db, 0)..next_tx_num(&db, 20), pruned_receipts)
+        );
+    }
+}
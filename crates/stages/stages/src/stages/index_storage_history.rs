@@ -1,5 +1,6 @@
 use super::{collect_history_indices, load_history_indices};
 use crate::{StageCheckpoint, StageId};
+use alloy_primitives::Address;
 use reth_config::config::{EtlConfig, IndexHistoryConfig};
 use reth_db_api::{
     models::{storage_sharded_key::StorageShardedKey, AddressStorageKey, BlockNumberAddress},
@@ -7,9 +8,13 @@ use reth_db_api::{
     tables,
     transaction::DbTxMut,
 };
-use reth_provider::{DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter};
+use reth_provider::{
+    DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+    StaticFileProviderFactory, StorageChangeSetReader,
+};
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
 use reth_stages_api::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
+use reth_static_file_types::StaticFileSegment;
 use std::fmt::Debug;
 use tracing::info;
 
@@ -46,8 +51,12 @@ impl Default for IndexStorageHistoryStage {
 
 impl<Provider> Stage<Provider> for IndexStorageHistoryStage
 where
-    Provider:
-        DBProvider<Tx: DbTxMut> + PruneCheckpointWriter + HistoryWriter + PruneCheckpointReader,
+    Provider: DBProvider<Tx: DbTxMut>
+        + PruneCheckpointWriter
+        + HistoryWriter
+        + PruneCheckpointReader
+        + StorageChangeSetReader
+        + StaticFileProviderFactory,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -108,7 +117,10 @@ where
         let collector =
             collect_history_indices::<_, tables::StorageChangeSets, tables::StoragesHistory, _>(
                 provider,
-                BlockNumberAddress::range(range.clone()),
+                range.clone(),
+                StaticFileSegment::StorageChangeSets,
+                |block| provider.storage_changeset(block),
+                |block| BlockNumberAddress((block, Address::ZERO)),
                 |AddressStorageKey((address, storage_key)), highest_block_number| {
                     StorageShardedKey::new(address, storage_key, highest_block_number)
                 },
@@ -159,7 +171,7 @@ mod tests {
         cursor::DbCursorRO,
         models::{
             sharded_key, storage_sharded_key::NUM_OF_INDICES_IN_SHARD, ShardedKey,
-            StoredBlockBodyIndices,
+            StaticFileStorageChangeSet, StorageBeforeTx, StoredBlockBodyIndices,
         },
         transaction::DbTx,
         BlockNumberList,
@@ -280,6 +292,49 @@ mod tests {
         assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0])]));
     }
 
+    #[tokio::test]
+    async fn insert_index_from_static_file_changesets() {
+        // init
+        let db = TestStageDB::default();
+
+        // setup
+        partial_setup(&db);
+
+        // move the changesets of the first blocks to static files
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.get_writer(0, StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=1 {
+            writer
+                .append_storage_change_set(
+                    &StaticFileStorageChangeSet {
+                        changes: vec![StorageBeforeTx {
+                            address: ADDRESS,
+                            entry: storage(STORAGE_KEY),
+                        }],
+                    },
+                    block,
+                )
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        db.commit(|tx| {
+            for block in 0..=1 {
+                tx.delete::<tables::StorageChangeSets>(block_number_address(block), None)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        // run
+        run(&db, 3, None);
+
+        // verify
+        let table = cast(db.table::<tables::StoragesHistory>().unwrap());
+        assert_eq!(table, BTreeMap::from([(shard(u64::MAX), vec![0, 1, 2, 3])]));
+    }
+
     #[tokio::test]
     async fn insert_index_to_not_empty_shard() {
         // init
@@ -11,8 +11,8 @@ use reth_db_api::{
 };
 use reth_primitives_traits::{GotExpected, NodePrimitives, SignedTransaction};
 use reth_provider::{
-    BlockReader, DBProvider, HeaderProvider, ProviderError, PruneCheckpointReader,
-    StaticFileProviderFactory, StatsReader,
+    providers::StaticFileWriter, BlockReader, DBProvider, HeaderProvider, ProviderError,
+    PruneCheckpointReader, StaticFileProviderFactory, StatsReader,
 };
 use reth_prune_types::PruneSegment;
 use reth_stages_api::{
@@ -37,7 +37,8 @@ type RecoveryResultSender = mpsc::Sender<Result<(u64, Address), Box<SenderRecove
 
 /// The sender recovery stage iterates over existing transactions,
 /// recovers the transaction signer and stores them
-/// in [`TransactionSenders`][reth_db_api::tables::TransactionSenders] table.
+/// in [`TransactionSenders`][reth_db_api::tables::TransactionSenders] table, or in the
+/// [`StaticFileSegment::Senders`] static files if senders are not pruned.
 #[derive(Clone, Debug)]
 pub struct SenderRecoveryStage {
     /// The size of inserted items after which the control
@@ -84,8 +85,30 @@ where
             input.next_block_range_with_transaction_threshold(provider, self.commit_threshold)?;
         let end_block = *block_range.end();
 
+        // Senders that are never pruned are appended to static files, as long as the segment
+        // continues at this block range. Otherwise, they are written to the database and later
+        // moved to static files by the `StaticFileProducer`.
+        let static_file_provider = provider.static_file_provider();
+        let mut static_file_writer = None;
+        if provider.prune_modes_ref().sender_recovery.is_none() &&
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::Senders)
+                .is_some()
+        {
+            let writer = static_file_provider.latest_writer(StaticFileSegment::Senders)?;
+            if writer.next_block_number() == *block_range.start() {
+                static_file_writer = Some(writer);
+            }
+        }
+
         // No transactions to walk over
         if tx_range.is_empty() {
+            if let Some(writer) = static_file_writer.as_mut() {
+                for block in block_range {
+                    writer.increment_block(block)?;
+                }
+            }
+
             info!(target: "sync::stages::sender_recovery", ?tx_range, "Target transaction already reached");
             return Ok(ExecOutput {
                 checkpoint: StageCheckpoint::new(end_block)
@@ -94,9 +117,6 @@ where
             })
         }
 
-        // Acquire the cursor for inserting elements
-        let mut senders_cursor = provider.tx_ref().cursor_write::<tables::TransactionSenders>()?;
-
         info!(target: "sync::stages::sender_recovery", ?tx_range, "Recovering senders");
 
         // Iterate over transactions in batches, recover the senders and append them
@@ -108,8 +128,41 @@ where
 
         let tx_batch_sender = setup_range_recovery(provider);
 
-        for range in batch {
-            recover_range(range, provider, tx_batch_sender.clone(), &mut senders_cursor)?;
+        if let Some(mut writer) = static_file_writer {
+            let mut blocks =
+                block_range.clone().zip(provider.block_body_indices_range(block_range)?).peekable();
+
+            let mut append_sender = |tx_id: TxNumber, sender: Address| {
+                // Move the static file to the block of this transaction, including any empty
+                // blocks before it.
+                while let Some((block, _)) =
+                    blocks.next_if(|(_, indices)| indices.first_tx_num <= tx_id)
+                {
+                    writer.increment_block(block)?;
+                }
+                writer.append_sender(tx_id, &sender)?;
+                Ok(())
+            };
+            for range in batch {
+                recover_range(range, provider, tx_batch_sender.clone(), &mut append_sender)?;
+            }
+
+            // Trailing empty blocks
+            for (block, _) in blocks {
+                writer.increment_block(block)?;
+            }
+        } else {
+            // Acquire the cursor for inserting elements
+            let mut senders_cursor =
+                provider.tx_ref().cursor_write::<tables::TransactionSenders>()?;
+
+            let mut append_sender = |tx_id: TxNumber, sender: Address| {
+                senders_cursor.append(tx_id, &sender)?;
+                Ok(())
+            };
+            for range in batch {
+                recover_range(range, provider, tx_batch_sender.clone(), &mut append_sender)?;
+            }
         }
 
         Ok(ExecOutput {
@@ -134,6 +187,20 @@ where
             .last_tx_num();
         provider.tx_ref().unwind_table_by_num::<tables::TransactionSenders>(latest_tx_id)?;
 
+        let static_file_provider = provider.static_file_provider();
+        if static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::Senders)
+            .is_some_and(|highest| highest > unwind_to)
+        {
+            let to_delete = static_file_provider
+                .get_highest_static_file_tx(StaticFileSegment::Senders)
+                .map(|highest_tx| highest_tx.saturating_sub(latest_tx_id))
+                .unwrap_or_default();
+            static_file_provider
+                .latest_writer(StaticFileSegment::Senders)?
+                .prune_senders(to_delete, unwind_to)?;
+        }
+
         Ok(UnwindOutput {
             checkpoint: StageCheckpoint::new(unwind_to)
                 .with_entities_stage_checkpoint(stage_checkpoint(provider)?),
@@ -141,15 +208,15 @@ where
     }
 }
 
-fn recover_range<Provider, CURSOR>(
+fn recover_range<Provider, F>(
     tx_range: Range<u64>,
     provider: &Provider,
     tx_batch_sender: mpsc::Sender<Vec<(Range<u64>, RecoveryResultSender)>>,
-    senders_cursor: &mut CURSOR,
+    append_sender: &mut F,
 ) -> Result<(), StageError>
 where
     Provider: DBProvider + HeaderProvider + StaticFileProviderFactory,
-    CURSOR: DbCursorRW<tables::TransactionSenders>,
+    F: FnMut(TxNumber, Address) -> Result<(), StageError>,
 {
     debug!(target: "sync::stages::sender_recovery", ?tx_range, "Sending batch for processing");
 
@@ -169,7 +236,7 @@ where
         return Err(StageError::Fatal(err.into()));
     }
 
-    debug!(target: "sync::stages::sender_recovery", ?tx_range, "Appending recovered senders to the database");
+    debug!(target: "sync::stages::sender_recovery", ?tx_range, "Appending recovered senders");
 
     let mut processed_transactions = 0;
     for channel in receivers {
@@ -209,7 +276,7 @@ where
                     }
                 }
             };
-            senders_cursor.append(tx_id, &sender)?;
+            append_sender(tx_id, sender)?;
             processed_transactions += 1;
         }
     }
@@ -324,17 +391,21 @@ fn recover_sender<T: SignedTransaction>(
 
 fn stage_checkpoint<Provider>(provider: &Provider) -> Result<EntitiesCheckpoint, StageError>
 where
-    Provider: StatsReader + StaticFileProviderFactory + PruneCheckpointReader,
+    Provider: DBProvider + StaticFileProviderFactory + PruneCheckpointReader,
 {
     let pruned_entries = provider
         .get_prune_checkpoint(PruneSegment::SenderRecovery)?
         .and_then(|checkpoint| checkpoint.tx_number)
         .unwrap_or_default();
+    let static_file_entries =
+        provider.static_file_provider().count_entries::<tables::TransactionSenders>()? as u64;
     Ok(EntitiesCheckpoint {
         // If `TransactionSenders` table was pruned, we will have a number of entries in it not
         // matching the actual number of processed transactions. To fix that, we add the
-        // number of pruned `TransactionSenders` entries.
-        processed: provider.count_entries::<tables::TransactionSenders>()? as u64 + pruned_entries,
+        // number of pruned `TransactionSenders` entries. Entries moved to static files are
+        // pruned from the database as well, so only the larger of both is added.
+        processed: provider.tx_ref().entries::<tables::TransactionSenders>()? as u64 +
+            pruned_entries.max(static_file_entries),
         // Count only static files entries. If we count the database entries too, we may have
         // duplicates. We're sure that the static files have all entries that database has,
         // because we run the `StaticFileProducer` before starting the pipeline.
@@ -520,6 +591,96 @@ mod tests {
         assert!(runner.validate_execution(first_input, result.ok()).is_ok(), "validation failed");
     }
 
+    /// Execute and unwind the stage with senders kept in static files
+    #[test]
+    fn execute_and_unwind_static_files() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=100,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..4, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("insert blocks");
+
+        let mut expected_senders = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body().transactions {
+                expected_senders.push(transaction.recover_signer().expect("recover signer"));
+            }
+        }
+
+        // Senders of the genesis block are already in static files
+        {
+            let static_file_provider = db.factory.static_file_provider();
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::Senders).unwrap();
+            writer.increment_block(0).unwrap();
+            for (tx_num, sender) in
+                expected_senders.iter().take(blocks[0].transaction_count()).enumerate()
+            {
+                writer.append_sender(tx_num as u64, sender).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output = SenderRecoveryStage::default()
+            .execute(
+                &provider,
+                ExecInput { target: Some(100), checkpoint: Some(StageCheckpoint::new(0)) },
+            )
+            .unwrap();
+        assert!(output.done);
+        provider.static_file_provider().commit().unwrap();
+        provider.commit().unwrap();
+
+        assert!(db.table::<tables::TransactionSenders>().unwrap().is_empty());
+        let static_file_provider = db.factory.static_file_provider();
+        assert_eq!(
+            static_file_provider.get_highest_static_file_block(StaticFileSegment::Senders),
+            Some(100)
+        );
+        let provider = db.factory.provider().unwrap();
+        for (tx_num, sender) in expected_senders.iter().enumerate() {
+            assert_eq!(provider.transaction_sender(tx_num as u64).unwrap(), Some(*sender));
+        }
+        assert_eq!(
+            provider.senders_by_tx_range(0..expected_senders.len() as u64).unwrap(),
+            expected_senders
+        );
+        drop(provider);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        SenderRecoveryStage::default()
+            .unwind(
+                &provider,
+                UnwindInput {
+                    checkpoint: StageCheckpoint::new(100),
+                    unwind_to: 50,
+                    bad_block: None,
+                },
+            )
+            .unwrap();
+        provider.static_file_provider().commit().unwrap();
+        provider.commit().unwrap();
+
+        let last_tx_num = blocks[..=50]
+            .iter()
+            .map(|block| block.transaction_count() as u64)
+            .sum::<u64>()
+            .checked_sub(1);
+        assert_eq!(
+            static_file_provider.get_highest_static_file_block(StaticFileSegment::Senders),
+            Some(50)
+        );
+        assert_eq!(
+            static_file_provider.get_highest_static_file_tx(StaticFileSegment::Senders),
+            last_tx_num
+        );
+    }
+
     #[test]
     fn stage_checkpoint_pruned() {
         let db = TestStageDB::default();
@@ -118,7 +118,9 @@ where
 
         // 500MB temporary files
         let mut hash_collector: Collector<TxHash, TxNumber> =
-            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone())
+                .with_compression_level(self.etl_config.compression_level)
+                .with_max_disk_usage(self.etl_config.max_disk_usage);
 
         info!(
             target: "sync::stages::transaction_lookup",
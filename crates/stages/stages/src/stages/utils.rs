@@ -10,12 +10,12 @@ use reth_db_api::{
 };
 use reth_etl::Collector;
 use reth_provider::{
-    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError,
+    providers::StaticFileProvider, BlockReader, DBProvider, ProviderError, StageCheckpointReader,
     StaticFileProviderFactory,
 };
-use reth_stages_api::StageError;
+use reth_stages_api::{StageError, StageId};
 use reth_static_file_types::StaticFileSegment;
-use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
+use std::{collections::HashMap, hash::Hash, ops::RangeInclusive};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
@@ -38,22 +38,45 @@ const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 ///
 /// As a result, the `Collector` will contain entries such as `(Address1.3, [1,2,3])` and
 /// `(Address1.300, [100,300])`. The entries may be stored across one or more files.
+///
+/// The changesets of the blocks that were already moved to the static file `segment` are read
+/// block by block with `static_file_changeset`, the changesets of the later blocks are walked from
+/// the `CS` table starting at the key returned by `block_key`.
+#[expect(clippy::too_many_arguments)]
 pub(crate) fn collect_history_indices<Provider, CS, H, P>(
     provider: &Provider,
-    range: impl RangeBounds<CS::Key>,
+    range: RangeInclusive<BlockNumber>,
+    segment: StaticFileSegment,
+    static_file_changeset: impl Fn(BlockNumber) -> Result<Vec<(CS::Key, CS::Value)>, ProviderError>,
+    block_key: impl Fn(BlockNumber) -> CS::Key,
     sharded_key_factory: impl Fn(P, BlockNumber) -> H::Key,
     partial_key_factory: impl Fn((CS::Key, CS::Value)) -> (u64, P),
     etl_config: &EtlConfig,
 ) -> Result<Collector<H::Key, H::Value>, StageError>
 where
-    Provider: DBProvider,
+    Provider: DBProvider + StaticFileProviderFactory,
     CS: Table,
     H: Table<Value = BlockNumberList>,
     P: Copy + Eq + Hash,
 {
+    let (start, end) = range.into_inner();
+    let db_start = provider
+        .static_file_provider()
+        .get_highest_static_file_block(segment)
+        .map_or(start, |highest| start.max(highest + 1));
+    let static_file_changesets =
+        (start..db_start.min(end + 1)).flat_map(|block| match static_file_changeset(block) {
+            Ok(changeset) => changeset.into_iter().map(Ok).collect(),
+            Err(err) => vec![Err(err)],
+        });
     let mut changeset_cursor = provider.tx_ref().cursor_read::<CS>()?;
+    let database_changesets = changeset_cursor
+        .walk(Some(block_key(db_start)))?
+        .map(|entry| entry.map_err(ProviderError::from));
 
-    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone())
+        .with_compression_level(etl_config.compression_level)
+        .with_max_disk_usage(etl_config.max_disk_usage);
     let mut cache: HashMap<P, Vec<u64>> = HashMap::default();
 
     let mut collect = |cache: &HashMap<P, Vec<u64>>| {
@@ -73,8 +96,11 @@ where
 
     let mut flush_counter = 0;
     let mut current_block_number = u64::MAX;
-    for (idx, entry) in changeset_cursor.walk_range(range)?.enumerate() {
+    for (idx, entry) in static_file_changesets.chain(database_changesets).enumerate() {
         let (block_number, key) = partial_key_factory(entry?);
+        if block_number > end {
+            break
+        }
         cache.entry(key).or_default().push(block_number);
 
         if idx > 0 && idx % interval == 0 && total_changesets > 1000 {
@@ -283,3 +309,13 @@ where
         segment,
     })
 }
+
+/// Returns `true` if the state was downloaded by the `SnapSync` stage.
+pub(crate) fn is_snap_synced<Provider: StageCheckpointReader>(
+    provider: &Provider,
+) -> Result<bool, StageError> {
+    Ok(provider
+        .get_stage_checkpoint(StageId::SnapSync)?
+        .and_then(|checkpoint| checkpoint.snap_sync_pivot())
+        .is_some())
+}
@@ -144,6 +144,21 @@ pub struct IndexHistoryCheckpoint {
     pub progress: EntitiesCheckpoint,
 }
 
+/// Saves the progress of `SnapSync` stage.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "test-utils"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapSyncCheckpoint {
+    /// The block whose state was downloaded last.
+    pub pivot: BlockNumber,
+    /// The next hashed account to download, or `None` once all accounts were downloaded.
+    pub next_account: Option<B256>,
+    /// Progress measured in the covered part of the account key space.
+    pub progress: EntitiesCheckpoint,
+}
+
 /// Saves the progress of abstract stage iterating over or downloading entities.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(any(test, feature = "test-utils"), derive(arbitrary::Arbitrary))]
@@ -267,7 +282,23 @@ impl StageCheckpoint {
             StageUnitCheckpoint::IndexHistory(IndexHistoryCheckpoint {
                 progress: entities,
                 ..
-            }) => Some(entities),
+            }) |
+            StageUnitCheckpoint::SnapSync(SnapSyncCheckpoint { progress: entities, .. }) => {
+                Some(entities)
+            }
+        }
+    }
+
+    /// Returns the block whose state was downloaded by the `SnapSync` stage, if the stage has
+    /// finished downloading it.
+    pub const fn snap_sync_pivot(&self) -> Option<BlockNumber> {
+        match self.stage_checkpoint {
+            Some(StageUnitCheckpoint::SnapSync(SnapSyncCheckpoint { pivot, .. }))
+                if pivot > 0 && self.block_number >= pivot =>
+            {
+                Some(pivot)
+            }
+            _ => None,
         }
     }
 }
@@ -293,6 +324,8 @@ pub enum StageUnitCheckpoint {
     Headers(HeadersCheckpoint),
     /// Saves the progress of Index History stage.
     IndexHistory(IndexHistoryCheckpoint),
+    /// Saves the progress of `SnapSync` stage.
+    SnapSync(SnapSyncCheckpoint),
 }
 
 impl StageUnitCheckpoint {
@@ -401,6 +434,15 @@ stage_unit_checkpoints!(
         index_history_stage_checkpoint,
         /// Sets the stage checkpoint to index history.
         with_index_history_stage_checkpoint
+    ),
+    (
+        6,
+        SnapSync,
+        SnapSyncCheckpoint,
+        /// Returns the snap sync stage checkpoint, if any.
+        snap_sync_stage_checkpoint,
+        /// Sets the stage checkpoint to snap sync.
+        with_snap_sync_stage_checkpoint
     )
 );
 
@@ -429,4 +471,26 @@ mod tests {
         let (decoded, _) = MerkleCheckpoint::from_compact(&buf, encoded);
         assert_eq!(decoded, checkpoint);
     }
+
+    #[test]
+    fn snap_sync_pivot() {
+        let checkpoint = SnapSyncCheckpoint { pivot: 100, ..Default::default() };
+
+        // The download is still in progress
+        assert_eq!(
+            StageCheckpoint::new(0).with_snap_sync_stage_checkpoint(checkpoint).snap_sync_pivot(),
+            None
+        );
+        // The state of the pivot was downloaded and later blocks were executed
+        assert_eq!(
+            StageCheckpoint::new(100).with_snap_sync_stage_checkpoint(checkpoint).snap_sync_pivot(),
+            Some(100)
+        );
+        assert_eq!(
+            StageCheckpoint::new(150).with_snap_sync_stage_checkpoint(checkpoint).snap_sync_pivot(),
+            Some(100)
+        );
+        // The node wasn't snap synced
+        assert_eq!(StageCheckpoint::new(150).snap_sync_pivot(), None);
+    }
 }
@@ -21,6 +21,13 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Optional stage indexing the transactions of every address. It's not part of
+    /// [`StageId::ALL`], because it's only added to the pipeline when enabled.
+    IndexAddressTransactions,
+    /// Optional stage downloading the state of a recent block over the snap protocol instead of
+    /// executing all blocks before it. It's not part of [`StageId::ALL`], because it's only added
+    /// to the pipeline when enabled.
+    SnapSync,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -78,6 +85,8 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexAddressTransactions => "IndexAddressTransactions",
+            Self::SnapSync => "SnapSync",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -123,6 +132,8 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexAddressTransactions.to_string(), "IndexAddressTransactions");
+        assert_eq!(StageId::SnapSync.to_string(), "SnapSync");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
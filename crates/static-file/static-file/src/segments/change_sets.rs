@@ -0,0 +1,97 @@
+use crate::segments::Segment;
+use alloy_primitives::BlockNumber;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::{
+        BlockNumberAddress, StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx,
+    },
+    tables,
+    transaction::DbTx,
+};
+use reth_provider::{providers::StaticFileWriter, DBProvider, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Static File segment responsible for the [`StaticFileSegment::AccountChangeSets`] or
+/// [`StaticFileSegment::StorageChangeSets`] part of data.
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeSets {
+    kind: ChangeSetsKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ChangeSetsKind {
+    Accounts,
+    Storages,
+}
+
+impl ChangeSets {
+    /// Creates the segment moving [`tables::AccountChangeSets`] to static files.
+    pub const fn accounts() -> Self {
+        Self { kind: ChangeSetsKind::Accounts }
+    }
+
+    /// Creates the segment moving [`tables::StorageChangeSets`] to static files.
+    pub const fn storages() -> Self {
+        Self { kind: ChangeSetsKind::Storages }
+    }
+}
+
+impl<Provider: StaticFileProviderFactory + DBProvider> Segment<Provider> for ChangeSets {
+    fn segment(&self) -> StaticFileSegment {
+        match self.kind {
+            ChangeSetsKind::Accounts => StaticFileSegment::AccountChangeSets,
+            ChangeSetsKind::Storages => StaticFileSegment::StorageChangeSets,
+        }
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: Provider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let static_file_provider = provider.static_file_provider();
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), <Self as Segment<Provider>>::segment(self))?;
+
+        match self.kind {
+            ChangeSetsKind::Accounts => {
+                let mut cursor =
+                    provider.tx_ref().cursor_dup_read::<tables::AccountChangeSets>()?;
+                for block in block_range {
+                    let changes = cursor
+                        .walk_dup(Some(block), None)?
+                        .map(|result| result.map(|(_, account_before)| account_before))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    static_file_writer.append_account_change_set(
+                        &StaticFileAccountChangeSet { changes },
+                        block,
+                    )?;
+                }
+            }
+            ChangeSetsKind::Storages => {
+                let mut cursor = provider.tx_ref().cursor_read::<tables::StorageChangeSets>()?;
+                for block in block_range {
+                    let changes = cursor
+                        .walk_range(BlockNumberAddress::range(block..=block))?
+                        .map(|result| {
+                            result.map(|(block_address, entry)| StorageBeforeTx {
+                                address: block_address.address(),
+                                entry,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    static_file_writer.append_storage_change_set(
+                        &StaticFileStorageChangeSet { changes },
+                        block,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
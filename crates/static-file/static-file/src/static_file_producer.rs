@@ -96,7 +96,8 @@ where
                     Receipt: Value + Compact,
                 >,
             > + StageCheckpointReader
-                          + BlockReader,
+                          + BlockReader
+                          + ChainStateBlockReader,
         >,
 {
     /// Listen for events on the `static_file_producer`.
@@ -140,6 +141,15 @@ where
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.senders.clone() {
+            segments.push((Box::new(segments::Senders), block_range));
+        }
+        if let Some(block_range) = targets.account_change_sets.clone() {
+            segments.push((Box::new(segments::ChangeSets::accounts()), block_range));
+        }
+        if let Some(block_range) = targets.storage_change_sets.clone() {
+            segments.push((Box::new(segments::ChangeSets::storages()), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -175,21 +185,42 @@ where
     /// Copies data from database to static files according to
     /// [stage checkpoints](reth_stages_types::StageCheckpoint).
     ///
-    /// Returns highest block numbers for all static file segments.
+    /// Account and storage changesets are only copied up to the last finalized block, since
+    /// unwinding them from static files is not supported.
+    ///
+    /// Returns highest block numbers for all static file segments, except the changesets ones: the
+    /// database changesets are pruned separately and shouldn't hold back the pruning of other
+    /// segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
         let provider = self.provider.database_provider_ro()?;
-        let stages_checkpoints = [StageId::Headers, StageId::Execution, StageId::Bodies]
-            .into_iter()
-            .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
-            .collect::<Result<Vec<_>, _>>()?;
+        let stages_checkpoints = [
+            StageId::Headers,
+            StageId::Execution,
+            StageId::Bodies,
+            StageId::SenderRecovery,
+            StageId::Finish,
+        ]
+        .into_iter()
+        .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
+        .collect::<Result<Vec<_>, _>>()?;
+        let change_sets_tip = stages_checkpoints[4]
+            .zip(provider.last_finalized_block_number()?)
+            .map(|(finished, finalized)| finished.min(finalized));
 
         let highest_static_files = HighestStaticFiles {
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
             block_meta: stages_checkpoints[2],
+            senders: stages_checkpoints[3],
+            account_change_sets: None,
+            storage_change_sets: None,
         };
-        let targets = self.get_static_file_targets(highest_static_files)?;
+        let targets = self.get_static_file_targets(HighestStaticFiles {
+            account_change_sets: change_sets_tip,
+            storage_change_sets: change_sets_tip,
+            ..highest_static_files
+        })?;
         self.run(targets)?;
 
         Ok(highest_static_files)
@@ -230,6 +261,43 @@ where
             block_meta: finalized_block_numbers.block_meta.and_then(|finalized_block_number| {
                 self.get_static_file_target(highest_static_files.block_meta, finalized_block_number)
             }),
+            // StaticFile senders only if they're not pruned according to the user configuration
+            senders: if self.prune_modes.sender_recovery.is_none() {
+                finalized_block_numbers.senders.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.senders,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
+            // StaticFile changesets only if the history is not pruned according to the user
+            // configuration
+            account_change_sets: if self.prune_modes.account_history.is_none() &&
+                self.prune_modes.storage_history.is_none()
+            {
+                finalized_block_numbers.account_change_sets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.account_change_sets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
+            storage_change_sets: if self.prune_modes.account_history.is_none() &&
+                self.prune_modes.storage_history.is_none()
+            {
+                finalized_block_numbers.storage_change_sets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.storage_change_sets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -261,16 +329,17 @@ mod tests {
     };
     use alloy_primitives::{B256, U256};
     use assert_matches::assert_matches;
-    use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_db_api::{database::Database, tables, transaction::DbTx};
     use reth_provider::{
-        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ProviderError,
-        ProviderFactory, StaticFileProviderFactory,
+        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ChangeSetReader,
+        ProviderError, ProviderFactory, StaticFileProviderFactory, StorageChangeSetReader,
     };
-    use reth_prune_types::PruneModes;
+    use reth_prune_types::{PruneMode, PruneModes};
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_static_file_types::{HighestStaticFiles, StaticFileSegment};
     use reth_testing_utils::generators::{
-        self, random_block_range, random_receipt, BlockRangeParams,
+        self, random_block_range, random_changeset_range, random_eoa_accounts, random_receipt,
+        BlockRangeParams,
     };
     use std::{sync::mpsc::channel, time::Duration};
     use tempfile::TempDir;
@@ -327,6 +396,9 @@ mod tests {
                 receipts: Some(1),
                 transactions: Some(1),
                 block_meta: None,
+                senders: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -335,7 +407,10 @@ mod tests {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
                 transactions: Some(0..=1),
-                block_meta: None
+                block_meta: None,
+                senders: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
@@ -345,7 +420,10 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
-                block_meta: None
+                block_meta: None,
+                senders: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             }
         );
 
@@ -355,6 +433,9 @@ mod tests {
                 receipts: Some(3),
                 transactions: Some(3),
                 block_meta: None,
+                senders: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -363,7 +444,10 @@ mod tests {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
                 transactions: Some(2..=3),
-                block_meta: None
+                block_meta: None,
+                senders: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
@@ -373,7 +457,10 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                senders: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             }
         );
 
@@ -383,6 +470,9 @@ mod tests {
                 receipts: Some(4),
                 transactions: Some(4),
                 block_meta: None,
+                senders: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -391,7 +481,10 @@ mod tests {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
                 transactions: Some(4..=4),
-                block_meta: None
+                block_meta: None,
+                senders: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             }
         );
         assert_matches!(
@@ -404,11 +497,95 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                senders: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             }
         );
     }
 
+    #[test]
+    fn run_change_sets() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=3,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 2);
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(address, account)| (address, (account, Vec::new()))),
+            1..3,
+            1..3,
+        );
+        db.insert_changesets(changesets, None).expect("insert changesets");
+
+        let static_file_producer =
+            StaticFileProducerInner::new(db.factory.clone(), PruneModes::default());
+
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                block_meta: None,
+                senders: None,
+                account_change_sets: Some(3),
+                storage_change_sets: Some(3),
+            })
+            .expect("get static file targets");
+        assert_eq!(targets.account_change_sets, Some(0..=3));
+        assert_eq!(targets.storage_change_sets, Some(0..=3));
+        assert_matches!(static_file_producer.run(targets), Ok(_));
+
+        let static_file_provider = db.factory.static_file_provider();
+        let account_changesets = db.table::<tables::AccountChangeSets>().unwrap();
+        let storage_changesets = db.table::<tables::StorageChangeSets>().unwrap();
+        for block in 0..=3 {
+            assert_eq!(
+                static_file_provider.account_block_changeset(block).unwrap(),
+                account_changesets
+                    .iter()
+                    .filter(|(block_number, _)| *block_number == block)
+                    .map(|(_, account_before)| account_before.clone())
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                static_file_provider.storage_changeset(block).unwrap(),
+                storage_changesets
+                    .iter()
+                    .filter(|(block_address, _)| block_address.block_number() == block)
+                    .copied()
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        // Changesets are not copied if the history is pruned.
+        let static_file_producer = StaticFileProducerInner::new(
+            db.factory,
+            PruneModes { account_history: Some(PruneMode::Full), ..Default::default() },
+        );
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                block_meta: None,
+                senders: None,
+                account_change_sets: Some(4),
+                storage_change_sets: Some(4),
+            })
+            .expect("get static file targets");
+        assert!(!targets.any());
+    }
+
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
     #[test]
     fn only_one() {
@@ -434,6 +611,9 @@ mod tests {
                         receipts: Some(1),
                         transactions: Some(1),
                         block_meta: None,
+                        senders: None,
+                        account_change_sets: None,
+                        storage_change_sets: None,
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
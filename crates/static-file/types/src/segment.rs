@@ -41,6 +41,15 @@ pub enum StaticFileSegment {
     /// Static File segment responsible for the `BlockBodyIndices`, `BlockOmmers`,
     /// `BlockWithdrawals` tables.
     BlockMeta,
+    #[strum(serialize = "accountchangesets")]
+    /// Static File segment responsible for the `AccountChangeSets` table.
+    AccountChangeSets,
+    #[strum(serialize = "storagechangesets")]
+    /// Static File segment responsible for the `StorageChangeSets` table.
+    StorageChangeSets,
+    #[strum(serialize = "senders")]
+    /// Static File segment responsible for the `TransactionSenders` table.
+    Senders,
 }
 
 impl StaticFileSegment {
@@ -51,6 +60,9 @@ impl StaticFileSegment {
             Self::Transactions => "transactions",
             Self::Receipts => "receipts",
             Self::BlockMeta => "blockmeta",
+            Self::AccountChangeSets => "accountchangesets",
+            Self::StorageChangeSets => "storagechangesets",
+            Self::Senders => "senders",
         }
     }
 
@@ -58,7 +70,16 @@ impl StaticFileSegment {
     pub fn iter() -> impl Iterator<Item = Self> {
         // The order of segments is significant and must be maintained to ensure correctness. For
         // example, Transactions require BlockBodyIndices from Blockmeta to be sound.
-        [Self::Headers, Self::BlockMeta, Self::Transactions, Self::Receipts].into_iter()
+        [
+            Self::Headers,
+            Self::BlockMeta,
+            Self::Transactions,
+            Self::Receipts,
+            Self::AccountChangeSets,
+            Self::StorageChangeSets,
+            Self::Senders,
+        ]
+        .into_iter()
     }
 
     /// Returns the default configuration of the segment.
@@ -70,7 +91,11 @@ impl StaticFileSegment {
     pub const fn columns(&self) -> usize {
         match self {
             Self::Headers | Self::BlockMeta => 3,
-            Self::Transactions | Self::Receipts => 1,
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets |
+            Self::Senders => 1,
         }
     }
 
@@ -143,14 +168,28 @@ impl StaticFileSegment {
         matches!(self, Self::Receipts)
     }
 
+    /// Returns `true` if the segment is `StaticFileSegment::Senders`.
+    pub const fn is_senders(&self) -> bool {
+        matches!(self, Self::Senders)
+    }
+
+    /// Returns `true` if the segment is `StaticFileSegment::AccountChangeSets` or
+    /// `StaticFileSegment::StorageChangeSets`.
+    pub const fn is_change_sets(&self) -> bool {
+        matches!(self, Self::AccountChangeSets | Self::StorageChangeSets)
+    }
+
     /// Returns `true` if a segment row is linked to a transaction.
     pub const fn is_tx_based(&self) -> bool {
-        matches!(self, Self::Receipts | Self::Transactions)
+        matches!(self, Self::Receipts | Self::Transactions | Self::Senders)
     }
 
     /// Returns `true` if a segment row is linked to a block.
     pub const fn is_block_based(&self) -> bool {
-        matches!(self, Self::Headers | Self::BlockMeta)
+        matches!(
+            self,
+            Self::Headers | Self::BlockMeta | Self::AccountChangeSets | Self::StorageChangeSets
+        )
     }
 }
 
@@ -166,7 +166,7 @@ fn generate_from_compact(
             if flags.__zstd() != 0 {
                 #decompressor.with(|decompressor| {
                     let decompressor = &mut decompressor.borrow_mut();
-                    let decompressed = decompressor.decompress(buf);
+                    let decompressed = decompressor.decompress(buf).expect("Failed to decompress");
                     let mut original_buf = buf;
 
                     let mut buf: &[u8] = decompressed;
@@ -191,7 +191,7 @@ impl<T: Envelope + ToTxCompact + Transaction + Send + Sync> CompactEnvelope for
             {
                 reth_zstd_compressors::TRANSACTION_DECOMPRESSOR.with(|decompressor| {
                     let mut decompressor = decompressor.borrow_mut();
-                    let decompressed = decompressor.decompress(buf);
+                    let decompressed = decompressor.decompress(buf).expect("Failed to decompress");
 
                     let (tx_type, tx_buf) = T::TxType::from_compact(decompressed, tx_bits);
                     let (tx, _) = Self::from_tx_compact(tx_buf, tx_type, signature);
@@ -202,7 +202,7 @@ impl<T: Envelope + ToTxCompact + Transaction + Send + Sync> CompactEnvelope for
             #[cfg(not(feature = "std"))]
             {
                 let mut decompressor = reth_zstd_compressors::create_tx_decompressor();
-                let decompressed = decompressor.decompress(buf);
+                let decompressed = decompressor.decompress(buf).expect("Failed to decompress");
                 let (tx_type, tx_buf) = T::TxType::from_compact(decompressed, tx_bits);
                 let (tx, _) = Self::from_tx_compact(tx_buf, tx_type, signature);
 
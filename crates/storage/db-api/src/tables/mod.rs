@@ -436,6 +436,19 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the transactions in which an address was the sender, the recipient or
+    /// emitted a log.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], except that the list and the
+    /// `highest_block_number` of the key hold transaction numbers instead of block numbers. The
+    /// last shard of an address has `u64::MAX` as its highest transaction number.
+    ///
+    /// This table is only populated if the `IndexAddressTransactions` stage is enabled.
+    table AddressTransactions {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -151,6 +151,9 @@ where
     let segment = StaticFileSegment::Transactions;
     static_file_provider.latest_writer(segment)?.increment_block(0)?;
 
+    let segment = StaticFileSegment::Senders;
+    static_file_provider.latest_writer(segment)?.increment_block(0)?;
+
     // `commit_unwind`` will first commit the DB and then the static file provider, which is
     // necessary on `init_genesis`.
     UnifiedStorageWriter::commit_unwind(provider_rw)?;
@@ -466,7 +469,9 @@ fn parse_accounts(
     etl_config: EtlConfig,
 ) -> Result<Collector<Address, GenesisAccount>, eyre::Error> {
     let mut line = String::new();
-    let mut collector = Collector::new(etl_config.file_size, etl_config.dir);
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir)
+        .with_compression_level(etl_config.compression_level)
+        .with_max_disk_usage(etl_config.max_disk_usage);
 
     while let Ok(n) = reader.read_line(&mut line) {
         if n == 0 {
@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use alloy_primitives::Address;
 use reth_primitives_traits::Account;
 
@@ -48,3 +49,14 @@ impl reth_codecs::Compact for AccountBeforeTx {
         (Self { address, info }, buf)
     }
 }
+
+/// Account changesets of a single block, as they are saved in static files.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StaticFileAccountChangeSet {
+    /// Accounts changed in the block, with their state before the change.
+    pub changes: Vec<AccountBeforeTx>,
+}
@@ -13,12 +13,16 @@ extern crate alloc;
 
 /// Accounts
 pub mod accounts;
-pub use accounts::AccountBeforeTx;
+pub use accounts::{AccountBeforeTx, StaticFileAccountChangeSet};
 
 /// Blocks
 pub mod blocks;
 pub use blocks::{StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlockWithdrawals};
 
+/// Storage
+pub mod storage;
+pub use storage::{StaticFileStorageChangeSet, StorageBeforeTx};
+
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
@@ -0,0 +1,46 @@
+use alloc::vec::Vec;
+use alloy_primitives::Address;
+use reth_primitives_traits::StorageEntry;
+
+/// Storage slot as it is saved in the static file changesets.
+///
+/// Unlike the database changesets, the [`Address`] is part of the value, since static file rows
+/// are keyed by block number only.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+pub struct StorageBeforeTx {
+    /// Address of the account the storage slot belongs to.
+    pub address: Address,
+    /// Storage slot with its value before the transaction.
+    pub entry: StorageEntry,
+}
+
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for StorageBeforeTx {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        self.entry.to_compact(buf) + 20
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..20]);
+        let (entry, buf) = StorageEntry::from_compact(&buf[20..], len - 20);
+        (Self { address, entry }, buf)
+    }
+}
+
+/// Storage changesets of a single block, as they are saved in static files.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(any(test, feature = "reth-codec"), derive(reth_codecs::Compact))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StaticFileStorageChangeSet {
+    /// Storage slots changed in the block, with their values before the change.
+    pub changes: Vec<StorageBeforeTx>,
+}
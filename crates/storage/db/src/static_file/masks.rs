@@ -3,9 +3,12 @@ use crate::{
     static_file::mask::{ColumnSelectorOne, ColumnSelectorTwo},
     BlockBodyIndices, HeaderTerminalDifficulties,
 };
-use alloy_primitives::BlockHash;
+use alloy_primitives::{Address, BlockHash};
 use reth_db_api::{
-    models::{StaticFileBlockWithdrawals, StoredBlockOmmers},
+    models::{
+        StaticFileAccountChangeSet, StaticFileBlockWithdrawals, StaticFileStorageChangeSet,
+        StoredBlockOmmers,
+    },
     table::Table,
 };
 
@@ -46,6 +49,12 @@ add_static_file_mask! {
     TransactionMask<T>, T, 0b1
 }
 
+// SENDER MASKS
+add_static_file_mask! {
+    #[doc = "Mask for selecting a single transaction sender from Senders static file segment"]
+    SenderMask, Address, 0b1
+}
+
 // BLOCK_META MASKS
 add_static_file_mask! {
     #[doc = "Mask for a `StoredBlockBodyIndices` from `BlockMeta` static file segment"]
@@ -59,3 +68,13 @@ add_static_file_mask! {
     #[doc = "Mask for a `StaticFileBlockWithdrawals` from `BlockMeta` static file segment"]
     WithdrawalsMask, StaticFileBlockWithdrawals, 0b100
 }
+
+// CHANGESET MASKS
+add_static_file_mask! {
+    #[doc = "Mask for a `StaticFileAccountChangeSet` from `AccountChangeSets` static file segment"]
+    AccountChangeSetMask, StaticFileAccountChangeSet, 0b1
+}
+add_static_file_mask! {
+    #[doc = "Mask for a `StaticFileStorageChangeSet` from `StorageChangeSets` static file segment"]
+    StorageChangeSetMask, StaticFileStorageChangeSet, 0b1
+}
@@ -100,6 +100,10 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// The plain state can't be iterated, because it only holds the accounts and storage slots
+    /// changed after the pivot block of snap sync.
+    #[error("the plain state of a snap synced node can't be iterated")]
+    SnapSyncedPlainState,
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
@@ -1,7 +1,7 @@
 use crate::{
     database::Database,
     error::{mdbx_result, Error, Result},
-    flags::EnvironmentFlags,
+    flags::{CopyFlags, EnvironmentFlags},
     transaction::{RO, RW},
     txn_manager::{TxnManager, TxnManagerMessage, TxnPtr},
     Mode, SyncMode, Transaction, TransactionKind,
@@ -154,6 +154,17 @@ impl Environment {
         mdbx_result(unsafe { ffi::mdbx_env_sync_ex(self.env_ptr(), force, false) })
     }
 
+    /// Copies the environment to the given path, which must not exist yet.
+    ///
+    /// With [`CopyFlags::COMPACT`], free pages are omitted and the pages are renumbered
+    /// sequentially, so the copy is only as large as the data it holds. The copy is made from a
+    /// read transaction, so writers aren't blocked while it's in progress.
+    pub fn copy(&self, path: &Path, flags: CopyFlags) -> Result<()> {
+        let path = path_to_cstring(path)?;
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), path.as_ptr(), flags.bits()) })?;
+        Ok(())
+    }
+
     /// Retrieves statistics about this environment.
     pub fn stat(&self) -> Result<Stat> {
         unsafe {
@@ -242,6 +253,25 @@ struct EnvironmentInner {
     txn_manager: TxnManager,
 }
 
+/// Converts a path to a C string to pass it to MDBX.
+pub(crate) fn path_to_cstring(path: &Path) -> Result<CString> {
+    #[cfg(unix)]
+    fn path_to_bytes(path: &Path) -> Vec<u8> {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    }
+
+    #[cfg(windows)]
+    fn path_to_bytes(path: &Path) -> Vec<u8> {
+        // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+        // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+        // really help.
+        path.to_string_lossy().to_string().into_bytes()
+    }
+
+    CString::new(path_to_bytes(path)).map_err(|_| Error::Invalid)
+}
+
 impl Drop for EnvironmentInner {
     fn drop(&mut self) {
         // Close open mdbx environment on drop
@@ -690,24 +720,7 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
-                let path = match CString::new(path_to_bytes(path)) {
-                    Ok(path) => path,
-                    Err(_) => return Err(Error::Invalid),
-                };
+                let path = path_to_cstring(path)?;
                 mdbx_result(ffi::mdbx_env_open(
                     env,
                     path.as_ptr(),
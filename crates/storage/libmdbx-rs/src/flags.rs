@@ -223,3 +223,14 @@ bitflags! {
         const MULTIPLE = MDBX_MULTIPLE;
     }
 }
+
+bitflags! {
+    #[doc="Options for copying an environment."]
+    #[derive(Default)]
+    pub struct CopyFlags: MDBX_copy_flags_t {
+        const COMPACT = MDBX_CP_COMPACT;
+        const FORCE_DYNAMIC_SIZE = MDBX_CP_FORCE_DYNAMIC_SIZE;
+        const DONT_FLUSH = MDBX_CP_DONT_FLUSH;
+        const THROTTLE_MVCC = MDBX_CP_THROTTLE_MVCC;
+    }
+}
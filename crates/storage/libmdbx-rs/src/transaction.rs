@@ -1,8 +1,8 @@
 use crate::{
     database::Database,
-    environment::Environment,
+    environment::{path_to_cstring, Environment},
     error::{mdbx_result, Result},
-    flags::{DatabaseFlags, WriteFlags},
+    flags::{CopyFlags, DatabaseFlags, WriteFlags},
     txn_manager::{TxnManagerMessage, TxnPtr},
     Cursor, Error, Stat, TableObject,
 };
@@ -13,6 +13,7 @@ use std::{
     ffi::{c_uint, c_void},
     fmt::{self, Debug},
     mem::size_of,
+    path::Path,
     ptr, slice,
     sync::{atomic::AtomicBool, mpsc::sync_channel, Arc},
     time::Duration,
@@ -248,6 +249,17 @@ where
         Ok(DatabaseFlags::from_bits_truncate(flags.try_into().unwrap()))
     }
 
+    /// Copies the snapshot of the transaction to the given path, which must not exist yet.
+    ///
+    /// Unlike [`Environment::copy`], the copy holds exactly the data seen by the transaction.
+    pub fn copy(&self, path: &Path, flags: CopyFlags) -> Result<()> {
+        let path = path_to_cstring(path)?;
+        self.txn_execute(|txn| {
+            mdbx_result(unsafe { ffi::mdbx_txn_copy2pathname(txn, path.as_ptr(), flags.bits()) })
+        })??;
+        Ok(())
+    }
+
     /// Retrieves database statistics.
     pub fn db_stat(&self, db: &Database) -> Result<Stat> {
         self.db_stat_with_dbi(db.dbi())
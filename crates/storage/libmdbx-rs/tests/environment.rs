@@ -167,3 +167,78 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    // Write a few values and clear most of them to fill the freelist.
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in 0..1024u64 {
+        let mut key = [0u8; 8];
+        LittleEndian::write_u64(&mut key, i);
+        tx.put(db.dbi(), key, [0u8; 512], WriteFlags::default()).expect("tx.put");
+    }
+    tx.commit().expect("tx.commit");
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in 1..1024u64 {
+        let mut key = [0u8; 8];
+        LittleEndian::write_u64(&mut key, i);
+        tx.del(db.dbi(), key, None).expect("tx.del");
+    }
+    tx.commit().expect("tx.commit");
+
+    let copy_dir = tempdir().unwrap();
+    let copy_path = copy_dir.path().join("mdbx.dat");
+    env.copy(&copy_path, CopyFlags::COMPACT).unwrap();
+
+    // The destination must not exist yet.
+    assert!(env.copy(&copy_path, CopyFlags::COMPACT).is_err());
+
+    let copy = Environment::builder()
+        .set_flags(EnvironmentFlags {
+            mode: Mode::ReadOnly,
+            no_sub_dir: true,
+            ..Default::default()
+        })
+        .open(&copy_path)
+        .unwrap();
+    assert_eq!(copy.stat().unwrap().entries(), 1);
+    assert_eq!(copy.freelist().unwrap(), 0);
+    assert!(copy.info().unwrap().last_pgno() < env.info().unwrap().last_pgno());
+}
+
+#[test]
+fn test_txn_copy() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    tx.put(db.dbi(), b"key1", b"val1", WriteFlags::default()).expect("tx.put");
+    tx.commit().expect("tx.commit");
+
+    // Values written after the read transaction started aren't copied.
+    let read_tx = env.begin_ro_txn().expect("begin_ro_txn");
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    tx.put(db.dbi(), b"key2", b"val2", WriteFlags::default()).expect("tx.put");
+    tx.commit().expect("tx.commit");
+
+    let copy_dir = tempdir().unwrap();
+    let copy_path = copy_dir.path().join("mdbx.dat");
+    read_tx.copy(&copy_path, CopyFlags::COMPACT).unwrap();
+
+    let copy = Environment::builder()
+        .set_flags(EnvironmentFlags {
+            mode: Mode::ReadOnly,
+            no_sub_dir: true,
+            ..Default::default()
+        })
+        .open(&copy_path)
+        .unwrap();
+    assert_eq!(copy.stat().unwrap().entries(), 1);
+}
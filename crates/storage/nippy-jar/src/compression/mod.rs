@@ -41,7 +41,7 @@ pub trait Compression: Serialize + for<'a> Deserialize<'a> {
 }
 
 /// Enum with different [`Compression`] types.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(test, derive(PartialEq))]
 pub enum Compressors {
     /// Zstandard compression algorithm with custom settings.
@@ -51,6 +51,9 @@ pub use writer::NippyJarWriter;
 mod consistency;
 pub use consistency::NippyJarChecker;
 
+mod replace;
+pub use replace::finish_interrupted_replacements;
+
 /// The version number of the Nippy Jar format.
 const NIPPY_JAR_VERSION: usize = 1;
 /// The file extension used for index files.
@@ -162,6 +165,20 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Adds [`compression::Zstd`] compression using the given dictionary for each column.
+    pub fn with_zstd_dictionaries(mut self, dictionaries: Vec<Vec<u8>>) -> Self {
+        debug_assert_eq!(dictionaries.len(), self.columns);
+        self.compressor =
+            Some(Compressors::Zstd(compression::Zstd::with_dictionaries(dictionaries)));
+        self
+    }
+
+    /// Sets the compression applied to the data.
+    pub fn with_compressor(mut self, compressor: Option<Compressors>) -> Self {
+        self.compressor = compressor;
+        self
+    }
+
     /// Adds [`compression::Lz4`] compression.
     pub fn with_lz4(mut self) -> Self {
         self.compressor = Some(Compressors::Lz4(compression::Lz4::default()));
@@ -551,6 +568,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_zstd_with_dictionaries_append_after_load() {
+        let (col1, col2) = test_data(None);
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let dictionaries = vec![
+            zstd::dict::from_samples(&col1, 5000).unwrap(),
+            zstd::dict::from_samples(&col2, 5000).unwrap(),
+        ];
+        let nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_zstd_dictionaries(dictionaries);
+
+        // Write the first half of the rows
+        let half = col1.len() / 2;
+        let mut writer = NippyJarWriter::new(nippy).unwrap();
+        for (value1, value2) in col1.iter().zip(&col2).take(half) {
+            writer.append_column(Some(Ok(value1))).unwrap();
+            writer.append_column(Some(Ok(value2))).unwrap();
+        }
+        writer.commit().unwrap();
+
+        // Loaded dictionaries can still be used to compress the rest of the rows
+        let nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        let mut writer = NippyJarWriter::new(nippy).unwrap();
+        for (value1, value2) in col1.iter().zip(&col2).skip(half) {
+            writer.append_column(Some(Ok(value1))).unwrap();
+            writer.append_column(Some(Ok(value2))).unwrap();
+        }
+        writer.commit().unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(loaded_nippy.rows, col1.len());
+        assert!(
+            matches!(loaded_nippy.compressor(), Some(Compressors::Zstd(zstd)) if zstd.use_dict)
+        );
+
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
+    }
+
     #[test]
     fn test_lz4() {
         let (col1, col2) = test_data(None);
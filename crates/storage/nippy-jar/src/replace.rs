@@ -0,0 +1,147 @@
+use crate::{
+    NippyJar, NippyJarError, NippyJarHeader, CONFIG_FILE_EXTENSION, OFFSETS_FILE_EXTENSION,
+};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tracing::*;
+
+/// The file extension used for the marker of an ongoing jar replacement.
+const REPLACE_FILE_EXTENSION: &str = "replace";
+
+impl<H: NippyJarHeader> NippyJar<H> {
+    /// Moves the files of this jar over the files of the jar at `path`, and returns this jar at its
+    /// new location. Both jars need to be in the same directory.
+    ///
+    /// Before any file is moved, a marker pointing to this jar is written next to the target jar.
+    /// If the replacement is interrupted, [`finish_interrupted_replacements`] completes it, so the
+    /// target jar is never left with a mix of files from both jars.
+    pub fn replace(mut self, path: &Path) -> Result<Self, NippyJarError> {
+        let (Some(file_name), Some(dir)) = (self.path.file_name(), self.path.parent()) else {
+            return Err(NippyJarError::Custom(format!("invalid jar path: {:?}", self.path)))
+        };
+        if path.parent() != Some(dir) {
+            return Err(NippyJarError::Custom(format!(
+                "jar {:?} can only replace a jar in the same directory, got {path:?}",
+                self.path
+            )))
+        }
+
+        let marker = path.with_extension(REPLACE_FILE_EXTENSION);
+        reth_fs_util::atomic_write_file(&marker, |file| {
+            file.write_all(file_name.as_encoded_bytes())
+        })?;
+
+        move_jar_files(&self.path, path)?;
+        reth_fs_util::remove_file(&marker)?;
+
+        self.path = path.to_path_buf();
+        Ok(self)
+    }
+}
+
+/// Completes all jar replacements in `dir` that were interrupted.
+///
+/// See [`NippyJar::replace`].
+pub fn finish_interrupted_replacements(dir: &Path) -> Result<(), NippyJarError> {
+    if !dir.exists() {
+        return Ok(())
+    }
+
+    for entry in reth_fs_util::read_dir(dir)? {
+        let marker = entry.map_err(|err| NippyJarError::Custom(err.to_string()))?.path();
+        if marker.extension().is_none_or(|extension| extension != REPLACE_FILE_EXTENSION) {
+            continue
+        }
+
+        let source = dir.join(String::from_utf8_lossy(&reth_fs_util::read(&marker)?).as_ref());
+        let target = marker.with_extension("");
+        warn!(target: "nippy-jar", ?source, ?target, "Finishing interrupted jar replacement.");
+
+        move_jar_files(&source, &target)?;
+        reth_fs_util::remove_file(&marker)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the data, offsets and configuration files of the jar at `from` to `to`.
+///
+/// Files that don't exist at `from` are considered already moved.
+fn move_jar_files(from: &Path, to: &Path) -> Result<(), NippyJarError> {
+    for (from, to) in jar_files(from).into_iter().zip(jar_files(to)) {
+        if from.exists() {
+            reth_fs_util::rename(from, to)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the data, offsets and configuration file paths of the jar at `path`, in the order they
+/// are moved.
+fn jar_files(path: &Path) -> [PathBuf; 3] {
+    [
+        path.to_path_buf(),
+        path.with_extension(OFFSETS_FILE_EXTENSION),
+        path.with_extension(CONFIG_FILE_EXTENSION),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NippyJarCursor, NippyJarWriter};
+
+    fn write_jar(path: &Path, rows: &[&[u8]]) -> NippyJar {
+        let mut writer = NippyJarWriter::new(NippyJar::new_without_header(1, path)).unwrap();
+        for row in rows {
+            writer.append_column(Some(Ok(row))).unwrap();
+        }
+        writer.commit().unwrap();
+        writer.into_jar()
+    }
+
+    fn read_jar(path: &Path) -> Vec<Vec<u8>> {
+        let jar = NippyJar::load_without_header(path).unwrap();
+        let mut cursor = NippyJarCursor::new(&jar).unwrap();
+        let mut rows = Vec::new();
+        while let Some(row) = cursor.next_row().unwrap() {
+            rows.push(row[0].to_vec());
+        }
+        rows
+    }
+
+    #[test]
+    fn test_replace() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jar");
+        let new_path = dir.path().join("new_jar");
+
+        write_jar(&path, &[b"old"]);
+        let jar = write_jar(&new_path, &[b"new", b"rows"]).replace(&path).unwrap();
+
+        assert_eq!(jar.data_path(), path);
+        assert_eq!(read_jar(&path), vec![b"new".to_vec(), b"rows".to_vec()]);
+        assert_eq!(reth_fs_util::read_dir(dir.path()).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn test_finish_interrupted_replacement() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jar");
+        let new_path = dir.path().join("new_jar");
+
+        write_jar(&path, &[b"old"]);
+        write_jar(&new_path, &[b"new", b"rows"]);
+
+        // Simulate a replacement interrupted after moving the data file only
+        reth_fs_util::write(path.with_extension(REPLACE_FILE_EXTENSION), "new_jar").unwrap();
+        reth_fs_util::rename(&new_path, &path).unwrap();
+
+        finish_interrupted_replacements(dir.path()).unwrap();
+
+        assert_eq!(read_jar(&path), vec![b"new".to_vec(), b"rows".to_vec()]);
+        assert_eq!(reth_fs_util::read_dir(dir.path()).unwrap().count(), 3);
+    }
+}
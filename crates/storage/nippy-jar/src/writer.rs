@@ -1,12 +1,13 @@
 use crate::{
-    compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarError,
-    NippyJarHeader,
+    compression::{Compression, Compressors},
+    ColumnResult, NippyJar, NippyJarChecker, NippyJarError, NippyJarHeader,
 };
 use std::{
     fs::{File, OpenOptions},
     io::{BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
 };
+use zstd::{bulk::Compressor, zstd_safe::compress_bound};
 
 /// Size of one offset in bytes.
 pub(crate) const OFFSET_SIZE_BYTES: u8 = 8;
@@ -24,7 +25,6 @@ pub(crate) const OFFSET_SIZE_BYTES: u8 = 8;
 ///
 /// ## Data file layout
 /// The data file is represented just as a sequence of bytes of data without any delimiters
-#[derive(Debug)]
 pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     /// Associated [`NippyJar`], containing all necessary configurations for data
     /// handling.
@@ -35,6 +35,8 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     offsets_file: BufWriter<File>,
     /// Temporary buffer to reuse when compressing data.
     tmp_buf: Vec<u8>,
+    /// Column compressors of a jar compressed with zstd dictionaries. Created on the first write.
+    zstd_compressors: Option<Vec<Compressor<'static>>>,
     /// Used to find the maximum uncompressed size of a row in a jar.
     uncompressed_row_size: usize,
     /// Partial offset list which hasn't been flushed to disk.
@@ -45,6 +47,20 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     dirty: bool,
 }
 
+impl<H: NippyJarHeader> std::fmt::Debug for NippyJarWriter<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NippyJarWriter")
+            .field("jar", &self.jar)
+            .field("data_file", &self.data_file)
+            .field("offsets_file", &self.offsets_file)
+            .field("uncompressed_row_size", &self.uncompressed_row_size)
+            .field("offsets", &self.offsets)
+            .field("column", &self.column)
+            .field("dirty", &self.dirty)
+            .finish_non_exhaustive()
+    }
+}
+
 impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Creates a [`NippyJarWriter`] from [`NippyJar`].
     ///
@@ -75,6 +91,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             data_file,
             offsets_file,
             tmp_buf: Vec::with_capacity(1_000_000),
+            zstd_compressors: None,
             uncompressed_row_size: 0,
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
@@ -227,14 +244,35 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Writes column to data file. If it's the last column of the row, call `finalize_row()`
     fn write_column(&mut self, value: &[u8]) -> Result<usize, NippyJarError> {
         self.uncompressed_row_size += value.len();
-        let len = if let Some(compression) = &self.jar.compressor {
-            let before = self.tmp_buf.len();
-            let len = compression.compress_to(value, &mut self.tmp_buf)?;
-            self.data_file.write_all(&self.tmp_buf[before..before + len])?;
-            len
-        } else {
-            self.data_file.write_all(value)?;
-            value.len()
+        let len = match &self.jar.compressor {
+            Some(Compressors::Zstd(zstd)) if zstd.use_dict => {
+                if self.zstd_compressors.is_none() {
+                    self.zstd_compressors = zstd.compressors()?;
+                }
+                let compressor = self
+                    .zstd_compressors
+                    .as_mut()
+                    .and_then(|compressors| compressors.get_mut(self.column))
+                    .ok_or(NippyJarError::CompressorNotReady)?;
+
+                // Reserving the upper bound of the compressed size makes sure compression can't
+                // fail because of a small buffer.
+                self.tmp_buf.clear();
+                self.tmp_buf.reserve(compress_bound(value.len()));
+                let len = compressor.compress_to_buffer(value, &mut self.tmp_buf)?;
+                self.data_file.write_all(&self.tmp_buf)?;
+                len
+            }
+            Some(compression) => {
+                let before = self.tmp_buf.len();
+                let len = compression.compress_to(value, &mut self.tmp_buf)?;
+                self.data_file.write_all(&self.tmp_buf[before..before + len])?;
+                len
+            }
+            None => {
+                self.data_file.write_all(value)?;
+                value.len()
+            }
         };
 
         self.column += 1;
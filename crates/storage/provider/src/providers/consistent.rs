@@ -1,10 +1,10 @@
 use super::{DatabaseProviderRO, ProviderFactory, ProviderNodeTypes};
 use crate::{
-    providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider, ChangeSetReader, HeaderProvider,
-    ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateReader, StaticFileProviderFactory, TransactionVariant,
-    TransactionsProvider,
+    providers::StaticFileProvider, AccountReader, AccountWithStorage, AddressTransactionsReader,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    ChainSpecProvider, ChangeSetReader, HeaderProvider, PlainStateReader, ProviderError,
+    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
+    StateReader, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{
@@ -32,6 +32,7 @@ use reth_storage_api::{
 use reth_storage_errors::provider::ProviderResult;
 use revm_database::states::PlainStorageRevert;
 use std::{
+    collections::{BTreeMap, BTreeSet},
     ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
     sync::Arc,
 };
@@ -1396,6 +1397,177 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressTransactionsReader for ConsistentProvider<N> {
+    // The index is only maintained for persisted blocks, so the in-memory state is not consulted.
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.storage_provider.address_transactions(address, range, limit)
+    }
+
+    fn address_transactions_rev(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.storage_provider.address_transactions_rev(address, range, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> PlainStateReader for ConsistentProvider<N> {
+    fn changed_addresses_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let Some(head_block) = &self.head_block else {
+            return self.storage_provider.changed_addresses_with_range(range)
+        };
+
+        // blocks up to the anchor are persisted, the ones above it are in memory
+        let last_persisted = head_block.anchor().number;
+        let mut addresses = if *range.start() <= last_persisted {
+            self.storage_provider
+                .changed_addresses_with_range(*range.start()..=last_persisted.min(*range.end()))?
+        } else {
+            BTreeSet::new()
+        };
+        for state in head_block.chain().filter(|state| range.contains(&state.number())) {
+            addresses.extend(state.block_ref().execution_output.bundle.state.keys().copied());
+        }
+
+        Ok(addresses)
+    }
+
+    fn plain_state_at(
+        &self,
+        block_number: BlockNumber,
+        start: Address,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<BTreeMap<Address, AccountWithStorage>> {
+        let Some(head_block) =
+            self.head_block.as_ref().filter(|head| block_number > head.anchor().number)
+        else {
+            return self.storage_provider.plain_state_at(block_number, start, limit, storage_limit)
+        };
+
+        // apply the in-memory blocks up to the requested one on top of the persisted state, oldest
+        // first
+        let in_memory =
+            head_block.chain().filter(|state| state.number() <= block_number).collect::<Vec<_>>();
+        // the in-memory blocks can remove accounts from the persisted page, so fetch enough
+        // accounts to fill the page regardless
+        let changed = in_memory
+            .iter()
+            .map(|state| state.block_ref().execution_output.bundle.state.len())
+            .sum::<usize>();
+        let persisted_limit = limit.saturating_add(changed);
+        let mut accounts = self.storage_provider.plain_state_at(
+            head_block.anchor().number,
+            start,
+            persisted_limit,
+            storage_limit,
+        )?;
+        // if the persisted page is full, accounts after its last one are not part of it
+        let end = (accounts.len() == persisted_limit)
+            .then(|| accounts.keys().next_back().copied())
+            .flatten();
+        let mut storage_changed = BTreeSet::new();
+        for state in in_memory.into_iter().rev() {
+            for (address, account) in &state.block_ref().execution_output.bundle.state {
+                if *address < start || end.is_some_and(|end| *address > end) {
+                    continue
+                }
+                let Some(info) = &account.info else {
+                    accounts.remove(address);
+                    continue
+                };
+
+                accounts.entry(*address).or_default().info = info.into();
+                if account.was_destroyed() || !account.storage.is_empty() {
+                    storage_changed.insert(*address);
+                }
+            }
+        }
+        while accounts.len() > limit {
+            accounts.pop_last();
+        }
+        // the storage page of an account changed in memory is read again with the overlay
+        for (address, account) in &mut accounts {
+            if storage_changed.contains(address) {
+                account.storage =
+                    self.plain_storage_at(*address, block_number, B256::ZERO, storage_limit)?;
+            }
+        }
+
+        Ok(accounts)
+    }
+
+    fn plain_storage_at(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<BTreeMap<B256, U256>> {
+        let Some(head_block) =
+            self.head_block.as_ref().filter(|head| block_number > head.anchor().number)
+        else {
+            return self.storage_provider.plain_storage_at(address, block_number, start, limit)
+        };
+
+        let in_memory = head_block
+            .chain()
+            .filter(|state| state.number() <= block_number)
+            .filter_map(|state| {
+                state.block_ref().execution_output.bundle.account(&address).cloned()
+            })
+            .collect::<Vec<_>>();
+        // the in-memory blocks can remove slots from the persisted page, so fetch enough slots to
+        // fill the page regardless
+        let changed = in_memory.iter().map(|account| account.storage.len()).sum::<usize>();
+        let persisted_limit = limit.saturating_add(changed);
+        let mut storage = self.storage_provider.plain_storage_at(
+            address,
+            head_block.anchor().number,
+            start,
+            persisted_limit,
+        )?;
+        // if the persisted page is full, slots after its last one are not part of it
+        let mut end = (storage.len() == persisted_limit)
+            .then(|| storage.keys().next_back().copied())
+            .flatten();
+        for account in in_memory.into_iter().rev() {
+            if account.was_destroyed() {
+                // the storage is entirely known from the in-memory blocks from here on
+                storage.clear();
+                end = None;
+            }
+            for (key, slot) in &account.storage {
+                let key = B256::from(*key);
+                if key < start || end.is_some_and(|end| key > end) {
+                    continue
+                }
+                if slot.present_value.is_zero() {
+                    storage.remove(&key);
+                } else {
+                    storage.insert(key, slot.present_value);
+                }
+            }
+        }
+        while storage.len() > limit {
+            storage.pop_last();
+        }
+
+        Ok(storage)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -1437,7 +1609,7 @@ mod tests {
         test_utils::create_test_provider_factory, BlockWriter,
     };
     use alloy_eips::BlockHashOrNumber;
-    use alloy_primitives::B256;
+    use alloy_primitives::{map::HashMap, Address, B256, U256};
     use itertools::Itertools;
     use rand::Rng;
     use reth_chain_state::{
@@ -1446,13 +1618,16 @@ mod tests {
     use reth_db_api::models::AccountBeforeTx;
     use reth_ethereum_primitives::Block;
     use reth_execution_types::ExecutionOutcome;
-    use reth_primitives_traits::{RecoveredBlock, SealedBlock};
-    use reth_storage_api::{BlockReader, BlockSource, ChangeSetReader};
+    use reth_primitives_traits::{Account, RecoveredBlock, SealedBlock};
+    use reth_storage_api::{
+        AccountWithStorage, BlockReader, BlockSource, ChangeSetReader, PlainStateReader,
+    };
     use reth_testing_utils::generators::{
         self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
     };
     use revm_database::BundleState;
     use std::{
+        collections::{BTreeMap, BTreeSet},
         ops::{Bound, Range, RangeBounds},
         sync::Arc,
     };
@@ -1808,4 +1983,200 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_plain_state_reader() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (database_blocks, in_memory_blocks) = random_blocks(&mut rng, 3, 1, None, None, 0..1);
+
+        let (a, b) = (Address::with_last_byte(0xa), Address::with_last_byte(0xb));
+        let account = |balance: u64| Account { balance: U256::from(balance), ..Default::default() };
+        let (slot, other_slot) = (U256::from(1), U256::from(2));
+
+        // `b` is created in block 0, `a` in block 1 and updated along with its storage in block 2
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.append_blocks_with_state(
+            database_blocks
+                .into_iter()
+                .map(|b| b.try_recover().expect("failed to seal block with senders"))
+                .collect(),
+            &ExecutionOutcome {
+                bundle: BundleState::new(
+                    [
+                        (
+                            a,
+                            None,
+                            Some(account(2).into()),
+                            HashMap::from_iter([
+                                (slot, (U256::ZERO, U256::from(5))),
+                                (other_slot, (U256::ZERO, U256::from(6))),
+                            ]),
+                        ),
+                        (b, None, Some(account(10).into()), HashMap::default()),
+                    ],
+                    vec![
+                        vec![(b, Some(None), vec![])],
+                        vec![(a, Some(None), vec![])],
+                        vec![(
+                            a,
+                            Some(Some(account(1).into())),
+                            vec![(slot, U256::ZERO), (other_slot, U256::ZERO)],
+                        )],
+                    ],
+                    Vec::new(),
+                ),
+                first_block: 0,
+                ..Default::default()
+            },
+            Default::default(),
+            Default::default(),
+        )?;
+        provider_rw.commit()?;
+
+        // `b` is updated and the first slot of `a` is cleared in the in-memory block 3
+        let provider = BlockchainProvider::new(factory)?;
+        let block = in_memory_blocks.first().unwrap();
+        let senders = block.senders().expect("failed to recover senders");
+        provider.canonical_in_memory_state.update_chain(NewCanonicalChain::Commit {
+            new: vec![ExecutedBlockWithTrieUpdates::new(
+                Arc::new(RecoveredBlock::new_sealed(block.clone(), senders)),
+                Arc::new(ExecutionOutcome {
+                    bundle: BundleState::new(
+                        [
+                            (
+                                a,
+                                Some(account(2).into()),
+                                Some(account(2).into()),
+                                HashMap::from_iter([(slot, (U256::from(5), U256::ZERO))]),
+                            ),
+                            (
+                                b,
+                                Some(account(10).into()),
+                                Some(account(11).into()),
+                                HashMap::default(),
+                            ),
+                        ],
+                        [[
+                            (a, Some(Some(account(2).into())), vec![(slot, U256::from(5))]),
+                            (b, Some(Some(account(10).into())), Vec::new()),
+                        ]],
+                        [],
+                    ),
+                    first_block: block.number,
+                    ..Default::default()
+                }),
+                Default::default(),
+                ExecutedTrieUpdates::empty(),
+            )],
+        });
+        let consistent_provider = provider.consistent_provider()?;
+
+        let with_storage = |info: Account, storage: &[(U256, u64)]| AccountWithStorage {
+            info,
+            storage: storage
+                .iter()
+                .map(|(key, value)| (B256::from(*key), U256::from(*value)))
+                .collect(),
+        };
+        assert_eq!(
+            consistent_provider.plain_state_at(0, Address::ZERO, usize::MAX, usize::MAX)?,
+            BTreeMap::from([(b, with_storage(account(10), &[]))])
+        );
+        assert_eq!(
+            consistent_provider.plain_state_at(1, Address::ZERO, usize::MAX, usize::MAX)?,
+            BTreeMap::from([
+                (a, with_storage(account(1), &[])),
+                (b, with_storage(account(10), &[]))
+            ])
+        );
+        assert_eq!(
+            consistent_provider.plain_state_at(2, Address::ZERO, usize::MAX, usize::MAX)?,
+            BTreeMap::from([
+                (a, with_storage(account(2), &[(slot, 5), (other_slot, 6)])),
+                (b, with_storage(account(10), &[]))
+            ])
+        );
+        assert_eq!(
+            consistent_provider.plain_state_at(3, Address::ZERO, usize::MAX, usize::MAX)?,
+            BTreeMap::from([
+                (a, with_storage(account(2), &[(other_slot, 6)])),
+                (b, with_storage(account(11), &[]))
+            ])
+        );
+
+        // the state can be read in pages
+        assert_eq!(
+            consistent_provider.plain_state_at(3, Address::ZERO, 1, usize::MAX)?,
+            BTreeMap::from([(a, with_storage(account(2), &[(other_slot, 6)]))])
+        );
+        assert_eq!(
+            consistent_provider.plain_state_at(
+                3,
+                Address::with_last_byte(0xa + 1),
+                1,
+                usize::MAX
+            )?,
+            BTreeMap::from([(b, with_storage(account(11), &[]))])
+        );
+        assert_eq!(
+            consistent_provider.plain_state_at(2, a, 2, usize::MAX)?,
+            BTreeMap::from([
+                (a, with_storage(account(2), &[(slot, 5), (other_slot, 6)])),
+                (b, with_storage(account(10), &[]))
+            ])
+        );
+
+        // the storage of each account is bounded
+        assert_eq!(
+            consistent_provider.plain_state_at(2, Address::ZERO, 1, 1)?,
+            BTreeMap::from([(a, with_storage(account(2), &[(slot, 5)]))])
+        );
+        assert_eq!(
+            consistent_provider.plain_state_at(3, Address::ZERO, 1, 1)?,
+            BTreeMap::from([(a, with_storage(account(2), &[(other_slot, 6)]))])
+        );
+
+        assert_eq!(consistent_provider.changed_addresses_with_range(0..=0)?, BTreeSet::from([b]));
+        assert_eq!(consistent_provider.changed_addresses_with_range(1..=2)?, BTreeSet::from([a]));
+        assert_eq!(
+            consistent_provider.changed_addresses_with_range(2..=3)?,
+            BTreeSet::from([a, b])
+        );
+
+        let storage = |slots: &[(U256, u64)]| {
+            slots
+                .iter()
+                .map(|(key, value)| (B256::from(*key), U256::from(*value)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        assert_eq!(
+            consistent_provider.plain_storage_at(a, 1, B256::ZERO, usize::MAX)?,
+            storage(&[])
+        );
+        assert_eq!(
+            consistent_provider.plain_storage_at(a, 2, B256::ZERO, usize::MAX)?,
+            storage(&[(slot, 5), (other_slot, 6)])
+        );
+        assert_eq!(
+            consistent_provider.plain_storage_at(a, 3, B256::ZERO, usize::MAX)?,
+            storage(&[(other_slot, 6)])
+        );
+
+        // the storage can be read in pages
+        assert_eq!(
+            consistent_provider.plain_storage_at(a, 2, B256::ZERO, 1)?,
+            storage(&[(slot, 5)])
+        );
+        assert_eq!(
+            consistent_provider.plain_storage_at(a, 2, B256::from(other_slot), 1)?,
+            storage(&[(other_slot, 6)])
+        );
+        assert_eq!(
+            consistent_provider.plain_storage_at(a, 3, B256::ZERO, 1)?,
+            storage(&[(other_slot, 6)])
+        );
+
+        Ok(())
+    }
 }
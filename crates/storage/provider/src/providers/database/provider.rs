@@ -7,7 +7,8 @@ use crate::{
     },
     to_range,
     traits::{
-        AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
+        AccountExtReader, AddressTransactionsReader, BlockSource, ChangeSetReader,
+        PlainStateReader, ReceiptProvider, StageCheckpointWriter,
     },
     AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
     BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
@@ -28,7 +29,7 @@ use alloy_primitives::{
     map::{hash_map, B256Map, HashMap, HashSet},
     Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
 };
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use rayon::slice::ParallelSliceMut;
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_db_api::{
@@ -55,8 +56,9 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, StateProvider,
-    StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    address_transactions_by_tx_range, AccountWithStorage, BlockBodyIndicesProvider,
+    BlockBodyReader, NodePrimitivesProvider, StateProvider, StorageChangeSetReader,
+    TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
@@ -72,7 +74,7 @@ use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
-    ops::{Deref, DerefMut, Range, RangeBounds, RangeInclusive},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds, RangeInclusive},
     sync::{mpsc, Arc},
 };
 use tracing::{debug, trace};
@@ -260,6 +262,8 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<()> {
+        self.ensure_changesets_not_in_static_files(*range.start())?;
+
         let changed_accounts = self
             .tx
             .cursor_read::<tables::AccountChangeSets>()?
@@ -332,7 +336,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
                 root: GotExpected { got: new_state_root, expected: parent_state_root },
                 block_number: parent_number,
                 block_hash: parent_hash,
-            })))
+            })));
         }
         self.write_trie_updates(&trie_updates)?;
 
@@ -366,6 +370,35 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
 
         Ok(())
     }
+
+    /// Unwinds the address transactions index above the given block, if the index is maintained,
+    /// i.e. the `IndexAddressTransactions` stage checkpoint is above the block.
+    ///
+    /// Must be called before the transactions and receipts of the blocks are removed.
+    fn unwind_address_transactions_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self
+            .get_stage_checkpoint(StageId::IndexAddressTransactions)?
+            .filter(|checkpoint| checkpoint.block_number > block)
+        else {
+            return Ok(());
+        };
+
+        let first_tx_num = self
+            .block_body_indices(block + 1)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(block + 1))?
+            .first_tx_num();
+        let next_tx_num = self
+            .block_body_indices(checkpoint.block_number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(checkpoint.block_number))?
+            .next_tx_num();
+
+        let addresses = address_transactions_by_tx_range(self, first_tx_num..next_tx_num)?
+            .into_iter()
+            .map(|(address, indices)| (address, indices[0]));
+        self.unwind_address_transactions_indices(addresses)?;
+
+        self.save_stage_checkpoint(StageId::IndexAddressTransactions, StageCheckpoint::new(block))
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for DatabaseProvider<TX, N> {
@@ -452,6 +485,17 @@ impl<
     }
 }
 
+/// Returns the lowest block number a range of changeset keys may include.
+fn range_start_block<K>(
+    range: &impl RangeBounds<K>,
+    block_number: impl FnOnce(&K) -> BlockNumber,
+) -> BlockNumber {
+    match range.start_bound() {
+        Bound::Included(key) | Bound::Excluded(key) => block_number(key),
+        Bound::Unbounded => 0,
+    }
+}
+
 /// For a given key, unwind all history shards that are below the given block number.
 ///
 /// S - Sharded key subtype.
@@ -612,7 +656,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         F: FnMut(H, BodyTy<N>, Range<TxNumber>) -> ProviderResult<R>,
     {
         if range.is_empty() {
-            return Ok(Vec::new())
+            return Ok(Vec::new());
         }
 
         let len = range.end().saturating_sub(*range.start()) as usize;
@@ -675,16 +719,31 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         BF: Fn(H, BodyTy<N>, Vec<Address>) -> ProviderResult<B>,
     {
         let mut senders_cursor = self.tx.cursor_read::<tables::TransactionSenders>()?;
+        let highest_static_file_sender =
+            self.static_file_provider.get_highest_static_file_tx(StaticFileSegment::Senders);
 
         self.block_range(range, headers_range, |header, body, tx_range| {
             let senders = if tx_range.is_empty() {
                 Vec::new()
             } else {
-                // fetch senders from the senders table
-                let known_senders =
-                    senders_cursor
-                        .walk_range(tx_range.clone())?
-                        .collect::<Result<HashMap<_, _>, _>>()?;
+                // fetch senders from the senders static files and table
+                let mut known_senders = HashMap::<TxNumber, Address>::default();
+                let mut db_range = tx_range.clone();
+                if let Some(highest) = highest_static_file_sender {
+                    if db_range.start <= highest {
+                        let end = db_range.end.min(highest + 1);
+                        known_senders.extend((db_range.start..end).zip(
+                            self.static_file_provider.senders_by_tx_range(db_range.start..end)?,
+                        ));
+                        db_range.start = end;
+                    }
+                }
+                if !db_range.is_empty() {
+                    for entry in senders_cursor.walk_range(db_range)? {
+                        let (tx_num, sender) = entry?;
+                        known_senders.insert(tx_num, sender);
+                    }
+                }
 
                 let mut senders = Vec::with_capacity(body.transactions().len());
                 for (tx_num, tx) in tx_range.zip(body.transactions()) {
@@ -863,12 +922,8 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
+        self.account_changesets_range(range)?
+            .map(|entry| entry.map(|(_, account_before)| account_before.address))
             .collect()
     }
 
@@ -887,14 +942,12 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
+        let account_transitions = self.account_changesets_range(range)?.try_fold(
             BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
+            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| {
                 let (index, account) = entry?;
                 accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
+                Ok::<_, ProviderError>(accounts)
             },
         )?;
 
@@ -902,18 +955,160 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns an iterator over the account changesets of the block range in ascending block
+    /// order.
+    ///
+    /// Blocks already moved to [`StaticFileSegment::AccountChangeSets`] are read from static files
+    /// one block at a time, the rest are walked from [`tables::AccountChangeSets`].
+    fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumber, AccountBeforeTx)>> + '_>
+    {
+        let range = to_range(range);
+        let db_start = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .map_or(range.start, |highest| range.start.max(highest + 1));
+
+        let static_files = (range.start..range.end.min(db_start)).flat_map(move |block| match self
+            .static_file_provider
+            .account_block_changeset(block)
+        {
+            Ok(changeset) => Either::Left(
+                changeset.into_iter().map(move |account_before| Ok((block, account_before))),
+            ),
+            Err(err) => Either::Right(std::iter::once(Err(err))),
+        });
+
+        let mut cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
+        let mut seeked = false;
+        let database = std::iter::from_fn(move || {
+            let entry = if seeked {
+                cursor.next()
+            } else {
+                seeked = true;
+                cursor.seek(db_start)
+            };
+            match entry {
+                Ok(Some(entry)) if entry.0 < range.end => Some(Ok(entry)),
+                Ok(_) => None,
+                Err(err) => Some(Err(err.into())),
+            }
+        });
+
+        Ok(static_files.chain(database))
+    }
+
+    /// Returns an iterator over the storage changesets of the block range in ascending block
+    /// order.
+    ///
+    /// Blocks already moved to [`StaticFileSegment::StorageChangeSets`] are read from static files
+    /// one block at a time, the rest are walked from [`tables::StorageChangeSets`].
+    fn storage_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<impl Iterator<Item = ProviderResult<(BlockNumberAddress, StorageEntry)>> + '_>
+    {
+        let range = to_range(range);
+        let db_start = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .map_or(range.start, |highest| range.start.max(highest + 1));
+
+        let static_files = (range.start..range.end.min(db_start)).flat_map(move |block| match self
+            .static_file_provider
+            .storage_changeset(block)
+        {
+            Ok(changeset) => Either::Left(changeset.into_iter().map(Ok)),
+            Err(err) => Either::Right(std::iter::once(Err(err))),
+        });
+
+        let mut cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
+        let mut seeked = false;
+        let database = std::iter::from_fn(move || {
+            let entry = if seeked {
+                cursor.next()
+            } else {
+                seeked = true;
+                cursor.seek(BlockNumberAddress((db_start, Address::ZERO)))
+            };
+            match entry {
+                Ok(Some(entry)) if entry.0.block_number() < range.end => Some(Ok(entry)),
+                Ok(_) => None,
+                Err(err) => Some(Err(err.into())),
+            }
+        });
+
+        Ok(static_files.chain(database))
+    }
+
+    /// Returns an error if the changesets of the given block or any later block were already moved
+    /// to static files.
+    ///
+    /// Changesets are only moved to static files once finalized and are never unwound, so the state
+    /// can not be unwound to below them.
+    fn ensure_changesets_not_in_static_files(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<()> {
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            if self
+                .static_file_provider
+                .get_highest_static_file_block(segment)
+                .is_some_and(|highest| highest >= block_number)
+            {
+                return Err(ProviderError::FinalizedStaticFile(segment, block_number))
+            }
+        }
+        Ok(())
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N> {
     fn storage_changeset(
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
-        let range = block_number..=block_number;
-        let storage_range = BlockNumberAddress::range(range);
-        self.tx
-            .cursor_dup_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .map(|result| -> ProviderResult<_> { Ok(result?) })
-            .collect()
+        self.static_file_provider
+            .get_with_static_file_or_database(
+                StaticFileSegment::StorageChangeSets,
+                block_number,
+                |static_file| static_file.storage_changeset(block_number).map(Some),
+                || {
+                    let range = block_number..=block_number;
+                    let storage_range = BlockNumberAddress::range(range);
+                    self.tx
+                        .cursor_dup_read::<tables::StorageChangeSets>()?
+                        .walk_range(storage_range)?
+                        .map(|result| -> ProviderResult<_> { Ok(result?) })
+                        .collect::<ProviderResult<_>>()
+                        .map(Some)
+                },
+            )
+            .map(Option::unwrap_or_default)
+    }
+
+    fn get_storage_before_block(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        self.static_file_provider.get_with_static_file_or_database(
+            StaticFileSegment::StorageChangeSets,
+            block_number,
+            |static_file| static_file.get_storage_before_block(block_number, address, storage_key),
+            || {
+                Ok(self
+                    .tx
+                    .cursor_dup_read::<tables::StorageChangeSets>()?
+                    .seek_by_key_subkey((block_number, address).into(), storage_key)?
+                    .filter(|entry| entry.key == storage_key))
+            },
+        )
     }
 }
 
@@ -922,15 +1117,250 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        self.static_file_provider
+            .get_with_static_file_or_database(
+                StaticFileSegment::AccountChangeSets,
+                block_number,
+                |static_file| static_file.account_block_changeset(block_number).map(Some),
+                || {
+                    let range = block_number..=block_number;
+                    self.tx
+                        .cursor_read::<tables::AccountChangeSets>()?
+                        .walk_range(range)?
+                        .map(|result| -> ProviderResult<_> {
+                            let (_, account_before) = result?;
+                            Ok(account_before)
+                        })
+                        .collect::<ProviderResult<_>>()
+                        .map(Some)
+                },
+            )
+            .map(Option::unwrap_or_default)
+    }
+
+    fn get_account_before_block(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        self.static_file_provider.get_with_static_file_or_database(
+            StaticFileSegment::AccountChangeSets,
+            block_number,
+            |static_file| static_file.get_account_before_block(block_number, address),
+            || {
+                Ok(self
+                    .tx
+                    .cursor_dup_read::<tables::AccountChangeSets>()?
+                    .seek_by_key_subkey(block_number, address)?
+                    .filter(|account_before| account_before.address == address))
+            },
+        )
+    }
+}
+
+impl<TX: DbTx, N: NodeTypes> AddressTransactionsReader for DatabaseProvider<TX, N> {
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut transactions = Vec::new();
+
+        // The first shard that may contain transactions from the range is the first one with the
+        // highest transaction number not less than the start of the range.
+        let mut shard = cursor.seek(ShardedKey::new(address, *range.start()))?;
+        while let Some((sharded_key, list)) = shard {
+            if sharded_key.key != address {
+                break
+            }
+
+            for tx_number in list.iter().skip_while(|tx_number| tx_number < range.start()) {
+                if tx_number > *range.end() || transactions.len() >= limit {
+                    return Ok(transactions)
+                }
+                transactions.push(tx_number);
+            }
+
+            shard = cursor.next()?;
+        }
+
+        Ok(transactions)
+    }
+
+    fn address_transactions_rev(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut transactions = Vec::new();
+
+        // The last shard that may contain transactions from the range is the first one with the
+        // highest transaction number not less than the end of the range. The last shard of an
+        // address is keyed with `u64::MAX`, so it's always found if the address has any shards.
+        let mut shard = cursor.seek(ShardedKey::new(address, *range.end()))?;
+        while let Some((sharded_key, list)) = shard {
+            if sharded_key.key != address {
+                break
+            }
+
+            for tx_number in list.iter().rev().skip_while(|tx_number| tx_number > range.end()) {
+                if tx_number < *range.start() || transactions.len() >= limit {
+                    return Ok(transactions)
+                }
+                transactions.push(tx_number);
+            }
+
+            shard = cursor.prev()?;
+        }
+
+        Ok(transactions)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes + 'static> DatabaseProvider<TX, N> {
+    /// Returns an error if the account or storage changesets of the given block were pruned.
+    fn ensure_changesets_available(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            // The checkpoint stores the highest pruned block number.
+            if self
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .is_some_and(|pruned| block_number <= pruned)
+            {
+                return Err(ProviderError::StateAtBlockPruned(block_number))
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the state was downloaded with snap sync, in which case the plain state
+    /// can't be iterated.
+    fn ensure_plain_state_complete(&self) -> ProviderResult<()> {
+        if self
+            .get_stage_checkpoint(StageId::SnapSync)?
+            .and_then(|checkpoint| checkpoint.snap_sync_pivot())
+            .is_some()
+        {
+            return Err(ProviderError::SnapSyncedPlainState)
+        }
+        Ok(())
+    }
+
+    /// Returns the non-zero storage slots of the account in the given historical state, starting
+    /// at the `start` slot and holding at most `limit` slots.
+    ///
+    /// Every slot that was set in that state is either still in the plain state or was changed
+    /// by a later block, in which case it is in the storage history index. Both are walked from
+    /// the `start` slot in order, so only the slots of the page are read.
+    fn historical_storage(
+        &self,
+        state: &HistoricalStateProviderRef<'_, Self>,
+        address: Address,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<BTreeMap<B256, U256>> {
+        let mut plain_cursor = self.tx.cursor_dup_read::<tables::PlainStorageState>()?;
+        let mut plain = plain_cursor
+            .walk_dup(Some(address), Some(start))?
+            .map(|entry| entry.map(|(_, StorageEntry { key, .. })| key));
+        let mut history_cursor = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut history =
+            history_cursor.walk(Some(StorageShardedKey::new(address, start, 0)))?.map(|entry| {
+                entry.map(|(key, _)| (key.address == address).then_some(key.sharded_key.key))
+            });
+
+        let mut storage = BTreeMap::new();
+        let mut next_plain = plain.next().transpose()?;
+        let mut next_history = history.next().transpose()?.flatten();
+        while storage.len() < limit {
+            let Some(slot) = next_plain.into_iter().chain(next_history).min() else { break };
+            if next_plain == Some(slot) {
+                next_plain = plain.next().transpose()?;
+            }
+            // the history index holds one entry per shard of the slot
+            while next_history == Some(slot) {
+                next_history = history.next().transpose()?.flatten();
+            }
+
+            if let Some(value) = state.storage(address, slot)?.filter(|value| !value.is_zero()) {
+                storage.insert(slot, value);
+            }
+        }
+        Ok(storage)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes + 'static> PlainStateReader for DatabaseProvider<TX, N> {
+    fn changed_addresses_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.ensure_changesets_available(*range.start())?;
+
+        let mut addresses = self.changed_accounts_with_range(range.clone())?;
+        addresses.extend(self.changed_storages_with_range(range)?.into_keys());
+        Ok(addresses)
+    }
+
+    fn plain_state_at(
+        &self,
+        block_number: BlockNumber,
+        start: Address,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<BTreeMap<Address, AccountWithStorage>> {
+        self.ensure_plain_state_complete()?;
+        self.ensure_changesets_available(block_number + 1)?;
+        let state = HistoricalStateProviderRef::new(self, block_number + 1);
+
+        // Every account that existed after the block is either still in the plain state or was
+        // changed by a later block, in which case it is in the history index.
+        let mut plain_cursor = self.tx.cursor_read::<tables::PlainAccountState>()?;
+        let mut plain =
+            plain_cursor.walk(Some(start))?.map(|entry| entry.map(|(address, _)| address));
+        let mut history_cursor = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        let mut history = history_cursor
+            .walk(Some(ShardedKey::new(start, 0)))?
+            .map(|entry| entry.map(|(key, _)| key.key));
+
+        let mut accounts = BTreeMap::new();
+        let mut next_plain = plain.next().transpose()?;
+        let mut next_history = history.next().transpose()?;
+        while accounts.len() < limit {
+            let Some(address) = next_plain.into_iter().chain(next_history).min() else { break };
+            if next_plain == Some(address) {
+                next_plain = plain.next().transpose()?;
+            }
+            // the history index holds one entry per shard of the address
+            while next_history == Some(address) {
+                next_history = history.next().transpose()?;
+            }
+
+            if let Some(info) = state.basic_account(&address)? {
+                let storage =
+                    self.historical_storage(&state, address, B256::ZERO, storage_limit)?;
+                accounts.insert(address, AccountWithStorage { info, storage });
+            }
+        }
+
+        Ok(accounts)
+    }
+
+    fn plain_storage_at(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<BTreeMap<B256, U256>> {
+        self.ensure_plain_state_complete()?;
+        self.ensure_changesets_available(block_number + 1)?;
+        let state = HistoricalStateProviderRef::new(self, block_number + 1);
+        self.historical_storage(&state, address, start, limit)
     }
 }
 
@@ -1456,7 +1886,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
                     Ok(Some(Vec::new()))
                 } else {
                     Ok(Some(self.transactions_by_tx_range_with_cursor(tx_range, &mut tx_cursor)?))
-                }
+                };
             }
         }
         Ok(None)
@@ -1499,11 +1929,22 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        self.cursor_read_collect::<tables::TransactionSenders>(range)
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::Senders,
+            to_range(range),
+            |static_file, range, _| static_file.senders_by_tx_range(range),
+            |range, _| self.cursor_read_collect::<tables::TransactionSenders>(range),
+            |_| true,
+        )
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        Ok(self.tx.get::<tables::TransactionSenders>(id)?)
+        self.static_file_provider.get_with_static_file_or_database(
+            StaticFileSegment::Senders,
+            id,
+            |static_file| static_file.transaction_sender(id),
+            || Ok(self.tx.get::<tables::TransactionSenders>(id)?),
+        )
     }
 }
 
@@ -1538,7 +1979,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
                     Ok(Some(Vec::new()))
                 } else {
                     self.receipts_by_tx_range(tx_range).map(Some)
-                }
+                };
             }
         }
         Ok(None)
@@ -1577,7 +2018,7 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
         }
 
         if block_body_indices.is_empty() {
-            return Ok(Vec::new());
+            return Ok(Vec::new())
         }
 
         // find blocks with transactions to determine transaction range
@@ -1729,9 +2170,7 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
+        self.storage_changesets_range(range)?
             // fold all storages and save its old state so we can remove it from HashedStorage
             // it is needed as it is dup table.
             .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
@@ -1745,20 +2184,17 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
-        let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
-                BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
-                    storages
-                        .entry((index.address(), storage.key))
-                        .or_default()
-                        .push(index.block_number());
-                    Ok(storages)
-                },
-            )?;
+        let storage_changeset_lists = self.storage_changesets_range(range)?.try_fold(
+            BTreeMap::new(),
+            |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| {
+                let (index, storage) = entry?;
+                storages
+                    .entry((index.address(), storage.key))
+                    .or_default()
+                    .push(index.block_number());
+                Ok::<_, ProviderError>(storages)
+            },
+        )?;
 
         Ok(storage_changeset_lists)
     }
@@ -2077,10 +2513,12 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         block: BlockNumber,
         remove_receipts_from: StorageLocation,
     ) -> ProviderResult<()> {
+        self.ensure_changesets_not_in_static_files(block + 1)?;
+
         let range = block + 1..=self.last_block_number()?;
 
         if range.is_empty() {
-            return Ok(());
+            return Ok(())
         }
 
         // We are not removing block meta as it is used to get block changesets.
@@ -2172,6 +2610,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         block: BlockNumber,
         remove_receipts_from: StorageLocation,
     ) -> ProviderResult<ExecutionOutcome<Self::Receipt>> {
+        self.ensure_changesets_not_in_static_files(block + 1)?;
+
         let range = block + 1..=self.last_block_number()?;
 
         if range.is_empty() {
@@ -2409,6 +2849,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeMap<B256, Option<Account>>> {
+        self.ensure_changesets_not_in_static_files(range_start_block(&range, |block| *block))?;
+
         let changesets = self
             .tx
             .cursor_read::<tables::AccountChangeSets>()?
@@ -2473,6 +2915,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
+        self.ensure_changesets_not_in_static_files(range_start_block(&range, |key| {
+            key.block_number()
+        }))?;
+
         let changesets = self
             .tx
             .cursor_read::<tables::StorageChangeSets>()?
@@ -2589,7 +3035,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
                     root: GotExpected { got: state_root, expected: expected_state_root },
                     block_number: *range.end(),
                     block_hash: end_block_hash,
-                })))
+                })));
             }
             self.write_trie_updates(&trie_updates)?;
         }
@@ -2640,6 +3086,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<usize> {
+        self.ensure_changesets_not_in_static_files(range_start_block(&range, |block| *block))?;
+
         let changesets = self
             .tx
             .cursor_read::<tables::AccountChangeSets>()?
@@ -2698,6 +3146,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
+        self.ensure_changesets_not_in_static_files(range_start_block(&range, |key| {
+            key.block_number()
+        }))?;
+
         let changesets = self
             .tx
             .cursor_read::<tables::StorageChangeSets>()?
@@ -2718,6 +3170,44 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         )
     }
 
+    fn unwind_address_transactions_indices(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, TxNumber)>,
+    ) -> ProviderResult<usize> {
+        let mut first_indices = addresses.into_iter().collect::<Vec<_>>();
+        first_indices.sort_unstable();
+        // Keep only the lowest transaction number of every address.
+        first_indices.dedup_by_key(|(address, _)| *address);
+
+        let mut cursor = self.tx.cursor_write::<tables::AddressTransactions>()?;
+        for &(address, rem_index) in &first_indices {
+            let partial_shard = unwind_history_shards::<_, tables::AddressTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                rem_index,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(first_indices.len())
+    }
+
+    fn insert_address_transactions_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = TxNumber>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::AddressTransactions>(index_updates, ShardedKey::new)
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -2743,6 +3233,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         block: BlockNumber,
         remove_from: StorageLocation,
     ) -> ProviderResult<Chain<Self::Primitives>> {
+        self.unwind_address_transactions_above(block)?;
+
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range.clone())?;
@@ -2767,6 +3259,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
         block: BlockNumber,
         remove_from: StorageLocation,
     ) -> ProviderResult<()> {
+        self.unwind_address_transactions_above(block)?;
+
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_range(range)?;
@@ -2807,7 +3301,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
     /// [`BlockWithdrawals`](tables::BlockWithdrawals).
     ///
     /// If the provider has __not__ configured full sender pruning, this will modify
-    /// [`TransactionSenders`](tables::TransactionSenders).
+    /// [`TransactionSenders`](tables::TransactionSenders), or append to the
+    /// [`StaticFileSegment::Senders`] static files if senders are not pruned at all and the
+    /// segment is contiguous with the inserted block.
     ///
     /// If the provider has __not__ configured full transaction lookup pruning, this will modify
     /// [`TransactionHashNumbers`](tables::TransactionHashNumbers).
@@ -2862,11 +3358,29 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
 
         let tx_count = block.body().transaction_count() as u64;
 
+        // Senders that are never pruned go to static files, unless the segment does not
+        // continue at this block, e.g. because older senders are still in the database.
+        let mut senders_writer = None;
+        if write_to.static_files() &&
+            self.prune_modes.sender_recovery.is_none() &&
+            self.static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::Senders)
+                .is_some()
+        {
+            let mut writer = self.static_file_provider.latest_writer(StaticFileSegment::Senders)?;
+            if writer.next_block_number() == block_number {
+                writer.increment_block(block_number)?;
+                senders_writer = Some(writer);
+            }
+        }
+
         // Ensures we have all the senders for the block's transactions.
         for (transaction, sender) in block.body().transactions_iter().zip(block.senders_iter()) {
             let hash = transaction.tx_hash();
 
-            if self.prune_modes.sender_recovery.as_ref().is_none_or(|m| !m.is_full()) {
+            if let Some(writer) = senders_writer.as_mut() {
+                writer.append_sender(next_tx_num, sender)?;
+            } else if self.prune_modes.sender_recovery.as_ref().is_none_or(|m| !m.is_full()) {
                 self.tx.put::<tables::TransactionSenders>(next_tx_num, *sender)?;
             }
 
@@ -2997,6 +3511,22 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
 
         self.remove::<tables::TransactionSenders>(unwind_tx_from..)?;
 
+        if remove_from.static_files() &&
+            self.static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::Senders)
+                .is_some_and(|highest| highest > block)
+        {
+            let to_delete = self
+                .static_file_provider
+                .get_highest_static_file_tx(StaticFileSegment::Senders)
+                .map(|static_tx| (static_tx + 1).saturating_sub(unwind_tx_from))
+                .unwrap_or_default();
+
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::Senders)?
+                .prune_senders(to_delete, block)?;
+        }
+
         self.remove_bodies_above(block, remove_from)?;
 
         Ok(())
@@ -3193,6 +3723,9 @@ mod tests {
         test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
         BlockWriter,
     };
+    use reth_db_api::models::{
+        StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx,
+    };
     use reth_testing_utils::generators::{self, random_block, BlockParams};
 
     #[test]
@@ -3438,4 +3971,114 @@ mod tests {
 
         assert_eq!(range_result, individual_results);
     }
+
+    #[test]
+    fn test_unwind_across_static_file_changesets() {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_block(
+                data.genesis.clone().try_recover().unwrap(),
+                crate::StorageLocation::Database,
+            )
+            .unwrap();
+        for i in 0..3 {
+            provider_rw
+                .insert_block(data.blocks[i].0.clone(), crate::StorageLocation::Database)
+                .unwrap();
+            provider_rw
+                .write_state(
+                    &data.blocks[i].1,
+                    crate::OriginalValuesKnown::No,
+                    crate::StorageLocation::Database,
+                )
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        let changed_accounts = provider.changed_accounts_and_blocks_with_range(1..=3).unwrap();
+        let changed_storages = provider.changed_storages_and_blocks_with_range(1..=3).unwrap();
+        let account_changeset = provider.account_block_changeset(1).unwrap();
+        let storage_changeset = provider.storage_changeset(1).unwrap();
+        assert!(!account_changeset.is_empty());
+        drop(provider);
+
+        // Move the changesets up to block 1 to static files and prune them from the database.
+        let provider_rw = factory.provider_rw().unwrap();
+        let static_file_provider = factory.static_file_provider();
+        let mut account_writer =
+            static_file_provider.get_writer(0, StaticFileSegment::AccountChangeSets).unwrap();
+        let mut storage_writer =
+            static_file_provider.get_writer(0, StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=1 {
+            let storage_changes = provider_rw.storage_changeset(block).unwrap();
+            account_writer
+                .append_account_change_set(
+                    &StaticFileAccountChangeSet {
+                        changes: provider_rw.account_block_changeset(block).unwrap(),
+                    },
+                    block,
+                )
+                .unwrap();
+            storage_writer
+                .append_storage_change_set(
+                    &StaticFileStorageChangeSet {
+                        changes: storage_changes
+                            .iter()
+                            .map(|(block_address, entry)| StorageBeforeTx {
+                                address: block_address.address(),
+                                entry: *entry,
+                            })
+                            .collect(),
+                    },
+                    block,
+                )
+                .unwrap();
+
+            provider_rw.tx_ref().delete::<tables::AccountChangeSets>(block, None).unwrap();
+            for (block_address, _) in storage_changes {
+                provider_rw
+                    .tx_ref()
+                    .delete::<tables::StorageChangeSets>(block_address, None)
+                    .unwrap();
+            }
+        }
+        account_writer.commit().unwrap();
+        storage_writer.commit().unwrap();
+        drop((account_writer, storage_writer));
+        provider_rw.commit().unwrap();
+
+        // Ranges spanning the boundary read from both static files and the database.
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.changed_accounts_and_blocks_with_range(1..=3).unwrap(),
+            changed_accounts
+        );
+        assert_eq!(
+            provider.changed_storages_and_blocks_with_range(1..=3).unwrap(),
+            changed_storages
+        );
+        assert_eq!(provider.account_block_changeset(1).unwrap(), account_changeset);
+        assert_eq!(provider.storage_changeset(1).unwrap(), storage_changeset);
+        drop(provider);
+
+        // The state can not be unwound into the blocks whose changesets are in static files.
+        let provider_rw = factory.provider_rw().unwrap();
+        assert!(matches!(
+            provider_rw.remove_state_above(0, crate::StorageLocation::Database),
+            Err(ProviderError::FinalizedStaticFile(StaticFileSegment::AccountChangeSets, 1))
+        ));
+
+        // Unwinding down to the boundary only touches the database changesets.
+        provider_rw.remove_state_above(1, crate::StorageLocation::Database).unwrap();
+        assert!(provider_rw.account_block_changeset(2).unwrap().is_empty());
+        assert_eq!(provider_rw.account_block_changeset(1).unwrap(), account_changeset);
+        assert_eq!(
+            provider_rw.changed_accounts_and_blocks_with_range(1..=3).unwrap(),
+            provider_rw.changed_accounts_and_blocks_with_range(1..=1).unwrap()
+        );
+    }
 }
@@ -1,34 +1,41 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
+    providers::state::{macros::delegate_provider_impls, snap},
+    AccountReader, BlockHashReader, HashedPostStateProvider, ProviderError, StateProvider,
+    StateRootProvider, StaticFileProviderFactory,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    keccak256,
+    map::{AddressMap, B256Map},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
+};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
     table::Table,
     tables,
     transaction::DbTx,
     BlockNumberList,
 };
 use reth_primitives_traits::{Account, Bytecode};
+use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockNumReader, DBProvider, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    BlockNumReader, ChangeSetReader, DBProvider, StateCommitmentProvider, StateProofProvider,
+    StorageChangeSetReader, StorageRootProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, KeyHasher, MultiProof, MultiProofTargets,
+    StateRoot, StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
     DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness, StateCommitment,
 };
-use std::fmt::Debug;
+use std::{borrow::Cow, fmt::Debug};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -49,6 +56,8 @@ pub struct HistoricalStateProviderRef<'b, Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Whether the state was downloaded with snap sync.
+    snap_synced: Cow<'b, snap::SnapSynced>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -59,12 +68,24 @@ pub enum HistoryInfo {
     MaybeInPlainState,
 }
 
-impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProviderRef<'b, Provider>
+impl<
+        'b,
+        Provider: DBProvider
+            + BlockNumReader
+            + StateCommitmentProvider
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StaticFileProviderFactory,
+    > HistoricalStateProviderRef<'b, Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snap_synced: Cow::Owned(snap::SnapSynced::new()),
+        }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -74,7 +95,12 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
         block_number: BlockNumber,
         lowest_available_blocks: LowestAvailableBlocks,
     ) -> Self {
-        Self { provider, block_number, lowest_available_blocks }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks,
+            snap_synced: Cow::Owned(snap::SnapSynced::new()),
+        }
     }
 
     /// Lookup an account in the `AccountsHistory` table
@@ -134,9 +160,72 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedPostState::from_reverts::<
-            <Provider::StateCommitment as StateCommitment>::KeyHasher,
-        >(self.tx(), self.block_number)?)
+        type KH<P> =
+            <<P as StateCommitmentProvider>::StateCommitment as StateCommitment>::KeyHasher;
+
+        let mut state =
+            HashedPostState::from_reverts::<KH<Provider>>(self.tx(), self.block_number)?;
+
+        // Changesets moved to static files precede the ones left in the database, so the values
+        // recorded before their first occurring change take precedence. Every key changed since
+        // this block has to be discovered, so they are walked like the database ones are.
+        let static_file_provider = self.provider.static_file_provider();
+        let mut accounts = AddressMap::<Option<Account>>::default();
+        for block_number in self.static_file_change_sets_range(StaticFileSegment::AccountChangeSets)
+        {
+            for AccountBeforeTx { address, info } in
+                static_file_provider.account_block_changeset(block_number)?
+            {
+                accounts.entry(address).or_insert(info);
+            }
+        }
+        let mut storages = AddressMap::<B256Map<U256>>::default();
+        for block_number in self.static_file_change_sets_range(StaticFileSegment::StorageChangeSets)
+        {
+            for (block_address, entry) in static_file_provider.storage_changeset(block_number)? {
+                storages
+                    .entry(block_address.address())
+                    .or_default()
+                    .entry(entry.key)
+                    .or_insert(entry.value);
+            }
+        }
+
+        state.extend(HashedPostState {
+            accounts: accounts
+                .into_iter()
+                .map(|(address, info)| (KH::<Provider>::hash_key(address), info))
+                .collect(),
+            storages: storages
+                .into_iter()
+                .map(|(address, storage)| {
+                    (
+                        KH::<Provider>::hash_key(address),
+                        HashedStorage::from_iter(
+                            false,
+                            storage
+                                .into_iter()
+                                .map(|(slot, value)| (KH::<Provider>::hash_key(slot), value)),
+                        ),
+                    )
+                })
+                .collect(),
+        });
+        Ok(state)
+    }
+
+    /// Returns the blocks from this block on whose changesets of the given segment were moved to
+    /// static files.
+    fn static_file_change_sets_range(
+        &self,
+        segment: StaticFileSegment,
+    ) -> impl Iterator<Item = BlockNumber> {
+        self.provider
+            .static_file_provider()
+            .get_highest_static_file_block(segment)
+            .map(|highest| self.block_number..=highest)
+            .into_iter()
+            .flatten()
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
@@ -153,7 +242,58 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
+        let mut storage = HashedStorage::from_reverts(self.tx(), address, self.block_number)?;
+
+        // Static file changesets precede the database ones, see `Self::revert_state`. Instead of
+        // walking them, the first change of every slot since this block is found through the
+        // storage history index, so only the slots of this address are visited.
+        let Some(highest) = self
+            .provider
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest| *highest >= self.block_number)
+        else {
+            return Ok(storage)
+        };
+
+        let mut static_file_storage = HashedStorage::new(false);
+        let mut cursor = self.tx().cursor_read::<tables::StoragesHistory>()?;
+        let mut next_slot = cursor.seek(StorageShardedKey::new(address, B256::ZERO, 0))?;
+        while let Some((key, _)) = next_slot.filter(|(key, _)| key.address == address) {
+            let storage_key = key.sharded_key.key;
+
+            // The first shard of the slot ending at or after this block holds the first change
+            // since this block, if any.
+            if let Some(block_number) = cursor
+                .seek(StorageShardedKey::new(address, storage_key, self.block_number))?
+                .filter(|(key, _)| key.address == address && key.sharded_key.key == storage_key)
+                .and_then(|(_, blocks)| {
+                    blocks.iter().find(|block_number| *block_number >= self.block_number)
+                })
+                .filter(|block_number| *block_number <= highest)
+            {
+                let entry = self
+                    .provider
+                    .get_storage_before_block(block_number, address, storage_key)?
+                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                        block_number,
+                        address,
+                        storage_key: Box::new(storage_key),
+                    })?;
+                static_file_storage.storage.insert(keccak256(storage_key), entry.value);
+            }
+
+            // Skip the remaining shards of the slot, the last one is always keyed by `u64::MAX`.
+            next_slot = match cursor.seek(StorageShardedKey::last(address, storage_key))? {
+                Some((key, _)) if key.address == address && key.sharded_key.key == storage_key => {
+                    cursor.next()?
+                }
+                entry => entry,
+            };
+        }
+
+        storage.extend(&static_file_storage);
+        Ok(storage)
     }
 
     fn history_info<T, K>(
@@ -244,25 +384,32 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provi
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> AccountReader
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + StateCommitmentProvider
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StaticFileProviderFactory,
+    > AccountReader for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(*address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, *address)?
-                .filter(|acc| &acc.address == address)
+                .provider
+                .get_account_before_block(changeset_block_number, *address)?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address: *address,
                 })?
                 .info),
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
-                Ok(self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)?)
+                match self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)? {
+                    Some(account) => Ok(Some(account)),
+                    None => self.snap_synced.hashed_account(self.tx(), address),
+                }
             }
         }
     }
@@ -285,8 +432,14 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + StateCommitmentProvider
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StaticFileProviderFactory,
+    > StateRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
         let mut revert_state = self.revert_state()?;
@@ -321,8 +474,14 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + StateCommitmentProvider
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StaticFileProviderFactory,
+    > StorageRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn storage_root(
         &self,
@@ -360,8 +519,14 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProofProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + StateCommitmentProvider
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StaticFileProviderFactory,
+    > StateProofProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get account and storage proofs.
     fn proof(
@@ -401,8 +566,15 @@ impl<Provider: StateCommitmentProvider> HashedPostStateProvider
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider>
-    StateProvider for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + BlockHashReader
+            + StateCommitmentProvider
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StaticFileProviderFactory,
+    > StateProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get storage.
     fn storage(
@@ -413,10 +585,8 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx()
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.provider
+                    .get_storage_before_block(changeset_block_number, address, storage_key)?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -424,13 +594,21 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
                     })?
                     .value,
             )),
-            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::PlainStorageState>()?
-                .seek_by_key_subkey(address, storage_key)?
-                .filter(|entry| entry.key == storage_key)
-                .map(|entry| entry.value)
-                .or(Some(StorageValue::ZERO))),
+            HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
+                match self
+                    .tx()
+                    .cursor_dup_read::<tables::PlainStorageState>()?
+                    .seek_by_key_subkey(address, storage_key)?
+                    .filter(|entry| entry.key == storage_key)
+                {
+                    Some(entry) => Ok(Some(entry.value)),
+                    None => Ok(Some(
+                        self.snap_synced
+                            .hashed_storage(self.tx(), &address, &storage_key)?
+                            .unwrap_or(StorageValue::ZERO),
+                    )),
+                }
+            }
         }
     }
 
@@ -456,14 +634,27 @@ pub struct HistoricalStateProvider<Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Whether the state was downloaded with snap sync.
+    snap_synced: snap::SnapSynced,
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProvider<Provider>
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + StateCommitmentProvider
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StaticFileProviderFactory,
+    > HistoricalStateProvider<Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
-        Self { provider, block_number, lowest_available_blocks: Default::default() }
+        Self {
+            provider,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            snap_synced: snap::SnapSynced::new(),
+        }
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -487,11 +678,12 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     const fn as_ref(&self) -> HistoricalStateProviderRef<'_, Provider> {
-        HistoricalStateProviderRef::new_with_lowest_available_blocks(
-            &self.provider,
-            self.block_number,
-            self.lowest_available_blocks,
-        )
+        HistoricalStateProviderRef {
+            provider: &self.provider,
+            block_number: self.block_number,
+            lowest_available_blocks: self.lowest_available_blocks,
+            snap_synced: Cow::Borrowed(&self.snap_synced),
+        }
     }
 }
 
@@ -502,7 +694,7 @@ impl<Provider: StateCommitmentProvider> StateCommitmentProvider
 }
 
 // Delegates all provider impls to [HistoricalStateProviderRef]
-delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider]);
+delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider + ChangeSetReader + StorageChangeSetReader + StaticFileProviderFactory]);
 
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
@@ -535,21 +727,29 @@ impl LowestAvailableBlocks {
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
+        providers::{
+            state::historical::{HistoryInfo, LowestAvailableBlocks},
+            StaticFileWriter,
+        },
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        StaticFileProviderFactory,
     };
-    use alloy_primitives::{address, b256, Address, B256, U256};
+    use alloy_primitives::{address, b256, keccak256, Address, B256, U256};
     use reth_db_api::{
-        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey,
+            StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx,
+        },
         tables,
         transaction::{DbTx, DbTxMut},
         BlockNumberList,
     };
     use reth_primitives_traits::{Account, StorageEntry};
+    use reth_static_file_types::StaticFileSegment;
     use reth_storage_api::{
-        BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory,
-        StateCommitmentProvider,
+        BlockHashReader, BlockNumReader, ChangeSetReader, DBProvider, DatabaseProviderFactory,
+        StateCommitmentProvider, StorageChangeSetReader,
     };
     use reth_storage_errors::provider::ProviderError;
 
@@ -561,7 +761,13 @@ mod tests {
     const fn assert_state_provider<T: StateProvider>() {}
     #[expect(dead_code)]
     const fn assert_historical_state_provider<
-        T: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider,
+        T: DBProvider
+            + BlockNumReader
+            + BlockHashReader
+            + StateCommitmentProvider
+            + ChangeSetReader
+            + StorageChangeSetReader
+            + StaticFileProviderFactory,
     >() {
         assert_state_provider::<HistoricalStateProvider<T>>();
     }
@@ -774,6 +980,244 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn history_provider_get_from_static_files() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([3, 10]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([3, 10]).unwrap(),
+        )
+        .unwrap();
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at10 = Account { nonce: 10, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let entry_plain = StorageEntry { key: STORAGE, value: U256::from(100) };
+        let entry_at10 = StorageEntry { key: STORAGE, value: U256::from(10) };
+        let entry_at3 = StorageEntry { key: STORAGE, value: U256::from(3) };
+
+        // Changesets up to block 5 are in static files, the rest in the database.
+        let static_file_provider = factory.static_file_provider();
+        let mut account_writer =
+            static_file_provider.get_writer(0, StaticFileSegment::AccountChangeSets).unwrap();
+        let mut storage_writer =
+            static_file_provider.get_writer(0, StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=5 {
+            let (account_changes, storage_changes) = if block == 3 {
+                (
+                    vec![AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) }],
+                    vec![StorageBeforeTx { address: ADDRESS, entry: entry_at3 }],
+                )
+            } else {
+                Default::default()
+            };
+            account_writer
+                .append_account_change_set(
+                    &StaticFileAccountChangeSet { changes: account_changes },
+                    block,
+                )
+                .unwrap();
+            storage_writer
+                .append_storage_change_set(
+                    &StaticFileStorageChangeSet { changes: storage_changes },
+                    block,
+                )
+                .unwrap();
+        }
+        account_writer.commit().unwrap();
+        storage_writer.commit().unwrap();
+        drop((account_writer, storage_writer));
+
+        tx.put::<tables::AccountChangeSets>(
+            10,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at10) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>((10, ADDRESS).into(), entry_at10).unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, acc_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(ADDRESS, entry_plain).unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 3).basic_account(&ADDRESS),
+            Ok(Some(acc)) if acc == acc_at3
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 4).basic_account(&ADDRESS),
+            Ok(Some(acc)) if acc == acc_at10
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 11).basic_account(&ADDRESS),
+            Ok(Some(acc)) if acc == acc_plain
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 3).storage(ADDRESS, STORAGE),
+            Ok(Some(value)) if value == entry_at3.value
+        ));
+        assert!(matches!(
+            HistoricalStateProviderRef::new(&db, 4).storage(ADDRESS, STORAGE),
+            Ok(Some(value)) if value == entry_at10.value
+        ));
+
+        // Reverts from static files take precedence over the later database ones.
+        let revert_state = HistoricalStateProviderRef::new(&db, 3).revert_state().unwrap();
+        assert_eq!(revert_state.accounts.get(&keccak256(ADDRESS)), Some(&Some(acc_at3)));
+        assert_eq!(
+            revert_state
+                .storages
+                .get(&keccak256(ADDRESS))
+                .unwrap()
+                .storage
+                .get(&keccak256(STORAGE)),
+            Some(&entry_at3.value)
+        );
+        let revert_storage =
+            HistoricalStateProviderRef::new(&db, 4).revert_storage(ADDRESS).unwrap();
+        assert_eq!(revert_storage.storage.get(&keccak256(STORAGE)), Some(&entry_at10.value));
+    }
+
+    #[test]
+    fn history_provider_get_at_static_file_boundary() {
+        const OTHER_STORAGE: B256 =
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000002");
+
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([5, 6]).unwrap(),
+        )
+        .unwrap();
+        for (address, storage_key, blocks) in [
+            (ADDRESS, STORAGE, vec![5, 6]),
+            (ADDRESS, OTHER_STORAGE, vec![2]),
+            (HIGHER_ADDRESS, STORAGE, vec![5]),
+        ] {
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey {
+                    address,
+                    sharded_key: ShardedKey { key: storage_key, highest_block_number: u64::MAX },
+                },
+                BlockNumberList::new(blocks).unwrap(),
+            )
+            .unwrap();
+        }
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at6 = Account { nonce: 6, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at5 = Account { nonce: 5, balance: U256::ZERO, bytecode_hash: None };
+        let entry_plain = StorageEntry { key: STORAGE, value: U256::from(100) };
+        let entry_at6 = StorageEntry { key: STORAGE, value: U256::from(6) };
+        let entry_at5 = StorageEntry { key: STORAGE, value: U256::from(5) };
+        let other_entry_plain = StorageEntry { key: OTHER_STORAGE, value: U256::from(200) };
+        let other_entry_at2 = StorageEntry { key: OTHER_STORAGE, value: U256::from(2) };
+        let higher_entry_at5 = StorageEntry { key: STORAGE, value: U256::from(55) };
+
+        // Changesets up to block 5 are in static files, block 6 is the first one in the database.
+        let static_file_provider = factory.static_file_provider();
+        let mut account_writer =
+            static_file_provider.get_writer(0, StaticFileSegment::AccountChangeSets).unwrap();
+        let mut storage_writer =
+            static_file_provider.get_writer(0, StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=5 {
+            let (account_changes, storage_changes) = match block {
+                2 => (vec![], vec![StorageBeforeTx { address: ADDRESS, entry: other_entry_at2 }]),
+                5 => (
+                    vec![AccountBeforeTx { address: ADDRESS, info: Some(acc_at5) }],
+                    vec![
+                        StorageBeforeTx { address: ADDRESS, entry: entry_at5 },
+                        StorageBeforeTx { address: HIGHER_ADDRESS, entry: higher_entry_at5 },
+                    ],
+                ),
+                _ => Default::default(),
+            };
+            account_writer
+                .append_account_change_set(
+                    &StaticFileAccountChangeSet { changes: account_changes },
+                    block,
+                )
+                .unwrap();
+            storage_writer
+                .append_storage_change_set(
+                    &StaticFileStorageChangeSet { changes: storage_changes },
+                    block,
+                )
+                .unwrap();
+        }
+        account_writer.commit().unwrap();
+        storage_writer.commit().unwrap();
+        drop((account_writer, storage_writer));
+
+        tx.put::<tables::AccountChangeSets>(
+            6,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at6) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>((6, ADDRESS).into(), entry_at6).unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, acc_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(ADDRESS, entry_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(ADDRESS, other_entry_plain).unwrap();
+        tx.commit().unwrap();
+
+        let db = factory.provider().unwrap();
+
+        // The last block in static files, the first one in the database and the one after it.
+        for (block, account, value) in [
+            (5, acc_at5, entry_at5.value),
+            (6, acc_at6, entry_at6.value),
+            (7, acc_plain, entry_plain.value),
+        ] {
+            let provider = HistoricalStateProviderRef::new(&db, block);
+            assert_eq!(provider.basic_account(&ADDRESS).unwrap(), Some(account));
+            assert_eq!(provider.storage(ADDRESS, STORAGE).unwrap(), Some(value));
+            assert_eq!(
+                provider.storage(ADDRESS, OTHER_STORAGE).unwrap(),
+                Some(other_entry_plain.value)
+            );
+        }
+
+        // Only the slots of the address changed since the block are reverted.
+        let revert_storage =
+            HistoricalStateProviderRef::new(&db, 5).revert_storage(ADDRESS).unwrap();
+        assert_eq!(revert_storage.storage.len(), 1);
+        assert_eq!(revert_storage.storage.get(&keccak256(STORAGE)), Some(&entry_at5.value));
+        let revert_storage =
+            HistoricalStateProviderRef::new(&db, 6).revert_storage(ADDRESS).unwrap();
+        assert_eq!(revert_storage.storage.len(), 1);
+        assert_eq!(revert_storage.storage.get(&keccak256(STORAGE)), Some(&entry_at6.value));
+        let revert_storage =
+            HistoricalStateProviderRef::new(&db, 2).revert_storage(ADDRESS).unwrap();
+        assert_eq!(revert_storage.storage.len(), 2);
+        assert_eq!(
+            revert_storage.storage.get(&keccak256(OTHER_STORAGE)),
+            Some(&other_entry_at2.value)
+        );
+
+        let revert_state = HistoricalStateProviderRef::new(&db, 5).revert_state().unwrap();
+        assert_eq!(revert_state.accounts.get(&keccak256(ADDRESS)), Some(&Some(acc_at5)));
+        assert_eq!(
+            revert_state.storages[&keccak256(ADDRESS)].storage.get(&keccak256(STORAGE)),
+            Some(&entry_at5.value)
+        );
+        assert_eq!(
+            revert_state.storages[&keccak256(HIGHER_ADDRESS)].storage.get(&keccak256(STORAGE)),
+            Some(&higher_entry_at5.value)
+        );
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let factory = create_test_provider_factory();
@@ -1,6 +1,6 @@
 use crate::{
-    providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
-    HashedPostStateProvider, StateProvider, StateRootProvider,
+    providers::state::{macros::delegate_provider_impls, snap},
+    AccountReader, BlockHashReader, HashedPostStateProvider, StateProvider, StateRootProvider,
 };
 use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
 use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
@@ -20,17 +20,18 @@ use reth_trie_db::{
     DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
     DatabaseTrieWitness, StateCommitment,
 };
+use std::borrow::Cow;
 
 /// State provider over latest state that takes tx reference.
 ///
 /// Wraps a [`DBProvider`] to get access to database.
 #[derive(Debug)]
-pub struct LatestStateProviderRef<'b, Provider>(&'b Provider);
+pub struct LatestStateProviderRef<'b, Provider>(&'b Provider, Cow<'b, snap::SnapSynced>);
 
 impl<'b, Provider: DBProvider> LatestStateProviderRef<'b, Provider> {
     /// Create new state provider
     pub const fn new(provider: &'b Provider) -> Self {
-        Self(provider)
+        Self(provider, Cow::Owned(snap::SnapSynced::new()))
     }
 
     fn tx(&self) -> &Provider::Tx {
@@ -41,7 +42,10 @@ impl<'b, Provider: DBProvider> LatestStateProviderRef<'b, Provider> {
 impl<Provider: DBProvider> AccountReader for LatestStateProviderRef<'_, Provider> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
-        self.tx().get_by_encoded_key::<tables::PlainAccountState>(address).map_err(Into::into)
+        match self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)? {
+            Some(account) => Ok(Some(account)),
+            None => self.1.hashed_account(self.tx(), address),
+        }
     }
 }
 
@@ -175,7 +179,7 @@ impl<Provider: DBProvider + BlockHashReader + StateCommitmentProvider> StateProv
                 return Ok(Some(entry.value))
             }
         }
-        Ok(None)
+        self.1.hashed_storage(self.tx(), &account, &storage_key)
     }
 
     /// Get account code by its hash
@@ -192,18 +196,18 @@ impl<Provider: StateCommitmentProvider> StateCommitmentProvider
 
 /// State provider for the latest state.
 #[derive(Debug)]
-pub struct LatestStateProvider<Provider>(Provider);
+pub struct LatestStateProvider<Provider>(Provider, snap::SnapSynced);
 
 impl<Provider: DBProvider + StateCommitmentProvider> LatestStateProvider<Provider> {
     /// Create new state provider
     pub const fn new(db: Provider) -> Self {
-        Self(db)
+        Self(db, snap::SnapSynced::new())
     }
 
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     const fn as_ref(&self) -> LatestStateProviderRef<'_, Provider> {
-        LatestStateProviderRef::new(&self.0)
+        LatestStateProviderRef(&self.0, Cow::Borrowed(&self.1))
     }
 }
 
@@ -217,6 +221,13 @@ delegate_provider_impls!(LatestStateProvider<Provider> where [Provider: DBProvid
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        test_utils::create_test_provider_factory, PlainStateReader, StageCheckpointWriter,
+    };
+    use alloy_primitives::{keccak256, U256};
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives_traits::StorageEntry;
+    use reth_stages_types::{SnapSyncCheckpoint, StageCheckpoint, StageId};
 
     const fn assert_state_provider<T: StateProvider>() {}
     #[expect(dead_code)]
@@ -225,4 +236,52 @@ mod tests {
     >() {
         assert_state_provider::<LatestStateProvider<T>>();
     }
+
+    #[test]
+    fn snap_synced_state_falls_back_to_hashed_state() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, ..Default::default() };
+        let slot = B256::with_last_byte(2);
+        let value = U256::from(3);
+        provider_rw.tx_ref().put::<tables::HashedAccounts>(keccak256(address), account).unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::HashedStorages>(
+                keccak256(address),
+                StorageEntry { key: keccak256(slot), value },
+            )
+            .unwrap();
+
+        // The hashed state isn't used for nodes that weren't snap synced
+        let state = LatestStateProviderRef::new(&*provider_rw);
+        assert_eq!(state.basic_account(&address).unwrap(), None);
+        assert_eq!(state.storage(address, slot).unwrap(), None);
+
+        provider_rw
+            .save_stage_checkpoint(
+                StageId::SnapSync,
+                StageCheckpoint::new(10).with_snap_sync_stage_checkpoint(SnapSyncCheckpoint {
+                    pivot: 10,
+                    ..Default::default()
+                }),
+            )
+            .unwrap();
+        let state = LatestStateProviderRef::new(&*provider_rw);
+        assert_eq!(state.basic_account(&address).unwrap(), Some(account));
+        assert_eq!(state.storage(address, slot).unwrap(), Some(value));
+
+        // The plain state takes precedence
+        let plain_account = Account { nonce: 2, ..Default::default() };
+        provider_rw.tx_ref().put::<tables::PlainAccountState>(address, plain_account).unwrap();
+        assert_eq!(state.basic_account(&address).unwrap(), Some(plain_account));
+
+        // The plain state of a snap synced node is incomplete and can't be iterated
+        assert!(matches!(
+            provider_rw.plain_state_at(0, Address::ZERO, 1, 0),
+            Err(ProviderError::SnapSyncedPlainState)
+        ));
+    }
 }
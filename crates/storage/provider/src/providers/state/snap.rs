@@ -0,0 +1,71 @@
+//! Lookups in the hashed state for nodes whose state was downloaded with snap sync.
+//!
+//! Snap sync only downloads the hashed state of the pivot block, so the plain state tables only
+//! contain the accounts and storage slots changed by the blocks executed after it. Everything
+//! else is read from [`tables::HashedAccounts`] and [`tables::HashedStorages`], which are kept up
+//! to date together with the plain state.
+
+use alloy_primitives::{keccak256, Address, StorageKey, StorageValue};
+use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
+use reth_primitives_traits::Account;
+use reth_stages_types::StageId;
+use reth_storage_errors::provider::ProviderResult;
+use std::sync::OnceLock;
+
+/// Whether the state was downloaded with snap sync.
+///
+/// The stage checkpoint is read on the first lookup that misses the plain state, and cached for
+/// the lifetime of the state provider.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SnapSynced(OnceLock<bool>);
+
+impl SnapSynced {
+    /// Creates a new instance that reads the stage checkpoint on first use.
+    pub(crate) const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Returns `true` if the state was downloaded with snap sync.
+    fn get<TX: DbTx>(&self, tx: &TX) -> ProviderResult<bool> {
+        if let Some(snap_synced) = self.0.get() {
+            return Ok(*snap_synced)
+        }
+        let snap_synced = tx
+            .get::<tables::StageCheckpoints>(StageId::SnapSync.to_string())?
+            .and_then(|checkpoint| checkpoint.snap_sync_pivot())
+            .is_some();
+        Ok(*self.0.get_or_init(|| snap_synced))
+    }
+
+    /// Returns the account from the hashed state if it's missing from the plain state of a snap
+    /// synced node.
+    pub(crate) fn hashed_account<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: &Address,
+    ) -> ProviderResult<Option<Account>> {
+        if !self.get(tx)? {
+            return Ok(None)
+        }
+        Ok(tx.get_by_encoded_key::<tables::HashedAccounts>(&keccak256(address))?)
+    }
+
+    /// Returns the storage slot from the hashed state if it's missing from the plain state of a
+    /// snap synced node.
+    pub(crate) fn hashed_storage<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: &Address,
+        storage_key: &StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if !self.get(tx)? {
+            return Ok(None)
+        }
+        let hashed_slot = keccak256(storage_key);
+        Ok(tx
+            .cursor_dup_read::<tables::HashedStorages>()?
+            .seek_by_key_subkey(keccak256(address), hashed_slot)?
+            .filter(|entry| entry.key == hashed_slot)
+            .map(|entry| entry.value))
+    }
+}
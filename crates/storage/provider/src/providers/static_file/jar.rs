@@ -3,23 +3,24 @@ use super::{
     LoadedJarRef,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, HeaderProvider, ReceiptProvider,
-    TransactionsProvider,
+    to_range, BlockHashReader, BlockNumReader, ChangeSetReader, HeaderProvider, ReceiptProvider,
+    StorageChangeSetReader, TransactionsProvider,
 };
 use alloy_consensus::transaction::{SignerRecoverable, TransactionMeta};
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use reth_chainspec::ChainInfo;
 use reth_db::static_file::{
-    BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask, ReceiptMask, StaticFileCursor,
-    TDWithHashMask, TotalDifficultyMask, TransactionMask,
+    AccountChangeSetMask, BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask,
+    ReceiptMask, SenderMask, StaticFileCursor, StorageChangeSetMask, TDWithHashMask,
+    TotalDifficultyMask, TransactionMask,
 };
 use reth_db_api::{
-    models::StoredBlockBodyIndices,
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
     table::{Decompress, Value},
 };
 use reth_node_types::NodePrimitives;
-use reth_primitives_traits::{SealedHeader, SignedTransaction};
+use reth_primitives_traits::{SealedHeader, SignedTransaction, StorageEntry};
 use reth_storage_api::BlockBodyIndicesProvider;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
@@ -296,11 +297,28 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction>> TransactionsPr
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
+        if self.segment().is_senders() {
+            let range = to_range(range);
+            let mut cursor = self.cursor()?;
+            let mut senders = Vec::with_capacity((range.end - range.start) as usize);
+
+            for num in range {
+                if let Some(sender) = cursor.get_one::<SenderMask>(num.into())? {
+                    senders.push(sender)
+                }
+            }
+            return Ok(senders)
+        }
+
         let txs = self.transactions_by_tx_range(range)?;
         Ok(reth_primitives_traits::transaction::recover::recover_signers(&txs)?)
     }
 
     fn transaction_sender(&self, num: TxNumber) -> ProviderResult<Option<Address>> {
+        if self.segment().is_senders() {
+            return self.cursor()?.get_one::<SenderMask>(num.into())
+        }
+
         Ok(self
             .cursor()?
             .get_one::<TransactionMask<Self::Transaction>>(num.into())?
@@ -381,3 +399,35 @@ impl<N: NodePrimitives> BlockBodyIndicesProvider for StaticFileJarProvider<'_, N
         Ok(indices)
     }
 }
+
+impl<N: NodePrimitives> ChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        Ok(self
+            .cursor()?
+            .get_one::<AccountChangeSetMask>(block_number.into())?
+            .map(|change_set| change_set.changes)
+            .unwrap_or_default())
+    }
+}
+
+impl<N: NodePrimitives> StorageChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(self
+            .cursor()?
+            .get_one::<StorageChangeSetMask>(block_number.into())?
+            .map(|change_set| {
+                change_set
+                    .changes
+                    .into_iter()
+                    .map(|change| ((block_number, change.address).into(), change.entry))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
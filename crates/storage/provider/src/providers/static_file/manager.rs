@@ -3,9 +3,9 @@ use super::{
     StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
-    ReceiptProvider, StageCheckpointReader, StatsReader, TransactionVariant, TransactionsProvider,
-    TransactionsProviderExt,
+    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, ChangeSetReader,
+    HeaderProvider, ReceiptProvider, StageCheckpointReader, StatsReader, StorageChangeSetReader,
+    TransactionVariant, TransactionsProvider, TransactionsProviderExt,
 };
 use alloy_consensus::{
     transaction::{SignerRecoverable, TransactionMeta},
@@ -23,20 +23,24 @@ use reth_db::{
     lockfile::StorageLock,
     static_file::{
         iter_static_files, BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask,
-        ReceiptMask, StaticFileCursor, TDWithHashMask, TransactionMask,
+        ReceiptMask, SenderMask, StaticFileCursor, TDWithHashMask, TransactionMask,
     },
 };
 use reth_db_api::{
     cursor::DbCursorRO,
-    models::StoredBlockBodyIndices,
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
     table::{Decompress, Table, Value},
     tables,
     transaction::DbTx,
 };
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
-use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
+use reth_nippy_jar::{
+    finish_interrupted_replacements, NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION,
+};
 use reth_node_types::{FullNodePrimitives, NodePrimitives};
-use reth_primitives_traits::{RecoveredBlock, SealedBlock, SealedHeader, SignedTransaction};
+use reth_primitives_traits::{
+    RecoveredBlock, SealedBlock, SealedHeader, SignedTransaction, StorageEntry,
+};
 use reth_stages_types::{PipelineTarget, StageId};
 use reth_static_file_types::{
     find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive, StaticFileSegment,
@@ -253,7 +257,10 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
     /// Creates a new [`StaticFileProviderInner`].
     fn new(path: impl AsRef<Path>, access: StaticFileAccess) -> ProviderResult<Self> {
         let _lock_file = if access.is_read_write() {
-            StorageLock::try_acquire(path.as_ref()).map_err(ProviderError::other)?.into()
+            let lock = StorageLock::try_acquire(path.as_ref()).map_err(ProviderError::other)?;
+            // Static files might have been replaced offline, e.g. when recompressing them.
+            finish_interrupted_replacements(path.as_ref()).map_err(ProviderError::other)?;
+            Some(lock)
         } else {
             None
         };
@@ -706,6 +713,15 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 continue
             }
 
+            if segment.is_senders() &&
+                (provider.prune_modes_ref().sender_recovery.is_some() ||
+                    self.get_highest_static_file_block(segment).is_none())
+            {
+                // Pruned nodes and nodes created before senders were moved to static files keep
+                // them in the database only.
+                continue
+            }
+
             let initial_highest_block = self.get_highest_static_file_block(segment);
 
             //  File consistency is broken if:
@@ -727,8 +743,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             //
             // The updated `highest_block` may have decreased if we healed from a pruning
             // interruption.
+            //
+            // Changesets are only deleted from the database once they are committed to static
+            // files, so rows lost while healing them are still in the database.
             let mut highest_block = self.get_highest_static_file_block(segment);
-            if initial_highest_block != highest_block {
+            if initial_highest_block != highest_block && !segment.is_change_sets() {
                 info!(
                     target: "reth::providers::static_file",
                     ?initial_highest_block,
@@ -797,6 +816,13 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         highest_tx,
                         highest_block,
                     )?,
+                StaticFileSegment::Senders => self
+                    .ensure_invariants::<_, tables::TransactionSenders>(
+                        provider,
+                        segment,
+                        highest_tx,
+                        highest_block,
+                    )?,
                 StaticFileSegment::BlockMeta => self
                     .ensure_invariants::<_, tables::BlockBodyIndices>(
                         provider,
@@ -804,6 +830,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         highest_block,
                         highest_block,
                     )?,
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    self.ensure_change_set_invariants(provider, segment, highest_block)?
+                }
             } {
                 update_unwind_target(unwind);
             }
@@ -890,7 +919,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             .get_stage_checkpoint(match segment {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions | StaticFileSegment::BlockMeta => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::Senders => StageId::SenderRecovery,
+                StaticFileSegment::Receipts |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => StageId::Execution,
             })?
             .unwrap_or_default()
             .block_number;
@@ -928,6 +960,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 let number = highest_static_file_entry - block.last_tx_num();
                 if segment.is_receipts() {
                     writer.prune_receipts(number, checkpoint_block_number)?;
+                } else if segment.is_senders() {
+                    writer.prune_senders(number, checkpoint_block_number)?;
                 } else {
                     writer.prune_transactions(number, checkpoint_block_number)?;
                 }
@@ -938,6 +972,47 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(None)
     }
 
+    /// Check invariants of a changeset static file segment against the database.
+    ///
+    /// Changesets are copied to static files only after they were written to the database, so the
+    /// static files are expected to lag behind the [`StageId::Execution`] checkpoint. If they are
+    /// ahead of it instead, the database was unwound without the static files, and the extra rows
+    /// are removed. Never requests a pipeline unwind.
+    fn ensure_change_set_invariants<Provider>(
+        &self,
+        provider: &Provider,
+        segment: StaticFileSegment,
+        highest_static_file_block: Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: StageCheckpointReader,
+    {
+        let Some(highest_static_file_block) = highest_static_file_block else { return Ok(None) };
+
+        let checkpoint_block_number =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        if checkpoint_block_number < highest_static_file_block {
+            info!(
+                target: "reth::providers",
+                ?segment,
+                from = highest_static_file_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let mut writer = self.latest_writer(segment)?;
+            let to_delete = highest_static_file_block - checkpoint_block_number;
+            if segment == StaticFileSegment::AccountChangeSets {
+                writer.prune_account_change_sets(to_delete)?;
+            } else {
+                writer.prune_storage_change_sets(to_delete)?;
+            }
+            writer.commit()?;
+        }
+
+        Ok(None)
+    }
+
     /// Gets the highest static file block if it exists for a static file segment.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -962,6 +1037,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
             block_meta: self.get_highest_static_file_block(StaticFileSegment::BlockMeta),
+            senders: self.get_highest_static_file_block(StaticFileSegment::Senders),
+            account_change_sets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_change_sets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
         }
     }
 
@@ -1589,11 +1669,37 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction>> TransactionsPr
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
+        let range = to_range(range);
+        if self
+            .get_highest_static_file_tx(StaticFileSegment::Senders)
+            .is_some_and(|highest| range.end <= highest + 1)
+        {
+            return self.fetch_range_with_predicate(
+                StaticFileSegment::Senders,
+                range,
+                |cursor, number| cursor.get_one::<SenderMask>(number.into()),
+                |_| true,
+            )
+        }
+
         let txes = self.transactions_by_tx_range(range)?;
         Ok(reth_primitives_traits::transaction::recover::recover_signers(&txes)?)
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
+        if self.get_highest_static_file_tx(StaticFileSegment::Senders).is_some_and(|tx| id <= tx) {
+            return self
+                .get_segment_provider_from_transaction(StaticFileSegment::Senders, id, None)
+                .and_then(|provider| provider.transaction_sender(id))
+                .or_else(|err| {
+                    if let ProviderError::MissingStaticFileTx(_, _) = err {
+                        Ok(None)
+                    } else {
+                        Err(err)
+                    }
+                })
+        }
+
         match self.transaction_by_id_unhashed(id)? {
             Some(tx) => Ok(tx.recover_signer().ok()),
             None => Ok(None),
@@ -1720,6 +1826,48 @@ impl<N: NodePrimitives> BlockBodyIndicesProvider for StaticFileProvider<N> {
     }
 }
 
+impl<N: NodePrimitives> ChangeSetReader for StaticFileProvider<N> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::AccountChangeSets,
+            block_number,
+            None,
+        )
+        .and_then(|provider| provider.account_block_changeset(block_number))
+        .or_else(|err| {
+            if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                Ok(Vec::new())
+            } else {
+                Err(err)
+            }
+        })
+    }
+}
+
+impl<N: NodePrimitives> StorageChangeSetReader for StaticFileProvider<N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::StorageChangeSets,
+            block_number,
+            None,
+        )
+        .and_then(|provider| provider.storage_changeset(block_number))
+        .or_else(|err| {
+            if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                Ok(Vec::new())
+            } else {
+                Err(err)
+            }
+        })
+    }
+}
+
 impl<N: NodePrimitives> StatsReader for StaticFileProvider<N> {
     fn count_entries<T: Table>(&self) -> ProviderResult<usize> {
         match T::NAME {
@@ -1734,6 +1882,10 @@ impl<N: NodePrimitives> StatsReader for StaticFileProvider<N> {
                 .get_highest_static_file_tx(StaticFileSegment::Receipts)
                 .map(|receipts| receipts + 1)
                 .unwrap_or_default() as usize),
+            tables::TransactionSenders::NAME => Ok(self
+                .get_highest_static_file_tx(StaticFileSegment::Senders)
+                .map(|senders| senders + 1)
+                .unwrap_or_default() as usize),
             tables::Transactions::<TransactionSigned>::NAME => Ok(self
                 .get_highest_static_file_tx(StaticFileSegment::Transactions)
                 .map(|txs| txs + 1)
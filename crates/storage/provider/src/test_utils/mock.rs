@@ -1,9 +1,9 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EthStorage, HeaderProvider, ReceiptProviderIdExt,
-    StateProvider, StateProviderBox, StateProviderFactory, StateReader, StateRootProvider,
-    TransactionVariant, TransactionsProvider,
+    AccountReader, AddressTransactionsReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, EthStorage, HeaderProvider,
+    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateReader,
+    StateRootProvider, TransactionVariant, TransactionsProvider,
 };
 use alloy_consensus::{constants::EMPTY_ROOT_HASH, transaction::TransactionMeta, Header};
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
@@ -16,7 +16,7 @@ use reth_chain_state::{CanonStateNotifications, CanonStateSubscriptions};
 use reth_chainspec::{ChainInfo, EthChainSpec};
 use reth_db_api::{
     mock::{DatabaseMock, TxMock},
-    models::{AccountBeforeTx, StoredBlockBodyIndices},
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
 };
 use reth_ethereum_engine_primitives::EthEngineTypes;
 use reth_ethereum_primitives::{EthPrimitives, Receipt};
@@ -24,14 +24,14 @@ use reth_execution_types::ExecutionOutcome;
 use reth_node_types::NodeTypes;
 use reth_primitives_traits::{
     Account, Bytecode, GotExpected, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
-    SignerRecoverable,
+    SignerRecoverable, StorageEntry,
 };
 use reth_prune_types::PruneModes;
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DBProvider, DatabaseProviderFactory, HashedPostStateProvider,
-    NodePrimitivesProvider, StageCheckpointReader, StateCommitmentProvider, StateProofProvider,
-    StorageRootProvider,
+    AccountWithStorage, BlockBodyIndicesProvider, DBProvider, DatabaseProviderFactory,
+    HashedPostStateProvider, NodePrimitivesProvider, PlainStateReader, StageCheckpointReader,
+    StateCommitmentProvider, StateProofProvider, StorageChangeSetReader, StorageRootProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -40,7 +40,7 @@ use reth_trie::{
 };
 use reth_trie_db::MerklePatriciaTrie;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::Debug,
     ops::{RangeBounds, RangeInclusive},
     sync::Arc,
@@ -418,7 +418,7 @@ impl<ChainSpec: EthChainSpec + 'static> TransactionsProvider
                         excess_blob_gas: block.header.excess_blob_gas,
                         timestamp: block.header.timestamp,
                     };
-                    return Ok(Some((tx.clone(), meta)))
+                    return Ok(Some((tx.clone(), meta)));
                 }
             }
         }
@@ -430,7 +430,7 @@ impl<ChainSpec: EthChainSpec + 'static> TransactionsProvider
         let mut current_tx_number: TxNumber = 0;
         for block in lock.values() {
             if current_tx_number + (block.body.transactions.len() as TxNumber) > id {
-                return Ok(Some(block.header.number))
+                return Ok(Some(block.header.number));
             }
             current_tx_number += block.body.transactions.len() as TxNumber;
         }
@@ -967,6 +967,105 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvi
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> StorageChangeSetReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn storage_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> AddressTransactionsReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn address_transactions(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<TxNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn address_transactions_rev(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<TxNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> PlainStateReader for MockEthProvider<T, ChainSpec> {
+    fn changed_addresses_with_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::default())
+    }
+
+    /// Returns the current accounts, the mock doesn't keep any history.
+    fn plain_state_at(
+        &self,
+        _block_number: BlockNumber,
+        start: Address,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<BTreeMap<Address, AccountWithStorage>> {
+        Ok(self
+            .accounts
+            .lock()
+            .iter()
+            .filter(|(address, _)| **address >= start)
+            .map(|(address, account)| {
+                let storage = account
+                    .storage
+                    .iter()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(|(key, value)| (*key, *value))
+                    .collect::<BTreeMap<_, _>>()
+                    .into_iter()
+                    .take(storage_limit)
+                    .collect();
+                (*address, AccountWithStorage { info: account.account, storage })
+            })
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .take(limit)
+            .collect())
+    }
+
+    /// Returns the current storage, the mock doesn't keep any history.
+    fn plain_storage_at(
+        &self,
+        address: Address,
+        _block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<BTreeMap<B256, U256>> {
+        Ok(self
+            .accounts
+            .lock()
+            .get(&address)
+            .map(|account| {
+                account
+                    .storage
+                    .iter()
+                    .filter(|(key, value)| **key >= start && !value.is_zero())
+                    .map(|(key, value)| (*key, *value))
+                    .collect::<BTreeMap<_, _>>()
+                    .into_iter()
+                    .take(limit)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> StateReader for MockEthProvider<T, ChainSpec> {
     type Receipt = Receipt;
 
@@ -8,11 +8,15 @@ use reth_chain_state::{ExecutedBlock, ExecutedBlockWithTrieUpdates};
 use reth_db_api::transaction::{DbTx, DbTxMut};
 use reth_errors::{ProviderError, ProviderResult};
 use reth_primitives_traits::{NodePrimitives, SignedTransaction};
+use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{DBProvider, StageCheckpointWriter, TransactionsProviderExt};
+use reth_storage_api::{
+    insert_transaction_addresses, transaction_addresses, BlockBodyIndicesProvider, DBProvider,
+    StageCheckpointReader, StageCheckpointWriter, TransactionsProviderExt,
+};
 use reth_storage_errors::writer::UnifiedStorageWriterError;
 use revm_database::OriginalValuesKnown;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 use tracing::debug;
 
 /// [`UnifiedStorageWriter`] is responsible for managing the writing to storage with both database
@@ -126,6 +130,8 @@ where
         + TrieWriter
         + StateWriter
         + HistoryWriter
+        + BlockBodyIndicesProvider
+        + StageCheckpointReader
         + StageCheckpointWriter
         + BlockExecutionWriter
         + AsRef<ProviderDB>
@@ -151,6 +157,25 @@ where
 
         debug!(target: "provider::storage_writer", block_count = %blocks.len(), "Writing blocks and execution data to storage");
 
+        // The address transactions index is only maintained if it's been built up to the parent
+        // of the first block. Otherwise, the `IndexAddressTransactions` stage fills the gap on
+        // the next pipeline run.
+        let mut next_tx_num = if self
+            .database()
+            .get_stage_checkpoint(StageId::IndexAddressTransactions)?
+            .is_some_and(|checkpoint| checkpoint.block_number + 1 == first_number)
+        {
+            let parent = first_number - 1;
+            let parent_indices = self
+                .database()
+                .block_body_indices(parent)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(parent))?;
+            Some(parent_indices.next_tx_num())
+        } else {
+            None
+        };
+        let mut address_transactions = BTreeMap::new();
+
         // TODO: Do performant / batched writes for each type of object
         // instead of a loop over all blocks,
         // meaning:
@@ -166,6 +191,25 @@ where
         } in blocks
         {
             let block_hash = recovered_block.hash();
+
+            if let Some(next_tx_num) = &mut next_tx_num {
+                let receipts = execution_output.receipts.first();
+                for (index, (sender, transaction)) in
+                    recovered_block.transactions_with_sender().enumerate()
+                {
+                    insert_transaction_addresses(
+                        &mut address_transactions,
+                        *next_tx_num,
+                        transaction_addresses(
+                            *sender,
+                            transaction,
+                            receipts.and_then(|receipts| receipts.get(index)),
+                        ),
+                    );
+                    *next_tx_num += 1;
+                }
+            }
+
             self.database()
                 .insert_block(Arc::unwrap_or_clone(recovered_block), StorageLocation::Both)?;
 
@@ -188,6 +232,14 @@ where
         // update history indices
         self.database().update_history_indices(first_number..=last_block_number)?;
 
+        if next_tx_num.is_some() {
+            self.database().insert_address_transactions_index(address_transactions)?;
+            self.database().save_stage_checkpoint(
+                StageId::IndexAddressTransactions,
+                StageCheckpoint::new(last_block_number),
+            )?;
+        }
+
         // Update pipeline progress
         self.database().update_pipeline_stages(last_block_number, false)?;
 
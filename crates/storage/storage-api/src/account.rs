@@ -54,4 +54,16 @@ pub trait ChangeSetReader {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Returns the account state from before the given block, if the account was changed in it.
+    fn get_account_before_block(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        Ok(self
+            .account_block_changeset(block_number)?
+            .into_iter()
+            .find(|account_before| account_before.address == address))
+    }
 }
@@ -0,0 +1,136 @@
+use crate::{PruneCheckpointReader, ReceiptProvider, StageCheckpointReader, TransactionsProvider};
+use alloc::{collections::BTreeMap, vec::Vec};
+use alloy_consensus::{Transaction, TxReceipt};
+use alloy_primitives::{Address, BlockNumber, Log, TxNumber};
+use core::ops::{Range, RangeInclusive};
+use reth_primitives_traits::SignedTransaction;
+use reth_prune_types::PruneSegment;
+use reth_stages_types::StageId;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+
+/// Client trait for reading the index of transactions an address took part in.
+///
+/// An address takes part in a transaction if it's the sender, the recipient or emitted a log, see
+/// [`transaction_addresses`].
+#[auto_impl::auto_impl(&, Arc)]
+pub trait AddressTransactionsReader: Send + Sync {
+    /// Returns up to `limit` transaction numbers within the range the address took part in, in
+    /// ascending order.
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+
+    /// Returns up to `limit` transaction numbers within the range the address took part in, in
+    /// descending order.
+    fn address_transactions_rev(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+}
+
+/// Returns the range of blocks the address transactions index covers.
+///
+/// The index starts after the blocks that were pruned and ends at the checkpoint of the
+/// [`StageId::IndexAddressTransactions`] stage. Returns `None` if the index was never built.
+pub fn address_transactions_indexed_blocks<Provider>(
+    provider: &Provider,
+) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>
+where
+    Provider: StageCheckpointReader + PruneCheckpointReader,
+{
+    let Some(checkpoint) = provider.get_stage_checkpoint(StageId::IndexAddressTransactions)? else {
+        return Ok(None)
+    };
+    let first_block = provider
+        .get_prune_checkpoint(PruneSegment::AddressTransactions)?
+        .and_then(|checkpoint| checkpoint.block_number)
+        .map_or(0, |pruned| pruned + 1);
+    Ok(Some(first_block..=checkpoint.block_number))
+}
+
+/// Returns the addresses a transaction is indexed under: the sender, the recipient, if any, and
+/// the emitter of every log in the receipt.
+///
+/// The iterator may yield the same address more than once.
+pub fn transaction_addresses<'a, T, R>(
+    sender: Address,
+    transaction: &T,
+    receipt: Option<&'a R>,
+) -> impl Iterator<Item = Address> + 'a
+where
+    T: Transaction,
+    R: TxReceipt<Log = Log>,
+{
+    core::iter::once(sender)
+        .chain(transaction.to())
+        .chain(receipt.into_iter().flat_map(|receipt| receipt.logs().iter().map(|log| log.address)))
+}
+
+/// Returns the transaction numbers every address took part in within the range, in ascending
+/// order and without duplicates.
+///
+/// Senders are recovered from the transactions if they were pruned. Logs are only indexed for
+/// the transactions that still have their receipts.
+pub fn address_transactions_by_tx_range<Provider>(
+    provider: &Provider,
+    tx_range: Range<TxNumber>,
+) -> ProviderResult<BTreeMap<Address, Vec<TxNumber>>>
+where
+    Provider: TransactionsProvider + ReceiptProvider,
+{
+    let mut indices = BTreeMap::<Address, Vec<TxNumber>>::new();
+    if tx_range.is_empty() {
+        return Ok(indices)
+    }
+
+    let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+    let mut senders = provider.senders_by_tx_range(tx_range.clone())?;
+    if senders.len() != transactions.len() {
+        senders = transactions
+            .iter()
+            .map(|transaction| transaction.try_recover_unchecked())
+            .collect::<Result<_, _>>()
+            .map_err(|_| ProviderError::SenderRecoveryError)?;
+    }
+    let receipts = provider.receipts_by_tx_range(tx_range.clone())?;
+    let receipts = if receipts.len() == transactions.len() {
+        receipts.into_iter().map(Some).collect::<Vec<_>>()
+    } else {
+        // Some of the receipts were pruned, look them up one by one.
+        tx_range.clone().map(|tx_number| provider.receipt(tx_number)).collect::<Result<_, _>>()?
+    };
+
+    for (((tx_number, transaction), sender), receipt) in
+        tx_range.zip(&transactions).zip(senders).zip(&receipts)
+    {
+        insert_transaction_addresses(
+            &mut indices,
+            tx_number,
+            transaction_addresses(sender, transaction, receipt.as_ref()),
+        );
+    }
+
+    Ok(indices)
+}
+
+/// Appends the transaction number to the indices of the addresses, skipping the addresses it was
+/// already appended to.
+///
+/// Transactions must be inserted in ascending order.
+pub fn insert_transaction_addresses(
+    indices: &mut BTreeMap<Address, Vec<TxNumber>>,
+    tx_number: TxNumber,
+    addresses: impl IntoIterator<Item = Address>,
+) {
+    for address in addresses {
+        let entry = indices.entry(address).or_default();
+        if entry.last() != Some(&tx_number) {
+            entry.push(tx_number);
+        }
+    }
+}
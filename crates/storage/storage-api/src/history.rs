@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db_api::models::BlockNumberAddress;
@@ -53,6 +53,23 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: impl IntoIterator<Item = ((Address, B256), impl IntoIterator<Item = u64>)>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear address transactions indices.
+    ///
+    /// Every address is paired with the first transaction number to remove from its index.
+    ///
+    /// Returns number of addresses walked.
+    fn unwind_address_transactions_indices(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, TxNumber)>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert address transactions index to database. Used when persisting blocks from the
+    /// engine.
+    fn insert_address_transactions_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (Address, impl IntoIterator<Item = TxNumber>)>,
+    ) -> ProviderResult<()>;
+
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
@@ -16,6 +16,9 @@ pub use reth_storage_errors as errors;
 mod account;
 pub use account::*;
 
+mod address_transactions;
+pub use address_transactions::*;
+
 mod block;
 pub use block::*;
 
@@ -33,6 +36,9 @@ pub use chain::*;
 mod header;
 pub use header::*;
 
+mod plain_state;
+pub use plain_state::*;
+
 mod prune_checkpoint;
 pub use prune_checkpoint::*;
 
@@ -0,0 +1,60 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use core::ops::RangeInclusive;
+use reth_primitives_traits::Account;
+use reth_storage_errors::provider::ProviderResult;
+
+/// An account together with its non-zero storage slots.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountWithStorage {
+    /// The account info.
+    pub info: Account,
+    /// The non-zero storage slots by their plain key.
+    pub storage: BTreeMap<B256, U256>,
+}
+
+/// Client trait for reading the plain state as of a historical block.
+///
+/// Both methods rely on the account and storage changesets, so they fail with
+/// [`ProviderError::StateAtBlockPruned`](reth_storage_errors::provider::ProviderError::StateAtBlockPruned)
+/// if the history they need was pruned.
+///
+/// Iterating the plain state fails with
+/// [`ProviderError::SnapSyncedPlainState`](reth_storage_errors::provider::ProviderError::SnapSyncedPlainState)
+/// if the state was downloaded with snap sync, since the plain state then only holds the accounts
+/// and storage slots changed after the pivot block.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait PlainStateReader: Send + Sync {
+    /// Returns the addresses whose account or storage changed in the given block range.
+    fn changed_addresses_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>>;
+
+    /// Returns the accounts that exist after the given block was executed, together with their
+    /// storage, ordered by address.
+    ///
+    /// The page starts at the `start` address and holds at most `limit` accounts, so the entire
+    /// state can be read in pages. The storage of each account holds at most its first
+    /// `storage_limit` slots, larger storages can be read with [`Self::plain_storage_at`].
+    fn plain_state_at(
+        &self,
+        block_number: BlockNumber,
+        start: Address,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<BTreeMap<Address, AccountWithStorage>>;
+
+    /// Returns the non-zero storage slots of the account after the given block was executed,
+    /// ordered by slot.
+    ///
+    /// The page starts at the `start` slot and holds at most `limit` slots, so the entire storage
+    /// can be read in pages.
+    fn plain_storage_at(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<BTreeMap<B256, U256>>;
+}
@@ -41,6 +41,22 @@ pub trait StorageChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>>;
+
+    /// Returns the storage slot value from before the given block, if the slot was changed in it.
+    fn get_storage_before_block(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        Ok(self
+            .storage_changeset(block_number)?
+            .into_iter()
+            .find(|(block_address, entry)| {
+                block_address.address() == address && entry.key == storage_key
+            })
+            .map(|(_, entry)| entry))
+    }
 }
 
 /// An enum that represents the storage location for a piece of data.
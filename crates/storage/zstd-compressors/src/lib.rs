@@ -20,41 +20,160 @@ pub static RECEIPT_DICTIONARY: &[u8] = include_bytes!("../receipt_dictionary.bin
 /// Compression/Decompression dictionary for `Transaction`.
 pub static TRANSACTION_DICTIONARY: &[u8] = include_bytes!("../transaction_dictionary.bin");
 
+/// Kind of values compressed with a dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictionaryKind {
+    /// Receipts, see [`RECEIPT_DICTIONARY`].
+    Receipt,
+    /// Transactions, see [`TRANSACTION_DICTIONARY`].
+    Transaction,
+}
+
+/// Error returned when a value was compressed with a dictionary that isn't loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownDictionary {
+    /// Kind of the value.
+    pub kind: DictionaryKind,
+    /// ID of the dictionary the value was compressed with.
+    pub id: u32,
+}
+
+impl core::fmt::Display for UnknownDictionary {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?} value was compressed with unknown dictionary {}", self.kind, self.id)
+    }
+}
+
+impl core::error::Error for UnknownDictionary {}
+
+impl DictionaryKind {
+    /// Returns the dictionary embedded for this kind.
+    pub const fn embedded(&self) -> &'static [u8] {
+        match self {
+            Self::Receipt => RECEIPT_DICTIONARY,
+            Self::Transaction => TRANSACTION_DICTIONARY,
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 pub use locals::*;
 #[cfg(feature = "std")]
 mod locals {
     use super::*;
-    use core::cell::RefCell;
+    use core::{
+        cell::RefCell,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use std::sync::RwLock;
+
+    /// Dictionaries loaded with [`load_dictionary`], indexed by [`DictionaryKind`].
+    static LOADED_DICTIONARIES: RwLock<[Vec<&'static [u8]>; 2]> =
+        RwLock::new([Vec::new(), Vec::new()]);
+
+    /// Incremented on every [`load_dictionary`], so compressors and decompressors pick it up.
+    static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+    /// Loads a dictionary that takes the place of the embedded one of its kind.
+    ///
+    /// Values are compressed with the dictionary loaded last. Values compressed with the embedded
+    /// dictionary or an earlier loaded one can still be decompressed, as the dictionary is picked
+    /// by the ID stored in the compressed frame.
+    pub fn load_dictionary(kind: DictionaryKind, dictionary: Vec<u8>) {
+        // Dictionaries are loaded once on startup and live for the rest of the process.
+        let dictionary: &'static [u8] = Vec::leak(dictionary);
+        LOADED_DICTIONARIES.write().expect("not poisoned")[kind as usize].push(dictionary);
+        GENERATION.fetch_add(1, Ordering::Release);
+    }
+
+    /// Returns the current generation and the dictionaries of the given kind, starting with the
+    /// embedded one and ending with the one values are compressed with.
+    pub(crate) fn dictionaries(kind: DictionaryKind) -> (usize, Vec<&'static [u8]>) {
+        let loaded = LOADED_DICTIONARIES.read().expect("not poisoned");
+        let generation = GENERATION.load(Ordering::Acquire);
+        (
+            generation,
+            core::iter::once(kind.embedded()).chain(loaded[kind as usize].clone()).collect(),
+        )
+    }
+
+    /// Returns the current generation of the loaded dictionaries.
+    pub(crate) fn generation() -> usize {
+        GENERATION.load(Ordering::Acquire)
+    }
+
+    std::thread_local! {
+        /// Decompressed values recorded by [`with_decompressed_samples`].
+        static SAMPLES: RefCell<Option<Vec<Vec<u8>>>> = const { RefCell::new(None) };
+    }
+
+    /// Runs `f` and returns the values it decompressed on this thread alongside its result.
+    ///
+    /// The decompressed values are the encodings the dictionaries compress, so they're the samples
+    /// a replacing dictionary is trained on.
+    pub fn with_decompressed_samples<R>(f: impl FnOnce() -> R) -> (R, Vec<Vec<u8>>) {
+        let previous = SAMPLES.with(|samples| samples.borrow_mut().replace(Vec::new()));
+        let result = f();
+        let samples =
+            SAMPLES.with(|samples| core::mem::replace(&mut *samples.borrow_mut(), previous));
+        (result, samples.unwrap_or_default())
+    }
+
+    /// Records the decompressed value if called within [`with_decompressed_samples`].
+    pub(crate) fn record_sample(decompressed: &[u8]) {
+        SAMPLES.with(|samples| {
+            if let Some(samples) = samples.borrow_mut().as_mut() {
+                samples.push(decompressed.to_vec());
+            }
+        });
+    }
 
     // We use `thread_local` compressors and decompressors because dictionaries can be quite big,
     // and zstd-rs recommends to use one context/compressor per thread
     std::thread_local! {
         /// Thread Transaction compressor.
-        pub static TRANSACTION_COMPRESSOR: RefCell<Compressor<'static>> = RefCell::new(
-            Compressor::with_dictionary(0, TRANSACTION_DICTIONARY)
-                .expect("failed to initialize transaction compressor"),
-        );
+        pub static TRANSACTION_COMPRESSOR: RefCell<ReusableCompressor> =
+            RefCell::new(ReusableCompressor::new(DictionaryKind::Transaction));
 
         /// Thread Transaction decompressor.
         pub static TRANSACTION_DECOMPRESSOR: RefCell<ReusableDecompressor> =
-            RefCell::new(ReusableDecompressor::new(
-                Decompressor::with_dictionary(TRANSACTION_DICTIONARY)
-                    .expect("failed to initialize transaction decompressor"),
-            ));
+            RefCell::new(ReusableDecompressor::new(DictionaryKind::Transaction));
 
         /// Thread receipt compressor.
-        pub static RECEIPT_COMPRESSOR: RefCell<Compressor<'static>> = RefCell::new(
-            Compressor::with_dictionary(0, RECEIPT_DICTIONARY)
-                .expect("failed to initialize receipt compressor"),
-        );
+        pub static RECEIPT_COMPRESSOR: RefCell<ReusableCompressor> =
+            RefCell::new(ReusableCompressor::new(DictionaryKind::Receipt));
 
         /// Thread receipt decompressor.
         pub static RECEIPT_DECOMPRESSOR: RefCell<ReusableDecompressor> =
-            RefCell::new(ReusableDecompressor::new(
-                Decompressor::with_dictionary(RECEIPT_DICTIONARY)
-                    .expect("failed to initialize receipt decompressor"),
-            ));
+            RefCell::new(ReusableDecompressor::new(DictionaryKind::Receipt));
+    }
+
+    /// Compressor that uses the dictionary loaded last, or the embedded one if none was loaded.
+    #[expect(missing_debug_implementations)]
+    pub struct ReusableCompressor {
+        kind: DictionaryKind,
+        /// Generation of the loaded dictionaries the compressor was created with.
+        generation: usize,
+        /// The `zstd` compressor.
+        compressor: Compressor<'static>,
+    }
+
+    impl ReusableCompressor {
+        fn new(kind: DictionaryKind) -> Self {
+            let (generation, dictionaries) = dictionaries(kind);
+            let dictionary = dictionaries.last().expect("embedded dictionary");
+            let compressor = Compressor::with_dictionary(0, dictionary)
+                .unwrap_or_else(|err| panic!("failed to initialize {kind:?} compressor: {err}"));
+            Self { kind, generation, compressor }
+        }
+
+        /// Compresses `src`, switching to a newly loaded dictionary first.
+        pub fn compress(&mut self, src: &[u8]) -> std::io::Result<Vec<u8>> {
+            if self.generation != generation() {
+                *self = Self::new(self.kind);
+            }
+            self.compressor.compress(src)
+        }
     }
 }
 
@@ -65,10 +184,7 @@ pub fn create_tx_compressor() -> Compressor<'static> {
 
 /// Fn creates tx [`Decompressor`]
 pub fn create_tx_decompressor() -> ReusableDecompressor {
-    ReusableDecompressor::new(
-        Decompressor::with_dictionary(TRANSACTION_DICTIONARY)
-            .expect("Failed to instantiate tx decompressor"),
-    )
+    ReusableDecompressor::new(DictionaryKind::Transaction)
 }
 
 /// Fn creates receipt [`Compressor`]
@@ -79,32 +195,74 @@ pub fn create_receipt_compressor() -> Compressor<'static> {
 
 /// Fn creates receipt [`Decompressor`]
 pub fn create_receipt_decompressor() -> ReusableDecompressor {
-    ReusableDecompressor::new(
-        Decompressor::with_dictionary(RECEIPT_DICTIONARY)
-            .expect("Failed to instantiate receipt decompressor"),
-    )
+    ReusableDecompressor::new(DictionaryKind::Receipt)
 }
 
 /// Reusable decompressor that uses its own internal buffer.
+///
+/// Holds a decompressor for the embedded dictionary and every loaded one, and picks the one
+/// matching the dictionary ID of the compressed frame.
 #[expect(missing_debug_implementations)]
 pub struct ReusableDecompressor {
-    /// The `zstd` decompressor.
-    decompressor: Decompressor<'static>,
+    kind: DictionaryKind,
+    /// Generation of the loaded dictionaries the decompressors were created with.
+    #[cfg_attr(not(feature = "std"), expect(dead_code))]
+    generation: usize,
+    /// The `zstd` decompressors with the IDs of their dictionaries, starting with the embedded
+    /// one.
+    decompressors: Vec<(Option<u32>, Decompressor<'static>)>,
     /// The buffer to decompress to.
     buf: Vec<u8>,
 }
 
 impl ReusableDecompressor {
-    fn new(decompressor: Decompressor<'static>) -> Self {
-        Self { decompressor, buf: Vec::with_capacity(4096) }
+    fn new(kind: DictionaryKind) -> Self {
+        #[cfg(feature = "std")]
+        let (generation, dictionaries) = dictionaries(kind);
+        #[cfg(not(feature = "std"))]
+        let (generation, dictionaries) = (0, alloc::vec![kind.embedded()]);
+
+        let decompressors = dictionaries
+            .into_iter()
+            .map(|dictionary| {
+                let decompressor =
+                    Decompressor::with_dictionary(dictionary).unwrap_or_else(|err| {
+                        panic!("failed to initialize {kind:?} decompressor: {err}")
+                    });
+                (
+                    zstd::zstd_safe::get_dict_id_from_dict(dictionary).map(|id| id.get()),
+                    decompressor,
+                )
+            })
+            .collect();
+        Self { kind, generation, decompressors, buf: Vec::with_capacity(4096) }
     }
 
     /// Decompresses `src` reusing the decompressor and its internal buffer.
-    pub fn decompress(&mut self, src: &[u8]) -> &[u8] {
+    ///
+    /// Returns an error if `src` was compressed with a dictionary that isn't loaded.
+    pub fn decompress(&mut self, src: &[u8]) -> Result<&[u8], UnknownDictionary> {
+        #[cfg(feature = "std")]
+        if self.generation != generation() {
+            let buf = core::mem::take(&mut self.buf);
+            *self = Self { buf, ..Self::new(self.kind) };
+        }
+
+        // Frames without a dictionary ID were compressed with the embedded dictionary.
+        let dictionary_id = zstd::zstd_safe::get_dict_id_from_frame(src).map(|id| id.get());
+        let decompressor = match self.decompressors.iter().position(|(id, _)| *id == dictionary_id)
+        {
+            Some(index) => &mut self.decompressors[index].1,
+            None => match dictionary_id {
+                Some(id) => return Err(UnknownDictionary { kind: self.kind, id }),
+                None => &mut self.decompressors[0].1,
+            },
+        };
+
         // If the decompression fails because the buffer is too small, we try to reserve more space
         // by getting the upper bound and retry the decompression.
         let mut reserved_upper_bound = false;
-        while let Err(err) = self.decompressor.decompress_to_buffer(src, &mut self.buf) {
+        while let Err(err) = decompressor.decompress_to_buffer(src, &mut self.buf) {
             let err = err.to_string();
             assert!(
                 err.contains("Destination buffer is too small"),
@@ -129,22 +287,80 @@ impl ReusableDecompressor {
                 // This should normally not be reached as the upper bound should be enough.
                 self.buf.capacity() + 24_000
             };
-            self.reserve(additional, src.len());
+            reserve(&mut self.buf, additional, src.len());
         }
 
+        #[cfg(feature = "std")]
+        record_sample(&self.buf);
+
         // `decompress_to_buffer` sets the length of the vector to the number of bytes written, so
         // we can safely return it as a slice.
-        &self.buf
+        Ok(&self.buf)
     }
+}
 
-    #[track_caller]
-    fn reserve(&mut self, additional: usize, src_len: usize) {
-        if let Err(e) = self.buf.try_reserve(additional) {
-            panic!(
-                "failed to allocate to {existing} + {additional} bytes \
-                 for the decompression of {src_len} bytes: {e}",
-                existing = self.buf.capacity(),
-            );
-        }
+#[track_caller]
+fn reserve(buf: &mut Vec<u8>, additional: usize, src_len: usize) {
+    if let Err(e) = buf.try_reserve(additional) {
+        panic!(
+            "failed to allocate to {existing} + {additional} bytes \
+             for the decompression of {src_len} bytes: {e}",
+            existing = buf.capacity(),
+        );
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loaded_dictionary_replaces_embedded() {
+        let samples = (0..1000u32)
+            .map(|i| format!("custom transaction {i} with some repeated content").into_bytes())
+            .collect::<Vec<_>>();
+        let value = samples[0].clone();
+
+        let embedded = TRANSACTION_COMPRESSOR
+            .with(|compressor| compressor.borrow_mut().compress(&value))
+            .unwrap();
+
+        let dictionary = zstd::dict::from_samples(&samples, 4096).unwrap();
+        let dictionary_id = zstd::zstd_safe::get_dict_id_from_dict(&dictionary);
+        load_dictionary(DictionaryKind::Transaction, dictionary);
+
+        // The compressor switches to the loaded dictionary
+        let loaded = TRANSACTION_COMPRESSOR
+            .with(|compressor| compressor.borrow_mut().compress(&value))
+            .unwrap();
+        assert_eq!(zstd::zstd_safe::get_dict_id_from_frame(&loaded), dictionary_id);
+        assert_ne!(zstd::zstd_safe::get_dict_id_from_frame(&embedded), dictionary_id);
+
+        // Values compressed with either dictionary are decompressed and recorded
+        let (_, decompressed) = with_decompressed_samples(|| {
+            TRANSACTION_DECOMPRESSOR.with(|decompressor| {
+                let mut decompressor = decompressor.borrow_mut();
+                assert_eq!(decompressor.decompress(&embedded).unwrap(), value.as_slice());
+                assert_eq!(decompressor.decompress(&loaded).unwrap(), value.as_slice());
+            })
+        });
+        assert_eq!(decompressed, vec![value.clone(), value]);
+    }
+
+    #[test]
+    fn unknown_dictionary() {
+        let samples = (0..1000u32)
+            .map(|i| format!("custom receipt {i} with some repeated content").into_bytes())
+            .collect::<Vec<_>>();
+        let dictionary = zstd::dict::from_samples(&samples, 4096).unwrap();
+        let id = zstd::zstd_safe::get_dict_id_from_dict(&dictionary).unwrap().get();
+        let compressed =
+            Compressor::with_dictionary(0, &dictionary).unwrap().compress(&samples[0]).unwrap();
+
+        // The dictionary was never loaded, so the value can't be decompressed
+        assert_eq!(
+            create_receipt_decompressor().decompress(&compressed),
+            Err(UnknownDictionary { kind: DictionaryKind::Receipt, id })
+        );
     }
 }
@@ -1,11 +1,13 @@
 use std::{
     fmt,
     path::{Path, PathBuf},
+    sync::Mutex,
 };
 
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
+use tracing::level_filters::LevelFilter;
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Layer, Registry};
 
 use crate::formatter::LogFormat;
 
@@ -30,18 +32,29 @@ const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 5] = [
     "jsonrpsee-server=off",
 ];
 
+/// Filters of the layers installed by the global tracing subscriber that can be changed at
+/// runtime, see [`set_log_verbosity`] and [`set_log_directives`].
+static RELOADABLE_FILTERS: Mutex<ReloadableFilters> = Mutex::new(ReloadableFilters::new());
+
 /// Manages the collection of layers for a tracing subscriber.
 ///
 /// `Layers` acts as a container for different logging layers such as stdout, file, or journald.
 /// Each layer can be configured separately and then combined into a tracing subscriber.
+///
+/// The filters of the stdout, file and journald layers are reloadable, so they can be changed
+/// after the subscriber has been installed.
 #[derive(Default)]
 pub struct Layers {
     inner: Vec<BoxedLayer<Registry>>,
+    filters: Vec<ReloadableFilter>,
 }
 
 impl fmt::Debug for Layers {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Layers").field("layers_count", &self.inner.len()).finish()
+        f.debug_struct("Layers")
+            .field("layers_count", &self.inner.len())
+            .field("reloadable_filters_count", &self.filters.len())
+            .finish()
     }
 }
 
@@ -59,9 +72,28 @@ impl Layers {
         self.inner.push(layer.boxed());
     }
 
-    /// Consumes the `Layers` instance, returning the inner vector of layers.
-    pub(crate) fn into_inner(self) -> Vec<BoxedLayer<Registry>> {
-        self.inner
+    /// Consumes the `Layers` instance, returning the inner vector of layers and the reloadable
+    /// filters of these layers.
+    pub(crate) fn into_inner(self) -> (Vec<BoxedLayer<Registry>>, Vec<ReloadableFilter>) {
+        (self.inner, self.filters)
+    }
+
+    /// Builds a reloadable environment filter, see [`build_env_filter`].
+    ///
+    /// The reload handle is kept until the layers are installed.
+    fn reloadable_env_filter(
+        &mut self,
+        default_directive: Option<Directive>,
+        directives: &str,
+    ) -> eyre::Result<reload::Layer<EnvFilter, Registry>> {
+        let filter = build_env_filter(default_directive.clone(), directives)?;
+        let (filter, handle) = reload::Layer::new(filter);
+        self.filters.push(ReloadableFilter {
+            default_directive,
+            directives: directives.to_string(),
+            handle,
+        });
+        Ok(filter)
     }
 
     /// Adds a journald layer to the layers collection.
@@ -72,7 +104,7 @@ impl Layers {
     /// # Returns
     /// An `eyre::Result<()>` indicating the success or failure of the operation.
     pub(crate) fn journald(&mut self, filter: &str) -> eyre::Result<()> {
-        let journald_filter = build_env_filter(None, filter)?;
+        let journald_filter = self.reloadable_env_filter(None, filter)?;
         let layer = tracing_journald::layer()?.with_filter(journald_filter);
         self.add_layer(layer);
         Ok(())
@@ -98,7 +130,7 @@ impl Layers {
         filters: &str,
         color: Option<String>,
     ) -> eyre::Result<()> {
-        let filter = build_env_filter(Some(default_directive), filters)?;
+        let filter = self.reloadable_env_filter(Some(default_directive), filters)?;
         let layer = format.apply(filter, color, None);
         self.add_layer(layer);
         Ok(())
@@ -120,13 +152,117 @@ impl Layers {
         file_info: FileInfo,
     ) -> eyre::Result<FileWorkerGuard> {
         let (writer, guard) = file_info.create_log_writer();
-        let file_filter = build_env_filter(None, filter)?;
+        let file_filter = self.reloadable_env_filter(None, filter)?;
         let layer = format.apply(file_filter, None, Some(writer));
         self.add_layer(layer);
         Ok(guard)
     }
 }
 
+/// A filter of an installed layer that can be rebuilt with different directives.
+pub(crate) struct ReloadableFilter {
+    /// The default directive the filter was built with.
+    default_directive: Option<Directive>,
+    /// The directives the filter was built with.
+    directives: String,
+    /// Handle to replace the filter of the layer.
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl ReloadableFilter {
+    /// Rebuilds the filter from its startup configuration, overriding the level with `verbosity`
+    /// and then applying `directives`, and swaps it into the layer.
+    fn reload(&self, verbosity: Option<LevelFilter>, directives: &[Directive]) -> eyre::Result<()> {
+        let mut filter = build_env_filter(self.default_directive.clone(), &self.directives)?;
+        // A directive without a target replaces the default level of the filter.
+        if let Some(verbosity) = verbosity {
+            filter = filter.add_directive(verbosity.into());
+        }
+        for directive in directives {
+            filter = filter.add_directive(directive.clone());
+        }
+        self.handle.reload(filter)?;
+        Ok(())
+    }
+}
+
+/// The reloadable filters of the installed layers and the runtime overrides applied to them.
+struct ReloadableFilters {
+    filters: Vec<ReloadableFilter>,
+    verbosity: Option<LevelFilter>,
+    directives: Vec<Directive>,
+}
+
+impl ReloadableFilters {
+    const fn new() -> Self {
+        Self { filters: Vec::new(), verbosity: None, directives: Vec::new() }
+    }
+
+    /// Sets the maximum level of all filters, keeping the directives.
+    fn set_verbosity(&mut self, level: LevelFilter) -> eyre::Result<()> {
+        let directives = self.directives.clone();
+        self.update(Some(level), directives)
+    }
+
+    /// Sets the additional directives of all filters, keeping the maximum level.
+    fn set_directives(&mut self, directives: Vec<Directive>) -> eyre::Result<()> {
+        self.update(self.verbosity, directives)
+    }
+
+    /// Resets all filters to their startup configuration.
+    fn reset(&mut self) -> eyre::Result<()> {
+        self.update(None, Vec::new())
+    }
+
+    /// Sets the overrides and reloads all filters with them.
+    fn update(
+        &mut self,
+        verbosity: Option<LevelFilter>,
+        directives: Vec<Directive>,
+    ) -> eyre::Result<()> {
+        if self.filters.is_empty() {
+            eyre::bail!("no reloadable log filters are installed")
+        }
+        for filter in &self.filters {
+            filter.reload(verbosity, &directives)?;
+        }
+        self.verbosity = verbosity;
+        self.directives = directives;
+        Ok(())
+    }
+}
+
+/// Registers the filters of the layers that were installed by the global tracing subscriber.
+pub(crate) fn install_reloadable_filters(filters: Vec<ReloadableFilter>) {
+    let mut reloadable = RELOADABLE_FILTERS.lock().unwrap_or_else(|err| err.into_inner());
+    reloadable.filters.extend(filters);
+}
+
+/// Sets the maximum level of all reloadable log filters, overriding the default level and any
+/// level without a target they were configured with.
+///
+/// Directives set with [`set_log_directives`] are kept.
+///
+/// Returns an error if no reloadable filters are installed, e.g. because the global subscriber
+/// was not initialized by a [`Tracer`](crate::Tracer) using [`Layers`].
+pub fn set_log_verbosity(level: LevelFilter) -> eyre::Result<()> {
+    RELOADABLE_FILTERS.lock().unwrap_or_else(|err| err.into_inner()).set_verbosity(level)
+}
+
+/// Sets additional directives of all reloadable log filters, e.g. `engine::tree=trace` to enable
+/// trace logs for a single target.
+///
+/// The directives replace the ones set by a previous call, the level set with
+/// [`set_log_verbosity`] is kept.
+pub fn set_log_directives(directives: Vec<Directive>) -> eyre::Result<()> {
+    RELOADABLE_FILTERS.lock().unwrap_or_else(|err| err.into_inner()).set_directives(directives)
+}
+
+/// Resets all reloadable log filters to the configuration they were installed with.
+pub fn reset_log_filters() -> eyre::Result<()> {
+    RELOADABLE_FILTERS.lock().unwrap_or_else(|err| err.into_inner()).reset()
+}
+
 /// Holds configuration information for file logging.
 ///
 /// Contains details about the log file's path, name, size, and rotation strategy.
@@ -201,3 +337,47 @@ fn build_env_filter(
             Ok(env_filter.add_directive(directive.parse()?))
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::Level;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn reload_filters() {
+        let mut layers = Layers::new();
+        let filter = layers.reloadable_env_filter(Some(LevelFilter::INFO.into()), "").unwrap();
+        let subscriber = Registry::default()
+            .with(tracing_subscriber::fmt::layer().with_writer(std::io::sink).with_filter(filter));
+        let mut reloadable = ReloadableFilters::new();
+        assert!(reloadable.reset().is_err());
+        reloadable.filters = layers.into_inner().1;
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(tracing::enabled!(Level::INFO));
+            assert!(!tracing::enabled!(Level::DEBUG));
+
+            reloadable.set_verbosity(LevelFilter::DEBUG).unwrap();
+            assert!(tracing::enabled!(Level::DEBUG));
+            assert!(!tracing::enabled!(target: "sync", Level::TRACE));
+            // the default directives are kept
+            assert!(!tracing::enabled!(target: "discv5", Level::INFO));
+
+            reloadable.set_directives(vec!["sync=trace".parse().unwrap()]).unwrap();
+            assert!(tracing::enabled!(target: "sync", Level::TRACE));
+            assert!(tracing::enabled!(Level::DEBUG));
+            assert!(!tracing::enabled!(Level::TRACE));
+
+            // the directives are kept when the verbosity changes
+            reloadable.set_verbosity(LevelFilter::WARN).unwrap();
+            assert!(tracing::enabled!(target: "sync", Level::TRACE));
+            assert!(!tracing::enabled!(Level::INFO));
+
+            reloadable.reset().unwrap();
+            assert!(tracing::enabled!(Level::INFO));
+            assert!(!tracing::enabled!(Level::DEBUG));
+            assert!(!tracing::enabled!(target: "sync", Level::TRACE));
+        });
+    }
+}
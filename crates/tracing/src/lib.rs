@@ -50,13 +50,16 @@ pub use tracing_subscriber;
 
 // Re-export our types
 pub use formatter::LogFormat;
-pub use layers::{FileInfo, FileWorkerGuard, Layers};
+pub use layers::{
+    reset_log_filters, set_log_directives, set_log_verbosity, FileInfo, FileWorkerGuard, Layers,
+};
 pub use test_tracer::TestTracer;
 
 mod formatter;
 mod layers;
 mod test_tracer;
 
+use layers::install_reloadable_filters;
 use tracing::level_filters::LevelFilter;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -224,9 +227,12 @@ impl Tracer for RethTracer {
             None
         };
 
+        let (layers, filters) = layers.into_inner();
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
-        let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
+        if tracing_subscriber::registry().with(layers).try_init().is_ok() {
+            install_reloadable_filters(filters);
+        }
         Ok(file_guard)
     }
 }
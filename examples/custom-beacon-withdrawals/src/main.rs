@@ -23,10 +23,10 @@ use reth_ethereum::{
         revm::{
             context::{result::ExecutionResult, TxEnv},
             db::State,
-            primitives::{address, hardfork::SpecId, Address},
+            primitives::{address, hardfork::SpecId, Address, Bytes},
             DatabaseCommit,
         },
-        EthBlockAssembler, EthEvmConfig, RethReceiptBuilder,
+        ConfigureExtraData, EthBlockAssembler, EthEvmConfig, RethReceiptBuilder,
     },
     node::{
         api::{ConfigureEvm, FullNodeTypes, NodeTypes},
@@ -157,6 +157,12 @@ impl ConfigureEvm for CustomEvmConfig {
     }
 }
 
+impl ConfigureExtraData for CustomEvmConfig {
+    fn set_extra_data(&mut self, extra_data: Bytes) {
+        self.inner.set_extra_data(extra_data);
+    }
+}
+
 pub struct CustomBlockExecutor<'a, Evm> {
     /// Inner Ethereum execution strategy.
     inner: EthBlockExecutor<'a, Evm, &'a Arc<ChainSpec>, &'a RethReceiptBuilder>,